@@ -0,0 +1,325 @@
+//! Artifacts of the proof rounds, recorded by the prover alongside a CDF file
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constraint::Hex;
+use crate::Scalar;
+
+/// Leading bytes of an artifacts file
+const MAGIC: &[u8; 4] = b"CDFP";
+
+/// Version of the artifacts layout; a file of another version is rejected
+const VERSION: u8 = 1;
+
+/// Artifacts of the proof of a circuit, written by the integration layer of
+/// the prover next to the CDF file.
+///
+/// The CDF file records the `pi` selector of every constraint, while the
+/// verifier receives the public input vector of the proof; a misalignment of
+/// the two is a common cause of verification failures. The artifacts are
+/// stored with the [`ProofArtifacts::EXTENSION`] extension, and
+/// [`CircuitDescription::open`](struct.CircuitDescription.html#method.open)
+/// loads them along with the file.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use dusk_cdf::{ProofArtifacts, Scalar};
+///
+/// let artifacts = ProofArtifacts {
+///     public_inputs: vec![Scalar::from([1; Scalar::LEN])],
+///     challenges: vec![("alpha".into(), Scalar::from([2; Scalar::LEN]))],
+/// };
+///
+/// let mut bytes = vec![];
+/// artifacts.write(&mut bytes)?;
+///
+/// assert_eq!(ProofArtifacts::read(bytes.as_slice())?, artifacts);
+///
+/// # Ok(()) }
+/// ```
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct ProofArtifacts {
+    /// Public input vector of the proof, in the order of their gates
+    pub public_inputs: Vec<Scalar>,
+    /// Challenges of the transcript, along with their labels, in the order
+    /// they were drawn
+    pub challenges: Vec<(String, Scalar)>,
+}
+
+impl ProofArtifacts {
+    /// Extension of the artifacts files
+    pub const EXTENSION: &'static str = "cdfp";
+
+    /// Path of the artifacts file of a CDF file
+    pub fn sidecar<P>(path: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        path.as_ref().with_extension(Self::EXTENSION)
+    }
+
+    /// Read the artifacts file of a CDF file
+    pub fn read_sidecar<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(Self::sidecar(path))?;
+
+        Self::read(io::BufReader::new(file))
+    }
+
+    /// Write the artifacts next to a CDF file, returning the path of the
+    /// artifacts file
+    pub fn write_sidecar<P>(&self, path: P) -> io::Result<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        let sidecar = Self::sidecar(path);
+
+        self.write(io::BufWriter::new(File::create(&sidecar)?))?;
+
+        Ok(sidecar)
+    }
+
+    /// Write the artifacts, as read by [`ProofArtifacts::read`]
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        write_len(&mut writer, self.public_inputs.len())?;
+        for value in &self.public_inputs {
+            writer.write_all(value.as_ref())?;
+        }
+
+        write_len(&mut writer, self.challenges.len())?;
+        for (label, value) in &self.challenges {
+            write_len(&mut writer, label.len())?;
+            writer.write_all(label.as_bytes())?;
+            writer.write_all(value.as_ref())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Read artifacts written by [`ProofArtifacts::write`]
+    pub fn read<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC || header[4] != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the file isn't a proof artifacts file of a supported version",
+            ));
+        }
+
+        let public_inputs = (0..read_len(&mut reader)?)
+            .map(|_| read_scalar(&mut reader))
+            .collect::<io::Result<_>>()?;
+
+        let challenges = (0..read_len(&mut reader)?)
+            .map(|_| {
+                let len = read_len(&mut reader)?;
+
+                let mut label = vec![];
+                reader.by_ref().take(len as u64).read_to_end(&mut label)?;
+
+                if label.len() < len {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+
+                let label = String::from_utf8(label).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, e)
+                })?;
+
+                Ok((label, read_scalar(&mut reader)?))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            public_inputs,
+            challenges,
+        })
+    }
+
+    /// Compare the public input vector with the public inputs recorded by
+    /// the constraints, as listed by
+    /// [`CircuitDescription::publics`](struct.CircuitDescription.html#method.publics),
+    /// position by position
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{ProofArtifacts, PublicInput, Scalar};
+    ///
+    /// let a = Scalar::from([1; Scalar::LEN]);
+    /// let b = Scalar::from([2; Scalar::LEN]);
+    /// let publics = [
+    ///     PublicInput { constraint: 3, value: a },
+    ///     PublicInput { constraint: 8, value: b },
+    /// ];
+    ///
+    /// let artifacts = ProofArtifacts {
+    ///     public_inputs: vec![a, b],
+    ///     ..Default::default()
+    /// };
+    /// assert!(artifacts.compare(&publics).is_empty());
+    ///
+    /// let artifacts = ProofArtifacts {
+    ///     public_inputs: vec![b],
+    ///     ..Default::default()
+    /// };
+    /// let mismatches = artifacts.compare(&publics);
+    ///
+    /// assert_eq!(mismatches.len(), 2);
+    /// assert_eq!(mismatches[0].constraint, Some(3));
+    /// assert_eq!(mismatches[1].proved, None);
+    /// ```
+    pub fn compare(&self, publics: &[PublicInput]) -> Vec<PublicMismatch> {
+        let len = publics.len().max(self.public_inputs.len());
+
+        (0..len)
+            .filter_map(|index| {
+                let public = publics.get(index);
+                let proved = self.public_inputs.get(index).copied();
+
+                if public.map(|p| p.value) == proved {
+                    return None;
+                }
+
+                Some(PublicMismatch {
+                    index,
+                    constraint: public.map(|p| p.constraint),
+                    recorded: public.map(|p| p.value),
+                    proved,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Public input recorded by the `pi` selector of a constraint
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct PublicInput {
+    /// Id of the constraint
+    pub constraint: usize,
+    /// Value of the `pi` selector
+    pub value: Scalar,
+}
+
+/// Position of the public input vector of the proof that differs from the
+/// public inputs recorded by the constraints
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct PublicMismatch {
+    /// Position in the public input vector
+    pub index: usize,
+    /// Id of the constraint recording the public input at the position, if
+    /// any
+    pub constraint: Option<usize>,
+    /// Value recorded by the constraint, if any
+    pub recorded: Option<Scalar>,
+    /// Value of the public input vector of the proof, if any
+    pub proved: Option<Scalar>,
+}
+
+impl fmt::Display for PublicMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "public input #{}: ", self.index)?;
+
+        match (self.constraint, &self.recorded, &self.proved) {
+            (Some(constraint), Some(recorded), Some(proved)) => write!(
+                f,
+                "constraint #{} records {}, the proof has {}",
+                constraint,
+                Hex(recorded),
+                Hex(proved)
+            ),
+
+            (Some(constraint), Some(recorded), None) => write!(
+                f,
+                "constraint #{} records {}, missing from the proof",
+                constraint,
+                Hex(recorded)
+            ),
+
+            (_, _, Some(proved)) => write!(
+                f,
+                "the proof has {}, recorded by no constraint",
+                Hex(proved)
+            ),
+
+            _ => f.write_str("recorded by no constraint"),
+        }
+    }
+}
+
+fn write_len<W>(writer: &mut W, len: usize) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writer.write_all(&(len as u64).to_le_bytes())
+}
+
+fn read_len<R>(reader: &mut R) -> io::Result<usize>
+where
+    R: io::Read,
+{
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+
+    usize::try_from(u64::from_le_bytes(bytes)).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the artifacts declare a length that overflows",
+        )
+    })
+}
+
+fn read_scalar<R>(reader: &mut R) -> io::Result<Scalar>
+where
+    R: io::Read,
+{
+    let mut bytes = [0u8; Scalar::LEN];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(Scalar::from(bytes))
+}
@@ -0,0 +1,677 @@
+mod watch;
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use dusk_cdf::{
+    CircuitDescription, CircuitIndex, ColorChoice, Config, Divergence,
+    LegacyCircuit, PatternConfig, ReportFormat, Style, TableStyle, Utf8Policy,
+};
+use toml_base_config::BaseConfig;
+
+/// CDF file inspection tool
+#[derive(Parser, Debug)]
+#[clap(author, version, about)]
+pub struct Args {
+    /// When to color the output: never, auto or always
+    #[clap(long, global = true, default_value_t)]
+    color: ColorChoice,
+
+    /// Rendering of the tables: ascii, unicode or tsv, separating the cells
+    /// with tabs to be processed by tools such as awk
+    #[clap(long, global = true, default_value_t)]
+    table_style: TableStyle,
+
+    /// Decoding of the embedded sources that aren't valid UTF-8: strict
+    /// rejects the file, lenient replaces the invalid sequences
+    #[clap(long, global = true, default_value_t)]
+    utf8: Utf8Policy,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a listing of the constraints, one per line
+    Dump {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Constraints to print, as `a..b`; either bound can be omitted
+        #[clap(long, value_parser = parse_range)]
+        range: Option<Range<usize>>,
+    },
+
+    /// Print the counts and layout of a CDF file, exiting with `1` if it
+    /// exceeds the constraint budget
+    Info {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Expected maximum number of constraints of the circuit
+        #[clap(long)]
+        max_constraints: Option<usize>,
+    },
+
+    /// Print the offsets and lengths of the sections of a CDF file, and of
+    /// the fields of its witnesses and constraints
+    Layout {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+    },
+
+    /// Print the constraints in which two CDF files differ, exiting with `1`
+    /// if they do
+    Diff {
+        /// Reference CDF file path
+        #[clap(value_parser)]
+        left: PathBuf,
+
+        /// CDF file path compared to the reference
+        #[clap(value_parser)]
+        right: PathBuf,
+
+        /// Print only the first divergent witness and constraint
+        #[clap(long)]
+        first: bool,
+    },
+
+    /// Print the sources and gadgets with the largest footprint, the sources
+    /// with the most failed constraints, the values shared by several
+    /// witnesses, and optionally the witnesses shared by several sources
+    Stats {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Number of entries printed in each section
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+
+        /// Print the witnesses wired into the constraints of more than one
+        /// source, along with the sources
+        #[clap(long)]
+        shared: bool,
+
+        /// TOML file declaring gate patterns, recognized before the builtin
+        /// ones
+        #[clap(long, value_parser)]
+        patterns: Option<PathBuf>,
+    },
+
+    /// Build the index file of a CDF file, used by the subsequent sessions
+    /// as long as the file is unchanged
+    Index {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+    },
+
+    /// Write a static report of the failures and findings of a CDF file, to
+    /// be shared for review
+    Report {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Report file path; the report is printed if omitted
+        #[clap(short, long, value_parser)]
+        output: Option<PathBuf>,
+
+        /// Format of the report: markdown or html. Defaults to the format of
+        /// the extension of the output
+        #[clap(long)]
+        format: Option<ReportFormat>,
+
+        /// Number of entries of each listing of the report
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Watch a directory for new CDF files, printing the structural and
+    /// failure changes of each one from the previous one
+    Watch {
+        /// Directory the CDF files are dumped to
+        #[clap(value_parser)]
+        dir: PathBuf,
+
+        /// Polling interval, in milliseconds
+        #[clap(long, default_value_t = 500)]
+        interval: u64,
+    },
+
+    /// Convert a file in the legacy format of dusk-plonk-cdf into the
+    /// current format, embedding the sources found on the disk
+    ConvertLegacy {
+        /// Legacy CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Converted CDF file path
+        #[clap(value_parser)]
+        output: PathBuf,
+    },
+
+    /// Check the selectors of a CDF file against the verifier data of the
+    /// compiled circuit, exiting with `1` if they differ
+    #[cfg(feature = "plonk-verify")]
+    Verify {
+        /// CDF file path
+        #[clap(value_parser)]
+        path: PathBuf,
+
+        /// Verifier data of the circuit, as JSON or bincode
+        #[clap(long, value_parser)]
+        verifier_data: PathBuf,
+
+        /// Write an artifact of the failures for CI instead of printing the
+        /// mismatches: junit or sarif
+        #[clap(long)]
+        format: Option<dusk_cdf::CiFormat>,
+
+        /// Artifact file path; the artifact is printed if omitted
+        #[clap(short, long, value_parser)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn parse_range(range: &str) -> Result<Range<usize>, String> {
+    let (start, end) = range.split_once("..").ok_or_else(|| {
+        format!("expected a range as `a..b`, got `{}`", range)
+    })?;
+
+    let bound = |b: &str, default: usize| match b.trim() {
+        "" => Ok(default),
+        b => b
+            .parse()
+            .map_err(|e| format!("invalid bound `{}`: {}", b, e)),
+    };
+
+    Ok(bound(start, 0)?..bound(end, usize::MAX)?)
+}
+
+fn dump(
+    path: PathBuf,
+    range: Option<Range<usize>>,
+    config: Config,
+    style: Style,
+) -> io::Result<()> {
+    let mut circuit = CircuitDescription::open_with_config(path, config)?;
+    let range = range.unwrap_or(0..usize::MAX);
+
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    match circuit.dump(range, style, &mut stdout) {
+        // the listing is often piped to `head` or `grep -m`
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Print the summary of `path`, returning whether it exceeds the budget
+fn info(
+    path: PathBuf,
+    max_constraints: Option<usize>,
+    config: Config,
+    style: Style,
+) -> io::Result<bool> {
+    let circuit = CircuitDescription::open_with_config(path, config)?;
+    let preamble = *circuit.preamble();
+
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    let values = match preamble.config.zeroed_scalar_values {
+        true => "zeroed",
+        false => "stored",
+    };
+
+    writeln!(
+        stdout,
+        "witnesses:     {}",
+        style.number(preamble.witnesses)
+    )?;
+    writeln!(
+        stdout,
+        "constraints:   {}",
+        style.number(preamble.constraints)
+    )?;
+    writeln!(
+        stdout,
+        "sources:       {}",
+        style.number(circuit.source_names().count())
+    )?;
+    writeln!(stdout, "scalar values: {}", values)?;
+    let cache = preamble.checked_source_cache_offset().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the preamble describes a layout that overflows",
+        )
+    })?;
+    writeln!(stdout, "source cache:  at byte {}", style.number(cache))?;
+
+    let overrun = max_constraints
+        .filter(|max| preamble.constraints > *max)
+        .map(|max| preamble.constraints - max);
+
+    match (max_constraints, overrun) {
+        (Some(max), Some(excess)) => writeln!(
+            stdout,
+            "budget:        {}",
+            style.failure(format!(
+                "{} constraints, exceeded by {}",
+                max, excess
+            ))
+        )?,
+        (Some(max), None) => writeln!(
+            stdout,
+            "budget:        {} constraints",
+            style.number(max)
+        )?,
+        _ => (),
+    }
+
+    stdout.flush()?;
+
+    Ok(overrun.is_some())
+}
+
+fn layout(path: PathBuf, config: Config, style: Style) -> io::Result<()> {
+    let mut circuit = CircuitDescription::open_with_config(path, config)?;
+    let layout = circuit.layout()?;
+
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    layout.write(&style, &mut stdout)?;
+    stdout.flush()
+}
+
+/// Print the divergences of `right` from `left`, returning whether there is
+/// any
+fn diff(
+    left: PathBuf,
+    right: PathBuf,
+    first: bool,
+    config: Config,
+    style: Style,
+) -> io::Result<bool> {
+    let mut left = CircuitDescription::open_with_config(left, config)?;
+    let mut right = CircuitDescription::open_with_config(right, config)?;
+
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    if first {
+        let divergence = left.first_divergence(&mut right)?;
+
+        write!(stdout, "{}", divergence)?;
+        stdout.flush()?;
+
+        return Ok(!divergence.is_empty());
+    }
+
+    let (l, r) = (left.preamble().constraints, right.preamble().constraints);
+    let mut diverged = l != r;
+
+    for idx in 0..l.min(r) {
+        for divergence in left.divergences(&mut right, idx)? {
+            writeln!(stdout, "{}", style.failure(divergence))?;
+            diverged = true;
+        }
+    }
+
+    if l != r {
+        let missing = Divergence::Missing {
+            constraint: l.min(r),
+            left: l > r,
+        };
+
+        writeln!(stdout, "{}", style.failure(missing))?;
+    }
+
+    stdout.flush()?;
+
+    Ok(diverged)
+}
+
+fn stats(
+    path: PathBuf,
+    top: usize,
+    shared: bool,
+    patterns: Option<PathBuf>,
+    config: Config,
+    style: Style,
+) -> io::Result<()> {
+    let mut circuit = CircuitDescription::open_with_config(path, config)?;
+
+    if let Some(patterns) = patterns {
+        let library = PatternConfig::load_path(patterns)?.library()?;
+
+        circuit.set_pattern_library(library);
+    }
+
+    let mut usage = circuit.usage()?;
+
+    usage.truncate(top);
+
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    let mut sources =
+        style.table(["bytes", "constraints", "witnesses", "source"]);
+    for s in &usage.sources {
+        sources.row([
+            style.number(s.bytes).to_string(),
+            style.number(s.constraints).to_string(),
+            style.number(s.witnesses).to_string(),
+            s.name.clone(),
+        ]);
+    }
+
+    writeln!(stdout, "sources by size:")?;
+    write!(stdout, "{}", sources)?;
+
+    let mut gadgets = style.table(["constraints", "gadget"]);
+    for g in &usage.gadgets {
+        gadgets.row([
+            style.number(g.constraints).to_string(),
+            format!("{}:{}", g.name, g.line),
+        ]);
+    }
+
+    writeln!(stdout, "gadgets by constraints:")?;
+    write!(stdout, "{}", gadgets)?;
+
+    let mut patterns = style.table(["constraints", "occurrences", "pattern"]);
+    for p in &usage.patterns {
+        patterns.row([
+            style.number(p.constraints).to_string(),
+            style.number(p.occurrences).to_string(),
+            p.name.clone(),
+        ]);
+    }
+
+    writeln!(stdout, "gadgets by pattern:")?;
+    write!(stdout, "{}", patterns)?;
+
+    let mut heatmap =
+        style.table(["failures", "rate", "constraints", "source"]);
+    for s in &usage.heatmap {
+        heatmap.row([
+            style.number(s.failures).to_string(),
+            format!("{:.2}%", 100.0 * s.failure_rate()),
+            style.number(s.constraints).to_string(),
+            s.name.clone(),
+        ]);
+    }
+
+    writeln!(stdout, "sources by failures:")?;
+    write!(stdout, "{}", heatmap)?;
+
+    let mut entropy = circuit.witness_entropy()?;
+
+    entropy.truncate(top);
+
+    let mut repeated = style.table(["witnesses", "value", "constant"]);
+    for r in &entropy.repeated {
+        let value: String =
+            r.value.iter().map(|b| format!("{:02x}", b)).collect();
+        let constant = r.constant.map(|c| c.to_string());
+
+        repeated.row([
+            style.number(r.witnesses).to_string(),
+            style.scalar(format!("0x{}", value)).to_string(),
+            constant.unwrap_or_else(|| "-".into()),
+        ]);
+    }
+
+    writeln!(
+        stdout,
+        "witness values: {} distinct of {}, {:.2} of {:.2} bits",
+        style.number(entropy.distinct),
+        style.number(entropy.witnesses),
+        entropy.bits,
+        entropy.max_bits()
+    )?;
+    write!(stdout, "{}", repeated)?;
+
+    if shared {
+        let mut wires = circuit.shared_wires()?;
+
+        wires.truncate(top);
+
+        let mut shared = style.table(["witness", "constraints", "sources"]);
+        for w in &wires {
+            shared.row([
+                style.number(w.witness).to_string(),
+                style.number(w.constraints).to_string(),
+                w.sources.join(", "),
+            ]);
+        }
+
+        writeln!(stdout, "witnesses shared by sources:")?;
+        write!(stdout, "{}", shared)?;
+    }
+
+    stdout.flush()
+}
+
+/// Write the index file of `path`, returning its path
+fn index(path: PathBuf, config: Config) -> io::Result<PathBuf> {
+    let mut circuit = CircuitDescription::open_with_config(&path, config)?;
+    let sidecar = CircuitIndex::sidecar(&path);
+
+    let file = File::create(&sidecar)?;
+
+    circuit.build_index()?.write(io::BufWriter::new(file))?;
+
+    Ok(sidecar)
+}
+
+fn report(
+    path: PathBuf,
+    output: Option<PathBuf>,
+    format: Option<ReportFormat>,
+    limit: usize,
+    config: Config,
+) -> io::Result<()> {
+    let mut circuit = CircuitDescription::open_with_config(&path, config)?;
+    let title = path.display().to_string();
+
+    match output {
+        Some(output) => {
+            let format =
+                format.unwrap_or_else(|| ReportFormat::from_path(&output));
+            let file = io::BufWriter::new(File::create(output)?);
+
+            circuit.report(&title, format, limit, file)
+        }
+
+        None => {
+            let format = format.unwrap_or_default();
+            let stdout = io::stdout();
+            let stdout = io::BufWriter::new(stdout.lock());
+
+            circuit.report(&title, format, limit, stdout)
+        }
+    }
+}
+
+/// Convert the legacy file `path` into `output`, returning the counts of
+/// witnesses and constraints
+fn convert_legacy(
+    path: PathBuf,
+    output: PathBuf,
+    config: Config,
+) -> io::Result<(usize, usize)> {
+    let mut legacy = LegacyCircuit::open(path)?;
+
+    legacy.convert_file(config, output)?;
+
+    Ok((legacy.witnesses(), legacy.constraints()))
+}
+
+/// Print the mismatches of `path` with `verifier_data`, returning whether
+/// there is any
+#[cfg(feature = "plonk-verify")]
+fn verify(
+    path: PathBuf,
+    verifier_data: PathBuf,
+    format: Option<dusk_cdf::CiFormat>,
+    output: Option<PathBuf>,
+    config: Config,
+    style: Style,
+) -> io::Result<bool> {
+    let mut circuit = CircuitDescription::open_with_config(&path, config)?;
+    let data = dusk_cdf::VerifierData::open(verifier_data)?;
+    let warnings = circuit.verifycheck(&data)?;
+
+    let stdout = io::stdout();
+    let mut stdout = io::BufWriter::new(stdout.lock());
+
+    // the artifact carries the mismatches along with the failed constraints
+    // and the findings of the circuit
+    if let Some(format) = format {
+        let title = path.display().to_string();
+
+        match output {
+            Some(output) => {
+                let file = io::BufWriter::new(File::create(output)?);
+
+                circuit.ci_report(&title, format, &warnings, file)?;
+            }
+            None => circuit.ci_report(&title, format, &warnings, stdout)?,
+        }
+
+        return Ok(!warnings.is_empty());
+    }
+
+    for warning in &warnings {
+        writeln!(stdout, "{}", style.failure(warning))?;
+    }
+
+    stdout.flush()?;
+
+    Ok(!warnings.is_empty())
+}
+
+fn main() -> io::Result<ExitCode> {
+    let Args {
+        color,
+        table_style,
+        utf8,
+        command,
+    } = Args::parse();
+
+    let style = *color.resolve().with_table_style(table_style);
+    let config = *Config::default().with_utf8(utf8);
+
+    match command {
+        Command::Dump { path, range } => {
+            dump(path, range, config, style).map(|_| ExitCode::SUCCESS)
+        }
+
+        Command::Info {
+            path,
+            max_constraints,
+        } => info(path, max_constraints, config, style).map(|overrun| {
+            if overrun {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }),
+
+        Command::Layout { path } => {
+            layout(path, config, style).map(|_| ExitCode::SUCCESS)
+        }
+
+        Command::Diff { left, right, first } => {
+            diff(left, right, first, config, style).map(|diverged| {
+                if diverged {
+                    ExitCode::FAILURE
+                } else {
+                    ExitCode::SUCCESS
+                }
+            })
+        }
+
+        Command::Stats {
+            path,
+            top,
+            shared,
+            patterns,
+        } => stats(path, top, shared, patterns, config, style)
+            .map(|_| ExitCode::SUCCESS),
+
+        Command::Index { path } => index(path, config).map(|sidecar| {
+            println!("{}", sidecar.display());
+
+            ExitCode::SUCCESS
+        }),
+
+        Command::Report {
+            path,
+            output,
+            format,
+            limit,
+        } => report(path, output, format, limit, config)
+            .map(|_| ExitCode::SUCCESS),
+
+        Command::Watch { dir, interval } => {
+            let interval = Duration::from_millis(interval);
+
+            watch::watch(dir, interval, config, style)
+                .map(|_| ExitCode::SUCCESS)
+        }
+
+        Command::ConvertLegacy { path, output } => convert_legacy(
+            path, output, config,
+        )
+        .map(|(witnesses, constraints)| {
+            println!(
+                "converted {} witnesses and {} constraints",
+                style.number(witnesses),
+                style.number(constraints)
+            );
+
+            ExitCode::SUCCESS
+        }),
+
+        #[cfg(feature = "plonk-verify")]
+        Command::Verify {
+            path,
+            verifier_data,
+            format,
+            output,
+        } => verify(path, verifier_data, format, output, config, style).map(
+            |mismatch| {
+                if mismatch {
+                    ExitCode::FAILURE
+                } else {
+                    ExitCode::SUCCESS
+                }
+            },
+        ),
+    }
+}
+
+#[test]
+fn parse_range_accepts_open_bounds() {
+    assert_eq!(parse_range("3..7"), Ok(3..7));
+    assert_eq!(parse_range("3.."), Ok(3..usize::MAX));
+    assert_eq!(parse_range("..7"), Ok(0..7));
+    assert!(parse_range("7").is_err());
+    assert!(parse_range("a..7").is_err());
+}
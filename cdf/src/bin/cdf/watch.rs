@@ -0,0 +1,263 @@
+//! Diffing of the successive CDF files dumped in a directory
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fmt, fs};
+
+use dusk_cdf::{CircuitDescription, Config, Style};
+
+/// Structure and failures of a CDF file, compared between successive dumps
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    constraints: usize,
+    witnesses: usize,
+    failed: usize,
+    /// Constraints and failures of every source
+    sources: BTreeMap<String, (usize, usize)>,
+    /// Source lines of the failed constraints
+    failures: BTreeSet<(String, u64)>,
+}
+
+impl Snapshot {
+    pub fn open(path: &Path, config: Config) -> io::Result<Self> {
+        let mut circuit = CircuitDescription::open_with_config(path, config)?;
+        let preamble = *circuit.preamble();
+
+        let sources = circuit
+            .usage()?
+            .sources
+            .into_iter()
+            .map(|s| (s.name, (s.constraints, s.failures)))
+            .collect();
+
+        let mut failed = 0;
+        let mut failures = BTreeSet::new();
+
+        for idx in 0..preamble.constraints {
+            let constraint = circuit.fetch_constraint(idx)?;
+
+            if !constraint.polynomial().is_ok() {
+                let name = constraint.name().to_string();
+
+                failed += 1;
+                failures.insert((name, constraint.line()));
+            }
+        }
+
+        Ok(Self {
+            constraints: preamble.constraints,
+            witnesses: preamble.witnesses,
+            failed,
+            sources,
+            failures,
+        })
+    }
+
+    /// Write the changes from `previous` to this snapshot, one per line
+    pub fn delta<W>(
+        &self,
+        previous: &Self,
+        style: Style,
+        mut writer: W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let counts = [
+            ("constraints", previous.constraints, self.constraints),
+            ("witnesses", previous.witnesses, self.witnesses),
+            ("failures", previous.failed, self.failed),
+        ];
+
+        for (name, before, after) in counts {
+            writeln!(
+                writer,
+                "  {:<12}{:>10} -> {:>10} ({})",
+                name,
+                style.number(before),
+                style.number(after),
+                Change(before, after)
+            )?;
+        }
+
+        let names: BTreeSet<_> =
+            previous.sources.keys().chain(self.sources.keys()).collect();
+
+        for name in names {
+            let (c0, f0) =
+                previous.sources.get(name).copied().unwrap_or_default();
+            let (c1, f1) = self.sources.get(name).copied().unwrap_or_default();
+
+            if (c0, f0) != (c1, f1) {
+                writeln!(
+                    writer,
+                    "  {} constraints ({}), {} failures ({})  {}",
+                    style.number(c1),
+                    Change(c0, c1),
+                    style.number(f1),
+                    Change(f0, f1),
+                    name
+                )?;
+            }
+        }
+
+        for (name, line) in self.failures.difference(&previous.failures) {
+            let failure = format!("new failure at {}:{}", name, line);
+
+            writeln!(writer, "  {}", style.failure(failure))?;
+        }
+
+        for (name, line) in previous.failures.difference(&self.failures) {
+            writeln!(writer, "  fixed failure at {}:{}", name, line)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Signed difference between two counts
+struct Change(usize, usize);
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 >= self.0 {
+            true => write!(f, "+{}", self.1 - self.0),
+            false => write!(f, "-{}", self.0 - self.1),
+        }
+    }
+}
+
+/// Newest CDF file of a directory, along with its modification time and
+/// size
+fn newest(dir: &Path) -> io::Result<Option<(PathBuf, SystemTime, u64)>> {
+    let mut newest = None;
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.extension().map(|e| e != "cdf").unwrap_or(true) {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        let modified = metadata.modified()?;
+
+        if newest
+            .as_ref()
+            .map(|(_, m, _)| modified > *m)
+            .unwrap_or(true)
+        {
+            newest.replace((path, modified, metadata.len()));
+        }
+    }
+
+    Ok(newest)
+}
+
+/// Poll `dir` every `interval`, printing the changes of every new CDF file
+/// from the previous one. A file is read once its size is stable for an
+/// interval and its encoder released its lock, so a dump still being written
+/// isn't compared.
+pub fn watch(
+    dir: PathBuf,
+    interval: Duration,
+    config: Config,
+    style: Style,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut previous: Option<Snapshot> = None;
+    let mut seen: Option<(PathBuf, SystemTime)> = None;
+    let mut pending: Option<(PathBuf, SystemTime, u64)> = None;
+
+    loop {
+        if let Some((path, modified, len)) = newest(&dir)? {
+            let known = seen
+                .as_ref()
+                .map(|(p, m)| p == &path && m == &modified)
+                .unwrap_or(false);
+
+            let stable = pending
+                .as_ref()
+                .map(|(p, m, l)| p == &path && m == &modified && *l == len)
+                .unwrap_or(false);
+
+            let snapshot = match !known && stable {
+                true => Some(Snapshot::open(&path, config)),
+                false => None,
+            };
+
+            // a dump locked by its encoder is read on a later poll
+            let snapshot = snapshot.filter(|s| {
+                !matches!(s, Err(e) if e.kind() == io::ErrorKind::WouldBlock)
+            });
+
+            if let Some(snapshot) = snapshot {
+                let mut stdout = stdout.lock();
+
+                writeln!(stdout, "{}", path.display())?;
+
+                match snapshot {
+                    Ok(snapshot) => {
+                        match &previous {
+                            Some(p) => snapshot.delta(p, style, &mut stdout)?,
+                            None => snapshot.delta(
+                                &Snapshot::default(),
+                                style,
+                                &mut stdout,
+                            )?,
+                        }
+
+                        previous.replace(snapshot);
+                    }
+
+                    Err(e) => writeln!(
+                        stdout,
+                        "  {}",
+                        style.failure(format!("failed to read: {}", e))
+                    )?,
+                }
+
+                stdout.flush()?;
+                seen.replace((path.clone(), modified));
+            }
+
+            pending.replace((path, modified, len));
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+#[test]
+fn delta_reports_the_new_and_fixed_failures() {
+    let previous = Snapshot {
+        constraints: 10,
+        witnesses: 8,
+        failed: 1,
+        sources: [("gadget.rs".to_string(), (10, 1))].into_iter().collect(),
+        failures: [("gadget.rs".to_string(), 3)].into_iter().collect(),
+    };
+
+    let current = Snapshot {
+        constraints: 12,
+        witnesses: 8,
+        failed: 1,
+        sources: [("gadget.rs".to_string(), (12, 1))].into_iter().collect(),
+        failures: [("gadget.rs".to_string(), 7)].into_iter().collect(),
+    };
+
+    let mut delta = vec![];
+    current
+        .delta(&previous, Style::PLAIN, &mut delta)
+        .expect("failed to write the delta");
+    let delta = String::from_utf8(delta).expect("invalid utf-8");
+
+    assert!(delta.contains("(+2)"));
+    assert!(delta.contains("12 constraints (+2), 1 failures (+0)  gadget.rs"));
+    assert!(delta.contains("new failure at gadget.rs:7"));
+    assert!(delta.contains("fixed failure at gadget.rs:3"));
+}
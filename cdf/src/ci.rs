@@ -0,0 +1,307 @@
+//! Machine-readable artifacts of the failures of a circuit, rendered natively
+//! by the CI services
+
+use std::str::FromStr;
+use std::{fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::{Escape, Findings};
+use crate::{CircuitDescription, Warning};
+
+/// Format of a CI artifact
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CiFormat {
+    /// JUnit XML, a test case per failure
+    #[default]
+    Junit,
+    /// SARIF 2.1.0 log, a result per failure
+    Sarif,
+}
+
+impl FromStr for CiFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "junit" => Ok(Self::Junit),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(format!(
+                "invalid CI format `{}`; expected junit or sarif",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CiFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Junit => f.write_str("junit"),
+            Self::Sarif => f.write_str("sarif"),
+        }
+    }
+}
+
+/// Rule of the failed constraints, along with the kinds of the warnings
+const FAILED_CONSTRAINT: &str = "failedConstraint";
+
+/// Failure of a circuit, located in the sources
+struct Failure {
+    rule: &'static str,
+    error: bool,
+    message: String,
+    location: Option<(String, u64, u64)>,
+}
+
+impl Failure {
+    fn collect<S>(
+        cdf: &mut CircuitDescription<S>,
+        warnings: &[Warning],
+    ) -> io::Result<Vec<Self>>
+    where
+        S: io::Read + io::Seek,
+    {
+        let findings = Findings::scan(cdf, 0)?;
+        let mut failures = vec![];
+
+        for id in findings.failures {
+            let constraint = cdf.fetch_constraint(id)?;
+
+            failures.push(Self {
+                rule: FAILED_CONSTRAINT,
+                error: true,
+                message: format!(
+                    "constraint #{}: the {} gate evaluates to false",
+                    id,
+                    constraint.polynomial().gate_kind()
+                ),
+                location: Some((
+                    constraint.name().to_string(),
+                    constraint.line(),
+                    constraint.col(),
+                )),
+            });
+        }
+
+        for warning in findings.warnings.iter().chain(warnings) {
+            // a corrupt constraint can't be fetched, so it isn't located
+            let location = match warning {
+                Warning::NonCanonicalWitness {
+                    path, line, col, ..
+                }
+                | Warning::NonCanonicalSelector {
+                    path, line, col, ..
+                } => Some((path.clone(), *line, *col)),
+
+                Warning::UnfixedConstant { witness, .. } => cdf
+                    .fetch_witness(*witness)
+                    .ok()
+                    .map(|w| (w.name().to_string(), w.line(), w.col())),
+
+                w => w
+                    .constraint()
+                    .and_then(|id| cdf.fetch_constraint(id).ok())
+                    .map(|c| (c.name().to_string(), c.line(), c.col())),
+            };
+
+            failures.push(Self {
+                rule: warning.kind(),
+                error: false,
+                message: warning.to_string(),
+                location,
+            });
+        }
+
+        Ok(failures)
+    }
+}
+
+pub(crate) fn write<S, W>(
+    cdf: &mut CircuitDescription<S>,
+    title: &str,
+    format: CiFormat,
+    warnings: &[Warning],
+    writer: W,
+) -> io::Result<()>
+where
+    S: io::Read + io::Seek,
+    W: io::Write,
+{
+    let failures = Failure::collect(cdf, warnings)?;
+
+    match format {
+        CiFormat::Junit => junit(title, &failures, writer),
+        CiFormat::Sarif => sarif(&failures, writer),
+    }
+}
+
+fn junit<W>(title: &str, failures: &[Failure], mut writer: W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let suites = [
+        ("constraints", "the constraints evaluate correctly", true),
+        ("findings", "the lint checks find nothing", false),
+    ];
+
+    // an empty suite is reported as a passing test case
+    let errors = failures.iter().filter(|f| f.error).count();
+    let tests = errors.max(1) + (failures.len() - errors).max(1);
+
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        writer,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">",
+        Escape(title),
+        tests,
+        failures.len()
+    )?;
+
+    for (suite, passed, errors) in suites {
+        let cases: Vec<_> =
+            failures.iter().filter(|f| f.error == errors).collect();
+
+        writeln!(
+            writer,
+            "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            suite,
+            cases.len().max(1),
+            cases.len()
+        )?;
+
+        if cases.is_empty() {
+            writeln!(
+                writer,
+                "<testcase name=\"{}\" classname=\"{}\"/>",
+                passed, suite
+            )?;
+        }
+
+        for case in cases {
+            let location = case.location.as_ref();
+
+            write!(
+                writer,
+                "<testcase name=\"{}\" classname=\"{}\"",
+                Escape(&case.message),
+                case.rule
+            )?;
+
+            if let Some((name, line, _)) = location {
+                write!(writer, " file=\"{}\" line=\"{}\"", Escape(name), line)?;
+            }
+
+            write!(
+                writer,
+                ">\n<failure message=\"{}\" type=\"{}\">",
+                Escape(&case.message),
+                case.rule
+            )?;
+
+            if let Some((name, line, col)) = location {
+                write!(writer, "{}:{}:{}", Escape(name), line, col)?;
+            }
+
+            writeln!(writer, "</failure>\n</testcase>")?;
+        }
+
+        writeln!(writer, "</testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")?;
+    writer.flush()
+}
+
+fn sarif<W>(failures: &[Failure], mut writer: W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let mut rules: Vec<_> = failures.iter().map(|f| f.rule).collect();
+
+    rules.sort_unstable();
+    rules.dedup();
+
+    let rules: Vec<_> = rules
+        .iter()
+        .map(|r| format!("{{\"id\":{}}}", Json(r)))
+        .collect();
+
+    let results: Vec<_> = failures
+        .iter()
+        .map(|f| {
+            let level = if f.error { "error" } else { "warning" };
+
+            let locations = match &f.location {
+                Some((name, line, col)) => {
+                    // the regions are 1-based; an unknown column is omitted
+                    let col = match *col {
+                        0 => String::new(),
+                        c => format!(",\"startColumn\":{}", c),
+                    };
+
+                    format!(
+                        "[{{\"physicalLocation\":{{\"artifactLocation\":\
+                         {{\"uri\":{}}},\"region\":{{\"startLine\":{}{}}}}}}}]",
+                        Json(name),
+                        (*line).max(1),
+                        col
+                    )
+                }
+                None => "[]".into(),
+            };
+
+            format!(
+                "{{\"ruleId\":{},\"level\":\"{}\",\"message\":{{\"text\":{}}},\
+                 \"locations\":{}}}",
+                Json(f.rule),
+                level,
+                Json(&f.message),
+                locations
+            )
+        })
+        .collect();
+
+    writeln!(
+        writer,
+        "{{\"version\":\"2.1.0\",\
+         \"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\
+         \"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"cdf\",\
+         \"version\":\"{}\",\"rules\":[{}]}}}},\"results\":[{}]}}]}}",
+        env!("CARGO_PKG_VERSION"),
+        rules.join(","),
+        results.join(",")
+    )?;
+
+    writer.flush()
+}
+
+/// JSON string literal
+struct Json<'a>(&'a str);
+
+impl fmt::Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+
+        self.0.chars().try_for_each(|c| match c {
+            '"' => f.write_str("\\\""),
+            '\\' => f.write_str("\\\\"),
+            '\n' => f.write_str("\\n"),
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32),
+            c => write!(f, "{}", c),
+        })?;
+
+        f.write_str("\"")
+    }
+}
+
+#[test]
+fn json_strings_are_escaped() {
+    assert_eq!(
+        Json("a \"b\"\\\n\u{1b}").to_string(),
+        "\"a \\\"b\\\"\\\\\\n\\u001b\""
+    );
+}
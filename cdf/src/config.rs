@@ -1,7 +1,8 @@
 //! CDF encoding/encoding configuration
 
 use core::mem;
-use std::io;
+use std::str::FromStr;
+use std::{fmt, io};
 
 use serde::{Deserialize, Serialize};
 use toml_base_config::BaseConfig;
@@ -18,7 +19,162 @@ use crate::{
 pub struct Config {
     /// Flag to zero skip scalar values during encoding, and zero them during
     /// decoding.
+    #[serde(default)]
     pub zeroed_scalar_values: bool,
+    /// Flag to check the wired witnesses of the constraints against the
+    /// witness table when they are decoded.
+    ///
+    /// This is a decoding parameter and isn't stored in the CDF file.
+    #[serde(default)]
+    pub check_wires: bool,
+    /// Policy for the corrupt constraint records found while debugging.
+    ///
+    /// This is a decoding parameter and isn't stored in the CDF file.
+    #[serde(default)]
+    pub corruption: CorruptionPolicy,
+    /// Policy for the sources embedded in the CDF file that aren't valid
+    /// UTF-8.
+    ///
+    /// This is a decoding parameter and isn't stored in the CDF file.
+    #[serde(default)]
+    pub utf8: Utf8Policy,
+    /// Bounds of the source cache accepted when a CDF file is loaded.
+    ///
+    /// This is a decoding parameter and isn't stored in the CDF file.
+    #[serde(default)]
+    pub cache_limits: CacheLimits,
+    /// Expected maximum number of constraints of the circuit, to track its
+    /// size regressions.
+    ///
+    /// This is an encoding parameter and isn't stored in the CDF file.
+    #[serde(default)]
+    pub max_constraints: Option<usize>,
+    /// Behavior of the encoder when the circuit exceeds
+    /// [`Config::max_constraints`].
+    ///
+    /// This is an encoding parameter and isn't stored in the CDF file.
+    #[serde(default)]
+    pub budget: BudgetPolicy,
+}
+
+/// Behavior of the encoder when a circuit exceeds its constraint budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BudgetPolicy {
+    /// Encode the circuit, reporting the overrun to the callback of the
+    /// encoder
+    Warn,
+    /// Reject the circuit before writing anything
+    Error,
+}
+
+impl Default for BudgetPolicy {
+    fn default() -> Self {
+        Self::Warn
+    }
+}
+
+/// Behavior of the debugger when a constraint record can't be decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CorruptionPolicy {
+    /// Stop at the corrupt constraint, so it can be inspected
+    Stop,
+    /// Skip the corrupt constraint, reporting it as a warning
+    Skip,
+}
+
+impl Default for CorruptionPolicy {
+    fn default() -> Self {
+        Self::Stop
+    }
+}
+
+/// Bounds of the source cache of a CDF file, and of the memory held by the
+/// caches of the decoder.
+///
+/// The sizes declared by the cache are checked before its sources are read,
+/// so a malformed file is rejected instead of exhausting the memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheLimits {
+    /// Maximum number of sources
+    pub max_sources: usize,
+    /// Maximum size in bytes of the name or the contents of a single source
+    pub max_source_size: usize,
+    /// Maximum size in bytes of the names and contents of all the sources
+    pub max_cache_size: usize,
+    /// Memory budget in bytes of the caches of the decoder and the debugger.
+    ///
+    /// The caches that can be rebuilt are dropped while the budget is
+    /// exceeded; see [`MemoryUsage`](struct.MemoryUsage.html).
+    pub max_memory: usize,
+}
+
+impl CacheLimits {
+    /// Store a const default of 65536 sources of up to 16 MiB each, adding up
+    /// to 256 MiB, and a memory budget of 1 GiB.
+    pub const DEFAULT: Self = Self {
+        max_sources: 1 << 16,
+        max_source_size: 16 << 20,
+        max_cache_size: 256 << 20,
+        max_memory: 1 << 30,
+    };
+
+    /// Lift the bounds, for the trusted files that exceed the defaults
+    pub const UNLIMITED: Self = Self {
+        max_sources: usize::MAX,
+        max_source_size: usize::MAX,
+        max_cache_size: usize::MAX,
+        max_memory: usize::MAX,
+    };
+}
+
+impl Default for CacheLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Decoding of the embedded sources that aren't valid UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Utf8Policy {
+    /// Reject the CDF file
+    Strict,
+    /// Replace the invalid sequences with `U+FFFD`, reporting the source as a
+    /// warning
+    Lenient,
+}
+
+impl Default for Utf8Policy {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+impl FromStr for Utf8Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Self::Strict),
+            "lenient" => Ok(Self::Lenient),
+            _ => Err(format!(
+                "invalid utf-8 policy `{}`; expected strict or lenient",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Utf8Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strict => f.write_str("strict"),
+            Self::Lenient => f.write_str("lenient"),
+        }
+    }
 }
 
 impl Default for Config {
@@ -31,13 +187,96 @@ impl Config {
     /// Serialized length.
     pub const LEN: usize = mem::size_of::<bool>();
 
-    /// Store a const default with [`zeroed_scalar_values`] set to false.
+    /// Bit of the encoded byte that stores [`Config::zeroed_scalar_values`].
+    /// The remaining bits are reserved for the flags of the [`Preamble`].
+    pub(crate) const ZEROED_SCALAR_VALUES: u8 = 0x01;
+
+    /// Store a const default with [`zeroed_scalar_values`] and
+    /// [`check_wires`] set to false, stopping at corrupt constraints,
+    /// rejecting the sources that aren't valid UTF-8, bounding the source
+    /// cache with [`CacheLimits::DEFAULT`] and without a constraint budget.
     ///
     /// [`zeroed_scalar_values`]: structfield.zeroed_scalar_values
+    /// [`check_wires`]: structfield.check_wires
     pub const DEFAULT: Self = Self {
         zeroed_scalar_values: false,
+        check_wires: false,
+        corruption: CorruptionPolicy::Stop,
+        utf8: Utf8Policy::Strict,
+        cache_limits: CacheLimits::DEFAULT,
+        max_constraints: None,
+        budget: BudgetPolicy::Warn,
     };
 
+    /// Store the scalar values and check the wired witnesses of the decoded
+    /// constraints, stopping at the corrupt ones.
+    ///
+    /// This is the profile for debugging a circuit with every detail
+    /// available.
+    pub const fn full() -> Self {
+        Self {
+            check_wires: true,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Skip the scalar values, keeping the gates, the wiring and the sources
+    /// of the circuit. The wired witnesses are still checked, as they don't
+    /// depend on the values.
+    pub const fn structure_only() -> Self {
+        Self {
+            zeroed_scalar_values: true,
+            check_wires: true,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Skip the scalar values so the witnesses of the prover aren't disclosed,
+    /// leaving the decoding checks to the receiver of the file.
+    pub const fn redacted() -> Self {
+        Self {
+            zeroed_scalar_values: true,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Produce the smallest file, skipping the scalar values, and decode it
+    /// without checks, skipping the corrupt constraints.
+    ///
+    /// The format has no compression: the file is smaller only because the
+    /// scalar values aren't stored.
+    pub const fn compressed() -> Self {
+        Self {
+            zeroed_scalar_values: true,
+            corruption: CorruptionPolicy::Skip,
+            ..Self::DEFAULT
+        }
+    }
+
+    /// Create a builder starting from [`Config::DEFAULT`], or use
+    /// [`ConfigBuilder::from`] to start from a preset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Config, ConfigBuilder, CorruptionPolicy};
+    ///
+    /// let config = Config::builder().check_wires(true).build();
+    /// assert_eq!(config, *Config::default().with_check_wires(true));
+    ///
+    /// let config = ConfigBuilder::from(Config::redacted())
+    ///     .corruption(CorruptionPolicy::Skip)
+    ///     .build();
+    ///
+    /// assert!(config.zeroed_scalar_values);
+    /// assert_eq!(config.corruption, CorruptionPolicy::Skip);
+    /// ```
+    pub const fn builder() -> ConfigBuilder {
+        ConfigBuilder {
+            config: Self::DEFAULT,
+        }
+    }
+
     /// If true, then don't store the scalar values and deserialize them as zero
     /// in [`Scalar`](struct.Scalar.html).
     pub fn with_zeroed_scalar_values(
@@ -47,6 +286,117 @@ impl Config {
         self.zeroed_scalar_values = zeroed_scalar_values;
         self
     }
+
+    /// If true, the wired witnesses of the decoded constraints will be
+    /// checked against the witness table, producing
+    /// [`Warning`](enum.Warning.html)s for the inconsistencies.
+    pub fn with_check_wires(&mut self, check_wires: bool) -> &mut Self {
+        self.check_wires = check_wires;
+        self
+    }
+
+    /// Define how the debugger handles the constraint records that can't be
+    /// decoded.
+    pub fn with_corruption(
+        &mut self,
+        corruption: CorruptionPolicy,
+    ) -> &mut Self {
+        self.corruption = corruption;
+        self
+    }
+
+    /// Define how the sources that aren't valid UTF-8 are decoded.
+    pub fn with_utf8(&mut self, utf8: Utf8Policy) -> &mut Self {
+        self.utf8 = utf8;
+        self
+    }
+
+    /// Bound the source cache accepted when a CDF file is loaded.
+    pub fn with_cache_limits(
+        &mut self,
+        cache_limits: CacheLimits,
+    ) -> &mut Self {
+        self.cache_limits = cache_limits;
+        self
+    }
+
+    /// Declare the expected maximum number of constraints of the circuit,
+    /// checked by the encoder according to [`Config::budget`].
+    pub fn with_max_constraints(
+        &mut self,
+        max_constraints: Option<usize>,
+    ) -> &mut Self {
+        self.max_constraints = max_constraints;
+        self
+    }
+
+    /// Define how the encoder handles a circuit that exceeds
+    /// [`Config::max_constraints`].
+    pub fn with_budget(&mut self, budget: BudgetPolicy) -> &mut Self {
+        self.budget = budget;
+        self
+    }
+}
+
+/// Builder for a [`Config`], created via [`Config::builder`] or from a preset.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// Set [`Config::zeroed_scalar_values`]
+    pub const fn zeroed_scalar_values(mut self, zeroed: bool) -> Self {
+        self.config.zeroed_scalar_values = zeroed;
+        self
+    }
+
+    /// Set [`Config::check_wires`]
+    pub const fn check_wires(mut self, check_wires: bool) -> Self {
+        self.config.check_wires = check_wires;
+        self
+    }
+
+    /// Set [`Config::corruption`]
+    pub const fn corruption(mut self, corruption: CorruptionPolicy) -> Self {
+        self.config.corruption = corruption;
+        self
+    }
+
+    /// Set [`Config::utf8`]
+    pub const fn utf8(mut self, utf8: Utf8Policy) -> Self {
+        self.config.utf8 = utf8;
+        self
+    }
+
+    /// Set [`Config::cache_limits`]
+    pub const fn cache_limits(mut self, cache_limits: CacheLimits) -> Self {
+        self.config.cache_limits = cache_limits;
+        self
+    }
+
+    /// Set [`Config::max_constraints`]
+    pub const fn max_constraints(mut self, max: Option<usize>) -> Self {
+        self.config.max_constraints = max;
+        self
+    }
+
+    /// Set [`Config::budget`]
+    pub const fn budget(mut self, budget: BudgetPolicy) -> Self {
+        self.config.budget = budget;
+        self
+    }
+
+    /// Build the configuration
+    pub const fn build(self) -> Config {
+        self.config
+    }
+}
+
+impl From<Config> for ConfigBuilder {
+    fn from(config: Config) -> Self {
+        Self { config }
+    }
 }
 
 impl BaseConfig for Config {
@@ -79,7 +429,7 @@ impl DecodableElement for Config {
     ) -> io::Result<()> {
         Self::validate_buffer(ctx.config(), buf)?;
 
-        let _ = self.zeroed_scalar_values.try_decode_in_place(ctx, buf)?;
+        self.zeroed_scalar_values = buf[0] & Self::ZEROED_SCALAR_VALUES != 0;
 
         Ok(())
     }
@@ -105,3 +455,31 @@ fn base_config_load_works() {
 
     Config::load().expect("failed to load default config");
 }
+
+#[test]
+fn config_presets_encode_as_their_flags() {
+    let presets = [
+        Config::full(),
+        Config::structure_only(),
+        Config::redacted(),
+        Config::compressed(),
+    ];
+
+    for preset in presets {
+        let bytes = preset.to_vec(&mut EncoderContext::default());
+        let decoded = Config::try_from_buffer(&DecoderContext::BASE, &bytes)
+            .expect("failed to decode the preset");
+
+        // only the encoding flags are stored in the file
+        let expected = Config::builder()
+            .zeroed_scalar_values(preset.zeroed_scalar_values)
+            .build();
+
+        assert_eq!(decoded, expected);
+    }
+
+    assert!(!Config::full().zeroed_scalar_values);
+    assert!(Config::structure_only().check_wires);
+    assert!(!Config::redacted().check_wires);
+    assert_eq!(Config::compressed().corruption, CorruptionPolicy::Skip);
+}
@@ -1,10 +1,12 @@
-use std::io;
+use std::str::FromStr;
+use std::{fmt, io};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Config, DecodableElement, DecodedSource, DecoderContext, Element,
     EncodableElement, EncodableSource, EncoderContext, Polynomial, Preamble,
+    Scalar, Selector, SourceId, Style, Wire, Witness,
 };
 
 /// Analogous to [`Constraint`]. This is a constraint that can be encoded into a
@@ -13,7 +15,16 @@ use crate::{
 /// This allows the [`Encoder`](struct.Encoder.html) to encode the constraint
 /// into a cdf file.
 #[derive(
-    Debug, Default, Clone, PartialEq, PartialOrd, Ord, Eq, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct EncodableConstraint {
     id: usize,
@@ -115,7 +126,8 @@ impl<'a> Constraint<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{CircuitDescription, ZkDebugger, Breakpoint};
     /// use std::fs::File;
@@ -136,7 +148,8 @@ impl<'a> Constraint<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{CircuitDescription, ZkDebugger, Breakpoint};
     /// use std::fs::File;
@@ -159,7 +172,8 @@ impl<'a> Constraint<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{CircuitDescription, ZkDebugger, Breakpoint};
     /// use std::fs::File;
@@ -179,7 +193,8 @@ impl<'a> Constraint<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{CircuitDescription, ZkDebugger, Breakpoint};
     /// use std::fs::File;
@@ -199,7 +214,8 @@ impl<'a> Constraint<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{CircuitDescription, ZkDebugger, Breakpoint};
     /// use std::fs::File;
@@ -215,11 +231,41 @@ impl<'a> Constraint<'a> {
         self.source.name
     }
 
-    /// Get the source code contents as a string.
+    /// Get the interned id of the source file, shared by the items of a same
+    /// file
     ///
     /// # Example
     ///
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let mut debugger = ZkDebugger::from_reader(file)?;
+    ///
+    /// let constraint = debugger.fetch_constraint(0)?;
+    /// let (source, name) = (constraint.source_id(), constraint.name().to_string());
+    ///
+    /// // the constraints 0 and 1 are declared in the same file
+    /// let constraint = debugger.fetch_constraint(1)?;
+    ///
+    /// assert_eq!(name, constraint.name());
+    /// assert_eq!(source, constraint.source_id());
+    ///
+    /// # Ok(()) }
     /// ```
+    pub const fn source_id(&self) -> SourceId {
+        self.source.id
+    }
+
+    /// Get the source code contents as a string.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{CircuitDescription, ZkDebugger, Breakpoint};
     /// use std::fs::File;
@@ -265,3 +311,489 @@ impl<'a> DecodableElement for Constraint<'a> {
         Ok(())
     }
 }
+
+/// Terms of the arithmetic equation of a polynomial, along with their
+/// selector
+const EQUATION_TERMS: [(Selector, &str); 7] = [
+    (Selector::Qm, "qm·a·b"),
+    (Selector::Ql, "ql·a"),
+    (Selector::Qr, "qr·b"),
+    (Selector::Qd, "qd·d"),
+    (Selector::Qo, "qo·o"),
+    (Selector::Qc, "qc"),
+    (Selector::Pi, "pi"),
+];
+
+/// Terms of the arithmetic equation of a polynomial in the notations of
+/// [`Notation`], along with their selector and wires
+const NOTATION_TERMS: [(Selector, &str, &str, &[Wire]); 7] = [
+    (Selector::Qm, "q_M", "q_M", &[Wire::A, Wire::B]),
+    (Selector::Ql, "q_L", "q_L", &[Wire::A]),
+    (Selector::Qr, "q_R", "q_R", &[Wire::B]),
+    (Selector::Qd, "q_D", "q_D", &[Wire::D]),
+    (Selector::Qo, "q_O", "q_O", &[Wire::O]),
+    (Selector::Qc, "q_C", "q_C", &[]),
+    (Selector::Pi, "PI", "\\mathrm{PI}", &[]),
+];
+
+/// Notation of a rendered equation
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Notation {
+    /// Plain text with Unicode operators, such as `q_M·a·b + q_C = 0`
+    #[default]
+    Unicode,
+    /// LaTeX math, such as `q_M \cdot a \cdot b + q_C = 0`
+    Latex,
+}
+
+impl Notation {
+    const fn product(&self) -> &'static str {
+        match self {
+            Self::Unicode => "·",
+            Self::Latex => " \\cdot ",
+        }
+    }
+
+    const fn relation(&self, evaluation: bool) -> &'static str {
+        match (self, evaluation) {
+            (_, true) => "=",
+            (Self::Unicode, false) => "≠",
+            (Self::Latex, false) => "\\neq",
+        }
+    }
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unicode" => Ok(Self::Unicode),
+            "latex" => Ok(Self::Latex),
+            _ => Err(format!(
+                "invalid notation `{}`; expected unicode or latex",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unicode => f.write_str("unicode"),
+            Self::Latex => f.write_str("latex"),
+        }
+    }
+}
+
+/// A decoded constraint along with the witnesses wired to its polynomial.
+///
+/// Created by [`ZkDebugger::resolve_current_constraint`] and
+/// [`CircuitDescription::resolve_constraint`].
+///
+/// [`ZkDebugger::resolve_current_constraint`]: struct.ZkDebugger.html#method.resolve_current_constraint
+/// [`CircuitDescription::resolve_constraint`]: struct.CircuitDescription.html#method.resolve_constraint
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ResolvedConstraint<'a> {
+    /// Decoded constraint
+    pub constraint: Constraint<'a>,
+    /// Witness wired to `a`
+    pub a: Witness<'a>,
+    /// Witness wired to `b`
+    pub b: Witness<'a>,
+    /// Witness wired to `d`
+    pub d: Witness<'a>,
+    /// Witness wired to `o`
+    pub o: Witness<'a>,
+}
+
+impl<'a> ResolvedConstraint<'a> {
+    /// Witness wired to `wire`
+    pub const fn wired(&self, wire: Wire) -> &Witness<'a> {
+        match wire {
+            Wire::A => &self.a,
+            Wire::B => &self.b,
+            Wire::D => &self.d,
+            Wire::O => &self.o,
+        }
+    }
+
+    /// Arithmetic equation of the nonzero terms of the polynomial, such as
+    /// `qm·a·b + qc = 0`
+    pub fn equation(&self) -> String {
+        let selectors = &self.constraint.polynomial().selectors;
+        let terms: Vec<_> = EQUATION_TERMS
+            .iter()
+            .filter(|(s, _)| !s.of(selectors).is_zero())
+            .map(|(_, term)| *term)
+            .collect();
+
+        match terms.is_empty() {
+            true => "0 = 0".into(),
+            false => format!("{} = 0", terms.join(" + ")),
+        }
+    }
+
+    /// Arithmetic equation of the nonzero terms of the polynomial in
+    /// `notation`, such as `q_M·a·b + q_C = 0`. A failed evaluation is
+    /// rendered as an inequation.
+    pub fn equation_in(&self, notation: Notation) -> String {
+        let evaluation = self.constraint.polynomial().evaluation;
+
+        format!(
+            "{} {} 0",
+            self.terms_in(notation),
+            notation.relation(evaluation)
+        )
+    }
+
+    /// Sum of the nonzero terms of the polynomial in `notation`
+    fn terms_in(&self, notation: Notation) -> String {
+        let selectors = &self.constraint.polynomial().selectors;
+        let terms: Vec<_> = NOTATION_TERMS
+            .iter()
+            .filter(|(s, ..)| !s.of(selectors).is_zero())
+            .map(|(_, unicode, latex, wires)| {
+                let symbol = match notation {
+                    Notation::Unicode => unicode,
+                    Notation::Latex => latex,
+                };
+
+                wires.iter().fold(symbol.to_string(), |term, wire| {
+                    format!("{}{}{}", term, notation.product(), wire)
+                })
+            })
+            .collect();
+
+        match terms.is_empty() {
+            true => "0".into(),
+            false => terms.join(" + "),
+        }
+    }
+
+    /// Equation of the constraint rendered in `notation`, followed by the
+    /// values of its nonzero selectors and of the witnesses of its terms.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, Notation};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let resolved = circuit.resolve_constraint(3)?;
+    ///
+    /// let unicode = resolved.rendered(Notation::Unicode).to_string();
+    /// let equation = resolved.equation_in(Notation::Unicode);
+    ///
+    /// assert_eq!(unicode.lines().next(), Some(equation.as_str()));
+    ///
+    /// let latex = resolved.rendered(Notation::Latex).to_string();
+    ///
+    /// assert!(latex.starts_with("\\begin{aligned}"));
+    /// assert!(latex.ends_with("\\end{aligned}\n"));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub const fn rendered(&self, notation: Notation) -> Equation<'_, 'a> {
+        Equation {
+            resolved: self,
+            notation,
+        }
+    }
+
+    /// Lines of the source within `context` lines of the constraint, along
+    /// with their number
+    pub fn excerpt(
+        &self,
+        context: usize,
+    ) -> impl Iterator<Item = (usize, &'a str)> {
+        let constraint = &self.constraint;
+        let line = constraint.line() as usize;
+
+        // the lines of the sources start at 1
+        let first = line.saturating_sub(context).max(1);
+
+        constraint
+            .source
+            .contents
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .skip(first - 1)
+            .take_while(move |(i, _)| *i <= line + context)
+    }
+
+    /// Single line listing of the constraint, displayed with `style`.
+    ///
+    /// The failed evaluations are highlighted, and the scalars dimmed.
+    pub const fn listing(&self, style: Style) -> Listing<'_, 'a> {
+        Listing {
+            resolved: self,
+            style,
+        }
+    }
+
+    /// Markdown snippet of the constraint, to be pasted in issues and chats.
+    ///
+    /// The snippet has the equation, the selectors, the wires and an excerpt
+    /// of the source with `context` lines around the constraint.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let resolved = circuit.resolve_constraint(3)?;
+    /// let snippet = resolved.markdown(2).to_string();
+    ///
+    /// assert!(snippet.starts_with("### Constraint #3"));
+    /// assert!(snippet.contains("| wire | witness | value |"));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub const fn markdown(&self, context: usize) -> Markdown<'_, 'a> {
+        Markdown {
+            resolved: self,
+            context,
+        }
+    }
+}
+
+/// Single line listing of the constraint: id, gate kind, evaluation, the
+/// nonzero selectors, the wired witnesses as `wire=#id:value` and the source
+/// location.
+impl fmt::Display for ResolvedConstraint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.listing(Style::PLAIN).fmt(f)
+    }
+}
+
+/// Styled listing of a [`ResolvedConstraint`], created via
+/// [`ResolvedConstraint::listing`]
+#[derive(Debug, Clone, Copy)]
+pub struct Listing<'r, 'a> {
+    resolved: &'r ResolvedConstraint<'a>,
+    style: Style,
+}
+
+impl fmt::Display for Listing<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { resolved, style } = self;
+
+        let constraint = &resolved.constraint;
+        let polynomial = constraint.polynomial();
+
+        write!(f, "#{} {} ", constraint.id(), polynomial.gate_kind())?;
+
+        if polynomial.evaluation {
+            f.write_str("ok")?;
+        } else {
+            write!(f, "{}", style.failure("fail"))?;
+        }
+
+        for selector in Selector::ALL {
+            let value = selector.of(&polynomial.selectors);
+
+            if !value.is_zero() {
+                write!(f, " {}={}", selector, style.scalar(Hex(value)))?;
+            }
+        }
+
+        for wire in Wire::ALL {
+            let witness = resolved.wired(wire);
+            let value = style.scalar(Hex(witness.value()));
+
+            write!(f, " {}=#{}:{}", wire, witness.id(), value)?;
+        }
+
+        write!(
+            f,
+            " {}:{}:{}",
+            constraint.name(),
+            constraint.line(),
+            constraint.col()
+        )
+    }
+}
+
+/// Markdown snippet of a [`ResolvedConstraint`], created via
+/// [`ResolvedConstraint::markdown`]
+#[derive(Debug, Clone, Copy)]
+pub struct Markdown<'r, 'a> {
+    resolved: &'r ResolvedConstraint<'a>,
+    context: usize,
+}
+
+impl fmt::Display for Markdown<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { resolved, context } = self;
+
+        let constraint = &resolved.constraint;
+        let polynomial = constraint.polynomial();
+        let line = constraint.line() as usize;
+
+        writeln!(
+            f,
+            "### Constraint #{}: {} gate, {}\n",
+            constraint.id(),
+            polynomial.gate_kind(),
+            if polynomial.evaluation {
+                "ok"
+            } else {
+                "failed"
+            }
+        )?;
+
+        writeln!(
+            f,
+            "`{}:{}:{}`\n",
+            constraint.name(),
+            constraint.line(),
+            constraint.col()
+        )?;
+
+        f.write_str("```rust\n")?;
+        for (i, l) in resolved.excerpt(*context) {
+            let marker = if i == line { '>' } else { ' ' };
+
+            writeln!(f, "{} {:>5} | {}", marker, i, l)?;
+        }
+        f.write_str("```\n\n")?;
+
+        writeln!(f, "`{}`\n", resolved.equation())?;
+
+        f.write_str("| selector | value |\n|---|---|\n")?;
+        for selector in Selector::ALL {
+            let value = selector.of(&polynomial.selectors);
+
+            if !value.is_zero() {
+                writeln!(f, "| {} | `{}` |", selector, Hex(value))?;
+            }
+        }
+
+        f.write_str("\n| wire | witness | value |\n|---|---|---|\n")?;
+        for wire in Wire::ALL {
+            let witness = resolved.wired(wire);
+
+            writeln!(
+                f,
+                "| {} | #{} | `{}` |",
+                wire,
+                witness.id(),
+                Hex(witness.value())
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Equation of a [`ResolvedConstraint`] along with the values of its terms,
+/// created via [`ResolvedConstraint::rendered`]
+#[derive(Debug, Clone, Copy)]
+pub struct Equation<'r, 'a> {
+    resolved: &'r ResolvedConstraint<'a>,
+    notation: Notation,
+}
+
+impl fmt::Display for Equation<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { resolved, notation } = self;
+
+        let selectors = &resolved.constraint.polynomial().selectors;
+        let terms: Vec<_> = NOTATION_TERMS
+            .iter()
+            .filter(|(s, ..)| !s.of(selectors).is_zero())
+            .collect();
+
+        // the wires of the terms, in the order of the polynomial
+        let wires = Wire::ALL
+            .iter()
+            .filter(|w| terms.iter().any(|(.., wires)| wires.contains(*w)));
+
+        match notation {
+            Notation::Unicode => {
+                writeln!(f, "{}", resolved.equation_in(*notation))?;
+
+                for (selector, symbol, ..) in &terms {
+                    writeln!(
+                        f,
+                        "{} = {}",
+                        symbol,
+                        Hex(selector.of(selectors))
+                    )?;
+                }
+
+                for wire in wires {
+                    let witness = resolved.wired(*wire);
+
+                    writeln!(
+                        f,
+                        "{} = {} (witness #{})",
+                        wire,
+                        Hex(witness.value()),
+                        witness.id()
+                    )?;
+                }
+            }
+
+            Notation::Latex => {
+                let evaluation = resolved.constraint.polynomial().evaluation;
+
+                // the relations are aligned
+                writeln!(f, "\\begin{{aligned}}")?;
+                write!(
+                    f,
+                    "{} &{} 0",
+                    resolved.terms_in(*notation),
+                    notation.relation(evaluation)
+                )?;
+
+                for (selector, _, symbol, _) in &terms {
+                    write!(
+                        f,
+                        " \\\\\n{} &= \\texttt{{{}}}",
+                        symbol,
+                        Hex(selector.of(selectors))
+                    )?;
+                }
+
+                for wire in wires {
+                    let witness = resolved.wired(*wire);
+
+                    write!(
+                        f,
+                        " \\\\\n{} &= \\texttt{{{}}} && \\text{{witness \\#{}}}",
+                        wire,
+                        Hex(witness.value()),
+                        witness.id()
+                    )?;
+                }
+
+                writeln!(f, "\n\\end{{aligned}}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Hexadecimal representation of a scalar, as in the print view
+pub(crate) struct Hex<'a>(pub &'a Scalar);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("0x")?;
+
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
@@ -0,0 +1,9 @@
+//! Contexts shared by the elements while encoding and decoding
+
+mod decoder;
+mod encoder;
+
+pub use decoder::DecoderContext;
+pub use encoder::{
+    EncoderContext, EncoderContextFileProvider, EncoderContextProvider,
+};
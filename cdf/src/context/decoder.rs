@@ -1,4 +1,4 @@
-use crate::Config;
+use crate::{Config, SourceId};
 
 /// Decoding context of a CDF file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -6,24 +6,30 @@ pub struct DecoderContext<'a> {
     config: &'a Config,
     source_names: &'a [String],
     source_contents: &'a [String],
+    source_ids: &'a [SourceId],
 }
 
 impl<'a> DecoderContext<'a> {
+    #[cfg(feature = "decoder")]
     pub(crate) const BASE: Self = Self {
         config: &Config::DEFAULT,
         source_names: &[],
         source_contents: &[],
+        source_ids: &[],
     };
 
+    #[cfg(feature = "decoder")]
     pub(crate) const fn new(
         config: &'a Config,
         source_names: &'a [String],
         source_contents: &'a [String],
+        source_ids: &'a [SourceId],
     ) -> Self {
         Self {
             config,
             source_names,
             source_contents,
+            source_ids,
         }
     }
 
@@ -41,12 +47,18 @@ impl<'a> DecoderContext<'a> {
     pub fn fetch_contents(&self, id: usize) -> Option<&'a str> {
         self.source_contents.get(id).map(|s| s.as_str())
     }
+
+    /// Fetch the interned id of a file indexed by `id`.
+    pub fn fetch_id(&self, id: usize) -> Option<SourceId> {
+        self.source_ids.get(id).copied()
+    }
 }
 
 #[test]
+#[cfg(feature = "decoder")]
 fn base_is_valid() {
     assert_eq!(
-        DecoderContext::new(&Config::default(), &[], &[]),
+        DecoderContext::new(&Config::default(), &[], &[], &[]),
         DecoderContext::BASE
     );
 }
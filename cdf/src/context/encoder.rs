@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::sync::Arc;
 use std::{fs, io};
 
+#[cfg(feature = "encoder")]
 use msgpacker::Message;
 
 use crate::{Config, Preamble};
@@ -46,11 +48,14 @@ where
     }
 }
 
-/// Context of encoding a CDF file
+/// Context of encoding a CDF file.
+///
+/// The path cache is shared between the clones of the context, and copied
+/// only when a clone registers a new path.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct EncoderContext {
     preamble: Preamble,
-    path_cache: HashMap<String, usize>,
+    path_cache: Arc<HashMap<String, usize>>,
 }
 
 impl EncoderContext {
@@ -58,10 +63,11 @@ impl EncoderContext {
     ///
     /// This function is not intended to be called outside the encoder
     /// initialization so we don't have duplicated contexts.
+    #[cfg(feature = "encoder")]
     pub(crate) fn from_preamble(preamble: Preamble) -> Self {
         Self {
             preamble,
-            path_cache: HashMap::new(),
+            path_cache: Arc::default(),
         }
     }
 
@@ -81,12 +87,20 @@ impl EncoderContext {
         P: Into<String>,
     {
         let path = path.into();
+
+        if let Some(idx) = self.path_cache.get(&path) {
+            return *idx;
+        }
+
         let len = self.path_cache.len();
 
-        *self.path_cache.entry(path).or_insert(len)
+        Arc::make_mut(&mut self.path_cache).insert(path, len);
+
+        len
     }
 }
 
+#[cfg(feature = "encoder")]
 impl EncoderContext {
     pub fn write_all<P, W>(
         &self,
@@ -129,10 +143,11 @@ impl Deref for EncoderContext {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "encoder"))]
 use std::path::PathBuf;
 
 #[test]
+#[cfg(feature = "encoder")]
 fn path_cache_is_not_duplicated() {
     let main = PathBuf::from("home")
         .join("zkp-debugger")
@@ -160,6 +175,7 @@ fn path_cache_is_not_duplicated() {
 }
 
 #[test]
+#[cfg(feature = "encoder")]
 fn context_derives_expected_map() {
     let main = PathBuf::from("home")
         .join("zkp-debugger")
@@ -191,6 +207,7 @@ fn context_derives_expected_map() {
 }
 
 #[test]
+#[cfg(feature = "encoder")]
 fn preamble_is_correctly_created() {
     // TODO test all permutations of preamble in integration/fuzz
     let preamble = Preamble::default();
@@ -1,26 +1,57 @@
 //! Debug Adapter Protocol provider
 
+mod client;
+mod connection;
+mod diagnostics;
+mod overflow;
+mod position;
 mod types;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
+use std::collections::VecDeque;
+use std::env;
 use std::fs::File;
 use std::io;
+use std::mem;
 use std::net::SocketAddr;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use dap_reactor::prelude::*;
 use tokio::net;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, oneshot, Mutex, OwnedMutexGuard, RwLock, Semaphore};
+use tokio::time;
+use toml_base_config::BaseConfig;
 
-use crate::{State, ZkDebugger};
+use crate::{
+    Config, Focus, GateKind, Movement, Notation, PatternConfig, PatternLibrary,
+    ResolvedConstraint, SharedZkDebugger, State, StateChange, ZkDebugger,
+};
 
+pub use client::{BackendCall, BackendReply, ZkDapClient};
+pub use overflow::{OverflowPolicy, ZkDapConfig};
 pub use types::*;
 
+use diagnostics::{Circuit, Diagnostics, Panicked};
+use overflow::Outputs;
+use position::Positions;
+
+/// Expression evaluated to the markdown snippet of the current constraint,
+/// such as when its variable is copied to the clipboard
+const SNIPPET_EXPRESSION: &str = "constraint";
+
+/// Source lines around the current one in a markdown snippet
+const SNIPPET_CONTEXT: usize = 3;
+
+/// Interval between the progress events of a running scan; the scans
+/// completing within it aren't reported
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Builder for the [`ZkDap`] service
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ZkDapBuilder<S> {
@@ -44,6 +75,36 @@ impl<S> ZkDapBuilder<S> {
         self.capacity = capacity;
         self
     }
+
+    /// Run the [`ZkDebugger`] via DAP in-process, reached through the
+    /// returned channels instead of a socket.
+    ///
+    /// The socket of the builder is ignored. The service runs until the
+    /// requests sender is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn build_local(self) -> ZkDapLocal {
+        let capacity = self.capacity;
+
+        let (requests, requests_rx) = mpsc::channel(capacity);
+        let (responses_tx, responses) = mpsc::channel(capacity);
+        let (events_tx, events) = mpsc::channel(capacity);
+
+        tokio::spawn(async move {
+            let (reverse, _reverse_rx) = mpsc::channel(capacity);
+            let dap = ZkDap::init(events_tx, reverse).await;
+
+            dap.dispatch(requests_rx, responses_tx).await;
+        });
+
+        ZkDapLocal {
+            requests,
+            responses,
+            events,
+        }
+    }
 }
 
 impl<S> ZkDapBuilder<S>
@@ -54,40 +115,125 @@ where
     pub async fn build(self) -> io::Result<ZkDapService> {
         let Self { capacity, socket } = self;
 
-        let reactor =
-            Reactor::new().with_capacity(capacity).bind(socket).await?;
+        let listener = net::TcpListener::bind(socket).await?;
 
-        Ok(ZkDapService { reactor })
+        Ok(ZkDapService { capacity, listener })
     }
 }
 
-/// Zk DAP reactor listener
+/// Zk DAP listener
 pub struct ZkDapService {
-    reactor: ReactorListener<ZkDap>,
+    capacity: usize,
+    listener: net::TcpListener,
 }
 
 impl Deref for ZkDapService {
-    type Target = ReactorListener<ZkDap>;
+    type Target = net::TcpListener;
 
     fn deref(&self) -> &Self::Target {
-        &self.reactor
+        &self.listener
     }
 }
 
 impl ZkDapService {
-    /// Listen for incoming connections to provide the DAP service
+    /// Listen for incoming connections to provide the DAP service, serving
+    /// every client in its own task
     pub async fn listen(self) -> io::Result<()> {
-        self.reactor.listen().await
+        let Self { capacity, listener } = self;
+
+        tracing::info!("listening on {}", listener.local_addr()?);
+
+        loop {
+            let (stream, addr) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    tracing::warn!("error accepting a connection: {}", e);
+                    continue;
+                }
+            };
+
+            tracing::trace!("incoming connection from {}", addr);
+
+            tokio::spawn(async move {
+                if let Err(e) = connection::serve(stream, capacity).await {
+                    tracing::warn!("error serving {}: {}", addr, e);
+                }
+            });
+        }
     }
 }
 
+/// Channels of a [`ZkDap`] service running in-process, created with
+/// [`ZkDapBuilder::build_local`]
+#[derive(Debug)]
+pub struct ZkDapLocal {
+    /// Requests to the service
+    pub requests: mpsc::Sender<Request>,
+    /// Responses of the service, one for every request and in their order; a
+    /// failed request is answered with a [`Response::Error`]
+    pub responses: mpsc::Receiver<Response>,
+    /// Events emitted by the service
+    pub events: mpsc::Receiver<Event>,
+}
+
 /// Debug adapter protocol provider for the [`ZkDebugger`]
 pub struct ZkDap {
     events: Sender<Event>,
-    backend: Arc<Mutex<Option<ZkDebugger<File>>>>,
+    backend: Arc<RwLock<Option<SharedZkDebugger<File>>>>,
+    changes: Arc<std::sync::Mutex<Vec<StateChange>>>,
+    outputs: Arc<Mutex<Outputs>>,
+    positions: Positions,
+    fetches: Arc<Semaphore>,
+    /// Held while a continue runs past its request, refusing the other
+    /// movements until it stops
+    running: Arc<Mutex<()>>,
+    diagnostics: Diagnostics,
+    progress: bool,
 }
 
 impl ZkDap {
+    pub(crate) fn new(events: Sender<Event>, config: ZkDapConfig) -> Self {
+        let backend = None;
+        let backend = RwLock::new(backend);
+        let backend = Arc::new(backend);
+
+        let outputs = Outputs::new(&config);
+        let outputs = Mutex::new(outputs);
+        let outputs = Arc::new(outputs);
+
+        // a session without permits would never complete a scan
+        let fetches = config.fetch_concurrency.max(1);
+        let fetches = Arc::new(Semaphore::new(fetches));
+
+        Self {
+            events,
+            backend,
+            changes: Arc::default(),
+            outputs,
+            positions: Positions::default(),
+            fetches,
+            running: Arc::default(),
+            diagnostics: Diagnostics::new(config.diagnostic_requests),
+            progress: false,
+        }
+    }
+
+    /// Handle to the session, for a continue reporting its stop after its
+    /// request was answered
+    fn session(&self) -> Self {
+        Self {
+            events: self.events.clone(),
+            backend: Arc::clone(&self.backend),
+            changes: Arc::clone(&self.changes),
+            outputs: Arc::clone(&self.outputs),
+            positions: self.positions,
+            fetches: Arc::clone(&self.fetches),
+            running: Arc::clone(&self.running),
+            diagnostics: self.diagnostics.clone(),
+            progress: self.progress,
+        }
+    }
+
     /// Define the implementation capabilities
     pub const fn capabilities() -> Capabilities {
         Capabilities {
@@ -125,7 +271,7 @@ impl ZkDap {
             supports_disassemble_request: false,
             supports_cancel_request: false,
             supports_breakpoint_locations_request: true,
-            supports_clipboard_context: false,
+            supports_clipboard_context: true,
             supports_stepping_granularity: false,
             supports_instruction_breakpoints: true,
             supports_exception_filter_options: false,
@@ -138,15 +284,15 @@ impl ZkDap {
     where
         S: net::ToSocketAddrs,
     {
-        let reactor = Reactor::<Self>::new()
+        let service = ZkDapBuilder::new(socket)
             .with_capacity(capacity)
-            .bind(socket)
+            .build()
             .await?;
 
-        let socket = reactor.local_addr()?;
+        let socket = service.local_addr()?;
 
         tokio::spawn(async move {
-            if let Err(e) = reactor.listen().await {
+            if let Err(e) = service.listen().await {
                 tracing::error!("error listening to dap: {}", e);
             }
         });
@@ -161,6 +307,251 @@ impl ZkDap {
         )
     }
 
+    /// The loaded debugger, shared with the running operations
+    async fn debugger(&self) -> io::Result<SharedZkDebugger<File>> {
+        self.backend
+            .read()
+            .await
+            .clone()
+            .ok_or_else(Self::not_initialized)
+    }
+
+    /// Keep the debugger still for a movement.
+    ///
+    /// A movement is refused while a continue runs, instead of waiting for
+    /// it, as the requests are served in order; the client pauses it first.
+    fn still(&self) -> io::Result<OwnedMutexGuard<()>> {
+        Arc::clone(&self.running).try_lock_owned().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "the debugger is running; pause it first",
+            )
+        })
+    }
+
+    /// Run an operation over the loaded debugger on the blocking threads.
+    ///
+    /// A running scan holds the debugger while it checks a chunk of
+    /// constraints, so the workers of the service never wait for it.
+    async fn blocking<F, T>(&self, operation: F) -> io::Result<T>
+    where
+        F: FnOnce(SharedZkDebugger<File>) -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let debugger = self.debugger().await?;
+
+        Self::joined(
+            tokio::task::spawn_blocking(move || operation(debugger)).await,
+        )
+    }
+
+    /// Run an operation over the locked debugger, as [`Self::blocking`]
+    async fn locked<F, T>(&self, operation: F) -> io::Result<T>
+    where
+        F: FnOnce(&mut ZkDebugger<File>) -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.blocking(move |shared| {
+            let mut debugger = shared.lock()?;
+
+            operation(&mut debugger)
+        })
+        .await
+    }
+
+    /// Result of a task of the blocking threads, with its panic turned into
+    /// an internal error
+    fn joined<T>(
+        joined: Result<io::Result<T>, tokio::task::JoinError>,
+    ) -> io::Result<T> {
+        match joined {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => {
+                let panic = e.into_panic();
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|m| m.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+
+                Err(Panicked::error(message))
+            }
+            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+        }
+    }
+
+    /// Run a scan on the blocking threads, keeping the service responsive.
+    ///
+    /// The blocking pool is shared by all the sessions, so each runs at most
+    /// [`ZkDapConfig::fetch_concurrency`] scans at once.
+    async fn scan<F, T>(&self, scan: F) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = Arc::clone(&self.fetches)
+            .acquire_owned()
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let scan = tokio::task::spawn_blocking(move || {
+            let result = scan();
+            drop(permit);
+            result
+        })
+        .await;
+
+        Self::joined(scan)
+    }
+
+    /// Run a scan of the debugger as [`Self::scan`], reporting its throughput
+    /// and remaining time with progress events if the client supports them
+    async fn scan_reported<F, T>(
+        &self,
+        title: &str,
+        debugger: SharedZkDebugger<File>,
+        scan: F,
+    ) -> io::Result<T>
+    where
+        F: FnOnce() -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        if !self.progress {
+            return self.scan(scan).await;
+        }
+
+        let (done, mut finished) = oneshot::channel::<()>();
+        let events = self.events.clone();
+        let title = title.to_string();
+
+        let reporter = tokio::spawn(async move {
+            let progress_id = String::from("scan");
+            let mut started = false;
+
+            while time::timeout(PROGRESS_INTERVAL, &mut finished)
+                .await
+                .is_err()
+            {
+                let progress = match debugger.progress() {
+                    Some(p) => p,
+                    None => continue,
+                };
+
+                let message = Some(progress.to_string());
+                let percentage = Some(progress.percentage());
+
+                let event = match started {
+                    true => Event::ProgressUpdate {
+                        progress_id: progress_id.clone(),
+                        message,
+                        percentage,
+                    },
+                    false => Event::ProgressStart {
+                        progress_id: progress_id.clone(),
+                        title: title.clone(),
+                        request_id: None,
+                        cancellable: false,
+                        message,
+                        percentage,
+                    },
+                };
+
+                started = true;
+
+                if events.send(event).await.is_err() {
+                    return;
+                }
+            }
+
+            if started {
+                let event = Event::ProgressEnd {
+                    progress_id,
+                    message: None,
+                };
+
+                events.send(event).await.ok();
+            }
+        });
+
+        let result = self.scan(scan).await;
+
+        done.send(()).ok();
+        reporter.await.ok();
+
+        result
+    }
+
+    /// Run a continue as [`Self::scan_reported`] past its request, so the
+    /// service keeps serving the other requests, such as a pause, while it
+    /// runs. Its stop is reported with the events of the debugger.
+    async fn spawn_continue<F>(
+        &self,
+        title: &'static str,
+        scan: F,
+    ) -> io::Result<()>
+    where
+        F: FnOnce(SharedZkDebugger<File>) -> io::Result<State> + Send + 'static,
+    {
+        let running = self.still()?;
+        let debugger = self.debugger().await?;
+        let session = self.session();
+
+        tokio::spawn(async move {
+            let d = debugger.clone();
+            let result = session
+                .scan_reported(title, d, move || scan(debugger))
+                .await;
+
+            // released before the stop is reported, so the client can move
+            // as soon as it sees it
+            drop(running);
+
+            if let Err(e) = &result {
+                tracing::warn!("error continuing: {}", e);
+
+                session
+                    .send_output(OutputCategory::Stderr, e.to_string())
+                    .await;
+
+                session.report_internal_error(e).await;
+            }
+
+            session.report().await;
+        });
+
+        Ok(())
+    }
+
+    /// Capture the diagnostic bundle of an internal error, and tell the
+    /// client where it was written
+    async fn report_internal_error(&self, e: &io::Error) {
+        if !diagnostics::is_internal(e) || self.diagnostics.is_disabled() {
+            return;
+        }
+
+        let circuit = self
+            .backend
+            .read()
+            .await
+            .as_ref()
+            .map(|d| Circuit::new(*d.preamble(), d.position()));
+
+        let written = self.diagnostics.write(env::temp_dir(), e, circuit);
+        let output = match written {
+            Ok(path) => format!(
+                "internal error of the debugger; please attach the \
+                 diagnostics written to {} to the bug report",
+                path.display()
+            ),
+            Err(e) => {
+                tracing::warn!("failed to write the diagnostics: {}", e);
+                return;
+            }
+        };
+
+        self.send_output(OutputCategory::Important, output).await;
+    }
+
     async fn send_event(&self, event: Event) -> io::Result<()> {
         self.events
             .send(event)
@@ -168,21 +559,147 @@ impl ZkDap {
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
     }
 
+    /// Send an output message to the client, applying the configured
+    /// [`OverflowPolicy`] if the events channel is full
+    async fn send_output(&self, category: OutputCategory, output: String) {
+        let event = Outputs::event(category, output);
+        let mut outputs = self.outputs.lock().await;
+
+        match outputs.policy() {
+            OverflowPolicy::Block => {
+                outputs.flush(&self.events);
+                drop(outputs);
+
+                self.events.send(event).await.ok();
+            }
+
+            _ => outputs.send(&self.events, event),
+        }
+    }
+
     async fn update_constraint(
         &self,
         reason: StoppedReason,
-        breakpoints: Vec<usize>,
+        state: &State,
     ) -> io::Result<()> {
         self.send_event(Event::Stopped {
             reason,
-            description: None,
+            description: Some(state.to_string()),
             thread_id: Some(0),
             preserve_focus_hint: false,
-            text: None,
+            text: state.location().map(ToString::to_string),
             all_threads_stopped: true,
-            hit_breakpoint_ids: breakpoints,
+            hit_breakpoint_ids: state.breakpoint().into_iter().collect(),
+        })
+        .await?;
+
+        // the clients without the extension show only the highlighted line
+        if let Some(summary) = self.summary(state).await {
+            self.send_output(OutputCategory::Console, summary).await;
+        }
+
+        Ok(())
+    }
+
+    /// One-line summary of the constraint the debugger stopped at, such as
+    /// `stopped at gadget.rs:12 (constraint 530, arith gate, eval ok)`
+    async fn summary(&self, state: &State) -> Option<String> {
+        let id = match state {
+            State::Corrupt { .. } | State::Witness { .. } => return None,
+            state => state.constraint()?,
+        };
+
+        self.locked(move |debugger| {
+            let constraint = debugger.fetch_constraint(id)?;
+            let polynomial = constraint.polynomial();
+
+            Ok(format!(
+                "stopped at {}:{} (constraint {}, {} gate, eval {})",
+                constraint.name(),
+                constraint.line(),
+                id,
+                polynomial.selectors().gate_kind(),
+                if polynomial.is_ok() { "ok" } else { "failed" }
+            ))
         })
         .await
+        .ok()
+    }
+
+    /// Report the changes and the warnings of the debugger to the client
+    async fn report(&self) {
+        if let Err(e) = self.report_changes().await {
+            tracing::warn!("error reporting the debugger changes: {}", e);
+        }
+
+        self.report_warnings().await;
+    }
+
+    /// Forward the warnings produced by the debugger to the client
+    async fn report_warnings(&self) {
+        let warnings = self
+            .locked(|debugger| Ok(debugger.take_warnings()))
+            .await
+            .unwrap_or_default();
+
+        for warning in warnings {
+            tracing::warn!("{}", warning);
+
+            self.send_output(
+                OutputCategory::Important,
+                format!("warning: {}", warning),
+            )
+            .await;
+        }
+    }
+
+    /// Translate the changes of the debugger into events to the client
+    async fn report_changes(&self) -> io::Result<()> {
+        let changes = self
+            .changes
+            .lock()
+            .map(|mut changes| mem::take(&mut *changes))
+            .unwrap_or_default();
+
+        for change in changes {
+            match change {
+                StateChange::Loaded { .. } => {
+                    self.send_event(Event::Thread {
+                        reason: ThreadReason::Started,
+                        thread_id: 0,
+                    })
+                    .await?;
+
+                    self.update_constraint(
+                        StoppedReason::Step,
+                        &State::Beginning,
+                    )
+                    .await?;
+                }
+
+                StateChange::Navigated {
+                    movement: Movement::Goto { .. },
+                    state,
+                } => {
+                    // a breakpoint landed on is reported as hit, for the
+                    // badges of the clients
+                    let reason = match state.breakpoint() {
+                        Some(_) => StoppedReason::Breakpoint,
+                        None => StoppedReason::Goto,
+                    };
+
+                    self.update_constraint(reason, &state).await?;
+                }
+
+                StateChange::Navigated { state, .. } => {
+                    self.consume_state(state).await?;
+                }
+
+                change => tracing::debug!("debugger change: {:?}", change),
+            }
+        }
+
+        Ok(())
     }
 
     async fn terminate(&self, exit_code: u64) -> io::Result<()> {
@@ -199,17 +716,40 @@ impl ZkDap {
     }
 
     async fn consume_state(&self, state: State) -> io::Result<()> {
-        match state {
-            State::Beginning | State::Constraint { .. } => {
-                self.update_constraint(StoppedReason::Step, vec![]).await?;
+        match &state {
+            State::Beginning
+            | State::Constraint { .. }
+            | State::Witness { .. } => {
+                self.update_constraint(StoppedReason::Step, &state).await?;
             }
 
             State::InvalidConstraint { .. } => {
+                self.send_output(
+                    OutputCategory::Important,
+                    format!("error: {}", state),
+                )
+                .await;
+
                 self.terminate(1).await?;
             }
 
-            State::Breakpoint { id } => {
-                self.update_constraint(StoppedReason::Breakpoint, vec![id])
+            State::Breakpoint { .. } => {
+                self.update_constraint(StoppedReason::Breakpoint, &state)
+                    .await?;
+            }
+
+            State::Paused { .. } | State::Exhausted { .. } => {
+                self.update_constraint(StoppedReason::Pause, &state).await?;
+            }
+
+            State::Corrupt { .. } => {
+                self.send_output(
+                    OutputCategory::Important,
+                    format!("error: {}", state),
+                )
+                .await;
+
+                self.update_constraint(StoppedReason::Exception, &state)
                     .await?;
             }
 
@@ -225,9 +765,6 @@ impl ZkDap {
         &self,
         arguments: Option<BreakpointLocationsArguments>,
     ) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
         let (source, line, end_line) = match arguments {
             Some(BreakpointLocationsArguments {
                 source:
@@ -242,26 +779,42 @@ impl ZkDap {
             _ => return Ok(Response::BreakpointLocations { body: None }),
         };
 
-        let end_line = end_line.unwrap_or(line);
-        let breakpoints = (line..=end_line)
-            .filter(|l| debugger.add_breakpoint(source.clone(), Some(*l)) > 0)
-            .map(|_| BreakpointLocation {
-                line,
-                column: None,
-                end_line: None,
-                end_column: None,
+        let positions = self.positions;
+        let line = positions.line_from_client(line);
+        let end_line = end_line
+            .map(|l| positions.line_from_client(l))
+            .unwrap_or(line);
+
+        let breakpoints = self
+            .locked(move |debugger| {
+                Ok((line..=end_line)
+                    .filter(|l| {
+                        debugger.add_breakpoint(source.clone(), Some(*l)) > 0
+                    })
+                    .map(|l| BreakpointLocation {
+                        line: positions.line_to_client(l),
+                        column: None,
+                        end_line: None,
+                        end_column: None,
+                    })
+                    .collect())
             })
-            .collect();
+            .await?;
 
         Ok(Response::BreakpointLocations {
             body: Some(BreakpointLocationsResponse { breakpoints }),
         })
     }
 
-    async fn evaluate(&self) -> io::Result<Response> {
+    async fn evaluate(&self, expression: &str) -> io::Result<Response> {
+        let result = match expression {
+            SNIPPET_EXPRESSION => self.snippet_markdown().await?.1,
+            _ => "evaluate is not implemented".into(),
+        };
+
         Ok(Response::Evaluate {
             body: EvaluateResponse {
-                result: "evaluate is not implemented".into(),
+                result,
                 r#type: None,
                 presentation_hint: Some(VariablePresentationHint {
                     kind: Some(VariablePresentationHintKind::Data),
@@ -286,12 +839,8 @@ impl ZkDap {
     }
 
     async fn r#continue(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
-        let state = debugger.cont()?;
-
-        self.consume_state(state).await?;
+        self.spawn_continue("Continuing", |debugger| debugger.cont())
+            .await?;
 
         Ok(Response::Continue {
             body: ContinueResponse {
@@ -301,35 +850,35 @@ impl ZkDap {
     }
 
     async fn goto(&self, arguments: GotoArguments) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
-        debugger.goto(arguments.target_id as usize)?;
+        let _still = self.still()?;
+        let idx = arguments.target_id as usize;
 
-        self.update_constraint(StoppedReason::Goto, vec![]).await?;
+        self.blocking(move |debugger| debugger.goto(idx)).await?;
 
         Ok(Response::Goto)
     }
 
     async fn next(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+        let _still = self.still()?;
 
-        let state = debugger.step()?;
-
-        self.consume_state(state).await?;
+        self.blocking(|debugger| debugger.step()).await?;
 
         Ok(Response::Goto)
     }
 
     async fn restart(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+        let _still = self.still()?;
+
+        let name = self
+            .locked(|debugger| {
+                debugger.goto(0)?;
 
-        debugger.goto(0)?;
+                Ok(debugger.to_string())
+            })
+            .await?;
 
         self.send_event(Event::Process {
-            name: debugger.to_string(),
+            name,
             system_process_id: None,
             is_local_process: true,
             start_method: Some(ProcessStartMethod::Launch),
@@ -337,18 +886,12 @@ impl ZkDap {
         })
         .await?;
 
-        self.update_constraint(StoppedReason::Step, vec![]).await?;
-
         Ok(Response::Restart)
     }
 
     async fn reverse_continue(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
-        let state = debugger.turn()?;
-
-        self.consume_state(state).await?;
+        self.spawn_continue("Continuing backwards", |debugger| debugger.turn())
+            .await?;
 
         Ok(Response::Continue {
             body: ContinueResponse {
@@ -361,30 +904,125 @@ impl ZkDap {
         &self,
         arguments: Option<Value>,
     ) -> io::Result<Response> {
-        let request = ZkRequest::try_from(arguments.as_ref())?;
+        match ZkRequest::try_from(arguments.as_ref())? {
+            ZkRequest::Batch { requests } => self.batch(requests).await,
+            request => self.zk_request(request).await,
+        }
+    }
+
+    /// Execute the requests of a batch in order, collecting their responses
+    /// and errors
+    async fn batch(&self, requests: Vec<ZkRequest>) -> io::Result<Response> {
+        let mut responses = Vec::with_capacity(requests.len());
+
+        for request in requests {
+            let response = match self.zk_request(request).await {
+                Ok(Response::Custom { body }) => {
+                    ZkResponse::try_from(body.as_ref())
+                        .map_err(|e| e.to_string())
+                }
+                Ok(_) => Err("unexpected response".into()),
+                Err(e) => Err(e.to_string()),
+            };
+
+            responses.push(response);
+        }
 
+        Ok(ZkResponse::Batch { responses }.into())
+    }
+
+    async fn zk_request(&self, request: ZkRequest) -> io::Result<Response> {
         match request {
-            ZkRequest::AddBreakpoint { breakpoint } => {
-                self.add_breakpoint(breakpoint).await
+            ZkRequest::AddBreakpoint { breakpoint, kind } => {
+                self.add_breakpoint(breakpoint, kind, false).await
             }
 
             ZkRequest::RemoveBreakpoint { id } => {
                 self.remove_breakpoint(id).await
             }
 
+            ZkRequest::AddBreakpoints { breakpoints, kind } => {
+                self.add_breakpoints(breakpoints, kind).await
+            }
+
+            ZkRequest::RemoveBreakpoints { source } => {
+                self.remove_breakpoints(source.as_deref()).await
+            }
+
+            ZkRequest::AddTemporaryBreakpoint { breakpoint, kind } => {
+                self.add_breakpoint(breakpoint, kind, true).await
+            }
+
+            ZkRequest::IgnoreBreakpoint { id, count } => {
+                self.ignore_breakpoint(id, count).await
+            }
+
             ZkRequest::LoadCdf { path } => self.load_cdf(path).await,
 
             ZkRequest::SourceContents => self.source_contents().await,
 
+            ZkRequest::SourceList { filter } => {
+                self.source_list(filter.as_deref()).await
+            }
+
+            ZkRequest::Sources {
+                filter,
+                offset,
+                limit,
+                known,
+            } => self.sources(filter.as_deref(), offset, limit, &known).await,
+
+            ZkRequest::SourceChunk { path, offset, len } => {
+                self.source_chunk(path, offset, len).await
+            }
+
             ZkRequest::Witness { id } => self.witness(id).await,
+            ZkRequest::GotoLine { source, line } => {
+                let line = self.positions.line_from_client(line);
+
+                self.goto_line(&source, line).await
+            }
+
+            ZkRequest::ContinueMax { max, reverse } => {
+                self.continue_max(max, reverse).await
+            }
+
+            ZkRequest::Focus { focus, failures } => {
+                self.focus(focus, failures).await
+            }
+            ZkRequest::Top { limit } => self.top(limit).await,
+
+            ZkRequest::WireCheck => self.wirecheck().await,
+            ZkRequest::Timeline => self.timeline().await,
+            ZkRequest::ScalarCheck => self.scalarcheck().await,
+            ZkRequest::ConstCheck => self.constcheck().await,
+            ZkRequest::SharedWires => self.shared_wires().await,
+            ZkRequest::Locals => self.locals().await,
+            ZkRequest::Frontier => self.frontier().await,
+            ZkRequest::Memory => self.memory().await,
+            ZkRequest::Snippet => self.snippet().await,
+            ZkRequest::Equation { notation } => self.equation(notation).await,
+            ZkRequest::Publics { compare } => self.publics(compare).await,
+            ZkRequest::Raw { id } => self.raw(id).await,
+
+            ZkRequest::Backend { call } => self.backend_call(call).await,
+
+            ZkRequest::Pause => self.pause().await,
+
+            ZkRequest::Batch { .. } => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "batches can't be nested",
+            )),
         }
     }
 
-    async fn add_breakpoint(
+    /// Breakpoint of the debugger for a breakpoint sent by the client
+    fn resolve_breakpoint(
         &self,
         breakpoint: Breakpoint,
-    ) -> io::Result<Response> {
-        let line = breakpoint.line;
+        kind: Option<GateKind>,
+    ) -> io::Result<crate::Breakpoint> {
+        let line = breakpoint.line.map(|l| self.positions.line_from_client(l));
         let name = breakpoint.source.and_then(|s| s.name).ok_or_else(|| {
             io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -392,64 +1030,289 @@ impl ZkDap {
             )
         })?;
 
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+        Ok(crate::Breakpoint {
+            source: name,
+            line,
+            kind,
+        })
+    }
 
-        let id = debugger.add_breakpoint(name, line) as u64;
+    async fn add_breakpoint(
+        &self,
+        breakpoint: Breakpoint,
+        kind: Option<GateKind>,
+        temporary: bool,
+    ) -> io::Result<Response> {
+        let breakpoint = self.resolve_breakpoint(breakpoint, kind)?;
+
+        let id = self
+            .locked(move |debugger| {
+                Ok(debugger.insert_breakpoint(breakpoint, temporary))
+            })
+            .await? as u64;
 
         Ok(ZkResponse::AddBreakpoint { id }.into())
     }
 
-    async fn remove_breakpoint(&self, id: u64) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+    async fn add_breakpoints(
+        &self,
+        breakpoints: Vec<Breakpoint>,
+        kind: Option<GateKind>,
+    ) -> io::Result<Response> {
+        // none is added unless all of them are valid
+        let breakpoints = breakpoints
+            .into_iter()
+            .map(|b| self.resolve_breakpoint(b, kind))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let ids = self
+            .locked(move |debugger| {
+                Ok(breakpoints
+                    .into_iter()
+                    .map(|b| debugger.insert_breakpoint(b, false) as u64)
+                    .collect())
+            })
+            .await?;
+
+        Ok(ZkResponse::AddBreakpoints { ids }.into())
+    }
+
+    async fn remove_breakpoints(
+        &self,
+        source: Option<&str>,
+    ) -> io::Result<Response> {
+        let source = source.map(String::from);
+
+        let ids = self
+            .locked(move |debugger| {
+                Ok(debugger
+                    .remove_breakpoints(source.as_deref())
+                    .into_iter()
+                    .map(|id| id as u64)
+                    .collect())
+            })
+            .await?;
+
+        Ok(ZkResponse::RemoveBreakpoints { ids }.into())
+    }
 
-        let removed = debugger.remove_breakpoint(id as usize).is_some();
+    async fn remove_breakpoint(&self, id: u64) -> io::Result<Response> {
+        let removed = self
+            .locked(move |debugger| {
+                Ok(debugger.remove_breakpoint(id as usize).is_some())
+            })
+            .await?;
 
         Ok(ZkResponse::RemoveBreakpoint { id, removed }.into())
     }
 
+    async fn ignore_breakpoint(
+        &self,
+        id: u64,
+        count: u64,
+    ) -> io::Result<Response> {
+        let ignored = self
+            .locked(move |debugger| {
+                Ok(debugger.ignore_breakpoint(id as usize, count as usize))
+            })
+            .await?;
+
+        Ok(ZkResponse::IgnoreBreakpoint { id, count, ignored }.into())
+    }
+
     async fn load_cdf(&self, path: String) -> io::Result<Response> {
+        let _still = self.still()?;
         let path = PathBuf::from(path);
-        let debugger = ZkDebugger::open(path)?;
+        let config = Config::load().unwrap_or_else(|e| {
+            tracing::warn!("failed to load the cdf config: {}", e);
+            Config::default()
+        });
+        let patterns = PatternConfig::load()
+            .and_then(|patterns| patterns.library())
+            .unwrap_or_else(|e| {
+                tracing::warn!("failed to load the gate patterns: {}", e);
+                PatternLibrary::builtin()
+            });
+        let mut debugger = ZkDebugger::open_with_config(path, config)?;
+        let changes = Arc::clone(&self.changes);
 
-        self.send_event(Event::Thread {
-            reason: ThreadReason::Started,
-            thread_id: 0,
-        })
-        .await?;
+        debugger.set_pattern_library(patterns);
 
-        self.update_constraint(StoppedReason::Step, vec![]).await?;
+        // the breakpoints of a reloaded circuit keep their ids, as the client
+        // keeps its own mapping of them. they are copied, so the previous
+        // circuit keeps them until the new one replaces it
+        let mut backend = self.backend.write().await;
+        if let Some(previous) = backend.as_ref() {
+            let breakpoints = previous.lock()?.breakpoints().clone();
 
-        self.backend.lock().await.replace(debugger);
+            debugger.replace_breakpoints(breakpoints);
+        }
+
+        let unbound = debugger.unbound_breakpoints()?;
+
+        let mut carried: Vec<_> = debugger
+            .breakpoints()
+            .iter()
+            .map(|(b, id)| (*id, b.line))
+            .collect();
+
+        carried.sort_unstable();
+
+        debugger.subscribe(move |change: &StateChange| {
+            if let Ok(mut changes) = changes.lock() {
+                changes.push(change.clone());
+            }
+        });
+
+        backend.replace(SharedZkDebugger::from(debugger));
+        drop(backend);
+
+        // every carried-over breakpoint is verified again against the new
+        // circuit, as a line may be bound or unbound by the reload
+        for (id, line) in carried {
+            let verified = unbound.binary_search(&id).is_err();
+            let message = (!verified).then(|| {
+                "no constraint of the circuit is at the breakpoint".into()
+            });
+            let line = line.map(|l| self.positions.line_to_client(l));
+
+            self.send_event(Event::Breakpoint {
+                reason: BreakpointReason::Changed,
+                breakpoint: Breakpoint {
+                    id: Some(id as u64),
+                    verified,
+                    message,
+                    source: None,
+                    line,
+                    column: None,
+                    end_line: line,
+                    end_column: None,
+                    instruction_reference: None,
+                    offset: None,
+                },
+            })
+            .await?;
+        }
 
         Ok(ZkResponse::LoadCdf.into())
     }
 
     async fn source_contents(&self) -> io::Result<Response> {
-        let debugger = self.backend.lock().await;
-        let debugger = debugger.as_ref().ok_or_else(Self::not_initialized)?;
+        let debugger = self.debugger().await?;
 
         let sources = debugger
             .sources()
-            .map(|(path, contents)| ZkSource {
+            .map(|(path, contents)| ZkSource::new(path, contents))
+            .collect();
+
+        Ok(ZkResponse::SourceContents { sources }.into())
+    }
+
+    async fn source_list(&self, filter: Option<&str>) -> io::Result<Response> {
+        let debugger = self.debugger().await?;
+
+        let filter = utils::source_filter(filter)?;
+        let sources = debugger
+            .sources()
+            .filter(|(path, _)| filter(path))
+            .map(|(path, contents)| ZkSourceInfo {
                 path: path.into(),
-                contents: contents.into(),
+                size: contents.len(),
+                hash: utils::source_hash(contents),
             })
             .collect();
 
-        Ok(ZkResponse::SourceContents { sources }.into())
+        Ok(ZkResponse::SourceList { sources }.into())
+    }
+
+    async fn sources(
+        &self,
+        filter: Option<&str>,
+        offset: usize,
+        limit: Option<usize>,
+        known: &[ZkSourceInfo],
+    ) -> io::Result<Response> {
+        let debugger = self.debugger().await?;
+
+        let filter = utils::source_filter(filter)?;
+        let limit = limit.unwrap_or(usize::MAX);
+
+        let mut total = 0;
+        let mut sources = vec![];
+        let mut unchanged = vec![];
+
+        for (path, contents) in debugger.sources().filter(|(p, _)| filter(p)) {
+            if total >= offset && sources.len() < limit {
+                let source = ZkSource::new(path, contents);
+
+                // the client caches the sources by path, so a known hash
+                // under another path is still transferred
+                match known
+                    .iter()
+                    .any(|k| k.path == source.path && k.hash == source.hash)
+                {
+                    true => unchanged.push(source.path),
+                    false => sources.push(source),
+                }
+            }
+
+            total += 1;
+        }
+
+        Ok(ZkResponse::Sources {
+            sources,
+            unchanged,
+            total,
+        }
+        .into())
+    }
+
+    async fn source_chunk(
+        &self,
+        path: String,
+        offset: usize,
+        len: usize,
+    ) -> io::Result<Response> {
+        let debugger = self.debugger().await?;
+
+        let contents = debugger
+            .sources()
+            .find_map(|(p, contents)| (p == path).then_some(contents))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("source not found: {}", path),
+                )
+            })?;
+
+        let size = contents.len();
+        let (offset, chunk) = utils::source_chunk(contents, offset, len);
+        let contents = chunk.into();
+
+        Ok(ZkResponse::SourceChunk {
+            path,
+            offset,
+            contents,
+            size,
+        }
+        .into())
     }
 
     async fn scopes(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+        let positions = self.positions;
+
+        let (variables_reference, source, line, column) = self
+            .locked(move |debugger| {
+                let constraint = debugger.fetch_current_constraint()?;
 
-        let constraint = debugger.fetch_current_constraint()?;
-        let variables_reference = constraint.id() as u64;
-        let source = Source::from(&constraint);
-        let line = constraint.line();
-        let column = constraint.col();
+                Ok((
+                    constraint.id() as u64,
+                    Source::from(&constraint),
+                    positions.line_to_client(constraint.line()),
+                    positions.column_to_client(constraint.col()),
+                ))
+            })
+            .await?;
 
         Ok(Response::Scopes {
             body: ScopesResponse {
@@ -491,22 +1354,64 @@ impl ZkDap {
             }
         };
 
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+        let lines: Vec<_> = breakpoints
+            .into_iter()
+            .map(|b| b.line)
+            .chain(lines)
+            .map(|line| self.positions.line_from_client(line))
+            .collect();
+
+        let requested = lines.clone();
+        let (ids, unbound) = self
+            .locked(move |debugger| {
+                // only the breakpoints set with this very path are replaced,
+                // as a pattern set for another source may be contained in it;
+                // the lines requested again keep their ids
+                let stale: Vec<_> = debugger
+                    .breakpoints()
+                    .iter()
+                    .filter(|(b, id)| {
+                        let kept = b.kind.is_none()
+                            && matches!(b.line, Some(l) if requested.contains(&l))
+                            && !debugger.breakpoints().is_temporary(**id);
+
+                        b.source == path && !kept
+                    })
+                    .map(|(_, id)| *id)
+                    .collect();
 
-        debugger.clear_breakpoints(path.as_str());
+                for id in stale {
+                    debugger.remove_breakpoint(id);
+                }
 
-        let breakpoints = breakpoints
+                let ids: Vec<_> = requested
+                    .iter()
+                    .map(|line| {
+                        debugger.add_breakpoint(path.clone(), Some(*line))
+                    })
+                    .collect();
+
+                // a line without constraints is kept, as a reload may bind it
+                let unbound = debugger.unbound_breakpoints()?;
+
+                Ok((ids, unbound))
+            })
+            .await?;
+
+        let breakpoints = ids
             .into_iter()
-            .map(|b| b.line)
-            .chain(lines.into_iter())
-            .map(|line| {
-                let id = debugger.add_breakpoint(path.clone(), Some(line));
+            .zip(lines)
+            .map(|(id, line)| {
+                let verified = unbound.binary_search(&id).is_err();
+                let message = (!verified).then(|| {
+                    "no constraint of the circuit is at the breakpoint".into()
+                });
+                let line = self.positions.line_to_client(line);
 
                 Breakpoint {
                     id: Some(id as u64),
-                    verified: true,
-                    message: None,
+                    verified,
+                    message,
                     source: None,
                     line: Some(line),
                     column: None,
@@ -524,20 +1429,32 @@ impl ZkDap {
     }
 
     async fn stack_trace(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
+        let positions = self.positions;
 
-        let constraint = debugger.fetch_current_constraint()?;
-        let source = Source::from(&constraint);
+        let (name, source, line, column) = self
+            .locked(move |debugger| {
+                let constraint = debugger.fetch_current_constraint()?;
+                let source = Source::from(&constraint);
 
-        let line = constraint.line();
-        let column = constraint.col();
+                let line = positions.line_to_client(constraint.line());
+                let column = positions.column_to_client(constraint.col());
+
+                // the frame is named after the gadget of the constraint, if
+                // any
+                let name = debugger
+                    .gate_match(constraint.id())?
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "cdf".into());
+
+                Ok((name, source, line, column))
+            })
+            .await?;
 
         Ok(Response::StackTrace {
             body: StackTraceResponse {
                 stack_frames: vec![StackFrame {
                     id: 0,
-                    name: "cdf".into(),
+                    name,
                     source: Some(source),
                     line,
                     column,
@@ -554,12 +1471,9 @@ impl ZkDap {
     }
 
     async fn step_back(&self) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
-        let state = debugger.afore()?;
+        let _still = self.still()?;
 
-        self.consume_state(state).await?;
+        self.blocking(|debugger| debugger.afore()).await?;
 
         Ok(Response::Goto)
     }
@@ -586,15 +1500,32 @@ impl ZkDap {
             });
         }
 
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
-        let constraint = debugger.fetch_current_constraint()?;
-        let id = constraint.id();
-
-        let polynomial = *constraint.polynomial();
+        // the witnesses borrow the debugger, so they are rendered under its
+        // lock
+        let (id, polynomial, [wa, wb, wd, wo]) = self
+            .locked(|debugger| {
+                let ResolvedConstraint {
+                    constraint,
+                    a,
+                    b,
+                    d,
+                    o,
+                } = debugger.resolve_current_constraint()?;
+
+                let wires = [
+                    utils::witness_to_var("Wa", a),
+                    utils::witness_to_var("Wb", b),
+                    utils::witness_to_var("Wd", d),
+                    utils::witness_to_var("Wo", o),
+                ];
+
+                Ok((constraint.id(), *constraint.polynomial(), wires))
+            })
+            .await?;
 
-        let idx = utils::idx_to_var("constraint", id);
+        let mut idx = utils::idx_to_var("constraint", id);
+        idx.evaluate_name = Some(SNIPPET_EXPRESSION.into());
+        let gate = utils::gate_to_var("Gate", polynomial.gate_kind());
 
         let qm = utils::scalar_to_var("Qm", &polynomial.selectors.qm);
         let ql = utils::scalar_to_var("Ql", &polynomial.selectors.ql);
@@ -618,38 +1549,337 @@ impl ZkDap {
 
         let eval = utils::bool_to_var("Evaluation", polynomial.evaluation);
 
-        let wa = debugger
-            .fetch_witness(polynomial.witnesses.a)
-            .map(|w| utils::witness_to_var("Wa", w))?;
-        let wb = debugger
-            .fetch_witness(polynomial.witnesses.b)
-            .map(|w| utils::witness_to_var("Wb", w))?;
-        let wd = debugger
-            .fetch_witness(polynomial.witnesses.d)
-            .map(|w| utils::witness_to_var("Wd", w))?;
-        let wo = debugger
-            .fetch_witness(polynomial.witnesses.o)
-            .map(|w| utils::witness_to_var("Wo", w))?;
-
         Ok(Response::Variables {
             body: VariablesResponse {
                 variables: vec![
-                    idx, qm, ql, qr, qd, qc, qo, pi, qarith, qlogic, qrange,
-                    qgroup, qadd, eval, wa, wb, wd, wo,
+                    idx, gate, qm, ql, qr, qd, qc, qo, pi, qarith, qlogic,
+                    qrange, qgroup, qadd, eval, wa, wb, wd, wo,
                 ],
             },
         })
     }
 
     async fn witness(&self, id: usize) -> io::Result<Response> {
-        let mut debugger = self.backend.lock().await;
-        let debugger = debugger.as_mut().ok_or_else(Self::not_initialized)?;
-
-        let witness = debugger.fetch_witness(id)?;
-        let witness = ZkWitness::from(witness);
+        let witness = self
+            .locked(move |debugger| {
+                debugger.fetch_witness(id).map(ZkWitness::from)
+            })
+            .await?;
 
         Ok(ZkResponse::Witness { witness }.into())
     }
+
+    async fn goto_line(&self, source: &str, line: u64) -> io::Result<Response> {
+        let _still = self.still()?;
+        let source = source.to_string();
+
+        let state = self
+            .blocking(move |debugger| debugger.goto_line(&source, line))
+            .await?;
+        let id = state.constraint().unwrap_or_default();
+
+        Ok(ZkResponse::GotoLine { id }.into())
+    }
+
+    async fn continue_max(
+        &self,
+        max: usize,
+        reverse: bool,
+    ) -> io::Result<Response> {
+        let _still = self.still()?;
+        let debugger = self.debugger().await?;
+        let d = debugger.clone();
+
+        let state = self
+            .scan_reported("Continuing", d, move || match reverse {
+                true => debugger.turn_max(max),
+                false => debugger.cont_max(max),
+            })
+            .await?;
+
+        Ok(ZkResponse::ContinueMax { state }.into())
+    }
+
+    async fn focus(
+        &self,
+        focus: Option<Focus>,
+        failures: bool,
+    ) -> io::Result<Response> {
+        let f = focus.clone();
+
+        self.locked(move |debugger| {
+            debugger.set_focus(f, failures);
+
+            Ok(())
+        })
+        .await?;
+
+        Ok(ZkResponse::Focus { focus }.into())
+    }
+
+    async fn top(&self, limit: usize) -> io::Result<Response> {
+        let mut usage = self.locked(|debugger| debugger.usage()).await?;
+
+        usage.truncate(limit);
+
+        Ok(ZkResponse::Top { usage }.into())
+    }
+
+    async fn wirecheck(&self) -> io::Result<Response> {
+        let warnings = self.locked(|debugger| debugger.wirecheck()).await?;
+
+        Ok(ZkResponse::WireCheck { warnings }.into())
+    }
+
+    async fn timeline(&self) -> io::Result<Response> {
+        let (warnings, patterns) = self
+            .locked(|debugger| {
+                let timeline = debugger.timeline()?;
+
+                Ok((timeline.warnings().to_vec(), timeline.patterns().to_vec()))
+            })
+            .await?;
+
+        Ok(ZkResponse::Timeline { warnings, patterns }.into())
+    }
+
+    async fn locals(&self) -> io::Result<Response> {
+        let (constraint, witnesses) = self
+            .locked(|debugger| {
+                let constraint = debugger.fetch_current_constraint()?.id();
+                let witnesses = debugger
+                    .locals(constraint)?
+                    .into_iter()
+                    .map(ZkWitness::from)
+                    .collect();
+
+                Ok((constraint, witnesses))
+            })
+            .await?;
+
+        Ok(ZkResponse::Locals {
+            constraint,
+            witnesses,
+        }
+        .into())
+    }
+
+    async fn frontier(&self) -> io::Result<Response> {
+        let frontier = self
+            .locked(|debugger| {
+                let constraint = debugger.fetch_current_constraint()?.id();
+
+                debugger.frontier(constraint)
+            })
+            .await?;
+
+        Ok(ZkResponse::Frontier { frontier }.into())
+    }
+
+    async fn memory(&self) -> io::Result<Response> {
+        let usage = self.locked(|debugger| Ok(debugger.memory_usage())).await?;
+
+        Ok(ZkResponse::Memory { usage }.into())
+    }
+
+    async fn snippet(&self) -> io::Result<Response> {
+        let (constraint, markdown) = self.snippet_markdown().await?;
+
+        Ok(ZkResponse::Snippet {
+            constraint,
+            markdown,
+        }
+        .into())
+    }
+
+    async fn snippet_markdown(&self) -> io::Result<(usize, String)> {
+        self.locked(|debugger| {
+            let resolved = debugger.resolve_current_constraint()?;
+            let markdown = resolved.markdown(SNIPPET_CONTEXT).to_string();
+
+            Ok((resolved.constraint.id(), markdown))
+        })
+        .await
+    }
+
+    async fn equation(&self, notation: Notation) -> io::Result<Response> {
+        let (constraint, equation) = self
+            .locked(move |debugger| {
+                let resolved = debugger.resolve_current_constraint()?;
+                let equation = resolved.rendered(notation).to_string();
+
+                Ok((resolved.constraint.id(), equation))
+            })
+            .await?;
+
+        Ok(ZkResponse::Equation {
+            constraint,
+            equation,
+        }
+        .into())
+    }
+
+    async fn scalarcheck(&self) -> io::Result<Response> {
+        let warnings = self.locked(|debugger| debugger.scalarcheck()).await?;
+
+        Ok(ZkResponse::ScalarCheck { warnings }.into())
+    }
+
+    async fn constcheck(&self) -> io::Result<Response> {
+        let warnings = self.locked(|debugger| debugger.constcheck()).await?;
+
+        Ok(ZkResponse::ConstCheck { warnings }.into())
+    }
+
+    async fn shared_wires(&self) -> io::Result<Response> {
+        let wires = self.locked(|debugger| debugger.shared_wires()).await?;
+
+        Ok(ZkResponse::SharedWires { wires }.into())
+    }
+
+    async fn publics(&self, compare: bool) -> io::Result<Response> {
+        let (publics, mismatches) = self
+            .locked(move |debugger| {
+                let publics = debugger.publics()?;
+                let mismatches = match compare {
+                    true => {
+                        let artifacts =
+                            debugger.artifacts().ok_or_else(|| {
+                                io::Error::new(
+                                io::ErrorKind::NotFound,
+                                "no proof artifacts were recorded alongside \
+                                 the CDF file",
+                            )
+                            })?;
+
+                        Some(artifacts.compare(&publics))
+                    }
+                    false => None,
+                };
+
+                Ok((publics, mismatches))
+            })
+            .await?;
+
+        Ok(ZkResponse::Publics {
+            publics,
+            mismatches,
+        }
+        .into())
+    }
+
+    async fn raw(&self, id: Option<usize>) -> io::Result<Response> {
+        let (constraint, offset, bytes) = self
+            .locked(move |debugger| {
+                let constraint = id.unwrap_or_else(|| debugger.position());
+                let bytes = debugger.raw_constraint_bytes(constraint)?;
+                let offset = debugger
+                    .preamble()
+                    .constraint_offset(constraint)
+                    .unwrap_or_default();
+
+                Ok((constraint, offset, bytes))
+            })
+            .await?;
+
+        Ok(ZkResponse::Raw {
+            constraint,
+            offset,
+            bytes,
+        }
+        .into())
+    }
+
+    async fn backend_call(&self, call: BackendCall) -> io::Result<Response> {
+        let _still = self.still()?;
+        let mut debugger = self.debugger().await?;
+
+        let reply = self.scan(move || call.apply(&mut debugger)).await?;
+
+        Ok(ZkResponse::Backend { reply }.into())
+    }
+
+    /// Pause the running continue; a stopped debugger is left as is
+    async fn pause(&self) -> io::Result<Response> {
+        self.interrupt();
+
+        Ok(ZkResponse::Pause.into())
+    }
+
+    /// Interrupt the running continue at its next interruption point, without
+    /// waiting for the backend
+    fn interrupt(&self) {
+        // the backend is written only while a file is loaded, when there is
+        // nothing to interrupt
+        let backend = match self.backend.try_read() {
+            Ok(backend) => backend,
+            Err(_) => return,
+        };
+
+        if self.running.try_lock().is_err() {
+            if let Some(debugger) = backend.as_ref() {
+                debugger.interrupt();
+            }
+        }
+    }
+
+    /// Answer the requests of a client, one response for every request and
+    /// in their order.
+    ///
+    /// The requests arriving while one is answered are queued, but a pause
+    /// request interrupts the running one right away, so a call scanning the
+    /// circuit doesn't hold it. The pause is still answered in its turn.
+    async fn dispatch(
+        mut self,
+        mut requests: mpsc::Receiver<Request>,
+        responses: mpsc::Sender<Response>,
+    ) {
+        let session = self.session();
+        let mut queued = VecDeque::new();
+
+        loop {
+            let request = match queued.pop_front() {
+                Some(request) => request,
+                None => match requests.recv().await {
+                    Some(request) => request,
+                    None => break,
+                },
+            };
+
+            let response = self.request(request);
+            tokio::pin!(response);
+
+            let response = loop {
+                tokio::select! {
+                    response = &mut response => break response,
+
+                    Some(request) = requests.recv() => {
+                        if Self::is_pause(&request) {
+                            session.interrupt();
+                        }
+
+                        queued.push_back(request);
+                    }
+                }
+            };
+
+            if let Some(response) = response {
+                if responses.send(response).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn is_pause(request: &Request) -> bool {
+        match request {
+            Request::Custom { arguments } => matches!(
+                ZkRequest::try_from(arguments.as_ref()),
+                Ok(ZkRequest::Pause)
+            ),
+            _ => false,
+        }
+    }
 }
 
 #[async_trait]
@@ -658,100 +1888,108 @@ impl Backend for ZkDap {
         events: Sender<Event>,
         _requests: Sender<ReactorReverseRequest>,
     ) -> Self {
-        let backend = None;
-        let backend = Mutex::new(backend);
-        let backend = Arc::new(backend);
+        let config = ZkDapConfig::load().unwrap_or_else(|e| {
+            tracing::warn!("failed to load the dap config: {}", e);
+            ZkDapConfig::default()
+        });
 
-        ZkDap { events, backend }
+        ZkDap::new(events, config)
     }
 
     async fn request(&mut self, request: Request) -> Option<Response> {
         tracing::debug!("request received: {:?}", request);
 
+        self.diagnostics.record(&request);
+
+        // a failed request is answered as well, so every request gets its
+        // response in order
+        let command = request.clone().into_protocol(0).command;
+
         let response = match request {
             // attach won't affect the state of the dap - we can have many
             // clients attached
-            Request::Attach { .. } => Ok(Some(Response::Attach)),
+            Request::Attach { .. } => Ok(Response::Attach),
 
             Request::BreakpointLocations { arguments } => {
-                self.breakpoint_locations(arguments).await.map(Some)
+                self.breakpoint_locations(arguments).await
             }
 
             // the backend is immediately ready after load
             Request::ConfigurationDone { .. } => {
-                Ok(Some(Response::ConfigurationDone))
+                Ok(Response::ConfigurationDone)
             }
 
-            Request::Continue { .. } => self.r#continue().await.map(Some),
+            Request::Continue { .. } => self.r#continue().await,
 
             Request::Custom { arguments } => {
-                self.custom_request(arguments).await.map(Some)
+                self.custom_request(arguments).await
             }
 
             // we might implement multi-session per dap provider in the future
-            Request::Disconnect { .. } => Ok(Some(Response::Disconnect)),
-            Request::Terminate { .. } => Ok(Some(Response::Terminate)),
-            Request::Launch { .. } => Ok(Some(Response::Launch)),
+            Request::Disconnect { .. } => Ok(Response::Disconnect),
+            Request::Terminate { .. } => Ok(Response::Terminate),
+            Request::Launch { .. } => Ok(Response::Launch),
 
-            Request::Evaluate { .. } => self.evaluate().await.map(Some),
+            Request::Evaluate { arguments } => {
+                self.evaluate(&arguments.expression).await
+            }
 
-            Request::Goto { arguments } => self.goto(arguments).await.map(Some),
+            Request::Goto { arguments } => self.goto(arguments).await,
 
-            Request::Initialize { .. } => self.initialize().await.map(Some),
+            Request::Initialize { arguments } => {
+                self.positions = Positions::from(&arguments);
+                self.progress = arguments.supports_progress_reporting;
+                self.initialize().await
+            }
 
-            Request::Next { .. } => self.next().await.map(Some),
+            Request::Next { .. } => self.next().await,
 
-            Request::Restart { .. } => self.restart().await.map(Some),
+            Request::Restart { .. } => self.restart().await,
 
-            Request::ReverseContinue { .. } => {
-                self.reverse_continue().await.map(Some)
-            }
+            Request::ReverseContinue { .. } => self.reverse_continue().await,
 
-            Request::Scopes { .. } => self.scopes().await.map(Some),
+            Request::Scopes { .. } => self.scopes().await,
 
             Request::SetBreakpoints { arguments } => {
-                self.set_breakpoints(arguments).await.map(Some)
+                self.set_breakpoints(arguments).await
             }
 
-            Request::StackTrace { .. } => self.stack_trace().await.map(Some),
+            Request::StackTrace { .. } => self.stack_trace().await,
 
-            Request::StepBack { .. } => self.step_back().await.map(Some),
+            Request::StepBack { .. } => self.step_back().await,
 
-            Request::Threads => self.threads().await.map(Some),
+            Request::Threads => self.threads().await,
 
-            Request::Variables { arguments } => {
-                self.variables(arguments).await.map(Some)
-            }
+            Request::Variables { arguments } => self.variables(arguments).await,
 
-            _ => {
-                tracing::warn!("not supported");
-                Ok(None)
-            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the request is not supported",
+            )),
         };
 
-        response
-            .map(|response| {
+        self.report().await;
+
+        match response {
+            Ok(response) => {
                 tracing::debug!("responding {:?}", response);
-                response
-            })
-            .unwrap_or_else(|e| {
+                Some(response)
+            }
+
+            Err(e) => {
                 tracing::warn!("error responding request: {}", e);
 
-                self.events
-                    .try_send(Event::Output {
-                        category: Some(OutputCategory::Stderr),
-                        output: e.to_string(),
-                        group: None,
-                        variables_reference: None,
-                        source: None,
-                        line: None,
-                        column: None,
-                        data: None,
-                    })
-                    .ok();
+                self.report_internal_error(&e).await;
 
-                None
-            })
+                Some(Response::Error {
+                    command,
+                    error: ProtocolResponseError {
+                        message: Some(e.to_string()),
+                        body: None,
+                    },
+                })
+            }
+        }
     }
 
     async fn response(&mut self, _id: u64, response: Response) {
@@ -0,0 +1,302 @@
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Breakpoint, DebuggerBackend, EncodableConstraint, EncodableWitness,
+    Preamble, State,
+};
+
+use super::{ZkRequest, ZkResponse};
+
+/// Operation of a [`DebuggerBackend`], forwarded to the DAP backend via
+/// [`ZkRequest::Backend`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "call", rename_all = "camelCase")]
+pub enum BackendCall {
+    /// [`DebuggerBackend::preamble`]
+    Preamble,
+    /// [`DebuggerBackend::afore`]
+    Afore,
+    /// [`DebuggerBackend::cont`]
+    Cont,
+    /// [`DebuggerBackend::goto`]
+    Goto {
+        /// Target constraint
+        constraint: usize,
+    },
+    /// [`DebuggerBackend::step`]
+    Step,
+    /// [`DebuggerBackend::turn`]
+    Turn,
+    /// [`DebuggerBackend::position`]
+    Position,
+    /// [`DebuggerBackend::current_constraint`]
+    CurrentConstraint,
+    /// [`DebuggerBackend::constraint`]
+    Constraint {
+        /// Id of the constraint
+        id: usize,
+    },
+    /// [`DebuggerBackend::witness`]
+    Witness {
+        /// Id of the witness
+        id: usize,
+    },
+    /// [`DebuggerBackend::add_breakpoint`]
+    AddBreakpoint {
+        /// Source pattern of the breakpoint
+        source: String,
+        /// Line of the breakpoint
+        line: Option<u64>,
+    },
+    /// [`DebuggerBackend::remove_breakpoint`]
+    RemoveBreakpoint {
+        /// Id of the breakpoint
+        id: usize,
+    },
+    /// [`DebuggerBackend::breakpoints`]
+    Breakpoints,
+}
+
+impl BackendCall {
+    /// Execute the operation over a backend
+    pub fn apply<B>(self, backend: &mut B) -> io::Result<BackendReply>
+    where
+        B: DebuggerBackend + ?Sized,
+    {
+        Ok(match self {
+            Self::Preamble => BackendReply::Preamble {
+                preamble: backend.preamble()?,
+            },
+
+            Self::Afore => BackendReply::State {
+                state: backend.afore()?,
+            },
+
+            Self::Cont => BackendReply::State {
+                state: backend.cont()?,
+            },
+
+            Self::Goto { constraint } => BackendReply::State {
+                state: backend.goto(constraint)?,
+            },
+
+            Self::Step => BackendReply::State {
+                state: backend.step()?,
+            },
+
+            Self::Turn => BackendReply::State {
+                state: backend.turn()?,
+            },
+
+            Self::Position => BackendReply::Position {
+                constraint: backend.position()?,
+            },
+
+            Self::CurrentConstraint => BackendReply::Constraint {
+                constraint: backend.current_constraint()?,
+            },
+
+            Self::Constraint { id } => BackendReply::Constraint {
+                constraint: backend.constraint(id)?,
+            },
+
+            Self::Witness { id } => BackendReply::Witness {
+                witness: backend.witness(id)?,
+            },
+
+            Self::AddBreakpoint { source, line } => {
+                BackendReply::AddBreakpoint {
+                    id: backend.add_breakpoint(source, line)?,
+                }
+            }
+
+            Self::RemoveBreakpoint { id } => BackendReply::RemoveBreakpoint {
+                removed: backend.remove_breakpoint(id)?,
+            },
+
+            Self::Breakpoints => BackendReply::Breakpoints {
+                breakpoints: backend.breakpoints()?,
+            },
+        })
+    }
+}
+
+/// Result of a [`BackendCall`], returned via [`ZkResponse::Backend`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reply", rename_all = "camelCase")]
+pub enum BackendReply {
+    /// Preamble of the loaded circuit
+    Preamble {
+        /// Preamble
+        preamble: Preamble,
+    },
+    /// State of the debugger after a movement
+    State {
+        /// Resulting state
+        state: State,
+    },
+    /// Position of the debugger
+    Position {
+        /// Id of the current constraint
+        constraint: usize,
+    },
+    /// A fetched constraint
+    Constraint {
+        /// Constraint
+        constraint: EncodableConstraint,
+    },
+    /// A fetched witness
+    Witness {
+        /// Witness
+        witness: EncodableWitness,
+    },
+    /// A breakpoint was added
+    AddBreakpoint {
+        /// Id of the breakpoint
+        id: usize,
+    },
+    /// A breakpoint removal was attempted
+    RemoveBreakpoint {
+        /// Flag on whether or not the breakpoint was present
+        removed: bool,
+    },
+    /// Breakpoints of the debugger
+    Breakpoints {
+        /// Breakpoints with their ids
+        breakpoints: Vec<(usize, Breakpoint)>,
+    },
+}
+
+/// [`DebuggerBackend`] that forwards the operations to a
+/// [`ZkDap`](struct.ZkDap.html) service.
+///
+/// The transport delivers a request to the service and waits for its
+/// response, so the client is agnostic to how the service is reached. The
+/// movements performed through the client are reported to the DAP frontend
+/// as stopped events, like the ones requested by the frontend itself.
+#[derive(Debug, Clone)]
+pub struct ZkDapClient<T> {
+    transport: T,
+}
+
+impl<T> ZkDapClient<T>
+where
+    T: Fn(ZkRequest) -> io::Result<ZkResponse>,
+{
+    /// Create a new client with the provided transport
+    pub const fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    fn call(&self, call: BackendCall) -> io::Result<BackendReply> {
+        match (self.transport)(ZkRequest::Backend { call })? {
+            ZkResponse::Backend { reply } => Ok(reply),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn state(&self, call: BackendCall) -> io::Result<State> {
+        match self.call(call)? {
+            BackendReply::State { state } => Ok(state),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn constraint_reply(
+        &self,
+        call: BackendCall,
+    ) -> io::Result<EncodableConstraint> {
+        match self.call(call)? {
+            BackendReply::Constraint { constraint } => Ok(constraint),
+            _ => Err(unexpected()),
+        }
+    }
+}
+
+fn unexpected() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "unexpected reply from the backend",
+    )
+}
+
+impl<T> DebuggerBackend for ZkDapClient<T>
+where
+    T: Fn(ZkRequest) -> io::Result<ZkResponse>,
+{
+    fn preamble(&self) -> io::Result<Preamble> {
+        match self.call(BackendCall::Preamble)? {
+            BackendReply::Preamble { preamble } => Ok(preamble),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn afore(&mut self) -> io::Result<State> {
+        self.state(BackendCall::Afore)
+    }
+
+    fn cont(&mut self) -> io::Result<State> {
+        self.state(BackendCall::Cont)
+    }
+
+    fn goto(&mut self, idx: usize) -> io::Result<State> {
+        self.state(BackendCall::Goto { constraint: idx })
+    }
+
+    fn step(&mut self) -> io::Result<State> {
+        self.state(BackendCall::Step)
+    }
+
+    fn turn(&mut self) -> io::Result<State> {
+        self.state(BackendCall::Turn)
+    }
+
+    fn position(&self) -> io::Result<usize> {
+        match self.call(BackendCall::Position)? {
+            BackendReply::Position { constraint } => Ok(constraint),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn current_constraint(&mut self) -> io::Result<EncodableConstraint> {
+        self.constraint_reply(BackendCall::CurrentConstraint)
+    }
+
+    fn constraint(&mut self, idx: usize) -> io::Result<EncodableConstraint> {
+        self.constraint_reply(BackendCall::Constraint { id: idx })
+    }
+
+    fn witness(&mut self, idx: usize) -> io::Result<EncodableWitness> {
+        match self.call(BackendCall::Witness { id: idx })? {
+            BackendReply::Witness { witness } => Ok(witness),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn add_breakpoint(
+        &mut self,
+        source: String,
+        line: Option<u64>,
+    ) -> io::Result<usize> {
+        match self.call(BackendCall::AddBreakpoint { source, line })? {
+            BackendReply::AddBreakpoint { id } => Ok(id),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn remove_breakpoint(&mut self, id: usize) -> io::Result<bool> {
+        match self.call(BackendCall::RemoveBreakpoint { id })? {
+            BackendReply::RemoveBreakpoint { removed } => Ok(removed),
+            _ => Err(unexpected()),
+        }
+    }
+
+    fn breakpoints(&self) -> io::Result<Vec<(usize, Breakpoint)>> {
+        match self.call(BackendCall::Breakpoints)? {
+            BackendReply::Breakpoints { breakpoints } => Ok(breakpoints),
+            _ => Err(unexpected()),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::io;
+
+use dap_reactor::prelude::*;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+    BufReader,
+};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+use super::position::Positions;
+use super::ZkDap;
+
+/// Serve the requests of a client connected to the service, until it
+/// disconnects.
+///
+/// The messages are framed and dispatched as the `dap-reactor` listener does,
+/// except that the raw arguments of the initialize request are read as well:
+/// its parser takes the bases the client omitted as 0, while the protocol
+/// defaults them to 1.
+pub(crate) async fn serve(
+    stream: TcpStream,
+    capacity: usize,
+) -> io::Result<()> {
+    let (inbound, mut outbound) = stream.into_split();
+
+    let (events_tx, mut events) = mpsc::channel::<Event>(capacity);
+    let (requests, requests_rx) = mpsc::channel(capacity);
+    let (responses_tx, mut responses) = mpsc::channel::<Response>(capacity);
+    let (rejected_tx, mut rejected) =
+        mpsc::channel::<(u64, Response)>(capacity);
+
+    // the responses are dispatched in the order of their requests, so they
+    // are matched with the seq of the requests in the same order
+    let (seqs_tx, mut seqs) = mpsc::channel::<u64>(capacity);
+
+    // the service sends no reverse requests to the client
+    let (reverse, _) = mpsc::channel(1);
+
+    let dap = ZkDap::init(events_tx, reverse).await;
+
+    tokio::spawn(dap.dispatch(requests_rx, responses_tx));
+
+    // a single writer numbers the outbound messages in the order they are sent
+    tokio::spawn(async move {
+        let mut seq = 0;
+
+        loop {
+            let (request_seq, response) = tokio::select! {
+                Some(event) = events.recv() => {
+                    seq += 1;
+
+                    let event = ProtocolMessage::from(event.into_protocol(seq));
+
+                    if let Err(e) = send(&mut outbound, event).await {
+                        tracing::error!("error sending event: {}", e);
+                        break;
+                    }
+
+                    continue;
+                }
+
+                Some(response) = responses.recv() => match seqs.recv().await {
+                    Some(request_seq) => (request_seq, response),
+                    None => break,
+                },
+
+                Some(rejected) = rejected.recv() => rejected,
+
+                else => break,
+            };
+
+            seq += 1;
+
+            let response = response.into_protocol(seq, request_seq);
+            let response = ProtocolMessage::Response(response);
+
+            if let Err(e) = send(&mut outbound, response).await {
+                tracing::error!("error sending response: {}", e);
+                break;
+            }
+        }
+    });
+
+    let mut inbound = BufReader::new(inbound);
+
+    while let Some(content) = read_message(&mut inbound).await? {
+        let request = match ProtocolMessage::try_from_json_bytes(content) {
+            Ok(ProtocolMessage::Request(request)) => request,
+
+            Ok(message) => {
+                tracing::debug!(
+                    "unexpected message from the client: {:?}",
+                    message
+                );
+                continue;
+            }
+
+            Err(e) => {
+                tracing::warn!("invalid message: {}", e);
+                continue;
+            }
+        };
+
+        let parsed = match Request::try_from(&request) {
+            Ok(Request::Initialize { mut arguments }) => {
+                Positions::default_omitted(
+                    &mut arguments,
+                    request.arguments.as_ref(),
+                );
+
+                Request::Initialize { arguments }
+            }
+
+            Ok(r) => r,
+
+            // answered right away, as the service receives only the parsed
+            // requests
+            Err(e) => {
+                tracing::warn!("invalid request from the client: {}", e);
+
+                let response = Response::Error {
+                    command: request.command,
+                    error: ProtocolResponseError {
+                        message: Some(e.to_string()),
+                        body: None,
+                    },
+                };
+
+                if rejected_tx.send((request.seq, response)).await.is_err() {
+                    break;
+                }
+
+                continue;
+            }
+        };
+
+        let sent = match seqs_tx.send(request.seq).await {
+            Ok(()) => requests.send(parsed).await.is_ok(),
+            Err(_) => false,
+        };
+
+        if !sent {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a message to the client, framed by its `Content-Length` header
+async fn send<W>(writer: &mut W, message: ProtocolMessage) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let message = message.into_adapter_message();
+
+    writer.write_all(message.as_bytes()).await
+}
+
+/// Read the content of the next message, framed by its `Content-Length`
+/// header. Returns `None` once the client disconnects.
+async fn read_message<R>(reader: &mut R) -> io::Result<Option<Vec<u8>>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut len = None;
+    let mut line = String::new();
+
+    let len = loop {
+        line.clear();
+
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim_end();
+
+        if header.is_empty() {
+            match len {
+                Some(len) => break len,
+                None => continue,
+            }
+        }
+
+        match header.split_once(':') {
+            Some((key, value))
+                if key.trim().eq_ignore_ascii_case("content-length") =>
+            {
+                len = value.trim().parse::<usize>().map(Some).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, e)
+                })?;
+            }
+            _ => tracing::debug!("ignored header: {}", header),
+        }
+    };
+
+    let mut content = vec![0; len];
+
+    reader.read_exact(&mut content).await?;
+
+    Ok(Some(content))
+}
@@ -0,0 +1,149 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::Preamble;
+
+/// Error of a handler that panicked while running on the blocking threads
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Panicked(pub String);
+
+impl fmt::Display for Panicked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a request handler panicked: {}", self.0)
+    }
+}
+
+impl Error for Panicked {}
+
+impl Panicked {
+    /// Wrap the panic into an IO error, recognized by [`is_internal`]
+    pub fn error(message: String) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, Self(message))
+    }
+}
+
+/// Whether an error is a fault of the backend rather than of the request,
+/// such as a malformed file or a panicked handler
+pub(crate) fn is_internal(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof => true,
+        _ => e
+            .get_ref()
+            .map_or(false, |e| e.downcast_ref::<Panicked>().is_some()),
+    }
+}
+
+/// State of the loaded circuit when the error occurred
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Circuit {
+    pub preamble: Preamble,
+    pub position: usize,
+    pub constraint_offset: Option<usize>,
+    pub witness_offset: Option<usize>,
+    pub source_cache_offset: Option<usize>,
+}
+
+impl Circuit {
+    pub fn new(preamble: Preamble, position: usize) -> Self {
+        Self {
+            preamble,
+            position,
+            constraint_offset: preamble.constraint_offset(position),
+            witness_offset: preamble.witness_offset(position),
+            source_cache_offset: preamble.checked_source_cache_offset(),
+        }
+    }
+}
+
+/// Bundle written when an internal error occurs, to be attached to a bug
+/// report against the debugger
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Bundle<'a> {
+    version: &'static str,
+    timestamp: u64,
+    error: String,
+    circuit: Option<Circuit>,
+    requests: &'a VecDeque<String>,
+}
+
+/// Latest requests of the session, captured with the state of the circuit
+/// into a bundle when an internal error occurs
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Diagnostics {
+    capacity: usize,
+    requests: VecDeque<String>,
+}
+
+impl Diagnostics {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            requests: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Whether the bundles are disabled by the config
+    pub const fn is_disabled(&self) -> bool {
+        self.capacity == 0
+    }
+
+    /// Record a request, discarding the oldest one past the capacity
+    pub fn record<R>(&mut self, request: &R)
+    where
+        R: fmt::Debug,
+    {
+        if self.is_disabled() {
+            return;
+        }
+
+        if self.requests.len() == self.capacity {
+            self.requests.pop_front();
+        }
+
+        self.requests.push_back(format!("{:?}", request));
+    }
+
+    /// Write the bundle of an error into a file of `dir`, returning its path
+    pub fn write<P>(
+        &self,
+        dir: P,
+        error: &io::Error,
+        circuit: Option<Circuit>,
+    ) -> io::Result<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|t| t.as_millis() as u64)
+            .unwrap_or_default();
+
+        let bundle = Bundle {
+            version: env!("CARGO_PKG_VERSION"),
+            timestamp,
+            error: error.to_string(),
+            circuit,
+            requests: &self.requests,
+        };
+
+        let bundle = serde_json::to_vec_pretty(&bundle)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let path = dir.as_ref().join(format!(
+            "dusk-cdf-dap-{}-{}.json",
+            timestamp,
+            std::process::id()
+        ));
+
+        fs::write(&path, bundle)?;
+
+        Ok(path)
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+
+use dap_reactor::prelude::*;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::error::TrySendError;
+use toml_base_config::BaseConfig;
+
+/// Policy applied to the output messages when the events channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverflowPolicy {
+    /// Wait until the client consumes the pending events
+    Block,
+    /// Queue the message into a bounded backlog, discarding the oldest queued
+    /// message when the backlog is full
+    DropOldest,
+    /// Discard the message
+    DropNew,
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self {
+        Self::DropNew
+    }
+}
+
+/// Configuration parameters of the [`ZkDap`](struct.ZkDap.html) service.
+///
+/// See [`BaseConfig`] for context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkDapConfig {
+    /// Policy applied to the output messages when the events channel is full
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
+    /// Maximum number of output messages retained by
+    /// [`OverflowPolicy::DropOldest`]
+    #[serde(default = "ZkDapConfig::default_overflow_backlog")]
+    pub overflow_backlog: usize,
+    /// Worker threads of the runtime of the binary, defaulting to the number
+    /// of cores
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+    /// Maximum number of blocking threads of the runtime of the binary,
+    /// shared by the scans of all the sessions
+    #[serde(default)]
+    pub max_blocking_threads: Option<usize>,
+    /// Maximum number of fetches and scans a session runs at once on the
+    /// blocking threads, so a single client can't exhaust the pool
+    #[serde(default = "ZkDapConfig::default_fetch_concurrency")]
+    pub fetch_concurrency: usize,
+    /// Latest requests of a session captured in the diagnostic bundle of an
+    /// internal error; `0` disables the bundles
+    #[serde(default = "ZkDapConfig::default_diagnostic_requests")]
+    pub diagnostic_requests: usize,
+}
+
+impl Default for ZkDapConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ZkDapConfig {
+    /// Store a const default that discards the output messages once the
+    /// events channel is full.
+    pub const DEFAULT: Self = Self {
+        overflow_policy: OverflowPolicy::DropNew,
+        overflow_backlog: 100,
+        worker_threads: None,
+        max_blocking_threads: None,
+        fetch_concurrency: 2,
+        diagnostic_requests: 20,
+    };
+
+    const fn default_overflow_backlog() -> usize {
+        Self::DEFAULT.overflow_backlog
+    }
+
+    const fn default_fetch_concurrency() -> usize {
+        Self::DEFAULT.fetch_concurrency
+    }
+
+    const fn default_diagnostic_requests() -> usize {
+        Self::DEFAULT.diagnostic_requests
+    }
+
+    /// Override the policy applied when the events channel is full
+    pub fn with_overflow_policy(
+        &mut self,
+        overflow_policy: OverflowPolicy,
+    ) -> &mut Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Override the backlog size of [`OverflowPolicy::DropOldest`]
+    pub fn with_overflow_backlog(
+        &mut self,
+        overflow_backlog: usize,
+    ) -> &mut Self {
+        self.overflow_backlog = overflow_backlog;
+        self
+    }
+
+    /// Override the worker threads of the runtime
+    pub fn with_worker_threads(&mut self, worker_threads: usize) -> &mut Self {
+        self.worker_threads.replace(worker_threads);
+        self
+    }
+
+    /// Override the maximum number of blocking threads of the runtime
+    pub fn with_max_blocking_threads(
+        &mut self,
+        max_blocking_threads: usize,
+    ) -> &mut Self {
+        self.max_blocking_threads.replace(max_blocking_threads);
+        self
+    }
+
+    /// Override the fetches and scans a session runs at once
+    pub fn with_fetch_concurrency(
+        &mut self,
+        fetch_concurrency: usize,
+    ) -> &mut Self {
+        self.fetch_concurrency = fetch_concurrency;
+        self
+    }
+
+    /// Override the requests captured in a diagnostic bundle
+    pub fn with_diagnostic_requests(
+        &mut self,
+        diagnostic_requests: usize,
+    ) -> &mut Self {
+        self.diagnostic_requests = diagnostic_requests;
+        self
+    }
+}
+
+impl BaseConfig for ZkDapConfig {
+    const PACKAGE: &'static str = "dusk-cdf-dap";
+}
+
+/// Output messages that couldn't be delivered to the client.
+///
+/// Dropped messages are accounted, and summarized into a single output
+/// message as soon as the channel has room for it.
+#[derive(Debug, Clone)]
+pub(crate) struct Outputs {
+    policy: OverflowPolicy,
+    capacity: usize,
+    backlog: VecDeque<Event>,
+    dropped: usize,
+}
+
+impl Outputs {
+    pub fn new(config: &ZkDapConfig) -> Self {
+        Self {
+            policy: config.overflow_policy,
+            capacity: config.overflow_backlog,
+            backlog: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+
+    pub const fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    pub const fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Create an output event with the provided category
+    pub fn event(category: OutputCategory, output: String) -> Event {
+        Event::Output {
+            category: Some(category),
+            output,
+            group: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            data: None,
+        }
+    }
+
+    /// Send the event without waiting for the client, applying the overflow
+    /// policy if the channel is full.
+    ///
+    /// [`OverflowPolicy::Block`] is handled as [`OverflowPolicy::DropOldest`]
+    /// since the caller is expected to await on the channel instead.
+    pub fn send(&mut self, events: &Sender<Event>, event: Event) {
+        self.flush(events);
+
+        let event = if self.backlog.is_empty() {
+            match events.try_send(event) {
+                Ok(()) => return,
+                Err(TrySendError::Full(event)) => event,
+                Err(TrySendError::Closed(_)) => return,
+            }
+        } else {
+            event
+        };
+
+        match self.policy {
+            OverflowPolicy::DropNew => self.drop_one(),
+
+            OverflowPolicy::Block | OverflowPolicy::DropOldest => {
+                self.backlog.push_back(event);
+
+                if self.backlog.len() > self.capacity {
+                    self.backlog.pop_front();
+                    self.drop_one();
+                }
+            }
+        }
+    }
+
+    /// Deliver the queued messages, followed by a summary of the dropped ones,
+    /// for as long as the channel has room for them.
+    pub fn flush(&mut self, events: &Sender<Event>) {
+        while let Some(event) = self.backlog.pop_front() {
+            match events.try_send(event) {
+                Ok(()) => (),
+                Err(TrySendError::Full(event)) => {
+                    self.backlog.push_front(event);
+                    return;
+                }
+                Err(TrySendError::Closed(_)) => return,
+            }
+        }
+
+        if self.dropped == 0 {
+            return;
+        }
+
+        let summary = Self::event(
+            OutputCategory::Important,
+            format!(
+                "{} output message(s) were dropped because the client didn't \
+                 consume the events in time",
+                self.dropped
+            ),
+        );
+
+        if events.try_send(summary).is_ok() {
+            self.dropped = 0;
+        }
+    }
+
+    fn drop_one(&mut self) {
+        self.dropped += 1;
+
+        tracing::warn!(
+            "events channel is full; {} output message(s) dropped",
+            self.dropped
+        );
+    }
+}
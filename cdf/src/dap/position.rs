@@ -0,0 +1,82 @@
+use dap_reactor::prelude::{InitializeArguments, Value};
+
+/// Bases of the lines and columns of the client, negotiated by the initialize
+/// request.
+///
+/// The debugger counts both from 1, as `line!()` and `column!()` do. Every
+/// line and column crossing the protocol boundary is converted here, so a
+/// 0-based client highlights the same source position as a 1-based one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Positions {
+    /// Whether the lines of the client start at 1
+    pub lines_start_at_1: bool,
+    /// Whether the columns of the client start at 1
+    pub columns_start_at_1: bool,
+}
+
+impl Default for Positions {
+    fn default() -> Self {
+        Self {
+            lines_start_at_1: true,
+            columns_start_at_1: true,
+        }
+    }
+}
+
+impl From<&InitializeArguments> for Positions {
+    fn from(arguments: &InitializeArguments) -> Self {
+        Self {
+            lines_start_at_1: arguments.lines_start_at_1,
+            columns_start_at_1: arguments.column_start_at_1,
+        }
+    }
+}
+
+impl Positions {
+    /// Set the bases the client omitted from the raw arguments of its
+    /// initialize request to 1, the default of the protocol. The parser of the
+    /// requests reads an absent base as 0.
+    pub fn default_omitted(
+        arguments: &mut InitializeArguments,
+        raw: Option<&Value>,
+    ) {
+        let omitted = |attribute| raw.and_then(|r| r.get(attribute)).is_none();
+
+        if omitted("linesStartAt1") {
+            arguments.lines_start_at_1 = true;
+        }
+
+        if omitted("columnStartAt1") {
+            arguments.column_start_at_1 = true;
+        }
+    }
+
+    /// Line of the debugger for a line sent by the client
+    pub const fn line_from_client(&self, line: u64) -> u64 {
+        Self::from_client(self.lines_start_at_1, line)
+    }
+
+    /// Line sent to the client for a line of the debugger
+    pub const fn line_to_client(&self, line: u64) -> u64 {
+        Self::to_client(self.lines_start_at_1, line)
+    }
+
+    /// Column sent to the client for a column of the debugger
+    pub const fn column_to_client(&self, column: u64) -> u64 {
+        Self::to_client(self.columns_start_at_1, column)
+    }
+
+    const fn from_client(start_at_1: bool, position: u64) -> u64 {
+        match start_at_1 {
+            true => position,
+            false => position.saturating_add(1),
+        }
+    }
+
+    const fn to_client(start_at_1: bool, position: u64) -> u64 {
+        match start_at_1 {
+            true => position,
+            false => position.saturating_sub(1),
+        }
+    }
+}
@@ -1,9 +1,8 @@
-use std::sync::Arc;
-
 use dap_reactor::models::Source;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::mpsc;
 
 use super::*;
+use crate::DebuggerBackend;
 
 impl From<ZkRequest> for Value {
     fn from(req: ZkRequest) -> Self {
@@ -24,6 +23,26 @@ impl From<Response> for ZkResponse {
     }
 }
 
+impl ZkDap {
+    /// Wait for the running continue to stop
+    async fn stopped(&self) {
+        drop(self.running.lock().await);
+    }
+}
+
+/// Path of the CDF file of the test assets
+fn test_cdf() -> String {
+    let path = std::env!("CARGO_MANIFEST_DIR");
+
+    std::path::PathBuf::from(path)
+        .parent()
+        .expect("failed to updir")
+        .join("assets")
+        .join("test.cdf")
+        .display()
+        .to_string()
+}
+
 #[tokio::test]
 async fn bind_client_wont_panic() -> io::Result<()> {
     let dap = ZkDapBuilder::new("127.0.0.1:0").build().await?;
@@ -81,21 +100,11 @@ async fn bind_client_wont_panic() -> io::Result<()> {
 
 #[tokio::test]
 async fn service_behavior() -> io::Result<()> {
-    let path = std::env!("CARGO_MANIFEST_DIR");
-    let path = std::path::PathBuf::from(path)
-        .parent()
-        .expect("failed to updir")
-        .join("assets")
-        .join("test.cdf")
-        .display()
-        .to_string();
+    let path = test_cdf();
 
     let (events, mut events_rx) = mpsc::channel(50);
 
-    let service = ZkDap {
-        events,
-        backend: Arc::new(Mutex::new(None)),
-    };
+    let service = ZkDap::new(events, ZkDapConfig::default());
 
     service.initialize().await?;
 
@@ -108,7 +117,8 @@ async fn service_behavior() -> io::Result<()> {
 
     service.restart().await?;
     service.breakpoint_locations(None).await?;
-    service.evaluate().await?;
+    service.evaluate("").await?;
+    service.evaluate("constraint").await?;
 
     while events_rx.try_recv().is_ok() {}
 
@@ -120,31 +130,80 @@ async fn service_behavior() -> io::Result<()> {
         })
         .await?;
     service.r#continue().await?;
+    service.stopped().await;
     service.reverse_continue().await?;
+    service.stopped().await;
     service
-        .add_breakpoint(Breakpoint {
-            id: None,
-            verified: true,
-            message: None,
-            source: Some(Source {
-                name: Some("foo".into()),
-                source_reference: Some(SourceReference::Path("bar".into())),
-                presentation_hint: None,
-                origin: None,
-                sources: vec![],
-                adapter_data: None,
-                checksums: vec![],
-            }),
-            line: None,
-            column: None,
-            end_line: None,
-            end_column: None,
-            instruction_reference: None,
-            offset: None,
-        })
+        .add_breakpoint(
+            Breakpoint {
+                id: None,
+                verified: true,
+                message: None,
+                source: Some(Source {
+                    name: Some("foo".into()),
+                    source_reference: Some(SourceReference::Path("bar".into())),
+                    presentation_hint: None,
+                    origin: None,
+                    sources: vec![],
+                    adapter_data: None,
+                    checksums: vec![],
+                }),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_reference: None,
+                offset: None,
+            },
+            Some(GateKind::Range),
+            false,
+        )
         .await?;
+    service.ignore_breakpoint(1, 2).await?;
     service.remove_breakpoint(0).await?;
+
+    let breakpoint = |line| Breakpoint {
+        id: None,
+        verified: true,
+        message: None,
+        source: Some(Source {
+            name: Some("lib.rs".into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: vec![],
+            adapter_data: None,
+            checksums: vec![],
+        }),
+        line: Some(line),
+        column: None,
+        end_line: None,
+        end_column: None,
+        instruction_reference: None,
+        offset: None,
+    };
+
+    let added = service
+        .add_breakpoints(vec![breakpoint(25), breakpoint(26)], None)
+        .await
+        .map(ZkResponse::from)?;
+    let removed = service
+        .remove_breakpoints(Some("lib.rs"))
+        .await
+        .map(ZkResponse::from)?;
+
+    match (added, removed) {
+        (
+            ZkResponse::AddBreakpoints { ids },
+            ZkResponse::RemoveBreakpoints { ids: removed },
+        ) => assert_eq!(ids, removed),
+        r => panic!("unexpected responses: {:?}", r),
+    }
+
+    service.remove_breakpoints(None).await?;
     service.source_contents().await?;
+    service.source_list(None).await?;
+    service.sources(Some("*.rs"), 1, Some(2), &[]).await?;
     service.scopes().await?;
     service
         .set_breakpoints(SetBreakpointsArguments {
@@ -178,8 +237,898 @@ async fn service_behavior() -> io::Result<()> {
         })
         .await?;
     service.witness(0).await?;
+    service.wirecheck().await?;
+    service.timeline().await?;
+    service.scalarcheck().await?;
+    service.constcheck().await?;
+    service.shared_wires().await?;
+    service.publics(false).await?;
+    service.raw(None).await?;
+    service.top(5).await?;
 
     while events_rx.try_recv().is_ok() {}
 
     Ok(())
 }
+
+#[tokio::test]
+async fn sources_are_filtered_and_paginated() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.load_cdf(path).await?;
+
+    let all = match ZkResponse::from(service.source_list(None).await?) {
+        ZkResponse::SourceList { sources } => sources,
+        _ => panic!("unexpected response"),
+    };
+
+    assert!(!all.is_empty());
+
+    let none = service.source_list(Some("*.none")).await?;
+
+    assert_eq!(
+        ZkResponse::from(none),
+        ZkResponse::SourceList { sources: vec![] }
+    );
+
+    let page = service.sources(None, 1, Some(1), &[]).await?;
+
+    match ZkResponse::from(page) {
+        ZkResponse::Sources { sources, total, .. } => {
+            assert_eq!(total, all.len());
+            assert_eq!(sources.len(), 1.min(all.len() - 1));
+        }
+        _ => panic!("unexpected response"),
+    }
+
+    let known = vec![all[0].clone()];
+    let page = service.sources(None, 0, None, &known).await?;
+
+    match ZkResponse::from(page) {
+        ZkResponse::Sources {
+            sources,
+            unchanged,
+            total,
+        } => {
+            assert_eq!(total, all.len());
+            assert_eq!(unchanged, vec![all[0].path.clone()]);
+            assert!(sources.iter().all(|s| s.path != all[0].path));
+        }
+        _ => panic!("unexpected response"),
+    }
+
+    // the same contents under another path are still transferred
+    let renamed = vec![ZkSourceInfo {
+        path: "renamed.rs".into(),
+        ..all[0].clone()
+    }];
+    let page = service.sources(None, 0, None, &renamed).await?;
+
+    match ZkResponse::from(page) {
+        ZkResponse::Sources {
+            sources, unchanged, ..
+        } => {
+            assert!(unchanged.is_empty());
+            assert_eq!(sources.len(), all.len());
+        }
+        _ => panic!("unexpected response"),
+    }
+
+    let source = &all[0];
+    let chunk = service.source_chunk(source.path.clone(), 0, 10).await?;
+
+    match ZkResponse::from(chunk) {
+        ZkResponse::SourceChunk {
+            offset,
+            contents,
+            size,
+            ..
+        } => {
+            assert_eq!(offset, 0);
+            assert_eq!(size, source.size);
+            assert!(contents.len() <= 10);
+        }
+        _ => panic!("unexpected response"),
+    }
+
+    service
+        .source_chunk("not-a-source".into(), 0, 10)
+        .await
+        .expect_err("the source doesn't exist");
+
+    Ok(())
+}
+
+#[test]
+fn source_chunk_respects_char_boundaries() {
+    let contents = "a\u{00e9}b";
+
+    assert_eq!(utils::source_chunk(contents, 0, 2), (0, "a"));
+    assert_eq!(utils::source_chunk(contents, 2, 2), (1, "\u{00e9}"));
+    assert_eq!(utils::source_chunk(contents, 1, 1), (1, "\u{00e9}"));
+    assert_eq!(utils::source_chunk(contents, 3, 10), (3, "b"));
+    assert_eq!(utils::source_chunk(contents, 10, 10), (4, ""));
+}
+
+fn output_text(event: Option<Event>) -> String {
+    match event {
+        Some(Event::Output { output, .. }) => output,
+        e => panic!("unexpected event: {:?}", e),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn client_forwards_operations_to_the_service() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.initialize().await?;
+    service
+        .custom_request(Some(Value::from(ZkRequest::LoadCdf {
+            path: path.clone(),
+        })))
+        .await?;
+
+    let handle = tokio::runtime::Handle::current();
+    let mut client = ZkDapClient::new(|request: ZkRequest| {
+        let request = Value::from(request);
+
+        tokio::task::block_in_place(|| {
+            handle.block_on(service.custom_request(Some(request)))
+        })
+        .map(ZkResponse::from)
+    });
+
+    let mut local = ZkDebugger::open(path)?;
+
+    assert_eq!(client.preamble()?, *local.preamble());
+
+    for _ in 0..3 {
+        assert_eq!(client.step()?, local.step()?);
+    }
+
+    assert_eq!(client.position()?, local.position());
+    assert_eq!(
+        client.current_constraint()?,
+        DebuggerBackend::current_constraint(&mut local)?
+    );
+    assert_eq!(client.witness(1)?, DebuggerBackend::witness(&mut local, 1)?);
+
+    let id = client.add_breakpoint("foo".into(), Some(2))?;
+
+    local.add_breakpoint("foo".into(), Some(2));
+
+    assert_eq!(client.breakpoints()?, DebuggerBackend::breakpoints(&local)?);
+    assert!(client.remove_breakpoint(id)?);
+    assert!(!client.remove_breakpoint(id)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn overflow_drop_new_summarizes_dropped_outputs() {
+    let (events, mut events_rx) = mpsc::channel(1);
+    let mut outputs = Outputs::new(&ZkDapConfig::DEFAULT);
+
+    for output in ["a", "b", "c"] {
+        outputs.send(
+            &events,
+            Outputs::event(OutputCategory::Stderr, output.into()),
+        );
+    }
+
+    assert_eq!(outputs.dropped(), 2);
+    assert_eq!(output_text(events_rx.recv().await), "a");
+
+    outputs.send(&events, Outputs::event(OutputCategory::Stderr, "d".into()));
+
+    assert!(output_text(events_rx.recv().await).starts_with("2 output"));
+    assert_eq!(outputs.dropped(), 1);
+}
+
+#[tokio::test]
+async fn overflow_drop_oldest_keeps_latest_outputs() {
+    let (events, mut events_rx) = mpsc::channel(1);
+
+    let mut config = ZkDapConfig::DEFAULT;
+    config
+        .with_overflow_policy(OverflowPolicy::DropOldest)
+        .with_overflow_backlog(1);
+
+    let mut outputs = Outputs::new(&config);
+
+    for output in ["a", "b", "c"] {
+        outputs.send(
+            &events,
+            Outputs::event(OutputCategory::Stderr, output.into()),
+        );
+    }
+
+    assert_eq!(outputs.dropped(), 1);
+    assert_eq!(output_text(events_rx.recv().await), "a");
+
+    outputs.flush(&events);
+
+    assert_eq!(output_text(events_rx.recv().await), "c");
+
+    outputs.flush(&events);
+
+    assert!(output_text(events_rx.recv().await).starts_with("1 output"));
+    assert_eq!(outputs.dropped(), 0);
+}
+
+#[tokio::test]
+async fn debugger_changes_are_reported_as_events() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, mut events_rx) = mpsc::channel(50);
+
+    let mut service = ZkDap::new(events, ZkDapConfig::default());
+
+    service
+        .request(Request::Custom {
+            arguments: Some(Value::from(ZkRequest::LoadCdf { path })),
+        })
+        .await;
+
+    assert!(matches!(events_rx.try_recv(), Ok(Event::Thread { .. })));
+    assert!(matches!(
+        events_rx.try_recv(),
+        Ok(Event::Stopped {
+            reason: StoppedReason::Step,
+            ..
+        })
+    ));
+    assert!(output_text(events_rx.try_recv().ok()).contains("(constraint 0, "));
+
+    service
+        .request(Request::Goto {
+            arguments: GotoArguments {
+                thread_id: 0,
+                target_id: 5,
+            },
+        })
+        .await;
+
+    match events_rx.try_recv() {
+        Ok(Event::Stopped {
+            reason: StoppedReason::Goto,
+            description,
+            text,
+            ..
+        }) => {
+            assert!(description.is_some());
+            assert!(text.is_some());
+        }
+        e => panic!("unexpected event: {:?}", e),
+    }
+
+    assert!(output_text(events_rx.try_recv().ok()).contains("(constraint 5, "));
+
+    let response = service
+        .custom_request(Some(Value::from(ZkRequest::GotoLine {
+            source: "naive-signature/src/gadget.rs".into(),
+            line: 28,
+        })))
+        .await
+        .map(ZkResponse::from)?;
+
+    assert_eq!(response, ZkResponse::GotoLine { id: 16 });
+    assert!(matches!(
+        events_rx.try_recv(),
+        Ok(Event::Stopped {
+            reason: StoppedReason::Goto,
+            ..
+        })
+    ));
+    assert!(output_text(events_rx.try_recv().ok()).starts_with(
+        "stopped at naive-signature/src/gadget.rs:28 (constraint 16, "
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn positions_convert_zero_based_clients() {
+    let zero = Positions {
+        lines_start_at_1: false,
+        columns_start_at_1: false,
+    };
+
+    assert_eq!(zero.line_from_client(0), 1);
+    assert_eq!(zero.line_to_client(1), 0);
+    assert_eq!(zero.column_to_client(1), 0);
+    assert_eq!(zero.column_to_client(0), 0);
+
+    let one = Positions::default();
+
+    assert_eq!(one.line_from_client(12), 12);
+    assert_eq!(one.line_to_client(12), 12);
+}
+
+#[tokio::test]
+async fn zero_based_clients_are_converted_at_the_boundary() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let mut service = ZkDap::new(events, ZkDapConfig::default());
+
+    service
+        .request(Request::Initialize {
+            arguments: InitializeArguments {
+                client_id: None,
+                client_name: None,
+                adapter_id: "cdf".into(),
+                locale: None,
+                lines_start_at_1: false,
+                column_start_at_1: false,
+                path_format: None,
+                supports_variable_type: false,
+                supports_variable_paging: false,
+                supports_run_in_terminal_request: false,
+                supports_memory_references: false,
+                supports_progress_reporting: false,
+                supports_invalidated_event: false,
+                supports_memory_event: false,
+                supports_args_can_be_interpreted_by_shell: false,
+            },
+        })
+        .await;
+
+    service.load_cdf(path).await?;
+
+    let response = service
+        .custom_request(Some(Value::from(ZkRequest::GotoLine {
+            source: "naive-signature/src/gadget.rs".into(),
+            line: 27,
+        })))
+        .await
+        .map(ZkResponse::from)?;
+
+    assert_eq!(response, ZkResponse::GotoLine { id: 16 });
+
+    let (line, column) = {
+        let shared = service.debugger().await?;
+        let mut debugger = shared.lock()?;
+        let constraint = debugger.fetch_constraint(16)?;
+
+        (constraint.line(), constraint.col())
+    };
+
+    match service.stack_trace().await? {
+        Response::StackTrace { body } => {
+            let frame = &body.stack_frames[0];
+
+            assert_eq!(frame.line, line - 1);
+            assert_eq!(frame.column, column.saturating_sub(1));
+        }
+        _ => panic!("unexpected response"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn omitted_bases_default_to_1() -> io::Result<()> {
+    let path = test_cdf();
+
+    let dap = ZkDapBuilder::new("127.0.0.1:0").build().await?;
+    let socket = dap.local_addr()?;
+
+    tokio::spawn(async move {
+        dap.listen().await.ok();
+    });
+
+    let mut client = dap_reactor::reactor::ClientBuilder::new()
+        .connect(socket)
+        .await?;
+
+    let initialize = Request::Initialize {
+        arguments: InitializeArguments {
+            client_id: None,
+            client_name: None,
+            adapter_id: "cdf".into(),
+            locale: None,
+            lines_start_at_1: false,
+            column_start_at_1: false,
+            path_format: None,
+            supports_variable_type: false,
+            supports_variable_paging: false,
+            supports_run_in_terminal_request: false,
+            supports_memory_references: false,
+            supports_progress_reporting: false,
+            supports_invalidated_event: false,
+            supports_memory_event: false,
+            supports_args_can_be_interpreted_by_shell: false,
+        },
+    };
+
+    // the false attributes are omitted on the wire, so the client sends an
+    // initialize request without either base
+    let arguments = initialize.clone().into_protocol(1).arguments;
+    assert!(arguments.is_some());
+    assert!(arguments.iter().all(|a| a.get("linesStartAt1").is_none()
+        && a.get("columnStartAt1").is_none()));
+
+    let requests_sent = [
+        initialize,
+        Request::from(ZkRequest::LoadCdf { path }),
+        Request::from(ZkRequest::GotoLine {
+            source: "naive-signature/src/gadget.rs".into(),
+            line: 28,
+        }),
+    ];
+
+    for request in requests_sent {
+        client
+            .request(request)
+            .await
+            .expect("failed to send request");
+    }
+
+    let mut responses = Vec::new();
+
+    while responses.len() < 3 {
+        let re = client
+            .responses
+            .recv()
+            .await
+            .expect("a response was expected");
+
+        responses.push(re.response);
+    }
+
+    let goto = responses.pop().map(ZkResponse::from);
+    let load = responses.pop().map(ZkResponse::from);
+
+    assert!(matches!(responses.pop(), Some(Response::Initialize { .. })));
+    assert_eq!(load, Some(ZkResponse::LoadCdf));
+    assert_eq!(goto, Some(ZkResponse::GotoLine { id: 16 }));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn continue_is_answered_before_its_stop() -> io::Result<()> {
+    use std::sync::mpsc as sync;
+
+    let path = test_cdf();
+
+    let (events, mut events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.load_cdf(path).await?;
+
+    while events_rx.try_recv().is_ok() {}
+
+    // the debugger is held by another thread, as by a long scan
+    let shared = service.debugger().await?;
+    let (held_tx, held) = sync::channel();
+    let (release, release_rx) = sync::channel::<()>();
+
+    let holder = std::thread::spawn(move || {
+        let _debugger = shared.lock();
+
+        held_tx.send(()).ok();
+        release_rx.recv().ok();
+    });
+
+    held.recv().ok();
+
+    let response = service.r#continue().await?;
+
+    assert!(matches!(response, Response::Continue { .. }));
+
+    // the requests that don't move the debugger are still answered, and the
+    // movements are refused until the continue stops
+    service.threads().await?;
+    service.source_list(None).await?;
+
+    assert!(matches!(
+        service.next().await,
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock
+    ));
+
+    let response = service
+        .custom_request(Some(Value::from(ZkRequest::Pause)))
+        .await
+        .map(ZkResponse::from)?;
+
+    assert_eq!(response, ZkResponse::Pause);
+
+    release.send(()).ok();
+    holder.join().expect("the holder panicked");
+
+    let stopped = loop {
+        match events_rx.recv().await {
+            Some(Event::Stopped { .. } | Event::Terminated { .. }) => {
+                break true
+            }
+            Some(_) => continue,
+            None => break false,
+        }
+    };
+
+    assert!(stopped);
+
+    service.stopped().await;
+    service.next().await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pause_interrupts_the_running_request() -> io::Result<()> {
+    use std::sync::mpsc as sync;
+    use std::time::{Duration, Instant};
+
+    let path = test_cdf();
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.load_cdf(path).await?;
+
+    let shared = service.debugger().await?;
+    let scanning = shared.clone();
+    let token = shared.interrupt_token();
+
+    let (requests, requests_rx) = mpsc::channel(50);
+    let (responses_tx, mut responses) = mpsc::channel(50);
+
+    tokio::spawn(service.dispatch(requests_rx, responses_tx));
+
+    // the debugger is held by another thread, as by a long scan, until the
+    // scan is interrupted
+    let (held_tx, held) = sync::channel();
+
+    let holder = std::thread::spawn(move || {
+        let debugger = shared.lock();
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        held_tx.send(()).ok();
+
+        while !token.is_cancelled() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // read before the release, as the resumed scan consumes it
+        let interrupted = token.is_cancelled();
+
+        drop(debugger);
+
+        interrupted
+    });
+
+    held.recv().ok();
+
+    let call = Request::from(ZkRequest::Backend {
+        call: BackendCall::Cont,
+    });
+
+    requests
+        .send(call)
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    while scanning.progress().is_none() {
+        time::sleep(Duration::from_millis(10)).await;
+    }
+
+    for request in [Request::from(ZkRequest::Pause), Request::Threads] {
+        requests
+            .send(request)
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
+
+    // the call is interrupted before the pause is answered
+    let interrupted = holder.join().expect("the holder panicked");
+
+    assert!(interrupted);
+
+    let response = responses.recv().await.map(ZkResponse::from);
+    assert!(matches!(response, Some(ZkResponse::Backend { .. })));
+
+    let response = responses.recv().await.map(ZkResponse::from);
+    assert_eq!(response, Some(ZkResponse::Pause));
+
+    let response = responses.recv().await;
+    assert!(matches!(response, Some(Response::Threads { .. })));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn scans_are_bounded_by_the_fetch_concurrency() -> io::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let mut config = ZkDapConfig::DEFAULT;
+    config.with_fetch_concurrency(1);
+
+    let service = ZkDap::new(events, config);
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let scan = || {
+        let running = Arc::clone(&running);
+        let peak = Arc::clone(&peak);
+
+        move || {
+            let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+
+            peak.fetch_max(n, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            running.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(())
+        }
+    };
+
+    let (a, b) = tokio::join!(service.scan(scan()), service.scan(scan()));
+
+    a?;
+    b?;
+
+    assert_eq!(peak.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[test]
+fn internal_errors_are_captured_into_a_bundle() -> io::Result<()> {
+    use super::diagnostics::{is_internal, Circuit, Diagnostics, Panicked};
+
+    let decode = io::Error::new(io::ErrorKind::InvalidData, "bad preamble");
+    let request = io::Error::new(io::ErrorKind::InvalidInput, "bad request");
+
+    assert!(is_internal(&decode));
+    assert!(is_internal(&Panicked::error("boom".into())));
+    assert!(!is_internal(&request));
+    assert!(!is_internal(&ZkDap::not_initialized()));
+
+    let mut diagnostics = Diagnostics::new(2);
+
+    for request in ["first", "second", "third"] {
+        diagnostics.record(&request);
+    }
+
+    let preamble = crate::Preamble::new(2, 3, Config::default());
+    let circuit = Circuit::new(preamble, 1);
+    let dir = std::env::temp_dir();
+    let path = diagnostics.write(&dir, &decode, Some(circuit))?;
+
+    let bundle = std::fs::read(&path)?;
+    let bundle: Value = serde_json::from_slice(&bundle)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    std::fs::remove_file(path)?;
+
+    assert_eq!(bundle["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(bundle["error"], "bad preamble");
+    assert_eq!(bundle["circuit"]["position"], 1);
+    assert_eq!(
+        bundle["circuit"]["constraint_offset"],
+        preamble.constraint_offset(1).expect("offset in bounds")
+    );
+    assert_eq!(
+        bundle["requests"],
+        serde_json::json!(["\"second\"", "\"third\""])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn breakpoints_survive_a_reload() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, mut events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.load_cdf(path.clone()).await?;
+
+    let (bound, unbound) = {
+        let shared = service.debugger().await?;
+        let mut debugger = shared.lock()?;
+
+        (
+            debugger.add_breakpoint("naive-hash".into(), Some(8)),
+            debugger.add_breakpoint("naive-hash".into(), Some(9)),
+        )
+    };
+
+    while events_rx.try_recv().is_ok() {}
+
+    service.load_cdf(path).await?;
+
+    {
+        let shared = service.debugger().await?;
+        let mut debugger = shared.lock()?;
+
+        assert!(debugger.fetch_breakpoint(bound).is_some());
+        assert!(debugger.fetch_breakpoint(unbound).is_some());
+        assert!(
+            debugger.add_breakpoint("naive-circuit".into(), None) > unbound
+        );
+    }
+
+    // every carried-over breakpoint is verified again
+    let mut changed = vec![];
+    while let Ok(event) = events_rx.try_recv() {
+        if let Event::Breakpoint { breakpoint, .. } = event {
+            changed.extend(breakpoint.id.map(|id| (id, breakpoint.verified)));
+        }
+    }
+
+    assert_eq!(changed, vec![(bound as u64, true), (unbound as u64, false)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn set_breakpoints_are_verified_per_source() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.load_cdf(path).await?;
+
+    let (name, pattern) = {
+        let shared = service.debugger().await?;
+        let mut debugger = shared.lock()?;
+
+        (
+            debugger.fetch_constraint(8)?.name().to_string(),
+            debugger.add_breakpoint("naive-hash".into(), None),
+        )
+    };
+
+    let set = |lines: Vec<u64>| {
+        service.set_breakpoints(SetBreakpointsArguments {
+            source: Source {
+                name: None,
+                source_reference: Some(SourceReference::Path(name.clone())),
+                presentation_hint: None,
+                origin: None,
+                sources: vec![],
+                adapter_data: None,
+                checksums: vec![],
+            },
+            breakpoints: vec![],
+            lines,
+            source_modified: false,
+        })
+    };
+
+    let breakpoints = |response: Response| match response {
+        Response::SetBreakpoints { body } => body.breakpoints,
+        r => panic!("unexpected response: {:?}", r),
+    };
+
+    let set_both = breakpoints(set(vec![8, 9]).await?);
+    let verified: Vec<_> = set_both.iter().map(|b| b.verified).collect();
+
+    assert_eq!(verified, vec![true, false]);
+    assert!(set_both[0].message.is_none());
+    assert!(set_both[1].message.is_some());
+
+    let set_one = breakpoints(set(vec![8]).await?);
+
+    assert_eq!(set_one.len(), 1);
+    assert_eq!(set_one[0].id, set_both[0].id);
+
+    let shared = service.debugger().await?;
+    let debugger = shared.lock()?;
+    let unbound = set_both[1].id.expect("the breakpoint has an id") as usize;
+
+    assert!(debugger.fetch_breakpoint(pattern).is_some());
+    assert!(debugger.fetch_breakpoint(unbound).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_service_replies_over_channels() -> io::Result<()> {
+    let path = test_cdf();
+
+    let ZkDapLocal {
+        requests,
+        mut responses,
+        ..
+    } = ZkDapBuilder::new(()).build_local();
+
+    let requests_sent = [
+        Request::from(ZkRequest::LoadCdf { path }),
+        Request::from(ZkRequest::Witness { id: usize::MAX }),
+        Request::from(ZkRequest::GotoLine {
+            source: "naive-signature/src/gadget.rs".into(),
+            line: 28,
+        }),
+    ];
+
+    for request in requests_sent {
+        requests
+            .send(request)
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
+
+    let response = responses.recv().await.map(ZkResponse::from);
+    assert!(matches!(response, Some(ZkResponse::LoadCdf)));
+
+    // a failed request is answered in its turn
+    match responses.recv().await {
+        Some(Response::Error { command, error }) => {
+            assert_eq!(command, "custom");
+            assert!(error.message.is_some());
+        }
+        r => panic!("unexpected response: {:?}", r),
+    }
+
+    let response = responses.recv().await.map(ZkResponse::from);
+    assert_eq!(response, Some(ZkResponse::GotoLine { id: 16 }));
+
+    drop(requests);
+    assert!(responses.recv().await.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn batched_requests_reply_in_order() -> io::Result<()> {
+    let path = test_cdf();
+
+    let (events, _events_rx) = mpsc::channel(50);
+
+    let service = ZkDap::new(events, ZkDapConfig::default());
+
+    service.load_cdf(path).await?;
+
+    let request = ZkRequest::Batch {
+        requests: vec![
+            ZkRequest::Witness { id: 3 },
+            ZkRequest::Witness { id: usize::MAX },
+            ZkRequest::Batch { requests: vec![] },
+            ZkRequest::Witness { id: 5 },
+        ],
+    };
+
+    let response = service.custom_request(Some(Value::from(request))).await?;
+
+    let responses = match ZkResponse::from(response) {
+        ZkResponse::Batch { responses } => responses,
+        r => panic!("unexpected response: {:?}", r),
+    };
+
+    // a failed request doesn't abort the next ones
+    let ids: Vec<_> = responses
+        .iter()
+        .map(|r| match r {
+            Ok(ZkResponse::Witness { witness }) => Some(witness.id),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(ids, vec![Some(3), None, None, Some(5)]);
+    assert!(responses[1].is_err() && responses[2].is_err());
+
+    Ok(())
+}
@@ -6,27 +6,88 @@ use dap_reactor::{reactor::ClientRequest, request::Request};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::Witness;
+use crate::{
+    CircuitUsage, Focus, Frontier, GateKind, GateMatch, MemoryUsage, Notation,
+    PublicInput, PublicMismatch, SharedWire, State, Warning, Witness,
+};
 
-use super::utils;
+use super::{utils, BackendCall, BackendReply};
 
 fn err(e: &str) -> io::Error {
-    io::Error::new(io::ErrorKind::InvalidData, e)
+    io::Error::new(io::ErrorKind::InvalidInput, e)
+}
+
+fn deserialize_array<T>(value: Option<&Value>) -> io::Result<Vec<T>>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    value
+        .and_then(Value::as_array)
+        .ok_or_else(|| err("expected an array attribute"))?
+        .iter()
+        .map(|s| {
+            T::deserialize(s)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+        })
+        .collect()
+}
+
+/// Optional gate kind attribute, absent or null if the breakpoint matches any
+/// gate
+fn gate_kind(value: Option<&Value>) -> io::Result<Option<GateKind>> {
+    value
+        .filter(|v| !v.is_null())
+        .map(|v| {
+            v.as_str()
+                .ok_or_else(|| err("invalid kind attribute"))?
+                .parse()
+                .map_err(|e: String| err(&e))
+        })
+        .transpose()
 }
 
 /// A request customized for the ZK backend
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ZkRequest {
     /// Add a new breakpoint
     AddBreakpoint {
         /// Breakpoint to be added
         breakpoint: Breakpoint,
+        /// Kind of the gates that trigger the breakpoint
+        kind: Option<GateKind>,
     },
     /// Remove a previously added breakpoint
     RemoveBreakpoint {
         /// Id of the breakpoint
         id: u64,
     },
+    /// Add several breakpoints in a single round trip
+    AddBreakpoints {
+        /// Breakpoints to be added
+        breakpoints: Vec<Breakpoint>,
+        /// Kind of the gates that trigger the breakpoints
+        kind: Option<GateKind>,
+    },
+    /// Remove the breakpoints of a source, or all of them
+    RemoveBreakpoints {
+        /// Source of the breakpoints. If empty, will remove all of them
+        source: Option<String>,
+    },
+    /// Add a breakpoint that is removed after its first hit
+    AddTemporaryBreakpoint {
+        /// Breakpoint to be added
+        breakpoint: Breakpoint,
+        /// Kind of the gates that trigger the breakpoint
+        kind: Option<GateKind>,
+    },
+    /// Ignore the next hits of a breakpoint
+    IgnoreBreakpoint {
+        /// Id of the breakpoint
+        id: u64,
+        /// Number of hits to be ignored
+        count: u64,
+    },
     /// Load a CDF file
     LoadCdf {
         /// Path of the CDF file to be loaded
@@ -34,20 +95,125 @@ pub enum ZkRequest {
     },
     /// Request the source contents of the CDF file
     SourceContents,
+    /// Request the names and sizes of the sources of the CDF file
+    SourceList {
+        /// Optional glob pattern to filter the source paths
+        filter: Option<String>,
+    },
+    /// Request a page of the sources of the CDF file
+    Sources {
+        /// Optional glob pattern to filter the source paths
+        filter: Option<String>,
+        /// Number of matching sources to skip
+        offset: usize,
+        /// Maximum number of sources to return
+        limit: Option<usize>,
+        /// Sources already known by the client, by path and content hash.
+        ///
+        /// A source known with the same path and hash is skipped from the
+        /// page, but is still accounted in the total and listed as
+        /// unchanged.
+        known: Vec<ZkSourceInfo>,
+    },
+    /// Request a chunk of the contents of a source
+    SourceChunk {
+        /// Path of the source, as returned by [`ZkRequest::SourceList`]
+        path: String,
+        /// Byte offset of the chunk
+        offset: usize,
+        /// Maximum length of the chunk, in bytes
+        len: usize,
+    },
     /// Return the internal data of a witness
     Witness {
         /// Id of the witness
         id: usize,
     },
+    /// Jump to the first constraint at a source line
+    GotoLine {
+        /// Source pattern, matched as the breakpoints are
+        source: String,
+        /// Line of the source
+        line: u64,
+    },
+    /// Continue or reverse the execution, visiting at most `max` constraints
+    ContinueMax {
+        /// Maximum number of constraints to visit
+        max: usize,
+        /// Reverse the execution instead
+        reverse: bool,
+    },
+    /// Limit the navigation to a portion of the circuit
+    Focus {
+        /// Portion of the circuit, or `None` to lift the limit
+        focus: Option<Focus>,
+        /// Report the failures out of the focus
+        failures: bool,
+    },
+    /// Fetch the sources and gadgets with the largest footprint
+    Top {
+        /// Maximum number of sources and gadgets
+        limit: usize,
+    },
+    /// Check the wired witnesses of all constraints against the witness table
+    WireCheck,
+    /// Check the constraints against the allocation order of their witnesses
+    Timeline,
+    /// Check that the scalars stored in the CDF file are canonical
+    ScalarCheck,
+    /// Check for the witnesses holding well-known constants that aren't
+    /// fixed by a constant gate
+    ConstCheck,
+    /// List the witnesses wired into the constraints of more than one source
+    SharedWires,
+    /// Fetch the witnesses created by the current constraint
+    Locals,
+    /// Trace the wires of the current constraint to the earliest failure they
+    /// depend on
+    Frontier,
+    /// Report the memory held by the caches of the debugger
+    Memory,
+    /// Render the current constraint as a markdown snippet
+    Snippet,
+    /// Render the equation of the current constraint
+    Equation {
+        /// Notation of the rendered equation
+        notation: Notation,
+    },
+    /// List the public inputs recorded by the constraints
+    Publics {
+        /// Compare them with the public input vector of the proof artifacts
+        compare: bool,
+    },
+    /// Fetch the encoded bytes of a constraint
+    Raw {
+        /// Id of the constraint, or `None` for the current one
+        id: Option<usize>,
+    },
+    /// Execute an operation of the [`DebuggerBackend`](crate::DebuggerBackend)
+    Backend {
+        /// Operation to be executed
+        call: BackendCall,
+    },
+    /// Pause the running continue at its next interruption point
+    Pause,
+    /// Execute several requests in a single round trip, in order.
+    ///
+    /// A failed request doesn't abort the next ones; batches can't be nested.
+    Batch {
+        /// Requests to be executed
+        requests: Vec<ZkRequest>,
+    },
 }
 
 impl From<ZkRequest> for Request {
     fn from(request: ZkRequest) -> Self {
         match request {
-            ZkRequest::AddBreakpoint { breakpoint } => Request::Custom {
+            ZkRequest::AddBreakpoint { breakpoint, kind } => Request::Custom {
                 arguments: Some(serde_json::json!({
                     "command": "addBreakpoint",
                     "breakpoint": Value::from(breakpoint),
+                    "kind": kind,
                 })),
             },
 
@@ -58,6 +224,44 @@ impl From<ZkRequest> for Request {
                 })),
             },
 
+            ZkRequest::AddBreakpoints { breakpoints, kind } => {
+                let breakpoints: Vec<_> =
+                    breakpoints.into_iter().map(Value::from).collect();
+
+                Request::Custom {
+                    arguments: Some(serde_json::json!({
+                        "command": "addBreakpoints",
+                        "breakpoints": breakpoints,
+                        "kind": kind,
+                    })),
+                }
+            }
+
+            ZkRequest::RemoveBreakpoints { source } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "removeBreakpoints",
+                    "source": source,
+                })),
+            },
+
+            ZkRequest::AddTemporaryBreakpoint { breakpoint, kind } => {
+                Request::Custom {
+                    arguments: Some(serde_json::json!({
+                        "command": "addTemporaryBreakpoint",
+                        "breakpoint": Value::from(breakpoint),
+                        "kind": kind,
+                    })),
+                }
+            }
+
+            ZkRequest::IgnoreBreakpoint { id, count } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "ignoreBreakpoint",
+                    "id": id,
+                    "count": count,
+                })),
+            },
+
             ZkRequest::LoadCdf { path } => Request::Custom {
                 arguments: Some(serde_json::json!({
                     "command": "loadCdf",
@@ -71,12 +275,179 @@ impl From<ZkRequest> for Request {
                 })),
             },
 
+            ZkRequest::SourceList { filter } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "sourceList",
+                    "filter": filter,
+                })),
+            },
+
+            ZkRequest::Sources {
+                filter,
+                offset,
+                limit,
+                known,
+            } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "sources",
+                    "filter": filter,
+                    "offset": offset,
+                    "limit": limit,
+                    "known": known,
+                })),
+            },
+
+            ZkRequest::SourceChunk { path, offset, len } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "sourceChunk",
+                    "path": path,
+                    "offset": offset,
+                    "len": len,
+                })),
+            },
+
             ZkRequest::Witness { id } => Request::Custom {
                 arguments: Some(serde_json::json!({
                     "command": "witness",
                     "id": id,
                 })),
             },
+
+            ZkRequest::GotoLine { source, line } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "gotoLine",
+                    "source": source,
+                    "line": line,
+                })),
+            },
+
+            ZkRequest::ContinueMax { max, reverse } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "continueMax",
+                    "max": max,
+                    "reverse": reverse,
+                })),
+            },
+
+            ZkRequest::Focus { focus, failures } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "focus",
+                    "focus": focus,
+                    "failures": failures,
+                })),
+            },
+
+            ZkRequest::Top { limit } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "top",
+                    "limit": limit,
+                })),
+            },
+
+            ZkRequest::WireCheck => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "wireCheck",
+                })),
+            },
+
+            ZkRequest::Timeline => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "timeline",
+                })),
+            },
+
+            ZkRequest::ScalarCheck => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "scalarCheck",
+                })),
+            },
+
+            ZkRequest::ConstCheck => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "constCheck",
+                })),
+            },
+
+            ZkRequest::SharedWires => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "sharedWires",
+                })),
+            },
+
+            ZkRequest::Locals => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "locals",
+                })),
+            },
+
+            ZkRequest::Frontier => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "frontier",
+                })),
+            },
+
+            ZkRequest::Memory => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "memory",
+                })),
+            },
+
+            ZkRequest::Snippet => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "snippet",
+                })),
+            },
+
+            ZkRequest::Equation { notation } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "equation",
+                    "notation": notation,
+                })),
+            },
+
+            ZkRequest::Publics { compare } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "publics",
+                    "compare": compare,
+                })),
+            },
+
+            ZkRequest::Raw { id } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "raw",
+                    "id": id,
+                })),
+            },
+
+            ZkRequest::Backend { call } => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "backend",
+                    "call": call,
+                })),
+            },
+
+            ZkRequest::Pause => Request::Custom {
+                arguments: Some(serde_json::json!({
+                    "command": "pause",
+                })),
+            },
+
+            ZkRequest::Batch { requests } => {
+                let requests: Vec<_> = requests
+                    .into_iter()
+                    .filter_map(|r| match Request::from(r) {
+                        Request::Custom { arguments } => arguments,
+                        _ => None,
+                    })
+                    .collect();
+
+                Request::Custom {
+                    arguments: Some(serde_json::json!({
+                        "command": "batch",
+                        "requests": requests,
+                    })),
+                }
+            }
         }
     }
 }
@@ -101,13 +472,19 @@ impl TryFrom<Option<&Value>> for ZkRequest {
             .ok_or_else(|| err("arguments should contain a command"))?;
 
         match command {
-            "addBreakpoint" => args
-                .get("breakpoint")
-                .and_then(Value::as_object)
-                .map(Breakpoint::try_from)
-                .transpose()?
-                .map(|breakpoint| ZkRequest::AddBreakpoint { breakpoint })
-                .ok_or_else(|| err("invalid breakpoint attribute")),
+            "addBreakpoint" => {
+                let kind = gate_kind(args.get("kind"))?;
+
+                args.get("breakpoint")
+                    .and_then(Value::as_object)
+                    .map(Breakpoint::try_from)
+                    .transpose()?
+                    .map(|breakpoint| ZkRequest::AddBreakpoint {
+                        breakpoint,
+                        kind,
+                    })
+                    .ok_or_else(|| err("invalid breakpoint attribute"))
+            }
 
             "removeBreakpoint" => args
                 .get("id")
@@ -115,6 +492,66 @@ impl TryFrom<Option<&Value>> for ZkRequest {
                 .map(|id| ZkRequest::RemoveBreakpoint { id })
                 .ok_or_else(|| err("invalid id attribute")),
 
+            "addBreakpoints" => {
+                let kind = gate_kind(args.get("kind"))?;
+
+                let breakpoints = args
+                    .get("breakpoints")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| err("invalid breakpoints attribute"))?
+                    .iter()
+                    .map(|b| {
+                        b.as_object()
+                            .ok_or_else(|| err("invalid breakpoint attribute"))
+                            .and_then(|b| Ok(Breakpoint::try_from(b)?))
+                    })
+                    .collect::<io::Result<_>>()?;
+
+                Ok(ZkRequest::AddBreakpoints { breakpoints, kind })
+            }
+
+            "removeBreakpoints" => {
+                let source = args
+                    .get("source")
+                    .filter(|v| !v.is_null())
+                    .map(|v| {
+                        v.as_str()
+                            .map(String::from)
+                            .ok_or_else(|| err("invalid source attribute"))
+                    })
+                    .transpose()?;
+
+                Ok(ZkRequest::RemoveBreakpoints { source })
+            }
+
+            "addTemporaryBreakpoint" => {
+                let kind = gate_kind(args.get("kind"))?;
+
+                args.get("breakpoint")
+                    .and_then(Value::as_object)
+                    .map(Breakpoint::try_from)
+                    .transpose()?
+                    .map(|breakpoint| ZkRequest::AddTemporaryBreakpoint {
+                        breakpoint,
+                        kind,
+                    })
+                    .ok_or_else(|| err("invalid breakpoint attribute"))
+            }
+
+            "ignoreBreakpoint" => {
+                let id = args
+                    .get("id")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid id attribute"))?;
+
+                let count = args
+                    .get("count")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid count attribute"))?;
+
+                Ok(ZkRequest::IgnoreBreakpoint { id, count })
+            }
+
             "loadCdf" => args
                 .get("path")
                 .and_then(Value::as_str)
@@ -123,12 +560,190 @@ impl TryFrom<Option<&Value>> for ZkRequest {
 
             "sourceContents" => Ok(ZkRequest::SourceContents),
 
+            "sourceList" => {
+                let filter =
+                    args.get("filter").and_then(Value::as_str).map(From::from);
+
+                Ok(ZkRequest::SourceList { filter })
+            }
+
+            "sources" => {
+                let filter =
+                    args.get("filter").and_then(Value::as_str).map(From::from);
+
+                let offset = args
+                    .get("offset")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid offset attribute"))?
+                    as usize;
+
+                let limit = args
+                    .get("limit")
+                    .and_then(Value::as_u64)
+                    .map(|l| l as usize);
+
+                let known = match args.get("known") {
+                    Some(known) => deserialize_array(Some(known))?,
+                    None => vec![],
+                };
+
+                Ok(ZkRequest::Sources {
+                    filter,
+                    offset,
+                    limit,
+                    known,
+                })
+            }
+
+            "sourceChunk" => {
+                let path = args
+                    .get("path")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("invalid path attribute"))?
+                    .into();
+
+                let offset = args
+                    .get("offset")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid offset attribute"))?
+                    as usize;
+
+                let len = args
+                    .get("len")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid len attribute"))?
+                    as usize;
+
+                Ok(ZkRequest::SourceChunk { path, offset, len })
+            }
+
             "witness" => args
                 .get("id")
                 .and_then(Value::as_u64)
                 .map(|id| ZkRequest::Witness { id: id as usize })
                 .ok_or_else(|| err("invalid id attribute")),
 
+            "gotoLine" => {
+                let source = args
+                    .get("source")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("invalid source attribute"))?
+                    .into();
+
+                let line = args
+                    .get("line")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid line attribute"))?;
+
+                Ok(ZkRequest::GotoLine { source, line })
+            }
+
+            "continueMax" => {
+                let max = args
+                    .get("max")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid max attribute"))?
+                    as usize;
+
+                let reverse = args
+                    .get("reverse")
+                    .and_then(Value::as_bool)
+                    .ok_or_else(|| err("invalid reverse attribute"))?;
+
+                Ok(ZkRequest::ContinueMax { max, reverse })
+            }
+
+            "focus" => {
+                let focus = args
+                    .get("focus")
+                    .map(Option::<Focus>::deserialize)
+                    .transpose()
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+                    .flatten();
+
+                let failures = args
+                    .get("failures")
+                    .and_then(Value::as_bool)
+                    .ok_or_else(|| err("invalid failures attribute"))?;
+
+                Ok(ZkRequest::Focus { focus, failures })
+            }
+
+            "top" => args
+                .get("limit")
+                .and_then(Value::as_u64)
+                .map(|limit| ZkRequest::Top {
+                    limit: limit as usize,
+                })
+                .ok_or_else(|| err("invalid limit attribute")),
+
+            "wireCheck" => Ok(ZkRequest::WireCheck),
+
+            "timeline" => Ok(ZkRequest::Timeline),
+
+            "scalarCheck" => Ok(ZkRequest::ScalarCheck),
+
+            "constCheck" => Ok(ZkRequest::ConstCheck),
+
+            "sharedWires" => Ok(ZkRequest::SharedWires),
+
+            "locals" => Ok(ZkRequest::Locals),
+
+            "frontier" => Ok(ZkRequest::Frontier),
+
+            "memory" => Ok(ZkRequest::Memory),
+
+            "snippet" => Ok(ZkRequest::Snippet),
+
+            "equation" => args
+                .get("notation")
+                .ok_or_else(|| err("notation is mandatory"))
+                .and_then(|notation| {
+                    Notation::deserialize(notation)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|notation| ZkRequest::Equation { notation }),
+
+            "publics" => args
+                .get("compare")
+                .and_then(Value::as_bool)
+                .map(|compare| ZkRequest::Publics { compare })
+                .ok_or_else(|| err("invalid compare attribute")),
+
+            "raw" => {
+                let id = match args.get("id") {
+                    Some(id) if !id.is_null() => Some(
+                        id.as_u64()
+                            .ok_or_else(|| err("invalid id attribute"))?
+                            as usize,
+                    ),
+                    _ => None,
+                };
+
+                Ok(ZkRequest::Raw { id })
+            }
+
+            "backend" => args
+                .get("call")
+                .cloned()
+                .ok_or_else(|| err("call is mandatory"))
+                .and_then(|call| {
+                    serde_json::from_value(call)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|call| ZkRequest::Backend { call }),
+
+            "pause" => Ok(ZkRequest::Pause),
+
+            "batch" => args
+                .get("requests")
+                .and_then(Value::as_array)
+                .ok_or_else(|| err("invalid requests attribute"))?
+                .iter()
+                .map(|r| ZkRequest::try_from(Some(r)))
+                .collect::<io::Result<_>>()
+                .map(|requests| ZkRequest::Batch { requests }),
+
             _ => Err(io::Error::new(io::ErrorKind::Other, "unknown command")),
         }
     }
@@ -143,6 +758,42 @@ pub struct ZkSource {
     pub path: String,
     /// Source contents
     pub contents: String,
+    /// Content hash, as hex-encoded SHA-256
+    #[serde(default)]
+    pub hash: String,
+}
+
+impl ZkSource {
+    /// Create a new source, computing its content hash
+    pub fn new<P, C>(path: P, contents: C) -> Self
+    where
+        P: Into<String>,
+        C: Into<String>,
+    {
+        let path = path.into();
+        let contents = contents.into();
+        let hash = utils::source_hash(&contents);
+
+        Self {
+            path,
+            contents,
+            hash,
+        }
+    }
+}
+
+/// Source metadata, without its contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkSourceInfo {
+    /// Path identifier.
+    ///
+    /// Won't necessarily reflect a real path in the disk.
+    pub path: String,
+    /// Length of the contents, in bytes
+    pub size: usize,
+    /// Content hash, as hex-encoded SHA-256
+    #[serde(default)]
+    pub hash: String,
 }
 
 /// Witness representation
@@ -236,6 +887,7 @@ impl TryFrom<&Value> for ZkWitness {
 
 /// A response produced by the ZK DAP backend
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ZkResponse {
     /// A breakpoint was added
     AddBreakpoint {
@@ -249,6 +901,25 @@ pub enum ZkResponse {
         /// Flag on whether or not the breakpoint was existent and removed
         removed: bool,
     },
+    /// Several breakpoints were added
+    AddBreakpoints {
+        /// Ids of the added breakpoints, in the order of the request
+        ids: Vec<u64>,
+    },
+    /// The breakpoints of a source, or all of them, were removed
+    RemoveBreakpoints {
+        /// Ids of the removed breakpoints
+        ids: Vec<u64>,
+    },
+    /// Set the hits of a breakpoint to be ignored
+    IgnoreBreakpoint {
+        /// Id of the breakpoint
+        id: u64,
+        /// Number of hits to be ignored
+        count: u64,
+        /// Flag on whether or not the breakpoint was existent
+        ignored: bool,
+    },
     /// The CDF file was loaded
     LoadCdf,
     /// List of sources contained in the loaded CDF file
@@ -256,11 +927,149 @@ pub enum ZkResponse {
         /// Sources list
         sources: Vec<ZkSource>,
     },
+    /// Names and sizes of the sources contained in the loaded CDF file
+    SourceList {
+        /// Sources list
+        sources: Vec<ZkSourceInfo>,
+    },
+    /// Page of the sources contained in the loaded CDF file
+    Sources {
+        /// Sources of the page
+        sources: Vec<ZkSource>,
+        /// Paths of the sources of the page skipped as known by the client
+        unchanged: Vec<String>,
+        /// Total number of sources matching the filter
+        total: usize,
+    },
+    /// Chunk of the contents of a source
+    SourceChunk {
+        /// Path of the source
+        path: String,
+        /// Effective byte offset of the chunk.
+        ///
+        /// Might be lower than the requested offset so the chunk starts in a
+        /// char boundary.
+        offset: usize,
+        /// Contents of the chunk
+        contents: String,
+        /// Length of the full contents of the source, in bytes
+        size: usize,
+    },
     /// Internal data of a witness evaluated
     Witness {
         /// Evaluated data
         witness: ZkWitness,
     },
+    /// The debugger jumped to the first constraint at a source line
+    GotoLine {
+        /// Id of the constraint
+        id: usize,
+    },
+    /// The bounded execution stopped
+    ContinueMax {
+        /// State the execution stopped at
+        state: State,
+    },
+    /// The navigation limit was replaced
+    Focus {
+        /// Portion of the circuit the navigation is limited to, if any
+        focus: Option<Focus>,
+    },
+    /// Sources and gadgets with the largest footprint
+    Top {
+        /// Usage of the circuit
+        usage: CircuitUsage,
+    },
+    /// Inconsistent wires of the loaded CDF file
+    WireCheck {
+        /// Inconsistencies found
+        warnings: Vec<Warning>,
+    },
+    /// Constraints referencing witnesses allocated after them
+    Timeline {
+        /// Violations found
+        warnings: Vec<Warning>,
+        /// Gate patterns spanning the violations
+        patterns: Vec<GateMatch>,
+    },
+    /// Non-canonical scalars of the loaded CDF file
+    ScalarCheck {
+        /// Offending scalars
+        warnings: Vec<Warning>,
+    },
+    /// Unfixed witnesses of the loaded CDF file holding well-known constants
+    ConstCheck {
+        /// Offending witnesses
+        warnings: Vec<Warning>,
+    },
+    /// Witnesses wired into the constraints of more than one source
+    SharedWires {
+        /// Shared witnesses, ordered by their number of sources
+        wires: Vec<SharedWire>,
+    },
+    /// Witnesses created by the current constraint
+    Locals {
+        /// Id of the current constraint
+        constraint: usize,
+        /// Witnesses whose origin is the constraint, in ascending order
+        witnesses: Vec<ZkWitness>,
+    },
+    /// Wires of the current constraint traced to the earliest failure they
+    /// depend on
+    Frontier {
+        /// Frontier of the current constraint
+        frontier: Frontier,
+    },
+    /// Memory held by the caches of the debugger
+    Memory {
+        /// Memory usage of every cache
+        usage: MemoryUsage,
+    },
+    /// Markdown snippet of the current constraint
+    Snippet {
+        /// Id of the rendered constraint
+        constraint: usize,
+        /// Equation, selectors, wires and source excerpt of the constraint
+        markdown: String,
+    },
+    /// Rendered equation of the current constraint
+    Equation {
+        /// Id of the rendered constraint
+        constraint: usize,
+        /// Equation of the constraint, followed by the values of its terms
+        equation: String,
+    },
+    /// Public inputs recorded by the constraints
+    Publics {
+        /// Public inputs, in the order of their constraints
+        publics: Vec<PublicInput>,
+        /// Positions in which the public input vector of the proof artifacts
+        /// differs, if they were compared
+        mismatches: Option<Vec<PublicMismatch>>,
+    },
+    /// Encoded bytes of a constraint
+    Raw {
+        /// Id of the constraint
+        constraint: usize,
+        /// Byte offset of the constraint in the CDF file
+        offset: usize,
+        /// Encoded bytes of the constraint
+        bytes: Vec<u8>,
+    },
+    /// Result of an operation of the
+    /// [`DebuggerBackend`](crate::DebuggerBackend)
+    Backend {
+        /// Result of the operation
+        reply: BackendReply,
+    },
+    /// The running continue, if any, was asked to pause
+    Pause,
+    /// Responses of a batch of requests
+    Batch {
+        /// Response of every request, or the message of its error, in the
+        /// order of the batch
+        responses: Vec<Result<ZkResponse, String>>,
+    },
 }
 
 impl From<ZkResponse> for Response {
@@ -281,6 +1090,31 @@ impl From<ZkResponse> for Response {
                 })),
             },
 
+            ZkResponse::AddBreakpoints { ids } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "addBreakpoints",
+                    "ids": ids,
+                })),
+            },
+
+            ZkResponse::RemoveBreakpoints { ids } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "removeBreakpoints",
+                    "ids": ids,
+                })),
+            },
+
+            ZkResponse::IgnoreBreakpoint { id, count, ignored } => {
+                Response::Custom {
+                    body: Some(serde_json::json!({
+                        "command": "ignoreBreakpoint",
+                        "id": id,
+                        "count": count,
+                        "ignored": ignored,
+                    })),
+                }
+            }
+
             ZkResponse::LoadCdf => Response::Custom {
                 body: Some(serde_json::json!({
                     "command": "loadCdf",
@@ -294,12 +1128,219 @@ impl From<ZkResponse> for Response {
                 })),
             },
 
+            ZkResponse::SourceList { sources } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "sourceList",
+                    "sources": sources,
+                })),
+            },
+
+            ZkResponse::Sources {
+                sources,
+                unchanged,
+                total,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "sources",
+                    "sources": sources,
+                    "unchanged": unchanged,
+                    "total": total,
+                })),
+            },
+
+            ZkResponse::SourceChunk {
+                path,
+                offset,
+                contents,
+                size,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "sourceChunk",
+                    "path": path,
+                    "offset": offset,
+                    "contents": contents,
+                    "size": size,
+                })),
+            },
+
             ZkResponse::Witness { witness } => Response::Custom {
                 body: Some(serde_json::json!({
                     "command": "witness",
                     "witness": Value::from(witness),
                 })),
             },
+
+            ZkResponse::GotoLine { id } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "gotoLine",
+                    "id": id,
+                })),
+            },
+
+            ZkResponse::ContinueMax { state } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "continueMax",
+                    "state": state,
+                })),
+            },
+
+            ZkResponse::Focus { focus } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "focus",
+                    "focus": focus,
+                })),
+            },
+
+            ZkResponse::Top { usage } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "top",
+                    "usage": usage,
+                })),
+            },
+
+            ZkResponse::WireCheck { warnings } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "wireCheck",
+                    "warnings": warnings,
+                })),
+            },
+
+            ZkResponse::Timeline { warnings, patterns } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "timeline",
+                    "warnings": warnings,
+                    "patterns": patterns,
+                })),
+            },
+
+            ZkResponse::ScalarCheck { warnings } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "scalarCheck",
+                    "warnings": warnings,
+                })),
+            },
+
+            ZkResponse::ConstCheck { warnings } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "constCheck",
+                    "warnings": warnings,
+                })),
+            },
+
+            ZkResponse::SharedWires { wires } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "sharedWires",
+                    "wires": wires,
+                })),
+            },
+
+            ZkResponse::Locals {
+                constraint,
+                witnesses,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "locals",
+                    "constraint": constraint,
+                    "witnesses": witnesses
+                        .into_iter()
+                        .map(Value::from)
+                        .collect::<Vec<_>>(),
+                })),
+            },
+
+            ZkResponse::Frontier { frontier } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "frontier",
+                    "frontier": frontier,
+                })),
+            },
+
+            ZkResponse::Memory { usage } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "memory",
+                    "usage": usage,
+                })),
+            },
+
+            ZkResponse::Snippet {
+                constraint,
+                markdown,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "snippet",
+                    "constraint": constraint,
+                    "markdown": markdown,
+                })),
+            },
+
+            ZkResponse::Equation {
+                constraint,
+                equation,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "equation",
+                    "constraint": constraint,
+                    "equation": equation,
+                })),
+            },
+
+            ZkResponse::Publics {
+                publics,
+                mismatches,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "publics",
+                    "publics": publics,
+                    "mismatches": mismatches,
+                })),
+            },
+
+            ZkResponse::Raw {
+                constraint,
+                offset,
+                bytes,
+            } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "raw",
+                    "constraint": constraint,
+                    "offset": offset,
+                    "bytes": hex::encode(bytes),
+                })),
+            },
+
+            ZkResponse::Backend { reply } => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "backend",
+                    "reply": reply,
+                })),
+            },
+
+            ZkResponse::Pause => Response::Custom {
+                body: Some(serde_json::json!({
+                    "command": "pause",
+                })),
+            },
+
+            ZkResponse::Batch { responses } => {
+                let responses: Vec<_> = responses
+                    .into_iter()
+                    .map(|r| match r.map(Response::from) {
+                        Ok(Response::Custom { body: Some(body) }) => body,
+                        Ok(_) => Value::Null,
+                        Err(message) => serde_json::json!({
+                            "command": "error",
+                            "message": message,
+                        }),
+                    })
+                    .collect();
+
+                Response::Custom {
+                    body: Some(serde_json::json!({
+                        "command": "batch",
+                        "responses": responses,
+                    })),
+                }
+            }
         }
     }
 }
@@ -338,26 +1379,306 @@ impl TryFrom<Option<&Value>> for ZkResponse {
                 Ok(Self::RemoveBreakpoint { id, removed })
             }
 
+            "addBreakpoints" => deserialize_array(body.get("ids"))
+                .map(|ids| Self::AddBreakpoints { ids }),
+
+            "removeBreakpoints" => deserialize_array(body.get("ids"))
+                .map(|ids| Self::RemoveBreakpoints { ids }),
+
+            "ignoreBreakpoint" => {
+                let id = body
+                    .get("id")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid id attribute"))?;
+
+                let count = body
+                    .get("count")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid count attribute"))?;
+
+                let ignored = body
+                    .get("ignored")
+                    .and_then(Value::as_bool)
+                    .ok_or_else(|| err("invalid ignored attribute"))?;
+
+                Ok(Self::IgnoreBreakpoint { id, count, ignored })
+            }
+
             "loadCdf" => Ok(Self::LoadCdf),
 
-            "sourceContents" => body
-                .get("sources")
-                .and_then(Value::as_array)
-                .ok_or_else(|| err("invalid sources attribute"))?
-                .iter()
-                .map(|s| {
-                    ZkSource::deserialize(s)
-                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
-                })
-                .collect::<io::Result<_>>()
+            "sourceContents" => deserialize_array(body.get("sources"))
                 .map(|sources| Self::SourceContents { sources }),
 
+            "sourceList" => deserialize_array(body.get("sources"))
+                .map(|sources| Self::SourceList { sources }),
+
+            "sources" => {
+                let sources = deserialize_array(body.get("sources"))?;
+                let unchanged = deserialize_array(body.get("unchanged"))?;
+
+                let total = body
+                    .get("total")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid total attribute"))?
+                    as usize;
+
+                Ok(Self::Sources {
+                    sources,
+                    unchanged,
+                    total,
+                })
+            }
+
+            "sourceChunk" => {
+                let path = body
+                    .get("path")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("invalid path attribute"))?
+                    .into();
+
+                let offset = body
+                    .get("offset")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid offset attribute"))?
+                    as usize;
+
+                let contents = body
+                    .get("contents")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("invalid contents attribute"))?
+                    .into();
+
+                let size = body
+                    .get("size")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid size attribute"))?
+                    as usize;
+
+                Ok(Self::SourceChunk {
+                    path,
+                    offset,
+                    contents,
+                    size,
+                })
+            }
+
             "witness" => body
                 .get("witness")
                 .ok_or_else(|| err("witness is mandatory"))
                 .and_then(ZkWitness::try_from)
                 .map(|witness| Self::Witness { witness }),
 
+            "gotoLine" => body
+                .get("id")
+                .and_then(Value::as_u64)
+                .map(|id| Self::GotoLine { id: id as usize })
+                .ok_or_else(|| err("invalid id attribute")),
+
+            "continueMax" => body
+                .get("state")
+                .cloned()
+                .ok_or_else(|| err("state is mandatory"))
+                .and_then(|state| {
+                    serde_json::from_value(state)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|state| Self::ContinueMax { state }),
+
+            "focus" => body
+                .get("focus")
+                .map(Option::<Focus>::deserialize)
+                .transpose()
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                .map(|focus| Self::Focus {
+                    focus: focus.flatten(),
+                }),
+
+            "top" => body
+                .get("usage")
+                .cloned()
+                .ok_or_else(|| err("usage is mandatory"))
+                .and_then(|usage| {
+                    serde_json::from_value(usage)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|usage| Self::Top { usage }),
+
+            "wireCheck" => deserialize_array(body.get("warnings"))
+                .map(|warnings| Self::WireCheck { warnings }),
+
+            "timeline" => {
+                let warnings = deserialize_array(body.get("warnings"))?;
+
+                // absent from the responses of older backends
+                let patterns = match body.get("patterns") {
+                    Some(p) => deserialize_array(Some(p))?,
+                    None => vec![],
+                };
+
+                Ok(Self::Timeline { warnings, patterns })
+            }
+
+            "scalarCheck" => deserialize_array(body.get("warnings"))
+                .map(|warnings| Self::ScalarCheck { warnings }),
+
+            "constCheck" => deserialize_array(body.get("warnings"))
+                .map(|warnings| Self::ConstCheck { warnings }),
+
+            "sharedWires" => deserialize_array(body.get("wires"))
+                .map(|wires| Self::SharedWires { wires }),
+
+            "locals" => {
+                let constraint = body
+                    .get("constraint")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid constraint attribute"))?
+                    as usize;
+
+                let witnesses = body
+                    .get("witnesses")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| err("invalid witnesses attribute"))?
+                    .iter()
+                    .map(ZkWitness::try_from)
+                    .collect::<io::Result<_>>()?;
+
+                Ok(Self::Locals {
+                    constraint,
+                    witnesses,
+                })
+            }
+
+            "frontier" => body
+                .get("frontier")
+                .cloned()
+                .ok_or_else(|| err("frontier is mandatory"))
+                .and_then(|frontier| {
+                    serde_json::from_value(frontier)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|frontier| Self::Frontier { frontier }),
+
+            "memory" => body
+                .get("usage")
+                .cloned()
+                .ok_or_else(|| err("usage is mandatory"))
+                .and_then(|usage| {
+                    serde_json::from_value(usage)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|usage| Self::Memory { usage }),
+
+            "snippet" => {
+                let constraint = body
+                    .get("constraint")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid constraint attribute"))?
+                    as usize;
+
+                let markdown = body
+                    .get("markdown")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("markdown is mandatory"))?
+                    .to_string();
+
+                Ok(Self::Snippet {
+                    constraint,
+                    markdown,
+                })
+            }
+
+            "equation" => {
+                let constraint = body
+                    .get("constraint")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid constraint attribute"))?
+                    as usize;
+
+                let equation = body
+                    .get("equation")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("equation is mandatory"))?
+                    .to_string();
+
+                Ok(Self::Equation {
+                    constraint,
+                    equation,
+                })
+            }
+
+            "publics" => {
+                let publics = deserialize_array(body.get("publics"))?;
+
+                let mismatches = match body.get("mismatches") {
+                    Some(m) if !m.is_null() => {
+                        Some(deserialize_array(Some(m))?)
+                    }
+                    _ => None,
+                };
+
+                Ok(Self::Publics {
+                    publics,
+                    mismatches,
+                })
+            }
+
+            "raw" => {
+                let constraint = body
+                    .get("constraint")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid constraint attribute"))?
+                    as usize;
+
+                let offset = body
+                    .get("offset")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| err("invalid offset attribute"))?
+                    as usize;
+
+                let bytes = body
+                    .get("bytes")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| err("bytes is mandatory"))
+                    .and_then(|bytes| {
+                        hex::decode(bytes)
+                            .map_err(|_| err("invalid bytes attribute"))
+                    })?;
+
+                Ok(Self::Raw {
+                    constraint,
+                    offset,
+                    bytes,
+                })
+            }
+
+            "backend" => body
+                .get("reply")
+                .cloned()
+                .ok_or_else(|| err("reply is mandatory"))
+                .and_then(|reply| {
+                    serde_json::from_value(reply)
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                })
+                .map(|reply| Self::Backend { reply }),
+
+            "pause" => Ok(Self::Pause),
+
+            "batch" => body
+                .get("responses")
+                .and_then(Value::as_array)
+                .ok_or_else(|| err("invalid responses attribute"))?
+                .iter()
+                .map(|r| match r.get("command").and_then(Value::as_str) {
+                    Some("error") => Ok(Err(r
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string())),
+                    _ => ZkResponse::try_from(Some(r)).map(Ok),
+                })
+                .collect::<io::Result<_>>()
+                .map(|responses| Self::Batch { responses }),
+
             _ => Err(io::Error::new(io::ErrorKind::Other, "unknown command")),
         }
     }
@@ -1,10 +1,13 @@
+use std::io;
+
 use dap_reactor::prelude::{
     Source, SourceReference, Variable, VariablePresentationHint,
     VariablePresentationHintAttribute, VariablePresentationHintKind,
     VariablePresentationHintVisibility,
 };
+use sha2::{Digest, Sha256};
 
-use crate::{Constraint, Scalar, Witness};
+use crate::{Constraint, GateKind, Scalar, Witness};
 
 impl From<&Constraint<'_>> for Source {
     fn from(constraint: &Constraint) -> Self {
@@ -22,6 +25,71 @@ impl From<&Constraint<'_>> for Source {
     }
 }
 
+/// Create a predicate to select source names that matches the provided glob
+/// pattern.
+///
+/// The pattern is tested against the full name and the name without the
+/// `dusk-cdf:` prefix. If no pattern is provided, all names will match.
+pub fn source_filter(
+    filter: Option<&str>,
+) -> io::Result<impl Fn(&str) -> bool> {
+    let pattern = filter
+        .map(glob::Pattern::new)
+        .transpose()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    Ok(move |name: &str| match &pattern {
+        Some(p) => {
+            p.matches(name)
+                || name
+                    .strip_prefix("dusk-cdf:")
+                    .map(|n| p.matches(n))
+                    .unwrap_or(false)
+        }
+        None => true,
+    })
+}
+
+/// Slice the contents with, at most, `len` bytes starting at `offset`.
+///
+/// The bounds are moved backwards to the closest char boundary so the chunks
+/// are always valid strings. The returned tuple contains the effective offset
+/// and the chunk.
+pub fn source_chunk(
+    contents: &str,
+    offset: usize,
+    len: usize,
+) -> (usize, &str) {
+    let boundary = |mut i: usize| {
+        i = i.min(contents.len());
+
+        while !contents.is_char_boundary(i) {
+            i -= 1;
+        }
+
+        i
+    };
+
+    let start = boundary(offset);
+    let mut end = boundary(start.saturating_add(len));
+
+    // a chunk smaller than a char must still progress
+    if end == start && len > 0 {
+        end = contents[start..]
+            .chars()
+            .next()
+            .map(|c| start + c.len_utf8())
+            .unwrap_or(start);
+    }
+
+    (start, &contents[start..end])
+}
+
+/// Compute the content hash of a source, as hex-encoded SHA-256
+pub fn source_hash(contents: &str) -> String {
+    hex::encode(Sha256::digest(contents.as_bytes()))
+}
+
 pub fn scalar_to_string(scalar: &Scalar) -> String {
     format!("0x{}", hex::encode(scalar.as_ref()))
 }
@@ -70,6 +138,28 @@ where
     }
 }
 
+pub fn gate_to_var<N>(name: N, kind: GateKind) -> Variable
+where
+    N: Into<String>,
+{
+    Variable {
+        name: name.into(),
+        value: kind.to_string(),
+        r#type: Some("gate".into()),
+        presentation_hint: Some(VariablePresentationHint {
+            kind: Some(VariablePresentationHintKind::Data),
+            attributes: vec![VariablePresentationHintAttribute::ReadOnly],
+            visibility: Some(VariablePresentationHintVisibility::Protected),
+            lazy: false,
+        }),
+        evaluate_name: None,
+        variables_reference: 0,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+    }
+}
+
 pub fn witness_to_var<N>(name: N, witness: Witness) -> Variable
 where
     N: Into<String>,
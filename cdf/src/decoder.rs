@@ -1,17 +1,37 @@
 //! Decoding CDF format
 
-mod context;
+mod cache;
 mod display;
 
 use std::fs::{File, OpenOptions};
+use std::ops::Range;
 use std::path::Path;
+use std::sync::Arc;
 use std::{fmt, io};
 
-pub use context::DecoderContext;
+use cache::SourceCache;
 pub use display::DecoderDisplay;
-use msgpacker::Message;
 
-use crate::{Constraint, DecodableElement, Preamble, Witness};
+use crate::memory::{Cache, HeapSize};
+use crate::warning::Warnings;
+#[cfg(feature = "plonk-verify")]
+use crate::VerifierData;
+use crate::{
+    ci, divergence, entropy, frontier, lock, pattern, report, shared, timeline,
+    usage,
+};
+#[cfg(feature = "index")]
+use crate::{index, CircuitIndex};
+#[cfg(feature = "encoder")]
+use crate::{legacy, LegacyCircuit};
+use crate::{
+    CiFormat, CircuitUsage, Config, Constraint, DecodableElement,
+    DecoderContext, Divergence, Element, FirstDivergence, Frontier, GateMatch,
+    Layout, MemoryUsage, PatternLibrary, Preamble, ProofArtifacts, PublicInput,
+    PublicMismatch, ReportFormat, ResolvedConstraint, Selector, SharedWire,
+    SourceCounts, SourceId, Style, Timeline, Warning, Wire, WiredWitnesses,
+    Witness, WitnessEntropy,
+};
 
 /// A circuit description file
 ///
@@ -20,9 +40,17 @@ use crate::{Constraint, DecodableElement, Preamble, Witness};
 #[derive(Debug, Clone)]
 pub struct CircuitDescription<S> {
     preamble: Preamble,
-    source_names: Vec<String>,
-    source_contents: Vec<String>,
+    source_names: Arc<[String]>,
+    source_contents: Arc<[String]>,
+    source_ids: Vec<SourceId>,
+    source_counts: Option<Vec<SourceCounts>>,
+    patterns: PatternLibrary,
+    gate_matches: Option<Vec<GateMatch>>,
+    #[cfg(feature = "index")]
+    index: Option<CircuitIndex>,
+    artifacts: Option<ProofArtifacts>,
     source: S,
+    warnings: Warnings,
 }
 
 impl<S> fmt::Display for CircuitDescription<S>
@@ -42,21 +70,42 @@ impl<S> CircuitDescription<S> {
             .zip(self.source_contents.iter().map(|s| s.as_str()))
     }
 
+    /// Names and contents of the sources, shared with the clones of the
+    /// circuit instead of copied
+    pub(crate) fn shared_sources(&self) -> (Arc<[String]>, Arc<[String]>) {
+        (
+            Arc::clone(&self.source_names),
+            Arc::clone(&self.source_contents),
+        )
+    }
+
     pub(crate) fn context(&mut self) -> (DecoderContext, &mut S) {
+        let (ctx, source, _) = self.context_with_warnings();
+
+        (ctx, source)
+    }
+
+    fn context_with_warnings(
+        &mut self,
+    ) -> (DecoderContext, &mut S, &mut Warnings) {
         let Self {
             preamble,
             source_names,
             source_contents,
+            source_ids,
             source,
+            warnings,
+            ..
         } = self;
 
         let ctx = DecoderContext::new(
             &preamble.config,
             source_names,
             source_contents,
+            source_ids,
         );
 
-        (ctx, source)
+        (ctx, source, warnings)
     }
 
     /// Helper method to return the preamble of the circuit description.
@@ -80,6 +129,163 @@ impl<S> CircuitDescription<S> {
     pub fn source_name_contains(&self, name: &str) -> bool {
         self.source_names.iter().any(|n| n.contains(name))
     }
+
+    /// Paths of the sources of the circuit, in the order they were encoded
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// assert!(circuit.source_names().any(|n| n.ends_with("gadget.rs")));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn source_names(&self) -> impl Iterator<Item = &str> {
+        self.source_names.iter().map(|s| s.as_str())
+    }
+
+    /// Paths of the sources of the circuit, along with the size in bytes of
+    /// their contents
+    pub fn source_sizes(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.sources()
+            .map(|(name, contents)| (name, contents.len()))
+    }
+
+    /// Index of the circuit, if it was loaded from a sidecar file or built
+    /// with [`build_index`](Self::build_index)
+    #[cfg(feature = "index")]
+    pub const fn index(&self) -> Option<&CircuitIndex> {
+        self.index.as_ref()
+    }
+
+    /// Artifacts of the proof of the circuit, if they were loaded from a
+    /// sidecar file or attached with [`set_artifacts`](Self::set_artifacts)
+    pub const fn artifacts(&self) -> Option<&ProofArtifacts> {
+        self.artifacts.as_ref()
+    }
+
+    /// Attach the artifacts of the proof of the circuit, replacing the
+    /// previous ones
+    pub fn set_artifacts(&mut self, artifacts: Option<ProofArtifacts>) {
+        self.artifacts = artifacts;
+    }
+
+    #[cfg(feature = "index")]
+    fn attach_index(&mut self, index: CircuitIndex) -> &CircuitIndex {
+        self.index = None;
+        self.source_counts = Some(index.source_counts().to_vec());
+        self.reclaim(index.heap_size(), Cache::Index);
+
+        self.index.insert(index)
+    }
+
+    /// Memory held by the caches of the circuit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// assert_eq!(circuit.memory_usage().source_counts, 0);
+    ///
+    /// circuit.source_counts()?;
+    ///
+    /// let usage = circuit.memory_usage();
+    /// assert!(usage.source_counts > 0);
+    /// assert!(!usage.is_over_budget());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        #[cfg(feature = "index")]
+        let index = self.index.as_ref().map(HeapSize::heap_size);
+        #[cfg(not(feature = "index"))]
+        let index: Option<usize> = None;
+
+        MemoryUsage {
+            budget: self.preamble.config.cache_limits.max_memory,
+            sources: self.source_names.heap_size()
+                + self.source_contents.heap_size()
+                + self.source_ids.heap_size(),
+            source_counts: self
+                .source_counts
+                .as_ref()
+                .map(HeapSize::heap_size)
+                .unwrap_or_default(),
+            patterns: self
+                .gate_matches
+                .as_ref()
+                .map(HeapSize::heap_size)
+                .unwrap_or_default(),
+            index: index.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Drop a cache of the circuit; it is rebuilt on its next use
+    pub(crate) fn evict(&mut self, cache: Cache) {
+        match cache {
+            Cache::SourceCounts => self.source_counts = None,
+            Cache::Patterns => self.gate_matches = None,
+            #[cfg(feature = "index")]
+            Cache::Index => self.index = None,
+            _ => (),
+        }
+    }
+
+    /// Drop the caches of the circuit other than `keep`, in eviction order,
+    /// while they exceed the memory budget along with the `extra` bytes held
+    /// by the debugger
+    pub(crate) fn reclaim(&mut self, extra: usize, keep: Cache) {
+        for cache in Cache::EVICTION {
+            let usage = self.memory_usage();
+
+            if usage.total() + extra <= usage.budget {
+                return;
+            }
+
+            if cache != keep {
+                self.evict(cache);
+            }
+        }
+    }
+
+    /// Take the warnings produced since the last call.
+    ///
+    /// Each warning is returned only once, even if the element that produced
+    /// it is decoded again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, Config};
+    ///
+    /// let config = *Config::default().with_check_wires(true);
+    /// let mut circuit =
+    ///     CircuitDescription::open_with_config("../assets/test.cdf", config)?;
+    ///
+    /// circuit.fetch_constraint(1)?;
+    ///
+    /// assert!(circuit.take_warnings().is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        self.warnings.take()
+    }
+
+    /// Queue a warning produced while debugging the circuit
+    #[cfg(feature = "debugger")]
+    pub(crate) fn push_warning(&mut self, warning: Warning) {
+        self.warnings.push(warning);
+    }
 }
 
 impl CircuitDescription<File> {
@@ -101,10 +307,82 @@ impl CircuitDescription<File> {
     where
         P: AsRef<Path>,
     {
-        OpenOptions::new()
-            .read(true)
-            .open(path)
-            .and_then(Self::from_reader)
+        Self::open_with_config(path, Config::default())
+    }
+
+    /// Use a path to create a new circuit description with the provided
+    /// decoding configuration. This uses [`from_reader_with_config`] behind.
+    ///
+    /// [`from_reader_with_config`]: CircuitDescription::from_reader_with_config
+    ///
+    /// If the file has an index file, as in [`CircuitIndex::sidecar`], whose
+    /// digest matches its contents, the index is loaded along with it. An
+    /// index that is stale or can't be read is ignored. The artifacts of the
+    /// proof, as in [`ProofArtifacts::sidecar`], are loaded the same way.
+    ///
+    /// A file in the legacy format of `dusk-plonk-cdf` is detected, and
+    /// converted into a temporary file first, as in [`LegacyCircuit`].
+    ///
+    /// The file is held under a shared advisory lock while open. A file
+    /// being written by an [`Encoder`] is rejected with
+    /// [`io::ErrorKind::WouldBlock`], so it can be opened again once
+    /// finalized.
+    ///
+    /// [`LegacyCircuit`]: crate::LegacyCircuit
+    /// [`Encoder`]: crate::Encoder
+    pub fn open_with_config<P>(path: P, config: Config) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        #[cfg_attr(not(feature = "encoder"), allow(unused_mut))]
+        let mut file = OpenOptions::new().read(true).open(path.as_ref())?;
+
+        lock::shared(&file)?;
+
+        #[cfg(feature = "encoder")]
+        if LegacyCircuit::sniff(&mut file)? {
+            return Self::open_legacy(file, config);
+        }
+
+        let mut circuit = Self::from_reader_with_config(file, config)?;
+
+        #[cfg(feature = "index")]
+        if let Ok(index) = circuit.load_sidecar(path.as_ref()) {
+            circuit.attach_index(index);
+        }
+
+        circuit.artifacts = ProofArtifacts::read_sidecar(path.as_ref()).ok();
+
+        Ok(circuit)
+    }
+
+    #[cfg(feature = "encoder")]
+    fn open_legacy(file: File, config: Config) -> io::Result<Self> {
+        let mut legacy = LegacyCircuit::from_reader(file)?;
+        let (file, path) = legacy::convert_to_temp(&mut legacy, config)?;
+
+        let circuit = Self::from_reader_with_config(file, config);
+
+        // the open handle keeps the contents; some platforms can't remove an
+        // open file, leaving it to the cleanup of the temporary directory
+        std::fs::remove_file(path).ok();
+
+        circuit
+    }
+
+    #[cfg(feature = "index")]
+    fn load_sidecar(&mut self, path: &Path) -> io::Result<CircuitIndex> {
+        let sidecar = File::open(CircuitIndex::sidecar(path))?;
+        let index = CircuitIndex::read(io::BufReader::new(sidecar))?;
+
+        if index.digest() != &index::digest(&mut self.source)? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the index is stale",
+            ));
+        }
+
+        Ok(index)
     }
 }
 
@@ -130,59 +408,104 @@ where
     /// # Ok(()) }
     /// ```
     /// [`open`]: CircuitDescription::open
-    pub fn from_reader(mut source: S) -> io::Result<Self> {
+    pub fn from_reader(source: S) -> io::Result<Self> {
+        Self::from_reader_with_config(source, Config::default())
+    }
+
+    /// Create a new circuit description instance from a readable and seekable
+    /// source, using the provided decoding configuration.
+    ///
+    /// The parameters stored in the CDF file take precedence over the
+    /// provided ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, Config};
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let config = *Config::default().with_check_wires(true);
+    /// let circuit = CircuitDescription::from_reader_with_config(file, config)?;
+    ///
+    /// assert!(circuit.preamble().config.check_wires);
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "decode", level = "debug", skip_all)
+    )]
+    pub fn from_reader_with_config(
+        mut source: S,
+        config: Config,
+    ) -> io::Result<Self> {
         // reset the cursor
         source.seek(io::SeekFrom::Start(0))?;
 
         // load the preamble with the base config
-        let preamble =
+        let mut preamble =
             Preamble::try_from_reader(&DecoderContext::BASE, source.by_ref())?;
 
-        let ofs = preamble.source_cache_offset();
-        let ofs = io::SeekFrom::Start(ofs as u64);
-        source.seek(ofs)?;
-
-        let source_names = Message::unpack(source.by_ref())?;
-        let source_contents = Message::unpack(source.by_ref())?;
-
-        let (source_names, source_contents) =
-            match (source_names, source_contents) {
-                (Message::Array(n), Message::Array(c)) => (n, c),
-                _ => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "the source cache isn't a valid array",
-                    ))
-                }
-            };
-
-        let source_names = source_names
-            .into_iter()
-            .map(|m| match m {
-                Message::String(s) => Ok(s),
-                _ => Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "the source names isn't composed of strings",
-                )),
-            })
-            .collect::<io::Result<Vec<_>>>()?;
-
-        let source_contents = source_contents
-            .into_iter()
-            .map(|m| match m {
-                Message::String(s) => Ok(s),
-                _ => Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "the source contents isn't composed of strings",
-                )),
-            })
-            .collect::<io::Result<Vec<_>>>()?;
+        if !preamble.finalized {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the CDF file wasn't finalized; the encoding was interrupted",
+            ));
+        }
+
+        preamble.config = Config {
+            zeroed_scalar_values: preamble.config.zeroed_scalar_values,
+            ..config
+        };
+
+        let ofs = preamble.checked_source_cache_offset().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the preamble describes a layout that overflows",
+            )
+        })?;
+
+        source.seek(io::SeekFrom::Start(ofs as u64))?;
+
+        let corrupt_cache = |e: io::Error| match e.kind() {
+            io::ErrorKind::InvalidData
+            | io::ErrorKind::UnexpectedEof
+            | io::ErrorKind::Other => io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("source cache at offset {:#x}: {}", ofs, e),
+            ),
+
+            _ => e,
+        };
+
+        let SourceCache {
+            names: source_names,
+            contents: source_contents,
+            warnings: cache_warnings,
+        } = SourceCache::read(source.by_ref(), &preamble.config)
+            .map_err(corrupt_cache)?;
+
+        let mut warnings = Warnings::default();
+
+        cache_warnings.into_iter().for_each(|w| warnings.push(w));
+
+        let source_ids = SourceId::intern(&source_names);
 
         Ok(Self {
             preamble,
-            source_names,
-            source_contents,
+            source_names: source_names.into(),
+            source_contents: source_contents.into(),
+            source_ids,
+            source_counts: None,
+            patterns: PatternLibrary::default(),
+            gate_matches: None,
+            #[cfg(feature = "index")]
+            index: None,
+            artifacts: None,
             source,
+            warnings,
         })
     }
 
@@ -205,21 +528,89 @@ where
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "fetch",
+            level = "trace",
+            skip_all,
+            fields(constraint = idx)
+        )
+    )]
     pub fn fetch_constraint(&mut self, idx: usize) -> io::Result<Constraint> {
-        self.preamble
-            .constraint_offset(idx)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::Other,
-                    "attempt to fetch invalid constraint",
-                )
-            })
-            .map(|ofs| io::SeekFrom::Start(ofs as u64))
-            .and_then(|ofs| self.source.seek(ofs))?;
+        let offset = self.preamble.constraint_offset(idx).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "attempt to fetch invalid constraint",
+            )
+        })?;
 
-        let (ctx, source) = self.context();
+        self.source.seek(io::SeekFrom::Start(offset as u64))?;
+
+        let preamble = self.preamble;
+        let (ctx, source, warnings) = self.context_with_warnings();
+
+        let constraint = Constraint::try_from_reader(&ctx, source.by_ref())
+            .map_err(|e| corrupt_constraint(idx, offset, e))?;
+
+        if preamble.config.check_wires {
+            let id = constraint.id();
+            let wires = constraint.polynomial().witnesses;
+
+            check_wires(&preamble, &ctx, source, id, &wires)?
+                .into_iter()
+                .for_each(|w| warnings.push(w));
+        }
+
+        Ok(constraint)
+    }
+
+    /// Read the encoded bytes of an indexed constraint, as stored in the
+    /// source.
+    ///
+    /// The bytes start at
+    /// [`Preamble::constraint_offset`](crate::Preamble::constraint_offset)
+    /// and aren't decoded, so the ones of a corrupt constraint are returned
+    /// as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let bytes = circuit.raw_constraint_bytes(1)?;
+    ///
+    /// assert_eq!(&bytes[..8], &1u64.to_le_bytes());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn raw_constraint_bytes(&mut self, idx: usize) -> io::Result<Vec<u8>> {
+        let offset = self.preamble.constraint_offset(idx).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "attempt to fetch invalid constraint",
+            )
+        })?;
+
+        self.source.seek(io::SeekFrom::Start(offset as u64))?;
+
+        let mut bytes = vec![0u8; Constraint::len(&self.preamble.config)];
+        self.source.read_exact(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Describe the sections of the file and the fields of its elements,
+    /// with their offsets and lengths under the configuration of the file.
+    ///
+    /// See [`Layout`] for an example.
+    pub fn layout(&mut self) -> io::Result<Layout> {
+        let len = self.source.seek(io::SeekFrom::End(0))?;
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
 
-        Constraint::try_from_reader(&ctx, source)
+        Layout::new(&self.preamble, len)
     }
 
     /// Attempt to read an indexed witness from the source.
@@ -241,20 +632,1044 @@ where
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "fetch",
+            level = "trace",
+            skip_all,
+            fields(witness = idx)
+        )
+    )]
     pub fn fetch_witness(&mut self, idx: usize) -> io::Result<Witness> {
-        self.preamble
-            .witness_offset(idx)
-            .ok_or_else(|| {
+        let offset = self.preamble.witness_offset(idx).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "attempt to fetch invalid witness",
+            )
+        })?;
+
+        self.source.seek(io::SeekFrom::Start(offset as u64))?;
+
+        let (ctx, source) = self.context();
+
+        Witness::try_from_reader(&ctx, source)
+            .map_err(|e| corrupt_witness(idx, offset, e))
+    }
+
+    /// Attempt to read a set of indexed witnesses from the source.
+    ///
+    /// The witnesses are returned in the order of `ids`. The reads are
+    /// performed in ascending order, and contiguous witnesses are loaded with
+    /// a single read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let mut circuit = CircuitDescription::from_reader(file)?;
+    /// let witnesses = circuit.fetch_witnesses(&[3, 1, 2, 1])?;
+    ///
+    /// assert_eq!(witnesses[0].id(), 3);
+    /// assert_eq!(witnesses[1].id(), 1);
+    /// assert_eq!(witnesses[2].id(), 2);
+    /// assert_eq!(witnesses[3].id(), 1);
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "fetch",
+            level = "trace",
+            skip_all,
+            fields(witnesses = ids.len())
+        )
+    )]
+    pub fn fetch_witnesses(
+        &mut self,
+        ids: &[usize],
+    ) -> io::Result<Vec<Witness>> {
+        let preamble = self.preamble;
+        let (ctx, source) = self.context();
+
+        read_witnesses(&preamble, &ctx, source, ids)
+    }
+
+    /// Attempt to read the witnesses created by an indexed constraint, that
+    /// is, the witnesses whose [`Witness::constraint`] origin is `idx`.
+    ///
+    /// The witnesses are returned in ascending order of id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// // the test circuit doesn't record the origin of its witnesses
+    /// assert!(circuit.locals(0)?.is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn locals(&mut self, idx: usize) -> io::Result<Vec<Witness>> {
+        let mut ids = vec![];
+
+        for id in 0..self.preamble.witnesses {
+            if self.fetch_witness(id)?.constraint() == Some(idx) {
+                ids.push(id);
+            }
+        }
+
+        self.fetch_witnesses(&ids)
+    }
+
+    /// Attempt to read an indexed constraint, along with its wired witnesses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let mut circuit = CircuitDescription::from_reader(file)?;
+    /// let resolved = circuit.resolve_constraint(1)?;
+    /// let wires = resolved.constraint.polynomial().witnesses;
+    ///
+    /// assert_eq!(resolved.constraint.id(), 1);
+    /// assert_eq!(resolved.a.id(), wires.a);
+    /// assert_eq!(resolved.o.id(), wires.o);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn resolve_constraint(
+        &mut self,
+        idx: usize,
+    ) -> io::Result<ResolvedConstraint> {
+        let preamble = self.preamble;
+        let ofs = preamble.constraint_offset(idx).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "attempt to fetch invalid constraint",
+            )
+        })?;
+
+        let (ctx, source, warnings) = self.context_with_warnings();
+
+        source.seek(io::SeekFrom::Start(ofs as u64))?;
+
+        let constraint = Constraint::try_from_reader(&ctx, source.by_ref())
+            .map_err(|e| corrupt_constraint(idx, ofs, e))?;
+        let wires = constraint.polynomial().witnesses;
+        let ids = [wires.a, wires.b, wires.d, wires.o];
+
+        if preamble.config.check_wires {
+            check_wires(&preamble, &ctx, source, constraint.id(), &wires)?
+                .into_iter()
+                .for_each(|w| warnings.push(w));
+        }
+
+        let mut witnesses =
+            read_witnesses(&preamble, &ctx, source, &ids)?.into_iter();
+        let mut next = || {
+            witnesses.next().ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::Other,
-                    "attempt to fetch invalid witness",
+                    "failed to resolve the wired witnesses",
                 )
             })
-            .map(|ofs| io::SeekFrom::Start(ofs as u64))
-            .and_then(|ofs| self.source.seek(ofs))?;
+        };
+
+        Ok(ResolvedConstraint {
+            a: next()?,
+            b: next()?,
+            d: next()?,
+            o: next()?,
+            constraint,
+        })
+    }
+
+    /// Check the wired witnesses of an indexed constraint against the witness
+    /// table, regardless of [`Config::check_wires`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// assert!(circuit.check_wires(1)?.is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn check_wires(&mut self, idx: usize) -> io::Result<Vec<Warning>> {
+        let preamble = self.preamble;
+        let ofs = preamble.constraint_offset(idx).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "attempt to fetch invalid constraint",
+            )
+        })?;
 
         let (ctx, source) = self.context();
 
-        Witness::try_from_reader(&ctx, source)
+        source.seek(io::SeekFrom::Start(ofs as u64))?;
+
+        let constraint = Constraint::try_from_reader(&ctx, source.by_ref())
+            .map_err(|e| corrupt_constraint(idx, ofs, e))?;
+        let wires = constraint.polynomial().witnesses;
+
+        check_wires(&preamble, &ctx, source, constraint.id(), &wires)
+    }
+
+    /// Check the wired witnesses of all the constraints of the circuit against
+    /// the witness table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// assert!(circuit.wirecheck()?.is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(check = "wirecheck")
+        )
+    )]
+    pub fn wirecheck(&mut self) -> io::Result<Vec<Warning>> {
+        let mut warnings = vec![];
+
+        for idx in 0..self.preamble.constraints {
+            warnings.extend(self.check_wires(idx)?);
+        }
+
+        Ok(warnings)
     }
+
+    /// Check that the stored scalars, the witness values and the selectors of
+    /// the constraints, are canonical.
+    ///
+    /// The check is meaningless if the CDF was encoded with
+    /// [`Config::zeroed_scalar_values`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// assert!(circuit.scalarcheck()?.is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(check = "scalarcheck")
+        )
+    )]
+    pub fn scalarcheck(&mut self) -> io::Result<Vec<Warning>> {
+        let mut warnings = vec![];
+
+        for idx in 0..self.preamble.witnesses {
+            let witness = self.fetch_witness(idx)?;
+
+            if !witness.value().is_canonical() {
+                warnings.push(Warning::NonCanonicalWitness {
+                    witness: idx,
+                    path: witness.name().to_string(),
+                    line: witness.line(),
+                    col: witness.col(),
+                });
+            }
+        }
+
+        for idx in 0..self.preamble.constraints {
+            let constraint = self.fetch_constraint(idx)?;
+            let id = constraint.id();
+            let selectors = constraint.polynomial().selectors;
+
+            warnings.extend(
+                Selector::ALL
+                    .iter()
+                    .filter(|s| !s.of(&selectors).is_canonical())
+                    .map(|&selector| Warning::NonCanonicalSelector {
+                        constraint: id,
+                        selector,
+                        path: constraint.name().to_string(),
+                        line: constraint.line(),
+                        col: constraint.col(),
+                    }),
+            );
+        }
+
+        Ok(warnings)
+    }
+
+    /// Report the witnesses holding a well-known [`Constant`](crate::Constant),
+    /// such as `0` or `1`, that aren't fixed by a constant gate, as
+    /// `ql·a + qc = 0` does. They are often placeholders that were never
+    /// assigned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, Warning};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// for warning in circuit.constcheck()? {
+    ///     assert!(matches!(warning, Warning::UnfixedConstant { .. }));
+    /// }
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(check = "constcheck")
+        )
+    )]
+    pub fn constcheck(&mut self) -> io::Result<Vec<Warning>> {
+        entropy::constcheck(self)
+    }
+
+    /// Check the selectors of the constraints against the gates of the
+    /// verifier data of the compiled circuit, reporting the constraints that
+    /// differ from their gate, and the gates missing from the file.
+    ///
+    /// A mismatch means the file was dumped from another version of the
+    /// circuit than the one that is proved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, VerifierData};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// let mut data = VerifierData::default();
+    /// for idx in 0..circuit.preamble().constraints {
+    ///     let constraint = circuit.fetch_constraint(idx)?;
+    ///
+    ///     data.gates.push(constraint.polynomial().selectors);
+    /// }
+    ///
+    /// assert!(circuit.verifycheck(&data)?.is_empty());
+    ///
+    /// data.gates.pop();
+    /// assert_eq!(circuit.verifycheck(&data)?.len(), 1);
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "plonk-verify")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(check = "verifycheck")
+        )
+    )]
+    pub fn verifycheck(
+        &mut self,
+        data: &VerifierData,
+    ) -> io::Result<Vec<Warning>> {
+        let mut warnings = vec![];
+
+        for idx in 0..self.preamble.constraints {
+            let constraint = self.fetch_constraint(idx)?;
+            let selectors = constraint.polynomial().selectors;
+
+            warnings.extend(data.check(constraint.id(), &selectors));
+        }
+
+        warnings.extend(data.check_padding(self.preamble.constraints));
+
+        Ok(warnings)
+    }
+
+    /// Public inputs recorded by the constraints, that is, the constraints
+    /// with a non-zero `pi` selector, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// for public in circuit.publics()? {
+    ///     assert!(!public.value.is_zero());
+    /// }
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn publics(&mut self) -> io::Result<Vec<PublicInput>> {
+        let mut publics = vec![];
+
+        for idx in 0..self.preamble.constraints {
+            let constraint = self.fetch_constraint(idx)?;
+            let value = constraint.polynomial().selectors.pi;
+
+            if !value.is_zero() {
+                publics.push(PublicInput {
+                    constraint: constraint.id(),
+                    value,
+                });
+            }
+        }
+
+        Ok(publics)
+    }
+
+    /// Compare the public input vector of the proof with the public inputs
+    /// recorded by the constraints, reporting the positions in which they
+    /// differ.
+    ///
+    /// A public input appended out of order, or missing from one side,
+    /// shifts all the following positions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, ProofArtifacts};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// let artifacts = ProofArtifacts {
+    ///     public_inputs: circuit.publics()?.iter().map(|p| p.value).collect(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(circuit.compare_publics(&artifacts)?.is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn compare_publics(
+        &mut self,
+        artifacts: &ProofArtifacts,
+    ) -> io::Result<Vec<PublicMismatch>> {
+        Ok(artifacts.compare(&self.publics()?))
+    }
+
+    /// Distribution of the witness values, along with the values shared by
+    /// several witnesses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let entropy = circuit.witness_entropy()?;
+    ///
+    /// assert_eq!(entropy.witnesses, circuit.preamble().witnesses);
+    /// assert!(entropy.bits <= entropy.max_bits());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn witness_entropy(&mut self) -> io::Result<WitnessEntropy> {
+        entropy::entropy(self)
+    }
+
+    /// Witnesses wired into the constraints of more than one source, ordered
+    /// by their number of sources.
+    ///
+    /// A witness shared by the constraints of several gadgets couples them,
+    /// and often explains a failure surfacing far from its cause.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// for wire in circuit.shared_wires()? {
+    ///     assert!(wire.sources.len() > 1);
+    /// }
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(check = "shared")
+        )
+    )]
+    pub fn shared_wires(&mut self) -> io::Result<Vec<SharedWire>> {
+        shared::shared_wires(self)
+    }
+
+    /// Break down the size of the CDF file per source, and rank the source
+    /// lines by the number of constraints they define.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let usage = circuit.usage()?;
+    ///
+    /// let constraints: usize = usage.sources.iter().map(|s| s.constraints).sum();
+    /// assert_eq!(constraints, circuit.preamble().constraints);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn usage(&mut self) -> io::Result<CircuitUsage> {
+        usage::usage(self)
+    }
+
+    /// Scan the circuit to build its index, replacing the current one.
+    ///
+    /// See [`CircuitIndex`] to store it as a sidecar file.
+    #[cfg(feature = "index")]
+    pub fn build_index(&mut self) -> io::Result<&CircuitIndex> {
+        let index = CircuitIndex::build(self)?;
+
+        Ok(self.attach_index(index))
+    }
+
+    /// Number of constraints and witnesses of each source, in the order of
+    /// [`source_names`](Self::source_names).
+    ///
+    /// The first call reads every constraint and witness of the file, unless
+    /// the circuit is indexed; the counts are kept for the subsequent calls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let constraints = circuit.preamble().constraints;
+    /// let counts = circuit.source_counts()?;
+    ///
+    /// let total: usize = counts.iter().map(|c| c.constraints).sum();
+    /// assert_eq!(total, constraints);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn source_counts(&mut self) -> io::Result<&[SourceCounts]> {
+        if self.source_counts.is_none() {
+            let counts = usage::counts(self)?;
+
+            self.source_counts.replace(counts);
+            self.reclaim(0, Cache::SourceCounts);
+        }
+
+        Ok(self.source_counts.as_deref().unwrap_or_default())
+    }
+
+    /// Patterns recognized by [`Self::gate_matches`]
+    pub fn pattern_library(&self) -> &PatternLibrary {
+        &self.patterns
+    }
+
+    /// Replace the patterns recognized by [`Self::gate_matches`], dropping
+    /// the current matches
+    pub fn set_pattern_library(&mut self, patterns: PatternLibrary) {
+        self.patterns = patterns;
+        self.gate_matches = None;
+    }
+
+    /// Occurrences of the gate patterns of the [`PatternLibrary`], in the
+    /// order of their constraints.
+    ///
+    /// The first call reads every constraint of the file; the matches are
+    /// kept for the subsequent calls.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let constraints = circuit.preamble().constraints;
+    ///
+    /// for m in circuit.gate_matches()? {
+    ///     assert!(m.start + m.len <= constraints);
+    /// }
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "scan", level = "debug", skip(self))
+    )]
+    pub fn gate_matches(&mut self) -> io::Result<&[GateMatch]> {
+        if self.gate_matches.is_none() {
+            let selectors = (0..self.preamble.constraints)
+                .map(|idx| {
+                    self.fetch_constraint(idx).map(|c| c.polynomial().selectors)
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+
+            let matches = self.patterns.recognize(selectors);
+
+            self.gate_matches.replace(matches);
+            self.reclaim(0, Cache::Patterns);
+        }
+
+        Ok(self.gate_matches.as_deref().unwrap_or_default())
+    }
+
+    /// Occurrence of a gate pattern spanning a constraint, if any, such as
+    /// `Poseidon round 12`
+    pub fn gate_match(&mut self, idx: usize) -> io::Result<Option<GateMatch>> {
+        let matches = self.gate_matches()?;
+
+        Ok(pattern::find(matches, idx).cloned())
+    }
+
+    /// Map every constraint to the allocation of its wired witnesses,
+    /// detecting the constraints that reference witnesses allocated after
+    /// them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let timeline = circuit.timeline()?;
+    ///
+    /// assert_eq!(timeline.entries().len(), circuit.preamble().constraints);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn timeline(&mut self) -> io::Result<Timeline> {
+        let anchors = timeline::anchors(self)?;
+        let mut timeline = Timeline::default();
+
+        for idx in 0..self.preamble.constraints {
+            let constraint = self.fetch_constraint(idx)?;
+            let id = constraint.id();
+            let wires = constraint.polynomial().witnesses;
+
+            timeline.push(&anchors, id, &wires);
+        }
+
+        let violations: Vec<_> =
+            timeline.violations().map(|e| e.constraint).collect();
+
+        if !violations.is_empty() {
+            let matches = self.gate_matches()?;
+            let patterns = violations
+                .iter()
+                .filter_map(|c| pattern::find(matches, *c))
+                .cloned()
+                .collect();
+
+            timeline.set_patterns(patterns);
+        }
+
+        Ok(timeline)
+    }
+
+    /// Trace the wires of a failed constraint backward, through the
+    /// preceding failed constraints, to the earliest failure each of them
+    /// depends on.
+    ///
+    /// The frontier is empty if the constraint didn't fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let frontier = circuit.frontier(16)?;
+    ///
+    /// assert_eq!(frontier.constraint(), 16);
+    /// assert!(frontier.entries().is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn frontier(&mut self, idx: usize) -> io::Result<Frontier> {
+        frontier::trace(self, idx)
+    }
+
+    /// Write a listing of the constraints in `range`, one line per resolved
+    /// constraint displayed with `style`. The range is clamped to the
+    /// constraints of the circuit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, Style};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let mut listing = vec![];
+    ///
+    /// circuit.dump(3..6, Style::PLAIN, &mut listing)?;
+    ///
+    /// let listing = String::from_utf8(listing).expect("the listing is utf-8");
+    /// let lines: Vec<_> = listing.lines().collect();
+    ///
+    /// assert_eq!(lines.len(), 3);
+    /// assert!(lines[0].starts_with("#3 "));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn dump<W>(
+        &mut self,
+        range: Range<usize>,
+        style: Style,
+        mut writer: W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let end = range.end.min(self.preamble.constraints);
+
+        for idx in range.start..end {
+            let resolved = self.resolve_constraint(idx)?;
+
+            writeln!(writer, "{}", resolved.listing(style))?;
+        }
+
+        writer.flush()
+    }
+
+    /// Write a static report of the circuit, to be shared for review: a
+    /// summary, the failed constraints with their source excerpts and
+    /// equations, the witnesses not wired to any constraint, the findings of
+    /// the wire, scalar, constant and timeline checks, and the sources and
+    /// gadgets with the most failures and constraints.
+    ///
+    /// Every listing is bounded to `limit` entries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, ReportFormat};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let mut report = vec![];
+    ///
+    /// circuit.report("test.cdf", ReportFormat::Markdown, 10, &mut report)?;
+    ///
+    /// let report = String::from_utf8(report).expect("the report is utf-8");
+    ///
+    /// assert!(report.starts_with("# test.cdf"));
+    /// assert!(report.contains("## Failed constraints"));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn report<W>(
+        &mut self,
+        title: &str,
+        format: ReportFormat,
+        limit: usize,
+        writer: W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        report::write(self, title, format, limit, writer)
+    }
+
+    /// Write a machine-readable artifact of the failures of the circuit, to
+    /// be rendered by a CI service: the failed constraints as errors, and
+    /// the findings of the wire, scalar, constant and timeline checks along
+    /// with the provided `warnings` as warnings, located in the sources.
+    ///
+    /// Under [`CiFormat::Junit`], every failure is a failing test case of the
+    /// `constraints` or the `findings` suite; under [`CiFormat::Sarif`], it
+    /// is a result of the rule of its kind.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CiFormat, CircuitDescription};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let mut junit = vec![];
+    ///
+    /// circuit.ci_report("test.cdf", CiFormat::Junit, &[], &mut junit)?;
+    ///
+    /// let junit = String::from_utf8(junit).expect("the artifact is utf-8");
+    ///
+    /// assert!(junit.starts_with("<?xml"));
+    /// assert!(junit.contains("<testsuite name=\"constraints\""));
+    ///
+    /// let mut sarif = vec![];
+    ///
+    /// circuit.ci_report("test.cdf", CiFormat::Sarif, &[], &mut sarif)?;
+    ///
+    /// let sarif = String::from_utf8(sarif).expect("the artifact is utf-8");
+    ///
+    /// assert!(sarif.starts_with("{\"version\":\"2.1.0\""));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn ci_report<W>(
+        &mut self,
+        title: &str,
+        format: CiFormat,
+        warnings: &[Warning],
+        writer: W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        ci::write(self, title, format, warnings, writer)
+    }
+
+    /// Field-level differences between the indexed constraint of this circuit
+    /// and the one of `other`, as in [`Divergence::between`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut ok = CircuitDescription::open("../assets/test.cdf")?;
+    /// let mut wrong = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// assert!(ok.divergences(&mut wrong, 3)?.is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn divergences<T>(
+        &mut self,
+        other: &mut CircuitDescription<T>,
+        idx: usize,
+    ) -> io::Result<Vec<Divergence>>
+    where
+        T: io::Read + io::Seek,
+    {
+        let left = self.resolve_constraint(idx)?;
+        let right = other.resolve_constraint(idx)?;
+
+        Ok(Divergence::between(&left, &right))
+    }
+
+    /// Search for the first witness and the first constraint in which this
+    /// circuit differs from `other`.
+    ///
+    /// The records are read in chunks, and only the constraints that may
+    /// diverge are resolved. A circuit with more records than the other
+    /// diverges at the first extra one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::CircuitDescription;
+    ///
+    /// let mut ok = CircuitDescription::open("../assets/test.cdf")?;
+    /// let mut wrong = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// let divergence = ok.first_divergence(&mut wrong)?;
+    ///
+    /// assert!(divergence.is_empty());
+    /// assert_eq!(divergence.constraint(), None);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn first_divergence<T>(
+        &mut self,
+        other: &mut CircuitDescription<T>,
+    ) -> io::Result<FirstDivergence>
+    where
+        T: io::Read + io::Seek,
+    {
+        divergence::first_divergence(self, other)
+    }
+}
+
+fn check_wires<S>(
+    preamble: &Preamble,
+    ctx: &DecoderContext,
+    source: &mut S,
+    constraint: usize,
+    wires: &WiredWitnesses,
+) -> io::Result<Vec<Warning>>
+where
+    S: io::Read + io::Seek,
+{
+    let mut warnings = vec![];
+    let mut wired = vec![];
+
+    for wire in Wire::ALL {
+        let witness = wire.of(wires);
+
+        if witness < preamble.witnesses {
+            wired.push((wire, witness));
+        } else {
+            warnings.push(Warning::WireOutOfBounds {
+                constraint,
+                wire,
+                witness,
+            });
+        }
+    }
+
+    let ids: Vec<_> = wired.iter().map(|(_, w)| *w).collect();
+    let found = read_witnesses(preamble, ctx, source, &ids)?;
+
+    for ((wire, witness), found) in wired.into_iter().zip(found) {
+        if found.id() != witness {
+            warnings.push(Warning::WireMismatch {
+                constraint,
+                wire,
+                witness,
+                found: found.id(),
+            });
+        }
+    }
+
+    Ok(warnings)
+}
+
+fn read_witnesses<'a, S>(
+    preamble: &Preamble,
+    ctx: &DecoderContext<'a>,
+    source: &mut S,
+    ids: &[usize],
+) -> io::Result<Vec<Witness<'a>>>
+where
+    S: io::Read + io::Seek,
+{
+    let len = Witness::len(ctx.config());
+
+    let mut sorted = ids.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut decoded = Vec::with_capacity(sorted.len());
+    let mut buf = vec![];
+
+    let mut start = 0;
+    while start < sorted.len() {
+        // extend the run while the witnesses are contiguous in the file
+        let mut end = start + 1;
+        while end < sorted.len() && sorted[end - 1] + 1 == sorted[end] {
+            end += 1;
+        }
+
+        let ofs = preamble
+            .witness_offset(sorted[start])
+            .filter(|_| preamble.witness_offset(sorted[end - 1]).is_some())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    "attempt to fetch invalid witness",
+                )
+            })?;
+
+        buf.resize(len * (end - start), 0);
+
+        source.seek(io::SeekFrom::Start(ofs as u64))?;
+        source.read_exact(&mut buf)?;
+
+        for (i, chunk) in buf.chunks_exact(len).enumerate() {
+            let witness =
+                Witness::try_from_buffer(ctx, chunk).map_err(|e| {
+                    corrupt_witness(sorted[start + i], ofs + i * len, e)
+                })?;
+
+            decoded.push(witness);
+        }
+
+        start = end;
+    }
+
+    ids.iter()
+        .map(|id| {
+            sorted
+                .binary_search(id)
+                .map(|i| decoded[i].clone())
+                .map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::Other,
+                        "attempt to fetch invalid witness",
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Error of a record that can't be decoded, stating the element and the
+/// offset it was read from.
+///
+/// The failures of the underlying source, other than a premature EOF, are
+/// preserved as they are not caused by the contents of the record.
+fn corrupt_element(
+    element: &str,
+    idx: usize,
+    offset: usize,
+    e: io::Error,
+) -> io::Error {
+    match e.kind() {
+        io::ErrorKind::InvalidData
+        | io::ErrorKind::UnexpectedEof
+        | io::ErrorKind::Other => io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} #{} at offset {:#x}: {}", element, idx, offset, e),
+        ),
+
+        _ => e,
+    }
+}
+
+/// Error of a constraint record that can't be decoded
+fn corrupt_constraint(idx: usize, offset: usize, e: io::Error) -> io::Error {
+    corrupt_element("constraint", idx, offset, e)
+}
+
+/// Error of a witness record that can't be decoded
+fn corrupt_witness(idx: usize, offset: usize, e: io::Error) -> io::Error {
+    corrupt_element("witness", idx, offset, e)
 }
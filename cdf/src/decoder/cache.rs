@@ -0,0 +1,178 @@
+//! Source cache of a CDF file, stored as two msgpack arrays of strings: the
+//! names of the sources, followed by their contents.
+//!
+//! The strings are read as raw bytes so the UTF-8 validation follows the
+//! [`Utf8Policy`] of the configuration, and their declared lengths are
+//! checked against its [`CacheLimits`] before they are read.
+
+use std::io::{self, Read};
+
+use crate::{CacheLimits, Config, Utf8Policy, Warning};
+
+/// Sources read from the cache, along with the warnings of their decoding
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct SourceCache {
+    pub names: Vec<String>,
+    pub contents: Vec<String>,
+    pub warnings: Vec<Warning>,
+}
+
+impl SourceCache {
+    pub fn read<R>(reader: &mut R, config: &Config) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        let mut strings = Strings {
+            utf8: config.utf8,
+            limits: config.cache_limits,
+            total: 0,
+            warnings: vec![],
+        };
+
+        let names = strings.read(reader, "name")?;
+        let contents = strings.read(reader, "contents")?;
+
+        if names.len() != contents.len() {
+            return Err(invalid(format!(
+                "the source cache has {} names but {} contents",
+                names.len(),
+                contents.len()
+            )));
+        }
+
+        Ok(Self {
+            names,
+            contents,
+            warnings: strings.warnings,
+        })
+    }
+}
+
+/// Reader of the string arrays of the cache, accounting their sizes
+struct Strings {
+    utf8: Utf8Policy,
+    limits: CacheLimits,
+    total: usize,
+    warnings: Vec<Warning>,
+}
+
+impl Strings {
+    fn read<R>(&mut self, reader: &mut R, what: &str) -> io::Result<Vec<String>>
+    where
+        R: io::Read,
+    {
+        let len = match read_u8(reader)? {
+            m @ 0x90..=0x9f => (m & 0x0f) as usize,
+            0xdc => read_len(reader, 2)?,
+            0xdd => read_len(reader, 4)?,
+            _ => return Err(invalid("the source cache isn't a valid array")),
+        };
+
+        if len > self.limits.max_sources {
+            return Err(invalid(format!(
+                "the source cache declares {} sources, above the limit of {}",
+                len, self.limits.max_sources
+            )));
+        }
+
+        (0..len)
+            .map(|source| self.read_string(reader, what, source))
+            .collect()
+    }
+
+    fn read_string<R>(
+        &mut self,
+        reader: &mut R,
+        what: &str,
+        source: usize,
+    ) -> io::Result<String>
+    where
+        R: io::Read,
+    {
+        let len = match read_u8(reader)? {
+            m @ 0xa0..=0xbf => (m & 0x1f) as usize,
+            0xd9 => read_len(reader, 1)?,
+            0xda => read_len(reader, 2)?,
+            0xdb => read_len(reader, 4)?,
+            _ => {
+                return Err(invalid(format!(
+                    "the source {} #{} isn't a string",
+                    what, source
+                )))
+            }
+        };
+
+        if len > self.limits.max_source_size {
+            return Err(invalid(format!(
+                "the source {} #{} is {} bytes, above the limit of {}",
+                what, source, len, self.limits.max_source_size
+            )));
+        }
+
+        self.total = self.total.saturating_add(len);
+
+        if self.total > self.limits.max_cache_size {
+            return Err(invalid(format!(
+                "the source cache exceeds the limit of {} bytes",
+                self.limits.max_cache_size
+            )));
+        }
+
+        let mut bytes = vec![];
+        reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+
+        if bytes.len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("the source {} #{} is truncated", what, source),
+            ));
+        }
+
+        match (String::from_utf8(bytes), self.utf8) {
+            (Ok(s), _) => Ok(s),
+
+            (Err(e), Utf8Policy::Strict) => Err(invalid(format!(
+                "invalid utf-8 in source {} #{}: {}",
+                what,
+                source,
+                e.utf8_error()
+            ))),
+
+            (Err(e), Utf8Policy::Lenient) => {
+                self.warnings.push(Warning::LossySource { source });
+
+                Ok(String::from_utf8_lossy(e.as_bytes()).into_owned())
+            }
+        }
+    }
+}
+
+fn read_u8<R>(reader: &mut R) -> io::Result<u8>
+where
+    R: io::Read,
+{
+    let mut byte = [0u8];
+
+    reader.read_exact(&mut byte)?;
+
+    Ok(byte[0])
+}
+
+/// Read a big-endian length of `n` bytes
+fn read_len<R>(reader: &mut R, n: usize) -> io::Result<usize>
+where
+    R: io::Read,
+{
+    let mut bytes = [0u8; 4];
+
+    reader.read_exact(&mut bytes[4 - n..])?;
+
+    Ok(u32::from_be_bytes(bytes) as usize)
+}
+
+fn invalid<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
@@ -0,0 +1,502 @@
+//! Field-level comparison of the constraints of two CDF files
+
+use std::{fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constraint::Hex;
+use crate::{
+    CircuitDescription, Constraint, DecodableElement, DecoderContext,
+    ResolvedConstraint, Scalar, Selector, Wire, Witness,
+};
+
+/// Number of records read at once while searching for a divergence
+const CHUNK: usize = 4096;
+
+/// Field in which two constraints with the same index differ
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Divergence {
+    /// The selector has different values
+    Selector {
+        /// Id of the constraint
+        constraint: usize,
+        /// Divergent selector
+        selector: Selector,
+        /// Value of the selector in the left circuit
+        left: Scalar,
+        /// Value of the selector in the right circuit
+        right: Scalar,
+    },
+    /// The wire references different witnesses
+    Wire {
+        /// Id of the constraint
+        constraint: usize,
+        /// Divergent wire
+        wire: Wire,
+        /// Witness wired in the left circuit
+        left: usize,
+        /// Witness wired in the right circuit
+        right: usize,
+    },
+    /// The witnesses wired have different values
+    Value {
+        /// Id of the constraint
+        constraint: usize,
+        /// Wire of the witness
+        wire: Wire,
+        /// Value of the witness in the left circuit
+        left: Scalar,
+        /// Value of the witness in the right circuit
+        right: Scalar,
+    },
+    /// The constraint evaluates differently
+    Evaluation {
+        /// Id of the constraint
+        constraint: usize,
+        /// Evaluation of the constraint in the left circuit
+        left: bool,
+        /// Evaluation of the constraint in the right circuit
+        right: bool,
+    },
+    /// The constraint exists in a single circuit
+    Missing {
+        /// Id of the constraint
+        constraint: usize,
+        /// Flag on whether the constraint exists in the left circuit, rather
+        /// than in the right one
+        left: bool,
+    },
+}
+
+impl Divergence {
+    /// Differences between two constraints: the selectors first, then the
+    /// wires, the values of the wired witnesses and the evaluation.
+    ///
+    /// The value of a wire is compared only if both circuits wire the same
+    /// witness.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CircuitDescription, Divergence};
+    ///
+    /// let mut left = CircuitDescription::open("../assets/test.cdf")?;
+    /// let mut right = CircuitDescription::open("../assets/test.cdf")?;
+    ///
+    /// let a = left.resolve_constraint(1)?;
+    /// let b = right.resolve_constraint(1)?;
+    ///
+    /// assert!(Divergence::between(&a, &b).is_empty());
+    ///
+    /// let c = right.resolve_constraint(2)?;
+    ///
+    /// assert!(!Divergence::between(&a, &c).is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn between(
+        left: &ResolvedConstraint,
+        right: &ResolvedConstraint,
+    ) -> Vec<Self> {
+        let constraint = left.constraint.id();
+        let l = left.constraint.polynomial();
+        let r = right.constraint.polynomial();
+
+        let selectors = Selector::ALL.into_iter().filter_map(|selector| {
+            let left = *selector.of(&l.selectors);
+            let right = *selector.of(&r.selectors);
+
+            (left != right).then_some(Self::Selector {
+                constraint,
+                selector,
+                left,
+                right,
+            })
+        });
+
+        let wires = Wire::ALL.into_iter().filter_map(|wire| {
+            let left = wire.of(&l.witnesses);
+            let right = wire.of(&r.witnesses);
+
+            (left != right).then_some(Self::Wire {
+                constraint,
+                wire,
+                left,
+                right,
+            })
+        });
+
+        let values = Wire::ALL.into_iter().filter_map(|wire| {
+            let (a, b) = (left.wired(wire), right.wired(wire));
+
+            (a.id() == b.id() && a.value() != b.value()).then_some(
+                Self::Value {
+                    constraint,
+                    wire,
+                    left: *a.value(),
+                    right: *b.value(),
+                },
+            )
+        });
+
+        let evaluation =
+            (l.evaluation != r.evaluation).then_some(Self::Evaluation {
+                constraint,
+                left: l.evaluation,
+                right: r.evaluation,
+            });
+
+        selectors
+            .chain(wires)
+            .chain(values)
+            .chain(evaluation)
+            .collect()
+    }
+
+    /// Id of the divergent constraint
+    pub const fn constraint(&self) -> usize {
+        match self {
+            Self::Selector { constraint, .. }
+            | Self::Wire { constraint, .. }
+            | Self::Value { constraint, .. }
+            | Self::Evaluation { constraint, .. }
+            | Self::Missing { constraint, .. } => *constraint,
+        }
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Selector {
+                constraint,
+                selector,
+                left,
+                right,
+            } => write!(
+                f,
+                "constraint #{}: selector {} is {} and {}",
+                constraint,
+                selector,
+                Hex(left),
+                Hex(right)
+            ),
+            Self::Wire {
+                constraint,
+                wire,
+                left,
+                right,
+            } => write!(
+                f,
+                "constraint #{}: wire {} references witness #{} and #{}",
+                constraint, wire, left, right
+            ),
+            Self::Value {
+                constraint,
+                wire,
+                left,
+                right,
+            } => write!(
+                f,
+                "constraint #{}: witness wired to {} is {} and {}",
+                constraint,
+                wire,
+                Hex(left),
+                Hex(right)
+            ),
+            Self::Evaluation {
+                constraint,
+                left,
+                right,
+            } => write!(
+                f,
+                "constraint #{}: evaluates to {} and {}",
+                constraint, left, right
+            ),
+            Self::Missing { constraint, left } => write!(
+                f,
+                "constraint #{}: exists only in the {} circuit",
+                constraint,
+                side(*left)
+            ),
+        }
+    }
+}
+
+/// Witness in which two circuits differ
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct WitnessDivergence {
+    /// Index of the witness
+    pub witness: usize,
+    /// Value of the witness in the left circuit, if it exists there
+    pub left: Option<Scalar>,
+    /// Value of the witness in the right circuit, if it exists there
+    pub right: Option<Scalar>,
+}
+
+impl fmt::Display for WitnessDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.left, &self.right) {
+            (Some(left), Some(right)) => write!(
+                f,
+                "witness #{}: is {} and {}",
+                self.witness,
+                Hex(left),
+                Hex(right)
+            ),
+            (left, _) => write!(
+                f,
+                "witness #{}: exists only in the {} circuit",
+                self.witness,
+                side(left.is_some())
+            ),
+        }
+    }
+}
+
+/// First witness and constraint in which two circuits differ, created by
+/// [`CircuitDescription::first_divergence`]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct FirstDivergence {
+    /// First witness with a different value
+    pub witness: Option<WitnessDivergence>,
+    /// Differences of the first divergent constraint, as in
+    /// [`Divergence::between`]
+    pub fields: Vec<Divergence>,
+}
+
+impl FirstDivergence {
+    /// Id of the first divergent constraint, if any
+    pub fn constraint(&self) -> Option<usize> {
+        self.fields.first().map(Divergence::constraint)
+    }
+
+    /// Check if the circuits have no divergent witness nor constraint
+    pub fn is_empty(&self) -> bool {
+        self.witness.is_none() && self.fields.is_empty()
+    }
+}
+
+/// One line for the witness, then one per divergent field of the constraint
+impl fmt::Display for FirstDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.witness {
+            Some(witness) => writeln!(f, "{}", witness)?,
+            None => writeln!(f, "no divergent witness")?,
+        }
+
+        if self.fields.is_empty() {
+            return writeln!(f, "no divergent constraint");
+        }
+
+        self.fields.iter().try_for_each(|d| writeln!(f, "{}", d))
+    }
+}
+
+const fn side(left: bool) -> &'static str {
+    if left {
+        "left"
+    } else {
+        "right"
+    }
+}
+
+pub(crate) fn first_divergence<S, T>(
+    left: &mut CircuitDescription<S>,
+    right: &mut CircuitDescription<T>,
+) -> io::Result<FirstDivergence>
+where
+    S: io::Read + io::Seek,
+    T: io::Read + io::Seek,
+{
+    let witness = first_witness(left, right)?;
+    let fields =
+        first_constraint(left, right, witness.as_ref().map(|w| w.witness))?;
+
+    Ok(FirstDivergence { witness, fields })
+}
+
+fn first_witness<S, T>(
+    left: &mut CircuitDescription<S>,
+    right: &mut CircuitDescription<T>,
+) -> io::Result<Option<WitnessDivergence>>
+where
+    S: io::Read + io::Seek,
+    T: io::Read + io::Seek,
+{
+    let (l, r) = (left.preamble().witnesses, right.preamble().witnesses);
+    let common = l.min(r);
+
+    for start in (0..common).step_by(CHUNK) {
+        let count = CHUNK.min(common - start);
+        let a: Vec<Witness> = read_chunk(left, start, count, witness_offset)?;
+        let b: Vec<Witness> = read_chunk(right, start, count, witness_offset)?;
+
+        let found = a.iter().zip(&b).enumerate().find_map(|(i, (a, b))| {
+            (a.value() != b.value()).then_some(WitnessDivergence {
+                witness: start + i,
+                left: Some(*a.value()),
+                right: Some(*b.value()),
+            })
+        });
+
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+
+    if l == r {
+        return Ok(None);
+    }
+
+    let (left, right) = if l > r {
+        (Some(*left.fetch_witness(common)?.value()), None)
+    } else {
+        (None, Some(*right.fetch_witness(common)?.value()))
+    };
+
+    Ok(Some(WitnessDivergence {
+        witness: common,
+        left,
+        right,
+    }))
+}
+
+/// The polynomials are compared in chunks; the wired values are resolved only
+/// for the constraints that wire a witness from `witness` onwards, as the
+/// previous ones are identical.
+fn first_constraint<S, T>(
+    left: &mut CircuitDescription<S>,
+    right: &mut CircuitDescription<T>,
+    witness: Option<usize>,
+) -> io::Result<Vec<Divergence>>
+where
+    S: io::Read + io::Seek,
+    T: io::Read + io::Seek,
+{
+    let (l, r) = (left.preamble().constraints, right.preamble().constraints);
+    let common = l.min(r);
+    let mut start = 0;
+
+    while start < common {
+        let count = CHUNK.min(common - start);
+        let candidate = {
+            let a: Vec<Constraint> =
+                read_chunk(left, start, count, constraint_offset)?;
+            let b: Vec<Constraint> =
+                read_chunk(right, start, count, constraint_offset)?;
+
+            a.iter().zip(&b).position(|(a, b)| {
+                let (a, b) = (a.polynomial(), b.polynomial());
+                let wires = |w: usize| {
+                    Wire::ALL.iter().any(|wire| {
+                        wire.of(&a.witnesses) >= w || wire.of(&b.witnesses) >= w
+                    })
+                };
+
+                a != b || witness.map_or(false, wires)
+            })
+        };
+
+        match candidate {
+            Some(i) => {
+                let fields = left.divergences(right, start + i)?;
+
+                if !fields.is_empty() {
+                    return Ok(fields);
+                }
+
+                start += i + 1;
+            }
+
+            None => start += count,
+        }
+    }
+
+    if l == r {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![Divergence::Missing {
+        constraint: common,
+        left: l > r,
+    }])
+}
+
+fn witness_offset<S>(cdf: &CircuitDescription<S>, idx: usize) -> Option<usize> {
+    cdf.preamble().witness_offset(idx)
+}
+
+fn constraint_offset<S>(
+    cdf: &CircuitDescription<S>,
+    idx: usize,
+) -> Option<usize> {
+    cdf.preamble().constraint_offset(idx)
+}
+
+/// Decode `count` contiguous records from `start` with a single read
+fn read_chunk<'a, S, E>(
+    cdf: &'a mut CircuitDescription<S>,
+    start: usize,
+    count: usize,
+    offset: fn(&CircuitDescription<S>, usize) -> Option<usize>,
+) -> io::Result<Vec<E>>
+where
+    S: io::Read + io::Seek,
+    E: DecodableElement + 'a,
+{
+    let ofs = offset(cdf, start)
+        .filter(|_| offset(cdf, start + count - 1).is_some())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "attempt to fetch invalid records",
+            )
+        })?;
+
+    let (ctx, source): (DecoderContext<'a>, _) = cdf.context();
+    let len = E::len(ctx.config());
+    let mut buf = vec![0u8; len * count];
+
+    source.seek(io::SeekFrom::Start(ofs as u64))?;
+    source.read_exact(&mut buf)?;
+
+    buf.chunks_exact(len)
+        .map(|chunk| E::try_from_buffer(&ctx, chunk))
+        .collect()
+}
@@ -8,7 +8,7 @@ use std::io;
 
 use crate::{Config, DecoderContext, EncoderContext, Preamble};
 
-pub use scalar::Scalar;
+pub use scalar::{ByteOrder, Scalar, ScalarFormat};
 
 /// Element that can be encoded into a CDF file
 pub trait EncodableElement: Element {
@@ -1,7 +1,7 @@
 use std::io;
 use std::ops::{Deref, DerefMut};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Config, DecodableElement, DecoderContext, Element, EncodableElement,
@@ -13,7 +13,17 @@ use crate::{
 /// This is agnostic to the curve choice and no canonical encoding assumption is
 /// involved.
 #[derive(
-    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct Scalar {
     scalar: [u8; Self::LEN],
@@ -22,6 +32,176 @@ pub struct Scalar {
 impl Scalar {
     /// Fixed serialized length
     pub const LEN: usize = 32;
+
+    /// Modulus of the BLS12-381 scalar field, in little endian.
+    ///
+    /// This is the field of the PLONK circuits produced by `dusk-plonk`.
+    pub const MODULUS: [u8; Self::LEN] = [
+        0x01, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xfe, 0x5b, 0xfe, 0xff,
+        0x02, 0xa4, 0xbd, 0x53, 0x05, 0xd8, 0xa1, 0x09, 0x08, 0xd8, 0x39, 0x33,
+        0x48, 0x7d, 0x9d, 0x29, 0x53, 0xa7, 0xed, 0x73,
+    ];
+
+    /// Check if all the bytes of the scalar are zero
+    pub fn is_zero(&self) -> bool {
+        self.scalar.iter().all(|b| *b == 0)
+    }
+
+    /// Check if the little endian scalar is canonical, that is, smaller than
+    /// [`Self::MODULUS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::Scalar;
+    ///
+    /// assert!(Scalar::default().is_canonical());
+    /// assert!(!Scalar::from(Scalar::MODULUS).is_canonical());
+    /// assert!(!Scalar::from([0xff; Scalar::LEN]).is_canonical());
+    /// ```
+    pub fn is_canonical(&self) -> bool {
+        self.scalar.iter().rev().lt(Self::MODULUS.iter().rev())
+    }
+}
+
+/// Byte order of the hexadecimal representation of a scalar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ByteOrder {
+    /// Bytes of the scalar as stored, the least significant first
+    Little,
+    /// Most significant byte first, as the scalar is written as a number
+    Big,
+}
+
+impl Default for ByteOrder {
+    fn default() -> Self {
+        Self::Little
+    }
+}
+
+/// Hexadecimal representation of the scalars, as displayed and as entered.
+///
+/// The default format is the one of the DAP backend: the bytes as stored,
+/// lowercase, after a `0x` prefix.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use dusk_cdf::{ByteOrder, Scalar, ScalarFormat};
+///
+/// let mut one = [0u8; Scalar::LEN];
+/// one[0] = 0xab;
+/// let one = Scalar::from(one);
+///
+/// let format = ScalarFormat {
+///     byte_order: ByteOrder::Big,
+///     prefix: false,
+///     uppercase: true,
+/// };
+///
+/// let hex = format.format(&one);
+///
+/// assert!(hex.starts_with("0000") && hex.ends_with("00AB"));
+/// assert_eq!(format.parse("0xab")?, one);
+/// assert_eq!(ScalarFormat::default().parse("ab")?, one);
+/// assert_ne!(ScalarFormat::default().parse(&hex)?, one);
+///
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScalarFormat {
+    /// Byte order of the digits
+    pub byte_order: ByteOrder,
+    /// Flag on whether or not the digits follow a `0x` prefix
+    pub prefix: bool,
+    /// Flag on whether or not the digits are uppercase
+    pub uppercase: bool,
+}
+
+impl Default for ScalarFormat {
+    fn default() -> Self {
+        Self {
+            byte_order: ByteOrder::Little,
+            prefix: true,
+            uppercase: false,
+        }
+    }
+}
+
+impl ScalarFormat {
+    /// Hexadecimal representation of the scalar
+    pub fn format(&self, scalar: &Scalar) -> String {
+        let mut bytes = scalar.scalar;
+
+        if self.byte_order == ByteOrder::Big {
+            bytes.reverse();
+        }
+
+        let prefix = if self.prefix { "0x" } else { "" };
+
+        bytes.iter().fold(String::from(prefix), |mut s, b| {
+            match self.uppercase {
+                true => s.push_str(&format!("{:02X}", b)),
+                false => s.push_str(&format!("{:02x}", b)),
+            }
+            s
+        })
+    }
+
+    /// Parse a scalar literal in the byte order of the format, with or
+    /// without the `0x` prefix and in either case.
+    ///
+    /// A literal shorter than a scalar is completed with zeros as its most
+    /// significant bytes; a little endian literal lists whole bytes.
+    pub fn parse(&self, literal: &str) -> io::Result<Scalar> {
+        let invalid = |reason: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid scalar `{}`: {}", literal, reason),
+            )
+        };
+
+        let digits = literal.trim();
+        let digits = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+            .unwrap_or(digits);
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid("expected hexadecimal digits"));
+        }
+
+        if digits.len() > 2 * Scalar::LEN {
+            return Err(invalid("too many digits"));
+        }
+
+        let padded = match self.byte_order {
+            ByteOrder::Big => format!("{:0>1$}", digits, 2 * Scalar::LEN),
+            ByteOrder::Little if digits.len() % 2 == 0 => {
+                format!("{:0<1$}", digits, 2 * Scalar::LEN)
+            }
+            ByteOrder::Little => {
+                return Err(invalid("expected an even number of digits"))
+            }
+        };
+
+        let mut scalar = [0u8; Scalar::LEN];
+
+        // the digits are ascii, so every pair is a byte
+        for (i, byte) in scalar.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&padded[2 * i..2 * i + 2], 16)
+                .map_err(|_| invalid("expected hexadecimal digits"))?;
+        }
+
+        if self.byte_order == ByteOrder::Big {
+            scalar.reverse();
+        }
+
+        Ok(Scalar::from(scalar))
+    }
 }
 
 impl From<[u8; Scalar::LEN]> for Scalar {
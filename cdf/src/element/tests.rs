@@ -18,6 +18,13 @@ impl Arbitrary for Config {
     fn arbitrary(g: &mut Gen) -> Self {
         Self {
             zeroed_scalar_values: bool::arbitrary(g),
+            // decoding parameters, not part of the encoded element
+            check_wires: false,
+            corruption: CorruptionPolicy::Stop,
+            utf8: Utf8Policy::Strict,
+            cache_limits: CacheLimits::DEFAULT,
+            max_constraints: None,
+            budget: BudgetPolicy::Warn,
         }
     }
 }
@@ -152,3 +159,22 @@ fn elements() {
     quickcheck(prop as fn(_, Polynomial) -> _);
     quickcheck(prop as fn(_, EncodedSource) -> _);
 }
+
+#[test]
+fn scalar_formats_parse_what_they_display() {
+    fn prop(scalar: Scalar, big: bool, prefix: bool, uppercase: bool) -> bool {
+        let format = ScalarFormat {
+            byte_order: if big {
+                ByteOrder::Big
+            } else {
+                ByteOrder::Little
+            },
+            prefix,
+            uppercase,
+        };
+
+        format.parse(&format.format(&scalar)).ok() == Some(scalar)
+    }
+
+    quickcheck(prop as fn(_, _, _, _) -> _);
+}
@@ -1,23 +1,102 @@
 //! Encoding into the CDF format
 
-mod context;
+mod parallel;
+#[cfg(feature = "plonk")]
+pub(crate) mod plonk;
+mod streaming;
 
-#[cfg(test)]
+// the encoded items are checked against their decoding
+#[cfg(all(test, feature = "decoder"))]
 mod tests;
 
 use std::borrow::Borrow;
-use std::fs::{File, OpenOptions};
-use std::io::{self, Seek, Write};
-use std::path::Path;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 
-pub use context::{
-    EncoderContext, EncoderContextFileProvider, EncoderContextProvider,
-};
+pub use parallel::PositionedWrite;
+#[cfg(feature = "plonk")]
+pub use plonk::ComposerDump;
+pub use streaming::StreamingEncoder;
 
 use crate::{
-    Config, EncodableConstraint, EncodableElement, EncodableWitness, Preamble,
+    lock, BudgetPolicy, Config, EncodableConstraint, EncodableElement,
+    EncodableSource, EncodableWitness, EncoderContext, EncoderContextProvider,
+    Preamble, Warning, Wire,
 };
 
+/// Progress of an encoding operation
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EncoderProgress {
+    /// Witnesses and constraints written so far
+    pub items: usize,
+    /// Total number of witnesses and constraints
+    pub total: usize,
+    /// Bytes written so far
+    pub bytes: usize,
+}
+
+/// Constraints of a circuit over its [`Config::max_constraints`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BudgetOverrun {
+    /// Constraints count of the circuit
+    pub constraints: usize,
+    /// Expected maximum number of constraints
+    pub max_constraints: usize,
+}
+
+impl BudgetOverrun {
+    /// Constraints over the budget
+    pub const fn excess(&self) -> usize {
+        self.constraints - self.max_constraints
+    }
+}
+
+impl fmt::Display for BudgetOverrun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} constraints exceed the budget of {} by {}",
+            self.constraints,
+            self.max_constraints,
+            self.excess()
+        )
+    }
+}
+
+/// Callback receiving the budget overrun of a circuit
+struct BudgetCallback(Box<dyn FnMut(&BudgetOverrun) + Send>);
+
+impl fmt::Debug for BudgetCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BudgetCallback")
+    }
+}
+
+/// Capacity bounds of the buffer wrapping file targets
+const FILE_BUFFER_MIN: usize = 8 * 1024;
+const FILE_BUFFER_MAX: usize = 1024 * 1024;
+
+/// Durability of the encoded file once the encoder is done writing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlushPolicy {
+    /// Flush the buffers and sync the file contents and metadata to the disk
+    /// when the writing is done. A successful write is durable even if the
+    /// process is terminated right after.
+    FsyncOnFinalize,
+    /// Flush the buffers when the writing is done, leaving the sync to the
+    /// operating system. A successful write might be lost on a system crash.
+    Never,
+}
+
+impl Default for FlushPolicy {
+    fn default() -> Self {
+        Self::FsyncOnFinalize
+    }
+}
+
 /// An encoder for CDF format
 #[derive(Debug)]
 pub struct Encoder<WI, CI, T> {
@@ -25,6 +104,12 @@ pub struct Encoder<WI, CI, T> {
     witnesses: WI,
     constraints: CI,
     target: T,
+    flush_policy: FlushPolicy,
+    file: Option<File>,
+    atomic: Option<(PathBuf, PathBuf)>,
+    on_budget_overrun: Option<BudgetCallback>,
+    validate_ids: bool,
+    validate_wires: bool,
 }
 
 impl<WI, CI, T> Encoder<WI, CI, T>
@@ -45,6 +130,12 @@ where
             witnesses,
             constraints,
             target,
+            flush_policy: FlushPolicy::default(),
+            file: None,
+            atomic: None,
+            on_budget_overrun: None,
+            validate_ids: true,
+            validate_wires: true,
         }
     }
 
@@ -65,16 +156,257 @@ where
     pub fn into_inner(self) -> T {
         self.target
     }
+
+    /// Set the durability of the file once the encoder is done writing.
+    ///
+    /// Only encoders writing into a file are synced to the disk; for other
+    /// targets, the policy is limited to flushing them.
+    pub fn flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.flush_policy = policy;
+        self
+    }
+
+    /// Set the callback that receives the overrun of the constraint budget,
+    /// called before the circuit is written if the [`Config::budget`] is to
+    /// warn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source.clone())];
+    /// let polynomial = Polynomial::builder().build();
+    /// let constraints: Vec<_> = (0..3)
+    ///     .map(|id| EncodableConstraint::new(id, polynomial, source.clone()))
+    ///     .collect();
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let config = Config::builder().max_constraints(Some(2)).build();
+    /// let warnings = Arc::new(Mutex::new(vec![]));
+    /// let w = Arc::clone(&warnings);
+    ///
+    /// let mut encoder =
+    ///     Encoder::init_cursor(config, witnesses.iter(), constraints.iter())
+    ///         .on_budget_overrun(move |o| w.lock().unwrap().push(*o));
+    ///
+    /// encoder.write_all(provider.clone()).expect("failed to encode");
+    ///
+    /// assert_eq!(warnings.lock().unwrap()[0].excess(), 1);
+    ///
+    /// let config = Config::builder()
+    ///     .max_constraints(Some(2))
+    ///     .budget(BudgetPolicy::Error)
+    ///     .build();
+    ///
+    /// Encoder::init_cursor(config, witnesses.iter(), constraints.iter())
+    ///     .write_all(provider)
+    ///     .expect_err("the circuit is over budget");
+    /// ```
+    pub fn on_budget_overrun<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&BudgetOverrun) + Send + 'static,
+    {
+        self.on_budget_overrun
+            .replace(BudgetCallback(Box::new(callback)));
+        self
+    }
+
+    /// Overrun of the [`Config::max_constraints`] of the encoded circuit, if
+    /// any
+    pub fn budget_overrun(&self) -> Option<BudgetOverrun> {
+        let preamble = self.context.preamble();
+        let max_constraints = self.context.config().max_constraints?;
+
+        (preamble.constraints > max_constraints).then(|| BudgetOverrun {
+            constraints: preamble.constraints,
+            max_constraints,
+        })
+    }
+
+    /// Report the overrun of the constraint budget to the callback, or reject
+    /// the circuit, according to the [`Config::budget`]
+    fn check_budget(&mut self) -> io::Result<()> {
+        let overrun = match self.budget_overrun() {
+            Some(overrun) => overrun,
+            None => return Ok(()),
+        };
+
+        match self.context.config().budget {
+            BudgetPolicy::Warn => {
+                if let Some(BudgetCallback(callback)) =
+                    self.on_budget_overrun.as_mut()
+                {
+                    callback(&overrun);
+                }
+
+                Ok(())
+            }
+
+            BudgetPolicy::Error => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                overrun.to_string(),
+            )),
+        }
+    }
+
+    /// Check the ids of the witnesses and constraints while writing, enabled
+    /// by default.
+    ///
+    /// The ids of each section must be dense and ordered, as the decoder
+    /// fetches an item by its position. The first item out of order fails
+    /// the encoding with its id, the expected one and its source location.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(7, 4, "lib.rs".into());
+    /// let witnesses = vec![
+    ///     EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+    ///     EncodableWitness::new(2, None, Scalar::default(), source),
+    /// ];
+    /// let constraints: Vec<EncodableConstraint> = vec![];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let e = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// )
+    /// .write_all(provider.clone())
+    /// .expect_err("the witness ids skip #1");
+    ///
+    /// assert_eq!(
+    ///     e.to_string(),
+    ///     "witness #2 at lib.rs:7:4 is at position 1: the witness ids must \
+    ///      be dense and ordered, expected #1"
+    /// );
+    ///
+    /// Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// )
+    /// .validate_ids(false)
+    /// .write_all(provider)
+    /// .expect("the ids aren't checked");
+    /// ```
+    pub fn validate_ids(mut self, validate: bool) -> Self {
+        self.validate_ids = validate;
+        self
+    }
+
+    /// Check the wired witnesses of every constraint against the witness
+    /// table while writing, enabled by default.
+    ///
+    /// The wires of a constraint are indexes into the witnesses written
+    /// before; a wire out of the table, or pointing to a witness of another
+    /// id, fails the encoding with the offending constraint, instead of
+    /// producing a file that only the [`wirecheck`] of the decoder reports.
+    ///
+    /// [`wirecheck`]: struct.CircuitDescription.html#method.wirecheck
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source.clone())];
+    /// let polynomial = Polynomial::builder().a(3).build();
+    /// let constraints = vec![EncodableConstraint::new(0, polynomial, source)];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let e = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// )
+    /// .write_all(provider.clone())
+    /// .expect_err("the wire is out of the witness table");
+    ///
+    /// assert!(e.to_string().starts_with("constraint #0: wire a"));
+    ///
+    /// Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// )
+    /// .validate_wires(false)
+    /// .write_all(provider)
+    /// .expect("the wires aren't checked");
+    /// ```
+    pub fn validate_wires(mut self, validate: bool) -> Self {
+        self.validate_wires = validate;
+        self
+    }
+
+    /// Check if a successful write is synced to the disk before returning
+    pub fn is_durable(&self) -> bool {
+        self.file.is_some() && self.flush_policy == FlushPolicy::FsyncOnFinalize
+    }
+
+    /// Sync the finalized file to the disk, if required by the flush policy,
+    /// move it into its final path if the encoder is atomic, and release its
+    /// lock to the readers.
+    fn commit(&mut self) -> io::Result<()> {
+        let durable = self.is_durable();
+
+        if let Some(file) = self.file.as_ref().filter(|_| durable) {
+            file.sync_all()?;
+        }
+
+        if let Some((tmp, path)) = self.atomic.take() {
+            fs::rename(tmp, &path)?;
+
+            if durable {
+                sync_parent(&path)?;
+            }
+        }
+
+        if let Some(file) = &self.file {
+            file.unlock()?;
+        }
+
+        Ok(())
+    }
 }
 
-impl<WI, CI> Encoder<WI, CI, File>
+impl<WI, CI> Encoder<WI, CI, io::BufWriter<File>>
 where
     WI: ExactSizeIterator,
     CI: ExactSizeIterator,
 {
     /// Initialize the encoder, filling a file with required bytes.
     ///
+    /// The file is wrapped in a buffer sized after the fixed length of the
+    /// elements, and synced to the disk according to the [`FlushPolicy`].
+    ///
+    /// The file is held under an exclusive advisory lock until the encoding
+    /// succeeds, so [`CircuitDescription::open`] rejects it meanwhile. A file
+    /// open by a reader can't be encoded in place, and fails with
+    /// [`io::ErrorKind::WouldBlock`]; [`Encoder::init_file_atomic`] replaces
+    /// it without tearing the reads.
+    ///
     /// Check [`File::set_len`]
+    ///
+    /// [`CircuitDescription::open`]: struct.CircuitDescription.html#method.open
     pub fn init_file<P>(
         config: Config,
         witnesses: WI,
@@ -84,12 +416,92 @@ where
     where
         P: AsRef<Path>,
     {
+        Self::open_file(config, witnesses, constraints, path.as_ref())
+    }
+
+    /// Initialize the encoder like [`Encoder::init_file`], writing into
+    /// `<path>.tmp` instead.
+    ///
+    /// The temporary file is renamed to `path` once the encoding succeeds, so
+    /// an interrupted encoding never replaces a previous file at `path`.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let path = std::env::temp_dir().join("dusk-cdf-atomic.cdf");
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    /// let constraints: Vec<EncodableConstraint> = vec![];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let mut encoder = Encoder::init_file_atomic(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    ///     &path,
+    /// )
+    /// .expect("failed to create the file");
+    ///
+    /// encoder.write_all(provider).expect("failed to encode");
+    ///
+    /// let circuit = CircuitDescription::open(&path).expect("failed to open");
+    ///
+    /// assert!(circuit.preamble().finalized);
+    /// ```
+    pub fn init_file_atomic<P>(
+        config: Config,
+        witnesses: WI,
+        constraints: CI,
+        path: P,
+    ) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        let tmp = PathBuf::from(tmp);
+        let mut slf = Self::open_file(config, witnesses, constraints, &tmp)?;
+
+        slf.atomic.replace((tmp, path.to_path_buf()));
+
+        Ok(slf)
+    }
+
+    fn open_file(
+        config: Config,
+        witnesses: WI,
+        constraints: CI,
+        path: &Path,
+    ) -> io::Result<Self> {
         let file = OpenOptions::new().write(true).create(true).open(path)?;
 
-        let slf = Self::new(config, witnesses, constraints, file);
-        let len = slf.context.preamble().source_cache_offset();
+        lock::exclusive(&file)?;
+
+        let preamble =
+            Preamble::new(witnesses.len(), constraints.len(), config);
+        let len = source_cache_offset(&preamble)?;
+
+        file.set_len(len as u64)?;
 
-        slf.target.set_len(len as u64)?;
+        let capacity = len.clamp(FILE_BUFFER_MIN, FILE_BUFFER_MAX);
+        let sync = file.try_clone()?;
+        let target = io::BufWriter::with_capacity(capacity, file);
+
+        let mut slf =
+            Self::with_preamble(preamble, witnesses, constraints, target);
+
+        slf.file.replace(sync);
 
         Ok(slf)
     }
@@ -110,7 +522,7 @@ where
     ) -> io::Result<Self> {
         let buffer = io::BufWriter::new(buffer);
         let mut slf = Self::new(config, witnesses, constraints, buffer);
-        let len = slf.context.preamble().source_cache_offset();
+        let len = source_cache_offset(slf.context.preamble())?;
 
         let n = slf
             .target
@@ -137,14 +549,36 @@ where
     CI: ExactSizeIterator,
 {
     /// Initialize the encoder, filling the buffer with required bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout of the circuit overflows. Use
+    /// [`Encoder::checked_init_cursor`] to handle it as an error.
     pub fn init_cursor(config: Config, witnesses: WI, constraints: CI) -> Self {
+        Self::checked_init_cursor(config, witnesses, constraints)
+            .expect("the CDF layout overflows")
+    }
+
+    /// Initialize the encoder, filling the buffer with required bytes.
+    ///
+    /// Fails if the layout of the circuit overflows.
+    pub fn checked_init_cursor(
+        config: Config,
+        witnesses: WI,
+        constraints: CI,
+    ) -> io::Result<Self> {
         let preamble =
             Preamble::new(witnesses.len(), constraints.len(), config);
-        let len = preamble.source_cache_offset();
+        let len = source_cache_offset(&preamble)?;
         let bytes = vec![0u8; len];
         let cursor = io::Cursor::new(bytes);
 
-        Self::with_preamble(preamble, witnesses, constraints, cursor)
+        Ok(Self::with_preamble(
+            preamble,
+            witnesses,
+            constraints,
+            cursor,
+        ))
     }
 }
 
@@ -156,35 +590,534 @@ where
     CI: Iterator<Item = C> + ExactSizeIterator,
     T: io::Write + io::Seek,
 {
-    /// Write all witnesses and constraints into the target
+    /// Write all witnesses and constraints into the target.
+    ///
+    /// A circuit over its [`Config::max_constraints`] is reported or rejected
+    /// before anything is written, according to the [`Config::budget`].
     pub fn write_all<P>(&mut self, provider: P) -> io::Result<usize>
     where
         P: EncoderContextProvider,
     {
+        self.write_all_with_progress(provider, |_| ())
+    }
+
+    /// Write all witnesses and constraints into the target, reporting the
+    /// progress after every item and once more after the source cache is
+    /// written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    /// let constraints: Vec<EncodableConstraint> = vec![];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let mut encoder = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// );
+    ///
+    /// let mut last = EncoderProgress::default();
+    /// let n = encoder
+    ///     .write_all_with_progress(provider, |p| last = *p)
+    ///     .expect("failed to encode");
+    ///
+    /// assert_eq!(last.items, last.total);
+    /// assert_eq!(last.bytes, n);
+    /// ```
+    pub fn write_all_with_progress<P, F>(
+        &mut self,
+        provider: P,
+        mut progress: F,
+    ) -> io::Result<usize>
+    where
+        P: EncoderContextProvider,
+        F: FnMut(&EncoderProgress),
+    {
+        self.check_budget()?;
+
         let Self {
             context,
             witnesses,
             constraints,
             target,
+            validate_ids,
+            validate_wires,
+            ..
         } = self;
 
-        let preamble = *context.preamble();
-        let n = preamble.try_to_writer(target.by_ref(), context)?;
+        // ids of the witness table, to check the wires against
+        let mut ids = vec![];
+
+        let preamble = context.preamble().with_finalized(false);
+        let mut report = EncoderProgress {
+            items: 0,
+            total: preamble.witnesses + preamble.constraints,
+            bytes: preamble.try_to_writer(target.by_ref(), context)?,
+        };
+
+        for (position, w) in witnesses.enumerate() {
+            let witness = w.borrow();
+
+            if *validate_ids {
+                check_id("witness", position, witness.id(), witness.source())?;
+            }
+
+            if *validate_wires {
+                ids.push(witness.id());
+            }
+
+            report.bytes +=
+                w.borrow().try_to_writer(target.by_ref(), context)?;
+            report.items += 1;
+
+            progress(&report);
+        }
+
+        for (position, c) in constraints.enumerate() {
+            let constraint = c.borrow();
+
+            if *validate_ids {
+                check_id(
+                    "constraint",
+                    position,
+                    constraint.id(),
+                    constraint.source(),
+                )?;
+            }
+
+            if *validate_wires {
+                check_wires(|w| ids.get(w).copied(), constraint)?;
+            }
+
+            report.bytes +=
+                c.borrow().try_to_writer(target.by_ref(), context)?;
+            report.items += 1;
+
+            progress(&report);
+        }
+
+        report.bytes += context.write_all(target.by_ref(), provider)?;
+
+        target.seek(SeekFrom::Start(0))?;
+        preamble
+            .with_finalized(true)
+            .try_to_writer(target.by_ref(), context)?;
+
+        target.flush()?;
+        self.commit()?;
+
+        progress(&report);
+
+        Ok(report.bytes)
+    }
+}
+
+impl<W, WI, C, CI, T> Encoder<WI, CI, T>
+where
+    W: Borrow<EncodableWitness> + Sync,
+    WI: Iterator<Item = W> + ExactSizeIterator,
+    C: Borrow<EncodableConstraint> + Sync,
+    CI: Iterator<Item = C> + ExactSizeIterator,
+    T: PositionedWrite,
+{
+    /// Write all witnesses and constraints into the target, serializing them
+    /// on `threads` worker threads.
+    ///
+    /// The offsets of the items are known from the preamble, so every batch of
+    /// serialized items is written at its final position with a single
+    /// [`PositionedWrite::write_all_at`]. The output is identical to the one
+    /// of [`Self::write_all`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    /// let constraints: Vec<EncodableConstraint> = vec![];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let mut sequential = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// );
+    ///
+    /// let mut parallel = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// );
+    ///
+    /// let threads = NonZeroUsize::new(4).unwrap();
+    ///
+    /// sequential.write_all(provider.clone()).expect("failed to encode");
+    /// parallel
+    ///     .write_all_parallel(provider, threads)
+    ///     .expect("failed to encode");
+    ///
+    /// assert_eq!(
+    ///     sequential.into_inner().into_inner(),
+    ///     parallel.into_inner().into_inner()
+    /// );
+    /// ```
+    pub fn write_all_parallel<P>(
+        &mut self,
+        provider: P,
+        threads: NonZeroUsize,
+    ) -> io::Result<usize>
+    where
+        P: EncoderContextProvider,
+    {
+        self.check_budget()?;
+
+        let Self {
+            context,
+            witnesses,
+            constraints,
+            target,
+            validate_ids,
+            validate_wires,
+            ..
+        } = self;
+
+        let validate_ids = *validate_ids;
+        let validate_wires = *validate_wires;
+        let mut ids = vec![];
+        let mut invalid = None;
+
+        let preamble = context.preamble().with_finalized(false);
+        let bytes = preamble.to_vec(context);
+
+        target.write_all_at(&bytes, 0)?;
+
+        // the items are written up to the first invalid one
+        let witnesses = witnesses.enumerate().map_while(|(position, w)| {
+            let witness = w.borrow();
+
+            if validate_ids {
+                let (id, source) = (witness.id(), witness.source());
+
+                if let Err(e) = check_id("witness", position, id, source) {
+                    invalid.replace(e);
+                    return None;
+                }
+            }
+
+            if validate_wires {
+                ids.push(witness.id());
+            }
+
+            Some(w)
+        });
+
+        let offset = parallel::write_batches(
+            context,
+            witnesses,
+            EncodableWitness::source,
+            threads,
+            target,
+            bytes.len(),
+        )?;
+
+        if let Some(e) = invalid.take() {
+            return Err(e);
+        }
+
+        let constraints = constraints.enumerate().map_while(|(position, c)| {
+            let constraint = c.borrow();
+            let (id, source) = (constraint.id(), constraint.source());
+
+            let checked = match validate_ids {
+                true => check_id("constraint", position, id, source),
+                false => Ok(()),
+            };
+
+            let checked = checked.and_then(|_| match validate_wires {
+                true => check_wires(&ids, constraint),
+                false => Ok(()),
+            });
+
+            match checked {
+                Ok(()) => Some(c),
+                Err(e) => {
+                    invalid.replace(e);
+                    None
+                }
+            }
+        });
+
+        let offset = parallel::write_batches(
+            context,
+            constraints,
+            EncodableConstraint::source,
+            threads,
+            target,
+            offset,
+        )?;
+
+        if let Some(e) = invalid {
+            return Err(e);
+        }
+
+        let mut cache = Vec::new();
+        let n = context.write_all(&mut cache, provider)?;
+
+        target.write_all_at(&cache, offset as u64)?;
 
-        let n = witnesses.try_fold(n, |n, w| {
-            w.borrow()
-                .try_to_writer(target.by_ref(), context)
-                .map(|x| n + x)
-        })?;
+        let bytes = preamble.with_finalized(true).to_vec(context);
 
-        let n = constraints.try_fold(n, |n, c| {
-            c.borrow()
-                .try_to_writer(target.by_ref(), context)
-                .map(|x| n + x)
-        })?;
+        target.write_all_at(&bytes, 0)?;
+        target.flush()?;
 
-        let n = n + self.context.write_all(target, provider)?;
+        self.commit()?;
 
-        Ok(n)
+        Ok(offset + n)
     }
 }
+
+impl<W, WI, C, CI, T> Encoder<WI, CI, T>
+where
+    W: Borrow<EncodableWitness>,
+    WI: Iterator<Item = W> + ExactSizeIterator,
+    C: Borrow<EncodableConstraint>,
+    CI: Iterator<Item = C> + ExactSizeIterator,
+{
+    /// Compute the exact length of the CDF file without writing anything to
+    /// the target.
+    ///
+    /// The witnesses and constraints are consumed to collect their sources,
+    /// and the contents of the sources are fetched from the provider.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    /// let constraints: Vec<EncodableConstraint> = vec![];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let len = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// )
+    /// .dry_run(provider.clone())
+    /// .expect("failed to compute the length");
+    ///
+    /// let mut encoder = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// );
+    ///
+    /// assert_eq!(encoder.write_all(provider).ok(), Some(len));
+    /// ```
+    pub fn dry_run<P>(self, provider: P) -> io::Result<usize>
+    where
+        P: EncoderContextProvider,
+    {
+        let Self {
+            context,
+            witnesses,
+            constraints,
+            ..
+        } = self;
+
+        Self::len_of(context, witnesses, constraints, provider)
+    }
+
+    /// Estimated length of the CDF file, in bytes, without consuming the
+    /// encoder.
+    ///
+    /// The preamble, witnesses and constraints have a fixed encoded size. The
+    /// source cache is computed from the paths of the witnesses and
+    /// constraints, and the contents fetched from the provider; the estimate
+    /// is exact if the provider returns the same contents when encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witnesses = vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    /// let constraints: Vec<EncodableConstraint> = vec![];
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let mut encoder = Encoder::init_cursor(
+    ///     Config::default(),
+    ///     witnesses.iter(),
+    ///     constraints.iter(),
+    /// );
+    ///
+    /// let len = encoder
+    ///     .estimated_len(provider.clone())
+    ///     .expect("failed to estimate the length");
+    ///
+    /// assert_eq!(encoder.write_all(provider).ok(), Some(len));
+    /// ```
+    pub fn estimated_len<P>(&self, provider: P) -> io::Result<usize>
+    where
+        P: EncoderContextProvider,
+        WI: Clone,
+        CI: Clone,
+    {
+        Self::len_of(
+            self.context.clone(),
+            self.witnesses.clone(),
+            self.constraints.clone(),
+            provider,
+        )
+    }
+
+    /// Length of the CDF file with the sources of the witnesses and
+    /// constraints registered into the context
+    fn len_of<P>(
+        mut context: EncoderContext,
+        witnesses: WI,
+        constraints: CI,
+        provider: P,
+    ) -> io::Result<usize>
+    where
+        P: EncoderContextProvider,
+    {
+        witnesses.for_each(|w| {
+            context.add_path(w.borrow().source().path());
+        });
+
+        constraints.for_each(|c| {
+            context.add_path(c.borrow().source().path());
+        });
+
+        let n = source_cache_offset(context.preamble())?;
+        let cache = context.write_all(io::sink(), provider)?;
+
+        n.checked_add(cache).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the layout of the circuit overflows",
+            )
+        })
+    }
+}
+
+/// Offset of the source cache, failing if the layout of the circuit
+/// overflows
+fn source_cache_offset(preamble: &Preamble) -> io::Result<usize> {
+    preamble.checked_source_cache_offset().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the layout of the circuit overflows",
+        )
+    })
+}
+
+/// Check the id of the item at `position` of its section, failing with its
+/// source location unless the ids of the section are dense and ordered
+fn check_id(
+    item: &str,
+    position: usize,
+    id: usize,
+    source: &EncodableSource,
+) -> io::Result<()> {
+    if id == position {
+        return Ok(());
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "{} #{} at {}:{}:{} is at position {}: the {} ids must be dense \
+             and ordered, expected #{}",
+            item,
+            id,
+            source.path(),
+            source.line(),
+            source.col(),
+            position,
+            item,
+            position
+        ),
+    ))
+}
+
+/// Check the wires of a constraint against the ids of the witness table,
+/// looked up by the index of the wired witness, failing with the first
+/// inconsistent wire
+fn check_wires<F>(id_of: F, constraint: &EncodableConstraint) -> io::Result<()>
+where
+    F: Fn(usize) -> Option<usize>,
+{
+    let wires = constraint.polynomial().witnesses();
+
+    for wire in Wire::ALL {
+        let witness = wire.of(wires);
+
+        let warning = match id_of(witness) {
+            Some(found) if found == witness => continue,
+
+            Some(found) => Warning::WireMismatch {
+                constraint: constraint.id(),
+                wire,
+                witness,
+                found,
+            },
+
+            None => Warning::WireOutOfBounds {
+                constraint: constraint.id(),
+                wire,
+                witness,
+            },
+        };
+
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            warning.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Sync the directory entries of the parent of `path`, so a rename into it is
+/// durable.
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+
+    File::open(parent)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use std::borrow::Borrow;
+use std::fs::File;
+use std::io::{self, SeekFrom};
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::{EncodableElement, EncodableSource, EncoderContext};
+
+/// Number of items serialized by each worker per batch
+pub(crate) const CHUNK_LEN: usize = 1024;
+
+/// Target that accepts writes at arbitrary offsets
+pub trait PositionedWrite {
+    /// Write the entire buffer into the target, starting at `offset`
+    fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()>;
+
+    /// Flush the pending writes into the underlying target
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl PositionedWrite for File {
+    #[cfg(unix)]
+    fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()> {
+        std::os::unix::fs::FileExt::write_all_at(self, buf, offset)
+    }
+
+    #[cfg(not(unix))]
+    fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()> {
+        seek_write(self, buf, offset)
+    }
+}
+
+impl PositionedWrite for io::Cursor<Vec<u8>> {
+    fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()> {
+        seek_write(self, buf, offset)
+    }
+}
+
+impl<B> PositionedWrite for io::BufWriter<B>
+where
+    B: PositionedWrite + io::Write,
+{
+    fn write_all_at(&mut self, buf: &[u8], offset: u64) -> io::Result<()> {
+        io::Write::flush(self)?;
+        self.get_mut().write_all_at(buf, offset)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(self)
+    }
+}
+
+fn seek_write<T>(target: &mut T, buf: &[u8], offset: u64) -> io::Result<()>
+where
+    T: io::Write + io::Seek,
+{
+    target.seek(SeekFrom::Start(offset))?;
+    target.write_all(buf)
+}
+
+/// Write the items into the target starting at `offset`, returning the offset
+/// past the last written item.
+///
+/// The items are collected in batches of `threads * CHUNK_LEN`; each batch is
+/// serialized on worker threads and written with a single positioned write.
+pub(crate) fn write_batches<E, I, It, F, T>(
+    context: &mut EncoderContext,
+    items: It,
+    source: F,
+    threads: NonZeroUsize,
+    target: &mut T,
+    mut offset: usize,
+) -> io::Result<usize>
+where
+    E: EncodableElement,
+    I: Borrow<E> + Sync,
+    It: Iterator<Item = I>,
+    F: Fn(&E) -> &EncodableSource,
+    T: PositionedWrite,
+{
+    let batch = threads.get().saturating_mul(CHUNK_LEN);
+    let mut items = items.peekable();
+
+    while items.peek().is_some() {
+        let batch: Vec<I> = items.by_ref().take(batch).collect();
+        let bytes = encode(context, &batch, &source, threads);
+
+        target.write_all_at(&bytes, offset as u64)?;
+        offset += bytes.len();
+    }
+
+    Ok(offset)
+}
+
+/// Serialize the items into a contiguous buffer, splitting them across the
+/// workers.
+///
+/// The source paths are registered in the order of the items before the
+/// workers start, so the path indexes match the ones of a sequential encoding.
+/// The workers only look the paths up, so their handles of the context share
+/// its path cache instead of copying it.
+fn encode<E, I, F>(
+    context: &mut EncoderContext,
+    items: &[I],
+    source: F,
+    threads: NonZeroUsize,
+) -> Vec<u8>
+where
+    E: EncodableElement,
+    I: Borrow<E> + Sync,
+    F: Fn(&E) -> &EncodableSource,
+{
+    items.iter().for_each(|i| {
+        context.add_path(source(i.borrow()).path());
+    });
+
+    let len = E::len(context.config());
+    let threads = threads.get();
+    let per_worker = ((items.len() + threads - 1) / threads).max(1);
+    let mut bytes = vec![0u8; len * items.len()];
+
+    thread::scope(|s| {
+        let chunks = items.chunks(per_worker);
+        let buffers = bytes.chunks_mut(per_worker * len);
+
+        for (items, mut buf) in chunks.zip(buffers) {
+            let mut ctx = context.clone();
+
+            s.spawn(move || {
+                for i in items {
+                    buf = i.borrow().encode(&mut ctx, buf);
+                }
+            });
+        }
+    });
+
+    bytes
+}
@@ -0,0 +1,117 @@
+use std::io::{self, Cursor};
+use std::vec::IntoIter;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, EncodableConstraint, EncodableWitness, Encoder};
+
+/// Witnesses and constraints recorded by a PLONK composer, in the serde
+/// representation of the encodable items of CDF.
+///
+/// The dump is accepted as JSON or bincode, so the circuits of a composer
+/// built without the recorder integration can still be converted into CDF.
+///
+/// The debug structures of `dusk-plonk` itself aren't mapped, as the crate
+/// doesn't depend on it: the composer side serializes its witnesses and
+/// constraints as [`EncodableWitness`] and [`EncodableConstraint`]. A bridge
+/// from the representation of `dusk-plonk` is left for a later version.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct ComposerDump {
+    /// Witnesses, in the order of their ids
+    pub witnesses: Vec<EncodableWitness>,
+    /// Constraints, in the order of their ids
+    pub constraints: Vec<EncodableConstraint>,
+}
+
+impl ComposerDump {
+    /// Parse a dump, as JSON if it starts with an object and as bincode
+    /// otherwise
+    pub fn from_slice(bytes: &[u8]) -> io::Result<Self> {
+        from_json_or_bincode(bytes)
+    }
+}
+
+/// Deserialize the serde representation of a composer artifact, as JSON if it
+/// starts with an object and as bincode otherwise
+pub(crate) fn from_json_or_bincode<T>(bytes: &[u8]) -> io::Result<T>
+where
+    T: DeserializeOwned,
+{
+    let json = bytes
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        .map_or(false, |b| *b == b'{');
+
+    if json {
+        serde_json::from_slice(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    } else {
+        bincode::deserialize(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Encoder of the witnesses and constraints of a [`ComposerDump`]
+type DumpEncoder = Encoder<
+    IntoIter<EncodableWitness>,
+    IntoIter<EncodableConstraint>,
+    Cursor<Vec<u8>>,
+>;
+
+impl DumpEncoder {
+    /// Initialize an encoder with the debug dump of a PLONK composer, as
+    /// JSON or bincode.
+    ///
+    /// The dump is a [`ComposerDump`], not the debug structures of
+    /// `dusk-plonk`.
+    ///
+    /// Use [`ComposerDump::from_slice`] along with [`Encoder::init_file`] to
+    /// write the file directly.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+    /// use std::collections::HashMap;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let dump = ComposerDump {
+    ///     witnesses: vec![EncodableWitness::new(0, None, Scalar::default(), source.clone())],
+    ///     constraints: vec![EncodableConstraint::new(0, Polynomial::default(), source)],
+    /// };
+    ///
+    /// let json = serde_json::to_vec(&dump).expect("failed to serialize");
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let mut encoder = Encoder::from_plonk_debug(Config::default(), &json)
+    ///     .expect("failed to parse the dump");
+    ///
+    /// encoder.write_all(provider).expect("failed to encode");
+    ///
+    /// let mut cursor = encoder.into_inner();
+    /// cursor.set_position(0);
+    ///
+    /// let circuit = CircuitDescription::from_reader(cursor).expect("failed to decode");
+    ///
+    /// assert_eq!(circuit.preamble().constraints, 1);
+    /// ```
+    pub fn from_plonk_debug(config: Config, dump: &[u8]) -> io::Result<Self> {
+        let ComposerDump {
+            witnesses,
+            constraints,
+        } = ComposerDump::from_slice(dump)?;
+
+        Self::checked_init_cursor(
+            config,
+            witnesses.into_iter(),
+            constraints.into_iter(),
+        )
+    }
+}
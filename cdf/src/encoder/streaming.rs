@@ -0,0 +1,417 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::iter::Empty;
+use std::path::{Path, PathBuf};
+
+use super::{check_id, check_wires, BudgetCallback};
+use crate::{
+    lock, BudgetOverrun, BudgetPolicy, Config, EncodableConstraint,
+    EncodableElement, EncodableWitness, Encoder, EncoderContext,
+    EncoderContextProvider, Preamble,
+};
+
+/// An encoder for CDF format that receives the witnesses and constraints one
+/// at a time, created via [`Encoder::init_streaming`].
+///
+/// The witnesses section follows the preamble, so the witnesses are written
+/// into the target as they are pushed. The constraints are written into a
+/// spill target, and appended to the witnesses by [`Self::finalize`], which
+/// patches the counts of the provisional preamble. Until then, the file is
+/// marked as pending and rejected on open.
+///
+/// The pushed items are validated as in [`Encoder::write_all`]: the ids must
+/// be dense and ordered, and the wires of a constraint must reference the
+/// witnesses pushed before it.
+#[derive(Debug)]
+pub struct StreamingEncoder<T, S> {
+    context: EncoderContext,
+    target: T,
+    spill: io::BufWriter<S>,
+    spill_path: Option<PathBuf>,
+    lock: Option<File>,
+    witnesses: usize,
+    constraints: usize,
+    bytes: usize,
+    finalized: bool,
+    on_budget_overrun: Option<BudgetCallback>,
+    validate_ids: bool,
+    validate_wires: bool,
+}
+
+impl Encoder<Empty<EncodableWitness>, Empty<EncodableConstraint>, ()> {
+    /// Initialize an encoder that receives the witnesses and constraints one
+    /// at a time, without knowing their counts up front.
+    ///
+    /// The constraints are buffered into `spill` until the encoder is
+    /// finalized, so the circuit is never held in memory.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+    /// use std::collections::HashMap;
+    /// use std::io;
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witness = EncodableWitness::new(0, None, Scalar::default(), source.clone());
+    /// let polynomial = Polynomial::builder().build();
+    /// let constraint = EncodableConstraint::new(0, polynomial, source);
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let mut encoder = Encoder::init_streaming(
+    ///     Config::default(),
+    ///     io::Cursor::new(vec![]),
+    ///     io::Cursor::new(vec![]),
+    /// )
+    /// .expect("failed to initialize the encoder");
+    ///
+    /// encoder.push_witness(&witness).expect("failed to push");
+    /// encoder.push_constraint(&constraint).expect("failed to push");
+    /// encoder.finalize(provider).expect("failed to finalize");
+    ///
+    /// let bytes = encoder.into_inner().into_inner();
+    /// let circuit = CircuitDescription::from_reader(io::Cursor::new(bytes))
+    ///     .expect("failed to decode");
+    ///
+    /// assert_eq!(circuit.preamble().witnesses, 1);
+    /// assert_eq!(circuit.preamble().constraints, 1);
+    /// ```
+    pub fn init_streaming<T, S>(
+        config: Config,
+        target: T,
+        spill: S,
+    ) -> io::Result<StreamingEncoder<T, S>>
+    where
+        T: io::Write + io::Seek,
+        S: io::Read + io::Write + io::Seek,
+    {
+        StreamingEncoder::new(config, target, spill, None)
+    }
+
+    /// Initialize a streaming encoder like [`Encoder::init_streaming`],
+    /// writing into a file.
+    ///
+    /// The constraints are spilled into `<path>.constraints`, removed once
+    /// the encoder is finalized. The file is locked against its readers until
+    /// then, as in [`Encoder::init_file`].
+    pub fn init_streaming_file<P>(
+        config: Config,
+        path: P,
+    ) -> io::Result<StreamingEncoder<io::BufWriter<File>, File>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let mut spill_path = path.as_os_str().to_owned();
+        spill_path.push(".constraints");
+
+        let spill_path = PathBuf::from(spill_path);
+
+        // the file and its spill are truncated once locked, under no reader
+        // or concurrent writer
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        lock::exclusive(&file)?;
+        file.set_len(0)?;
+
+        let spill = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&spill_path)?;
+
+        let lock = file.try_clone()?;
+        let target = io::BufWriter::new(file);
+
+        let mut encoder =
+            StreamingEncoder::new(config, target, spill, Some(spill_path))?;
+
+        encoder.lock.replace(lock);
+
+        Ok(encoder)
+    }
+}
+
+impl<T, S> StreamingEncoder<T, S>
+where
+    T: io::Write + io::Seek,
+    S: io::Read + io::Write + io::Seek,
+{
+    fn new(
+        config: Config,
+        mut target: T,
+        spill: S,
+        spill_path: Option<PathBuf>,
+    ) -> io::Result<Self> {
+        let preamble = Preamble::new(0, 0, config).with_finalized(false);
+        let mut context = EncoderContext::from_preamble(preamble);
+
+        target.seek(SeekFrom::Start(0))?;
+
+        let bytes = preamble.try_to_writer(target.by_ref(), &mut context)?;
+
+        Ok(Self {
+            context,
+            target,
+            spill: io::BufWriter::new(spill),
+            spill_path,
+            lock: None,
+            witnesses: 0,
+            constraints: 0,
+            bytes,
+            finalized: false,
+            on_budget_overrun: None,
+            validate_ids: true,
+            validate_wires: true,
+        })
+    }
+
+    /// Set the callback that receives the overrun of the constraint budget,
+    /// called when the encoder is finalized if the [`Config::budget`] is to
+    /// warn.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::io;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use dusk_cdf::*;
+    ///
+    /// let source = EncodableSource::new(1, 0, "lib.rs".into());
+    /// let witness = EncodableWitness::new(0, None, Scalar::default(), source.clone());
+    /// let polynomial = Polynomial::builder().build();
+    ///
+    /// let mut provider = HashMap::new();
+    /// provider.insert("lib.rs".to_string(), "fn main() {}");
+    ///
+    /// let config = Config::builder().max_constraints(Some(2)).build();
+    /// let warnings = Arc::new(Mutex::new(vec![]));
+    /// let w = Arc::clone(&warnings);
+    ///
+    /// let mut encoder = Encoder::init_streaming(
+    ///     config,
+    ///     io::Cursor::new(vec![]),
+    ///     io::Cursor::new(vec![]),
+    /// )
+    /// .expect("failed to initialize the encoder")
+    /// .on_budget_overrun(move |o| w.lock().unwrap().push(*o));
+    ///
+    /// encoder.push_witness(&witness).expect("failed to push");
+    ///
+    /// for id in 0..3 {
+    ///     let constraint = EncodableConstraint::new(id, polynomial, source.clone());
+    ///
+    ///     encoder.push_constraint(&constraint).expect("failed to push");
+    /// }
+    ///
+    /// encoder.finalize(provider).expect("failed to finalize");
+    ///
+    /// assert_eq!(warnings.lock().unwrap()[0].excess(), 1);
+    /// ```
+    pub fn on_budget_overrun<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&BudgetOverrun) + Send + 'static,
+    {
+        self.on_budget_overrun
+            .replace(BudgetCallback(Box::new(callback)));
+        self
+    }
+
+    /// Check the ids of the pushed witnesses and constraints, enabled by
+    /// default, as in [`Encoder::validate_ids`].
+    pub fn validate_ids(mut self, validate: bool) -> Self {
+        self.validate_ids = validate;
+        self
+    }
+
+    /// Check the wires of the pushed constraints against the pushed
+    /// witnesses, enabled by default, as in [`Encoder::validate_wires`].
+    pub fn validate_wires(mut self, validate: bool) -> Self {
+        self.validate_wires = validate;
+        self
+    }
+
+    /// Witnesses pushed so far
+    pub const fn witnesses(&self) -> usize {
+        self.witnesses
+    }
+
+    /// Constraints pushed so far
+    pub const fn constraints(&self) -> usize {
+        self.constraints
+    }
+
+    /// Return the underlying target
+    pub fn into_inner(self) -> T {
+        self.target
+    }
+
+    /// Append a witness into the target
+    pub fn push_witness(
+        &mut self,
+        witness: &EncodableWitness,
+    ) -> io::Result<()> {
+        self.check_pending()?;
+
+        if self.validate_ids {
+            check_id(
+                "witness",
+                self.witnesses,
+                witness.id(),
+                witness.source(),
+            )?;
+        }
+
+        self.bytes +=
+            witness.try_to_writer(self.target.by_ref(), &mut self.context)?;
+        self.witnesses += 1;
+
+        Ok(())
+    }
+
+    /// Append a constraint into the spill target.
+    ///
+    /// Under [`BudgetPolicy::Error`], the constraints over the
+    /// [`Config::max_constraints`] are rejected.
+    pub fn push_constraint(
+        &mut self,
+        constraint: &EncodableConstraint,
+    ) -> io::Result<()> {
+        self.check_pending()?;
+
+        let config = self.context.config();
+
+        if let (Some(max_constraints), BudgetPolicy::Error) =
+            (config.max_constraints, config.budget)
+        {
+            if self.constraints >= max_constraints {
+                let overrun = BudgetOverrun {
+                    constraints: self.constraints + 1,
+                    max_constraints,
+                };
+
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    overrun.to_string(),
+                ));
+            }
+        }
+
+        if self.validate_ids {
+            check_id(
+                "constraint",
+                self.constraints,
+                constraint.id(),
+                constraint.source(),
+            )?;
+        }
+
+        // the witness ids are dense, so the table index of a witness is its id
+        if self.validate_wires {
+            let witnesses = self.witnesses;
+
+            check_wires(|w| (w < witnesses).then_some(w), constraint)?;
+        }
+
+        self.bytes +=
+            constraint.try_to_writer(self.spill.by_ref(), &mut self.context)?;
+        self.constraints += 1;
+
+        Ok(())
+    }
+
+    /// Append the spilled constraints and the source cache into the target,
+    /// and patch the preamble with the pushed counts, returning the length of
+    /// the file.
+    ///
+    /// Under [`BudgetPolicy::Warn`], the overrun of the
+    /// [`Config::max_constraints`] is reported to the callback set via
+    /// [`Self::on_budget_overrun`] before the file is completed.
+    pub fn finalize<P>(&mut self, provider: P) -> io::Result<usize>
+    where
+        P: EncoderContextProvider,
+    {
+        self.check_pending()?;
+        self.report_budget_overrun();
+
+        let Self {
+            context,
+            target,
+            spill,
+            ..
+        } = self;
+
+        spill.flush()?;
+
+        let spill = spill.get_mut();
+
+        spill.seek(SeekFrom::Start(0))?;
+        io::copy(spill, target.by_ref())?;
+
+        self.bytes += context.write_all(target.by_ref(), provider)?;
+
+        let preamble =
+            Preamble::new(self.witnesses, self.constraints, *context.config());
+
+        target.seek(SeekFrom::Start(0))?;
+        preamble.try_to_writer(target.by_ref(), context)?;
+        target.flush()?;
+
+        if let Some(path) = self.spill_path.take() {
+            fs::remove_file(path)?;
+        }
+
+        if let Some(file) = self.lock.take() {
+            file.unlock()?;
+        }
+
+        self.finalized = true;
+
+        Ok(self.bytes)
+    }
+
+    /// Report the overrun of the constraint budget to the callback, if the
+    /// [`Config::budget`] is to warn. The overruns under
+    /// [`BudgetPolicy::Error`] are rejected as the constraints are pushed.
+    fn report_budget_overrun(&mut self) {
+        let config = self.context.config();
+
+        let max_constraints = match (config.max_constraints, config.budget) {
+            (Some(max), BudgetPolicy::Warn) if self.constraints > max => max,
+            _ => return,
+        };
+
+        let overrun = BudgetOverrun {
+            constraints: self.constraints,
+            max_constraints,
+        };
+
+        if let Some(BudgetCallback(callback)) = self.on_budget_overrun.as_mut()
+        {
+            callback(&overrun);
+        }
+    }
+
+    fn check_pending(&self) -> io::Result<()> {
+        if self.finalized {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "the streaming encoder was already finalized",
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::iter;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::{io, iter};
 
 use crate::*;
 use quickcheck::{quickcheck, Arbitrary, Gen, TestResult};
@@ -220,12 +222,48 @@ fn prop(
         constraints.clone().into_iter(),
     );
 
-    if let Err(e) = encoder.write_all(disk) {
-        return TestResult::error(e.to_string());
+    let estimated = match encoder.estimated_len(disk.clone()) {
+        Ok(n) => n,
+        Err(e) => return TestResult::error(e.to_string()),
+    };
+
+    let dry_run = Encoder::init_cursor(
+        config,
+        witnesses.clone().into_iter(),
+        constraints.clone().into_iter(),
+    )
+    .dry_run(disk.clone());
+
+    let mut parallel = Encoder::init_cursor(
+        config,
+        witnesses.clone().into_iter(),
+        constraints.clone().into_iter(),
+    );
+
+    let threads = NonZeroUsize::new(3).expect("non-zero threads");
+    let parallel = parallel
+        .write_all_parallel(disk.clone(), threads)
+        .map(|_| parallel.into_inner().into_inner());
+
+    let len = match encoder.write_all(disk) {
+        Ok(n) => n,
+        Err(e) => return TestResult::error(e.to_string()),
+    };
+
+    match dry_run {
+        Ok(n) if n == len && estimated == len => (),
+        Ok(_) => return TestResult::error("unexpected dry run length"),
+        Err(e) => return TestResult::error(e.to_string()),
     }
 
     let cursor = encoder.into_inner();
 
+    match parallel {
+        Ok(bytes) if &bytes == cursor.get_ref() => (),
+        Ok(_) => return TestResult::error("unexpected parallel encoding"),
+        Err(e) => return TestResult::error(e.to_string()),
+    }
+
     let mut circuit = match CircuitDescription::from_reader(cursor) {
         Ok(c) => c,
         Err(e) => return TestResult::error(e.to_string()),
@@ -259,6 +297,7 @@ fn prop(
             col,
             name: &name,
             contents,
+            id: w.source_id(),
         };
 
         let value = config
@@ -311,6 +350,7 @@ fn prop(
             col,
             name: &name,
             contents,
+            id: c.source_id(),
         };
 
         let constraint = Constraint::_new(constraint.id(), polynomial, source);
@@ -327,3 +367,169 @@ fn prop(
 fn encode_decode_works() {
     quickcheck(prop as fn(_, _, _, _) -> _);
 }
+
+#[test]
+fn streaming_encoder_matches_write_all() -> io::Result<()> {
+    let source = EncodableSource::new(2, 4, "lib.rs".into());
+    let witnesses: Vec<_> = (0..5)
+        .map(|i| {
+            let value = Scalar::default();
+
+            EncodableWitness::new(i, Some(i / 2), value, source.clone())
+        })
+        .collect();
+    let constraints: Vec<_> = (0..3)
+        .map(|i| {
+            let polynomial = Polynomial::builder().a(i).o(i + 1).build();
+
+            EncodableConstraint::new(i, polynomial, source.clone())
+        })
+        .collect();
+
+    let mut provider = HashMap::new();
+    provider.insert("lib.rs".to_string(), "fn main() {\n    a + b\n}\n");
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+    );
+
+    let len = encoder.write_all(provider.clone())?;
+    let expected = encoder.into_inner().into_inner();
+
+    let mut streaming = Encoder::init_streaming(
+        Config::default(),
+        io::Cursor::new(vec![]),
+        io::Cursor::new(vec![]),
+    )?;
+
+    // the composer interleaves the witnesses and the constraints, pushing a
+    // constraint once its wired witnesses are pushed
+    for (i, w) in witnesses.iter().enumerate() {
+        streaming.push_witness(w)?;
+
+        if let Some(c) = i.checked_sub(1).and_then(|i| constraints.get(i)) {
+            streaming.push_constraint(c)?;
+        }
+    }
+
+    assert_eq!(streaming.finalize(provider.clone())?, len);
+    streaming
+        .push_witness(&witnesses[0])
+        .expect_err("the encoder was finalized");
+
+    assert_eq!(streaming.into_inner().into_inner(), expected);
+
+    let config = Config::builder()
+        .max_constraints(Some(2))
+        .budget(BudgetPolicy::Error)
+        .build();
+
+    let mut streaming = Encoder::init_streaming(
+        config,
+        io::Cursor::new(vec![]),
+        io::Cursor::new(vec![]),
+    )?;
+
+    for w in &witnesses[..3] {
+        streaming.push_witness(w)?;
+    }
+
+    streaming.push_constraint(&constraints[0])?;
+    streaming.push_constraint(&constraints[1])?;
+    streaming
+        .push_constraint(&constraints[2])
+        .expect_err("the constraint is over budget");
+
+    Ok(())
+}
+
+#[test]
+fn streaming_encoder_validates_the_pushed_items() -> io::Result<()> {
+    let source = EncodableSource::new(2, 4, "lib.rs".into());
+    let witness =
+        |id| EncodableWitness::new(id, None, Scalar::default(), source.clone());
+    let constraint = |id, o| {
+        let polynomial = Polynomial::builder().o(o).build();
+
+        EncodableConstraint::new(id, polynomial, source.clone())
+    };
+
+    let mut provider = HashMap::new();
+    provider.insert("lib.rs".to_string(), "fn main() {}\n");
+
+    let config = Config::builder().max_constraints(Some(1)).build();
+    let overruns = Arc::new(Mutex::new(vec![]));
+    let o = Arc::clone(&overruns);
+
+    let mut streaming = Encoder::init_streaming(
+        config,
+        io::Cursor::new(vec![]),
+        io::Cursor::new(vec![]),
+    )?
+    .on_budget_overrun(move |overrun| o.lock().unwrap().push(*overrun));
+
+    let err = streaming
+        .push_witness(&witness(1))
+        .expect_err("the witness ids skip #0");
+
+    assert!(err.to_string().contains("lib.rs:2:4"), "{}", err);
+
+    streaming.push_witness(&witness(0))?;
+    streaming
+        .push_constraint(&constraint(0, 1))
+        .expect_err("the wire references a witness not pushed yet");
+    streaming
+        .push_constraint(&constraint(1, 0))
+        .expect_err("the constraint ids skip #0");
+
+    streaming.push_constraint(&constraint(0, 0))?;
+    streaming.push_constraint(&constraint(1, 0))?;
+
+    assert!(overruns.lock().unwrap().is_empty());
+
+    streaming.finalize(provider)?;
+
+    assert_eq!(
+        overruns.lock().unwrap().as_slice(),
+        &[BudgetOverrun {
+            constraints: 2,
+            max_constraints: 1,
+        }]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "plonk")]
+fn composer_dumps_are_parsed_as_json_or_bincode() -> io::Result<()> {
+    let source = EncodableSource::new(3, 1, "gadget.rs".into());
+    let dump = ComposerDump {
+        witnesses: vec![EncodableWitness::new(
+            0,
+            Some(0),
+            Scalar::default(),
+            source.clone(),
+        )],
+        constraints: vec![EncodableConstraint::new(
+            0,
+            Polynomial::builder().evaluation(true).build(),
+            source,
+        )],
+    };
+
+    let mut json = b"\n  ".to_vec();
+    json.extend(serde_json::to_vec(&dump)?);
+
+    let bin = bincode::serialize(&dump)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    assert_eq!(ComposerDump::from_slice(&json)?, dump);
+    assert_eq!(ComposerDump::from_slice(&bin)?, dump);
+    ComposerDump::from_slice(b"{ \"witnesses\": 1 }")
+        .expect_err("the witnesses aren't a list");
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+//! Distribution of the witness values of a circuit, revealing the
+//! placeholders that were never assigned
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CircuitDescription, Constant, Polynomial, Scalar, Selector, Warning, Wire,
+};
+
+/// Value shared by several witnesses
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct RepeatedValue {
+    /// Shared value
+    pub value: Scalar,
+    /// Number of witnesses holding the value
+    pub witnesses: usize,
+    /// Well-known constant equal to the value, if any
+    pub constant: Option<Constant>,
+}
+
+/// Distribution of the witness values of a circuit.
+///
+/// A circuit of meaningful values has an entropy close to its maximum, the
+/// base 2 logarithm of the number of witnesses. A low entropy, or a value
+/// held by a large share of the witnesses, hints at witnesses left with a
+/// placeholder.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WitnessEntropy {
+    /// Number of witnesses
+    pub witnesses: usize,
+    /// Number of distinct values
+    pub distinct: usize,
+    /// Shannon entropy of the values, in bits
+    pub bits: f64,
+    /// Values held by more than one witness, ordered by their number of
+    /// witnesses
+    pub repeated: Vec<RepeatedValue>,
+}
+
+impl WitnessEntropy {
+    /// Entropy of a circuit whose witnesses all hold distinct values
+    pub fn max_bits(&self) -> f64 {
+        (self.witnesses.max(1) as f64).log2()
+    }
+
+    /// Keep only the first `limit` repeated values
+    pub fn truncate(&mut self, limit: usize) {
+        self.repeated.truncate(limit);
+    }
+}
+
+pub(crate) fn entropy<S>(
+    cdf: &mut CircuitDescription<S>,
+) -> io::Result<WitnessEntropy>
+where
+    S: io::Read + io::Seek,
+{
+    let witnesses = cdf.preamble().witnesses;
+    let mut counts: HashMap<Scalar, usize> = HashMap::new();
+
+    for idx in 0..witnesses {
+        *counts.entry(*cdf.fetch_witness(idx)?.value()).or_default() += 1;
+    }
+
+    let total = witnesses as f64;
+    let bits = counts
+        .values()
+        .map(|&c| c as f64 / total)
+        .map(|p| -p * p.log2())
+        .sum::<f64>();
+
+    let mut repeated: Vec<_> = counts
+        .iter()
+        .filter(|(_, c)| **c > 1)
+        .map(|(value, &witnesses)| RepeatedValue {
+            value: *value,
+            witnesses,
+            constant: Constant::of(value),
+        })
+        .collect();
+
+    repeated.sort_by(|a, b| {
+        b.witnesses.cmp(&a.witnesses).then(a.value.cmp(&b.value))
+    });
+
+    Ok(WitnessEntropy {
+        witnesses,
+        distinct: counts.len(),
+        bits,
+        repeated,
+    })
+}
+
+/// Wire fixed to a constant by the polynomial, as `ql·a + qc = 0` does
+fn fixed_wire(polynomial: &Polynomial) -> Option<Wire> {
+    const LINEAR: [(Selector, Wire); 4] = [
+        (Selector::Ql, Wire::A),
+        (Selector::Qr, Wire::B),
+        (Selector::Qd, Wire::D),
+        (Selector::Qo, Wire::O),
+    ];
+
+    let selectors = polynomial.selectors();
+    let mut nonzero = Selector::ALL.iter().filter(|s| {
+        !matches!(s, Selector::Qc | Selector::Pi | Selector::Qarith)
+            && !s.of(selectors).is_zero()
+    });
+
+    match (nonzero.next(), nonzero.next()) {
+        (Some(s), None) => LINEAR
+            .iter()
+            .find_map(|(linear, wire)| (linear == s).then_some(*wire)),
+        _ => None,
+    }
+}
+
+pub(crate) fn constcheck<S>(
+    cdf: &mut CircuitDescription<S>,
+) -> io::Result<Vec<Warning>>
+where
+    S: io::Read + io::Seek,
+{
+    let mut fixed = HashSet::new();
+
+    for idx in 0..cdf.preamble().constraints {
+        let polynomial = *cdf.fetch_constraint(idx)?.polynomial();
+
+        if let Some(wire) = fixed_wire(&polynomial) {
+            fixed.insert(wire.of(polynomial.witnesses()));
+        }
+    }
+
+    let mut warnings = vec![];
+
+    for idx in 0..cdf.preamble().witnesses {
+        if fixed.contains(&idx) {
+            continue;
+        }
+
+        if let Some(constant) = Constant::of(cdf.fetch_witness(idx)?.value()) {
+            warnings.push(Warning::UnfixedConstant {
+                witness: idx,
+                constant,
+            });
+        }
+    }
+
+    Ok(warnings)
+}
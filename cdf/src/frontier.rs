@@ -0,0 +1,154 @@
+//! Backward tracing of the wires of a failed constraint to the earliest
+//! failure they depend on
+
+use std::collections::HashSet;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CircuitDescription, Wire, WiredWitnesses};
+
+/// Earliest failed constraint reached from a wire of a failed constraint
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct FrontierEntry {
+    /// Wire of the traced constraint
+    pub wire: Wire,
+    /// Witness wired to the traced constraint
+    pub witness: usize,
+    /// Earliest failed constraint the witness depends on. It is the traced
+    /// constraint itself if no preceding failure is reached.
+    pub constraint: usize,
+}
+
+impl FrontierEntry {
+    /// Check if the wire reaches a failure that precedes the traced
+    /// constraint
+    pub const fn is_upstream(&self, traced: usize) -> bool {
+        self.constraint < traced
+    }
+}
+
+/// Wires of a failed constraint traced backward to the first constraint where
+/// their values went wrong.
+///
+/// A witness is followed through the preceding constraints that wire it and
+/// whose evaluation, as recorded by the arithmetic backend of the prover,
+/// failed; the witnesses wired to these constraints are followed in turn. The
+/// earliest failed constraint reached from each wire is the frontier of the
+/// failure, and the usual place to start debugging it instead of the failure
+/// that surfaced.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct Frontier {
+    constraint: usize,
+    entries: Vec<FrontierEntry>,
+}
+
+impl Frontier {
+    /// Id of the traced constraint
+    pub const fn constraint(&self) -> usize {
+        self.constraint
+    }
+
+    /// Entries of the frontier, one per wire. Empty if the traced constraint
+    /// didn't fail.
+    pub fn entries(&self) -> &[FrontierEntry] {
+        &self.entries
+    }
+
+    /// Earliest failed constraint reached from any wire, if the traced
+    /// constraint failed
+    pub fn earliest(&self) -> Option<usize> {
+        self.entries.iter().map(|e| e.constraint).min()
+    }
+}
+
+/// Trace the wires of an indexed constraint
+pub(crate) fn trace<S>(
+    cdf: &mut CircuitDescription<S>,
+    idx: usize,
+) -> io::Result<Frontier>
+where
+    S: io::Read + io::Seek,
+{
+    let traced = cdf.fetch_constraint(idx)?;
+    let wires = traced.polynomial().witnesses;
+
+    let mut frontier = Frontier {
+        constraint: idx,
+        entries: vec![],
+    };
+
+    if traced.polynomial().is_ok() {
+        return Ok(frontier);
+    }
+
+    // wires of the preceding failures, and the failures wiring each witness
+    let mut failures: Vec<Option<WiredWitnesses>> = vec![None; idx];
+    let mut wired = vec![vec![]; cdf.preamble().witnesses];
+
+    for k in 0..idx {
+        let constraint = cdf.fetch_constraint(k)?;
+
+        if constraint.polynomial().is_ok() {
+            continue;
+        }
+
+        let w = constraint.polynomial().witnesses;
+
+        for wire in Wire::ALL {
+            // out of bounds wires are reported by the wirecheck
+            if let Some(constraints) = wired.get_mut(wire.of(&w)) {
+                if constraints.last() != Some(&k) {
+                    constraints.push(k);
+                }
+            }
+        }
+
+        failures[k] = Some(w);
+    }
+
+    for wire in Wire::ALL {
+        let witness = wire.of(&wires);
+
+        let mut earliest = idx;
+        let mut stack = vec![witness];
+        let mut seen = HashSet::from([witness]);
+
+        while let Some(w) = stack.pop() {
+            let constraints =
+                wired.get(w).map(Vec::as_slice).unwrap_or_default();
+
+            for k in constraints {
+                earliest = earliest.min(*k);
+
+                let upstream =
+                    failures[*k].as_ref().into_iter().flat_map(|w| {
+                        Wire::ALL.into_iter().map(move |wire| wire.of(w))
+                    });
+
+                stack.extend(upstream.filter(|w| seen.insert(*w)));
+            }
+        }
+
+        frontier.entries.push(FrontierEntry {
+            wire,
+            witness,
+            constraint: earliest,
+        });
+    }
+
+    Ok(frontier)
+}
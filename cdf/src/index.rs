@@ -0,0 +1,381 @@
+//! Sidecar index of a CDF file, to skip the full scans of repeated sessions
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::memory::HeapSize;
+use crate::{CircuitDescription, SourceCounts};
+
+/// Leading bytes of an index file
+const MAGIC: &[u8; 4] = b"CDFI";
+
+/// Version of the index layout; an index of another version is rejected
+const VERSION: u8 = 1;
+
+/// Size of the chunks hashed to compute the digest of a CDF file
+const DIGEST_CHUNK: usize = 1 << 16;
+
+/// Results of the full scans of a CDF file, bound to its contents by digest.
+///
+/// The index holds the first constraint of every source line, the
+/// constraints wiring each witness, the failed constraints and the counts of
+/// every source. It is stored next to the CDF file with the
+/// [`CircuitIndex::EXTENSION`] extension, and
+/// [`CircuitDescription::open`] uses it when its digest matches the file.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use dusk_cdf::{CircuitDescription, CircuitIndex};
+///
+/// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+/// let index = circuit.build_index()?.clone();
+///
+/// let mut bytes = vec![];
+/// index.write(&mut bytes)?;
+///
+/// assert_eq!(CircuitIndex::read(bytes.as_slice())?, index);
+/// assert!(index.failures().is_empty());
+///
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CircuitIndex {
+    digest: [u8; 32],
+    lines: Vec<(String, u64, usize)>,
+    wired: Vec<Vec<usize>>,
+    failures: Vec<usize>,
+    counts: Vec<SourceCounts>,
+}
+
+impl CircuitIndex {
+    /// Extension of the index files
+    pub const EXTENSION: &'static str = "cdfi";
+
+    /// Scan the constraints and witnesses of a circuit to build its index.
+    ///
+    /// The corrupt records are left out of the index.
+    pub fn build<S>(cdf: &mut CircuitDescription<S>) -> io::Result<Self>
+    where
+        S: io::Read + io::Seek,
+    {
+        let (_, source) = cdf.context();
+        let digest = digest(source)?;
+
+        let mut counts: Vec<_> = cdf
+            .source_names()
+            .map(|name| SourceCounts {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        let sources: HashMap<String, usize> = counts
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.name.clone(), i))
+            .collect();
+
+        let mut wired = vec![vec![]; cdf.preamble().witnesses];
+
+        for idx in 0..cdf.preamble().witnesses {
+            let witness = match skip_corrupt(cdf.fetch_witness(idx))? {
+                Some(w) => w,
+                None => continue,
+            };
+
+            if let Some(i) = sources.get(witness.name()) {
+                counts[*i].witnesses += 1;
+            }
+        }
+
+        let mut lines = vec![];
+        let mut failures = vec![];
+        let mut seen = HashMap::new();
+
+        for idx in 0..cdf.preamble().constraints {
+            let constraint = match skip_corrupt(cdf.fetch_constraint(idx))? {
+                Some(c) => c,
+                None => continue,
+            };
+
+            let name = constraint.name();
+            let line = constraint.line();
+
+            if let Some(i) = sources.get(name) {
+                counts[*i].constraints += 1;
+
+                seen.entry((*i, line)).or_insert_with(|| {
+                    lines.push((name.to_string(), line, idx));
+                });
+            }
+
+            if !constraint.polynomial().is_ok() {
+                failures.push(idx);
+            }
+
+            let wires = constraint.polynomial().witnesses;
+
+            for w in [wires.a, wires.b, wires.d, wires.o] {
+                if let Some(constraints) = wired.get_mut(w) {
+                    if constraints.last() != Some(&idx) {
+                        constraints.push(idx);
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            digest,
+            lines,
+            wired,
+            failures,
+            counts,
+        })
+    }
+
+    /// Path of the index file of a CDF file
+    pub fn sidecar<P>(path: P) -> PathBuf
+    where
+        P: AsRef<Path>,
+    {
+        path.as_ref().with_extension(Self::EXTENSION)
+    }
+
+    /// SHA-256 digest of the indexed CDF file
+    pub const fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+
+    /// First constraint of every source line, as source name, line and
+    /// constraint id
+    pub fn lines(&self) -> impl Iterator<Item = (&str, u64, usize)> {
+        self.lines
+            .iter()
+            .map(|(name, line, idx)| (name.as_str(), *line, *idx))
+    }
+
+    /// Constraints wiring the witness, in ascending order
+    pub fn wired(&self, witness: usize) -> &[usize] {
+        self.wired
+            .get(witness)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Constraints whose polynomial doesn't evaluate correctly, in ascending
+    /// order
+    pub fn failures(&self) -> &[usize] {
+        &self.failures
+    }
+
+    /// Number of constraints and witnesses of each source, as in
+    /// [`CircuitDescription::source_counts`]
+    pub fn source_counts(&self) -> &[SourceCounts] {
+        &self.counts
+    }
+
+    /// Write the index, as read by [`CircuitIndex::read`]
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&self.digest)?;
+
+        write_len(&mut writer, self.lines.len())?;
+        for (name, line, idx) in &self.lines {
+            write_str(&mut writer, name)?;
+            write_u64(&mut writer, *line)?;
+            write_len(&mut writer, *idx)?;
+        }
+
+        write_len(&mut writer, self.wired.len())?;
+        for constraints in &self.wired {
+            write_ids(&mut writer, constraints)?;
+        }
+
+        write_ids(&mut writer, &self.failures)?;
+
+        write_len(&mut writer, self.counts.len())?;
+        for counts in &self.counts {
+            write_str(&mut writer, &counts.name)?;
+            write_len(&mut writer, counts.constraints)?;
+            write_len(&mut writer, counts.witnesses)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Read an index written by [`CircuitIndex::write`]
+    pub fn read<R>(mut reader: R) -> io::Result<Self>
+    where
+        R: io::Read,
+    {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC || header[4] != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the file isn't a CDF index of a supported version",
+            ));
+        }
+
+        let mut digest = [0u8; 32];
+        reader.read_exact(&mut digest)?;
+
+        let lines = (0..read_len(&mut reader)?)
+            .map(|_| {
+                let name = read_str(&mut reader)?;
+                let line = read_u64(&mut reader)?;
+                let idx = read_len(&mut reader)?;
+
+                Ok((name, line, idx))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let wired = (0..read_len(&mut reader)?)
+            .map(|_| read_ids(&mut reader))
+            .collect::<io::Result<_>>()?;
+
+        let failures = read_ids(&mut reader)?;
+
+        let counts = (0..read_len(&mut reader)?)
+            .map(|_| {
+                Ok(SourceCounts {
+                    name: read_str(&mut reader)?,
+                    constraints: read_len(&mut reader)?,
+                    witnesses: read_len(&mut reader)?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            digest,
+            lines,
+            wired,
+            failures,
+            counts,
+        })
+    }
+}
+
+impl HeapSize for CircuitIndex {
+    fn heap_size(&self) -> usize {
+        self.lines.heap_size()
+            + self.wired.heap_size()
+            + self.failures.heap_size()
+            + self.counts.heap_size()
+    }
+}
+
+/// SHA-256 digest of the whole source, read from its start
+pub(crate) fn digest<S>(source: &mut S) -> io::Result<[u8; 32]>
+where
+    S: io::Read + io::Seek,
+{
+    source.seek(io::SeekFrom::Start(0))?;
+
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; DIGEST_CHUNK];
+
+    loop {
+        match source.read(&mut buf)? {
+            0 => break,
+            n => hasher.update(&buf[..n]),
+        }
+    }
+
+    Ok(hasher.finalize().into())
+}
+
+/// Discard the records that can't be decoded
+fn skip_corrupt<T>(record: io::Result<T>) -> io::Result<Option<T>> {
+    match record {
+        Ok(r) => Ok(Some(r)),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn write_u64<W>(writer: &mut W, n: u64) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writer.write_all(&n.to_le_bytes())
+}
+
+fn write_len<W>(writer: &mut W, len: usize) -> io::Result<()>
+where
+    W: io::Write,
+{
+    write_u64(writer, len as u64)
+}
+
+fn write_str<W>(writer: &mut W, s: &str) -> io::Result<()>
+where
+    W: io::Write,
+{
+    write_len(writer, s.len())?;
+    writer.write_all(s.as_bytes())
+}
+
+fn write_ids<W>(writer: &mut W, ids: &[usize]) -> io::Result<()>
+where
+    W: io::Write,
+{
+    write_len(writer, ids.len())?;
+    ids.iter().try_for_each(|id| write_len(writer, *id))
+}
+
+fn read_u64<R>(reader: &mut R) -> io::Result<u64>
+where
+    R: io::Read,
+{
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_len<R>(reader: &mut R) -> io::Result<usize>
+where
+    R: io::Read,
+{
+    usize::try_from(read_u64(reader)?).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the index declares a length that overflows",
+        )
+    })
+}
+
+fn read_str<R>(reader: &mut R) -> io::Result<String>
+where
+    R: io::Read,
+{
+    let len = read_len(reader)?;
+
+    let mut bytes = vec![];
+    reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+
+    if bytes.len() < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    String::from_utf8(bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn read_ids<R>(reader: &mut R) -> io::Result<Vec<usize>>
+where
+    R: io::Read,
+{
+    (0..read_len(reader)?).map(|_| read_len(reader)).collect()
+}
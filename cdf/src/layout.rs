@@ -0,0 +1,325 @@
+//! Byte layout of a CDF file under its configuration
+
+use std::fmt;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Config, Constraint, Element, Preamble, Scalar, Selector, Style, Table,
+    Witness,
+};
+
+/// Section of a CDF file
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    /// Counts and configuration of the file
+    Preamble,
+    /// Fixed length witnesses
+    Witnesses,
+    /// Fixed length constraints
+    Constraints,
+    /// Names and contents of the sources, up to the end of the file
+    SourceCache,
+}
+
+impl fmt::Display for SectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Preamble => f.write_str("preamble"),
+            Self::Witnesses => f.write_str("witnesses"),
+            Self::Constraints => f.write_str("constraints"),
+            Self::SourceCache => f.write_str("source cache"),
+        }
+    }
+}
+
+/// Position of a section in a CDF file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Section {
+    /// Kind of the section
+    pub kind: SectionKind,
+    /// Byte offset of the section
+    pub offset: usize,
+    /// Length of the section, in bytes
+    pub len: usize,
+    /// Number of elements of the section, if they have a fixed length
+    pub count: Option<usize>,
+    /// Length of an element of the section, in bytes, if fixed
+    pub element_len: Option<usize>,
+}
+
+/// Position of a field in an encoded element
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Field {
+    /// Name of the field
+    pub name: String,
+    /// Byte offset of the field, relative to the start of the element
+    pub offset: usize,
+    /// Length of the field, in bytes
+    pub len: usize,
+}
+
+/// Layout of a CDF file, as described by its preamble.
+///
+/// The offsets and lengths are computed under the [`Config`] of the file,
+/// so the offset math of a tool reading the file can be checked against
+/// them.
+///
+/// # Example
+///
+#[cfg_attr(feature = "decoder", doc = "```")]
+#[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+/// # fn main() -> std::io::Result<()> {
+/// use dusk_cdf::{CircuitDescription, SectionKind};
+///
+/// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+/// let preamble = *circuit.preamble();
+/// let layout = circuit.layout()?;
+///
+/// let constraints = layout.section(SectionKind::Constraints).unwrap();
+///
+/// assert_eq!(constraints.count, Some(preamble.constraints));
+/// assert_eq!(Some(constraints.offset), preamble.constraint_offset(0));
+///
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Layout {
+    /// Configuration the file is encoded with
+    pub config: Config,
+    /// Sections of the file, in the order they are stored
+    pub sections: Vec<Section>,
+    /// Fields of an encoded witness, in the order they are stored
+    pub witness: Vec<Field>,
+    /// Fields of an encoded constraint, in the order they are stored
+    pub constraint: Vec<Field>,
+}
+
+impl Layout {
+    /// Compute the layout of a file of `len` bytes, described by `preamble`
+    pub(crate) fn new(preamble: &Preamble, len: usize) -> io::Result<Self> {
+        let config = preamble.config;
+
+        let overflow = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the layout of the CDF file overflows",
+            )
+        };
+
+        let witnesses_size = preamble.witnesses_size().ok_or_else(overflow)?;
+        let constraints_offset =
+            preamble.constraints_offset().ok_or_else(overflow)?;
+        let constraints_size =
+            preamble.constraints_size().ok_or_else(overflow)?;
+        let source_cache_offset = preamble
+            .checked_source_cache_offset()
+            .ok_or_else(overflow)?;
+
+        let sections = vec![
+            Section {
+                kind: SectionKind::Preamble,
+                offset: 0,
+                len: preamble.witnesses_offset(),
+                count: None,
+                element_len: None,
+            },
+            Section {
+                kind: SectionKind::Witnesses,
+                offset: preamble.witnesses_offset(),
+                len: witnesses_size,
+                count: Some(preamble.witnesses),
+                element_len: Some(Witness::len(&config)),
+            },
+            Section {
+                kind: SectionKind::Constraints,
+                offset: constraints_offset,
+                len: constraints_size,
+                count: Some(preamble.constraints),
+                element_len: Some(Constraint::len(&config)),
+            },
+            Section {
+                kind: SectionKind::SourceCache,
+                offset: source_cache_offset,
+                len: len.saturating_sub(source_cache_offset),
+                count: None,
+                element_len: None,
+            },
+        ];
+
+        // the source of witnesses and constraints alike
+        let source = [
+            ("source.line", u64::len(&config)),
+            ("source.col", u64::len(&config)),
+            ("source.index", usize::len(&config)),
+        ];
+
+        let witness = fields(
+            [
+                ("id", usize::len(&config)),
+                ("constraint", <Option<usize>>::len(&config)),
+                ("value", Scalar::len(&config)),
+            ]
+            .into_iter()
+            .chain(source),
+        );
+
+        let selectors = Selector::ALL
+            .iter()
+            .map(|s| (s.name(), Scalar::len(&config)));
+        let wires = ["wire.a", "wire.b", "wire.d", "wire.o"]
+            .into_iter()
+            .map(|name| (name, usize::len(&config)));
+
+        let constraint = fields(
+            [("id", usize::len(&config))]
+                .into_iter()
+                .chain(selectors)
+                .chain(wires)
+                .chain([("evaluation", bool::len(&config))])
+                .chain(source),
+        );
+
+        Ok(Self {
+            config,
+            sections,
+            witness,
+            constraint,
+        })
+    }
+
+    /// Section of the provided kind
+    pub fn section(&self, kind: SectionKind) -> Option<&Section> {
+        self.sections.iter().find(|s| s.kind == kind)
+    }
+
+    /// Tables of the sections, of the fields of a witness, and of the fields
+    /// of a constraint, rendered with the table style of `style`
+    pub fn tables(&self, style: &Style) -> [Table; 3] {
+        let optional = |n: Option<usize>| match n {
+            Some(n) => style.number(n).to_string(),
+            None => "-".into(),
+        };
+
+        let mut sections =
+            style.table(["section", "offset", "length", "count", "element"]);
+
+        for section in &self.sections {
+            sections.row([
+                section.kind.to_string(),
+                style.number(section.offset).to_string(),
+                style.number(section.len).to_string(),
+                optional(section.count),
+                optional(section.element_len),
+            ]);
+        }
+
+        let fields = |element: &str, fields: &[Field]| {
+            let mut table = style.table([element, "offset", "length"]);
+
+            for field in fields {
+                table.row([
+                    field.name.clone(),
+                    style.number(field.offset).to_string(),
+                    style.number(field.len).to_string(),
+                ]);
+            }
+
+            table
+        };
+
+        [
+            sections,
+            fields("witness", &self.witness),
+            fields("constraint", &self.constraint),
+        ]
+    }
+
+    /// Write the layout, with its tables rendered with `style`
+    pub fn write<W>(&self, style: &Style, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let scalars = match self.config.zeroed_scalar_values {
+            true => "zeroed",
+            false => "stored",
+        };
+
+        writeln!(writer, "scalar values: {}", scalars)?;
+
+        for table in self.tables(style) {
+            writeln!(writer)?;
+            write!(writer, "{}", table)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = vec![];
+
+        self.write(&Style::PLAIN, &mut bytes)
+            .map_err(|_| fmt::Error)?;
+
+        f.write_str(&String::from_utf8_lossy(&bytes))
+    }
+}
+
+/// Lay the provided fields out contiguously
+fn fields<'a, I>(fields: I) -> Vec<Field>
+where
+    I: IntoIterator<Item = (&'a str, usize)>,
+{
+    let mut offset = 0;
+
+    fields
+        .into_iter()
+        .map(|(name, len)| {
+            let field = Field {
+                name: name.into(),
+                offset,
+                len,
+            };
+
+            offset += len;
+
+            field
+        })
+        .collect()
+}
+
+#[test]
+fn fields_add_up_to_the_element_len() {
+    for zeroed in [false, true] {
+        let config = *Config::default().with_zeroed_scalar_values(zeroed);
+        let preamble = Preamble::new(3, 5, config);
+        let layout = Layout::new(&preamble, 0).expect("the layout fits");
+
+        let len = |fields: &[Field]| {
+            fields.last().map(|f| f.offset + f.len).unwrap_or_default()
+        };
+
+        assert_eq!(len(&layout.witness), Witness::len(&config));
+        assert_eq!(len(&layout.constraint), Constraint::len(&config));
+
+        let sections = &layout.sections;
+        for pair in sections.windows(2) {
+            assert_eq!(pair[0].offset + pair[0].len, pair[1].offset);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Reader of the legacy dense format of `dusk-plonk-cdf`
+//!
+//! The layout is specified by `assets/cdf.bnf`: a preamble with the counts,
+//! followed by the witnesses and the constraints, every record carrying its
+//! source path inline and no source cache. The records have a fixed length,
+//! so a legacy file is detected by its size.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, process};
+
+use crate::{
+    Config, EncodableConstraint, EncodableSource, EncodableWitness, Encoder,
+    EncoderContextFileProvider, EncoderContextProvider, Polynomial, Scalar,
+    Selectors, WiredWitnesses,
+};
+
+const WORD: usize = 8;
+const PATH: usize = 1024;
+const SOURCE: usize = 2 * WORD + PATH;
+const PREAMBLE: usize = 2 * WORD;
+const WITNESS: usize = WORD + Scalar::LEN + SOURCE;
+const INDEXED_WITNESS: usize = WORD + 1 + WORD + Scalar::LEN;
+const POLYNOMIAL: usize = 7 * Scalar::LEN + 4 * INDEXED_WITNESS + 1;
+const CONSTRAINT: usize = WORD + POLYNOMIAL + SOURCE;
+
+/// A circuit in the legacy format of `dusk-plonk-cdf`.
+///
+/// The records are read on demand, and converted into their encodable
+/// counterparts, so the circuit can be written in the current format with
+/// [`LegacyCircuit::convert_file`].
+#[derive(Debug, Clone)]
+pub struct LegacyCircuit<S> {
+    source: S,
+    witnesses: usize,
+    constraints: usize,
+}
+
+impl<S> LegacyCircuit<S> {
+    /// Witnesses count
+    pub const fn witnesses(&self) -> usize {
+        self.witnesses
+    }
+
+    /// Constraints count
+    pub const fn constraints(&self) -> usize {
+        self.constraints
+    }
+}
+
+impl<S> LegacyCircuit<S>
+where
+    S: io::Read + io::Seek,
+{
+    /// Whether the source is laid out in the legacy format.
+    ///
+    /// The cursor is reset to the start of the source.
+    pub fn sniff(source: &mut S) -> io::Result<bool> {
+        let len = source.seek(SeekFrom::End(0))?;
+
+        source.seek(SeekFrom::Start(0))?;
+
+        let counts = match read_counts(source) {
+            Ok(counts) => counts,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                source.seek(SeekFrom::Start(0))?;
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+
+        source.seek(SeekFrom::Start(0))?;
+
+        Ok(legacy_len(counts) == Some(len))
+    }
+
+    /// Read a legacy circuit from a readable and seekable source
+    pub fn from_reader(mut source: S) -> io::Result<Self> {
+        if !Self::sniff(&mut source)? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the source isn't in the legacy dusk-plonk-cdf format",
+            ));
+        }
+
+        let (witnesses, constraints) = read_counts(&mut source)?;
+
+        Ok(Self {
+            source,
+            witnesses,
+            constraints,
+        })
+    }
+
+    /// Fetch a witness from its index
+    pub fn fetch_witness(
+        &mut self,
+        idx: usize,
+    ) -> io::Result<EncodableWitness> {
+        if idx >= self.witnesses {
+            return Err(out_of_bounds("witness", idx));
+        }
+
+        let offset = PREAMBLE + idx * WITNESS;
+
+        self.source.seek(SeekFrom::Start(offset as u64))?;
+
+        read_witness(&mut self.source)
+    }
+
+    /// Fetch a constraint from its index
+    pub fn fetch_constraint(
+        &mut self,
+        idx: usize,
+    ) -> io::Result<EncodableConstraint> {
+        if idx >= self.constraints {
+            return Err(out_of_bounds("constraint", idx));
+        }
+
+        let offset = PREAMBLE + self.witnesses * WITNESS + idx * CONSTRAINT;
+
+        self.source.seek(SeekFrom::Start(offset as u64))?;
+
+        read_constraint(&mut self.source)
+    }
+
+    /// Write the circuit in the current format into `path`, returning the
+    /// length of the file.
+    ///
+    /// The sources are read from the disk, as the legacy format doesn't
+    /// embed them; the ones that no longer exist are encoded empty.
+    pub fn convert_file<P>(
+        &mut self,
+        config: Config,
+        path: P,
+    ) -> io::Result<usize>
+    where
+        P: AsRef<Path>,
+    {
+        let mut encoder = Encoder::init_streaming_file(config, path)?;
+
+        self.source.seek(SeekFrom::Start(PREAMBLE as u64))?;
+
+        // the witnesses and constraints are contiguous, so they are read
+        // sequentially
+        let mut reader = io::BufReader::new(self.source.by_ref());
+
+        for _ in 0..self.witnesses {
+            encoder.push_witness(&read_witness(&mut reader)?)?;
+        }
+
+        for _ in 0..self.constraints {
+            encoder.push_constraint(&read_constraint(&mut reader)?)?;
+        }
+
+        encoder.finalize(LegacySources)
+    }
+}
+
+impl LegacyCircuit<File> {
+    /// Open a legacy circuit from a path
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        File::open(path).and_then(Self::from_reader)
+    }
+}
+
+/// Convert a legacy circuit into a temporary file in the current format,
+/// removed once opened
+pub(crate) fn convert_to_temp(
+    legacy: &mut LegacyCircuit<File>,
+    config: Config,
+) -> io::Result<(File, PathBuf)> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|t| t.subsec_nanos())
+        .unwrap_or_default();
+
+    let path = env::temp_dir().join(format!(
+        "dusk-cdf-legacy-{}-{}.cdf",
+        process::id(),
+        nanos
+    ));
+
+    if let Err(e) = legacy.convert_file(config, &path) {
+        fs::remove_file(&path).ok();
+        return Err(e);
+    }
+
+    let file = File::open(&path)?;
+
+    Ok((file, path))
+}
+
+/// Sources of a legacy circuit, read from the disk when they still exist
+struct LegacySources;
+
+impl EncoderContextProvider for LegacySources {
+    fn contents<P>(&mut self, path: P) -> io::Result<String>
+    where
+        P: AsRef<str>,
+    {
+        match EncoderContextFileProvider.contents(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            contents => contents,
+        }
+    }
+}
+
+fn out_of_bounds(kind: &str, idx: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("the {} {} is out of bounds", kind, idx),
+    )
+}
+
+fn legacy_len((witnesses, constraints): (usize, usize)) -> Option<u64> {
+    let len = witnesses
+        .checked_mul(WITNESS)?
+        .checked_add(constraints.checked_mul(CONSTRAINT)?)?
+        .checked_add(PREAMBLE)?;
+
+    u64::try_from(len).ok()
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut word = [0u8; WORD];
+
+    reader.read_exact(&mut word)?;
+
+    Ok(u64::from_le_bytes(word))
+}
+
+fn read_word<R: Read>(reader: &mut R) -> io::Result<usize> {
+    usize::try_from(read_u64(reader)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn read_bool<R: Read>(reader: &mut R) -> io::Result<bool> {
+    let mut b = [0u8; 1];
+
+    reader.read_exact(&mut b)?;
+
+    Ok(b[0] != 0)
+}
+
+fn read_scalar<R: Read>(reader: &mut R) -> io::Result<Scalar> {
+    let mut scalar = [0u8; Scalar::LEN];
+
+    reader.read_exact(&mut scalar)?;
+
+    Ok(Scalar::from(scalar))
+}
+
+fn read_counts<R: Read>(reader: &mut R) -> io::Result<(usize, usize)> {
+    Ok((read_word(reader)?, read_word(reader)?))
+}
+
+fn read_source<R: Read>(reader: &mut R) -> io::Result<EncodableSource> {
+    let line = read_u64(reader)?;
+    let col = read_u64(reader)?;
+
+    let mut path = vec![0u8; PATH];
+
+    reader.read_exact(&mut path)?;
+
+    // the path is padded with zeroes
+    let len = path.iter().position(|b| *b == 0).unwrap_or(PATH);
+    let path = String::from_utf8_lossy(&path[..len]).into_owned();
+
+    Ok(EncodableSource::new(line, col, path))
+}
+
+fn read_witness<R: Read>(reader: &mut R) -> io::Result<EncodableWitness> {
+    let id = read_word(reader)?;
+    let value = read_scalar(reader)?;
+    let source = read_source(reader)?;
+
+    Ok(EncodableWitness::new(id, None, value, source))
+}
+
+/// Read an indexed witness of a polynomial, returning its index
+fn read_indexed_witness<R: Read>(reader: &mut R) -> io::Result<usize> {
+    let index = read_word(reader)?;
+
+    // the origin and the value are read from the witnesses section instead
+    read_bool(reader)?;
+    read_u64(reader)?;
+    read_scalar(reader)?;
+
+    Ok(index)
+}
+
+fn read_constraint<R: Read>(reader: &mut R) -> io::Result<EncodableConstraint> {
+    let id = read_word(reader)?;
+
+    let selectors = Selectors {
+        qm: read_scalar(reader)?,
+        ql: read_scalar(reader)?,
+        qr: read_scalar(reader)?,
+        qd: read_scalar(reader)?,
+        qc: read_scalar(reader)?,
+        qo: read_scalar(reader)?,
+        pi: read_scalar(reader)?,
+        ..Selectors::default()
+    };
+
+    let witnesses = WiredWitnesses {
+        a: read_indexed_witness(reader)?,
+        b: read_indexed_witness(reader)?,
+        d: read_indexed_witness(reader)?,
+        o: read_indexed_witness(reader)?,
+    };
+
+    let evaluation = read_bool(reader)?;
+    let source = read_source(reader)?;
+
+    let polynomial = Polynomial {
+        selectors,
+        witnesses,
+        evaluation,
+    };
+
+    Ok(EncodableConstraint::new(id, polynomial, source))
+}
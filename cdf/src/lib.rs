@@ -17,38 +17,156 @@
 //! witnesses. Provided this, its witness index will reflect its line on the
 //! file, facilitating indexing.
 
+pub mod prelude;
+
+mod artifacts;
 mod config;
 mod constraint;
-mod decoder;
+mod context;
 mod element;
-mod encoder;
+mod layout;
+mod pattern;
 mod polynomial;
 mod preamble;
 mod source;
+mod style;
+mod table;
+mod warning;
 mod witness;
+
+#[cfg(feature = "decoder")]
+mod ci;
+#[cfg(feature = "decoder")]
+mod decoder;
+#[cfg(feature = "decoder")]
+mod divergence;
+#[cfg(feature = "decoder")]
+mod entropy;
+#[cfg(feature = "decoder")]
+mod frontier;
+#[cfg(feature = "decoder")]
+mod memory;
+#[cfg(feature = "decoder")]
+mod report;
+#[cfg(feature = "decoder")]
+mod shared;
+#[cfg(feature = "decoder")]
+mod timeline;
+#[cfg(feature = "decoder")]
+mod usage;
+
+#[cfg(feature = "index")]
+mod index;
+
+#[cfg(feature = "encoder")]
+mod encoder;
+
+#[cfg(any(feature = "decoder", feature = "encoder"))]
+mod lock;
+
+#[cfg(all(feature = "decoder", feature = "encoder"))]
+mod legacy;
+
+#[cfg(feature = "plonk-verify")]
+mod verifier;
+
+#[cfg(feature = "debugger")]
 mod zkdb;
 
 #[cfg(feature = "dap")]
 mod dap;
 
-pub use config::Config;
-pub use constraint::{Constraint, EncodableConstraint};
-pub use decoder::{CircuitDescription, DecoderContext, DecoderDisplay};
-pub use element::{DecodableElement, Element, EncodableElement, Scalar};
-pub use encoder::{
-    Encoder, EncoderContextFileProvider, EncoderContextProvider,
+pub use artifacts::{ProofArtifacts, PublicInput, PublicMismatch};
+pub use config::{
+    BudgetPolicy, CacheLimits, Config, ConfigBuilder, CorruptionPolicy,
+    Utf8Policy,
+};
+pub use constraint::{
+    Constraint, EncodableConstraint, Equation, Listing, Markdown, Notation,
+    ResolvedConstraint,
+};
+pub use context::{
+    DecoderContext, EncoderContextFileProvider, EncoderContextProvider,
+};
+pub use element::{
+    ByteOrder, DecodableElement, Element, EncodableElement, Scalar,
+    ScalarFormat,
+};
+pub use layout::{Field, Layout, Section, SectionKind};
+pub use pattern::{
+    GateMatch, GatePattern, GateStep, PatternConfig, PatternLibrary,
+    PatternUsage, SelectorValue,
+};
+pub use polynomial::{
+    GateKind, Polynomial, PolynomialBuilder, Selectors, WiredWitnesses,
 };
-pub use polynomial::{Polynomial, Selectors, WiredWitnesses};
 pub use preamble::Preamble;
-pub use source::EncodableSource;
+pub use source::{EncodableSource, SourceId};
+pub use style::{ColorChoice, Style};
+
+// returned by the style, and not meant to be named by the users
+#[doc(hidden)]
+pub use style::{Number, Styled};
+pub use table::{Table, TableStyle};
+pub use warning::{Constant, Selector, Warning, Wire};
 pub use witness::{EncodableWitness, Witness};
-pub use zkdb::{Breakpoint, State, ZkDebugger};
+
+#[cfg(feature = "decoder")]
+pub use ci::CiFormat;
+#[cfg(feature = "decoder")]
+pub use decoder::CircuitDescription;
+#[cfg(feature = "decoder")]
+#[doc(hidden)]
+pub use decoder::DecoderDisplay;
+#[cfg(feature = "decoder")]
+pub use divergence::{Divergence, FirstDivergence, WitnessDivergence};
+#[cfg(feature = "decoder")]
+pub use entropy::{RepeatedValue, WitnessEntropy};
+#[cfg(feature = "decoder")]
+pub use frontier::{Frontier, FrontierEntry};
+#[cfg(feature = "decoder")]
+pub use memory::MemoryUsage;
+#[cfg(feature = "decoder")]
+pub use report::ReportFormat;
+#[cfg(feature = "decoder")]
+pub use shared::SharedWire;
+#[cfg(feature = "decoder")]
+pub use timeline::{Timeline, TimelineEntry};
+#[cfg(feature = "decoder")]
+pub use usage::{CircuitUsage, GadgetUsage, SourceCounts, SourceUsage};
+
+#[cfg(feature = "index")]
+pub use index::CircuitIndex;
+
+#[cfg(feature = "encoder")]
+pub use encoder::{
+    BudgetOverrun, Encoder, EncoderProgress, FlushPolicy, PositionedWrite,
+    StreamingEncoder,
+};
+
+#[cfg(feature = "plonk")]
+pub use encoder::ComposerDump;
+
+#[cfg(all(feature = "decoder", feature = "encoder"))]
+pub use legacy::LegacyCircuit;
+
+#[cfg(feature = "plonk-verify")]
+pub use verifier::VerifierData;
+
+#[cfg(feature = "debugger")]
+pub use zkdb::{
+    Breakpoint, Breakpoints, CancelToken, DebuggerBackend, Focus, Location,
+    Movement, ScanProgress, SharedZkDebugger, State, StateChange, Until,
+    ZkDebugger, ZkDebuggerGuard,
+};
 
 #[cfg(feature = "dap")]
 pub use dap::{
-    ZkDap, ZkDapBuilder, ZkRequest, ZkResponse, ZkSource, ZkWitness,
+    BackendCall, BackendReply, OverflowPolicy, ZkDap, ZkDapBuilder,
+    ZkDapClient, ZkDapConfig, ZkDapLocal, ZkRequest, ZkResponse, ZkSource,
+    ZkSourceInfo, ZkWitness,
 };
 
 pub(crate) mod bytes;
-pub(crate) use encoder::EncoderContext;
+pub(crate) use context::EncoderContext;
 pub(crate) use source::DecodedSource;
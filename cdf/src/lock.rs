@@ -0,0 +1,45 @@
+//! Advisory locks of the CDF files, so a reader never decodes a file torn by
+//! a concurrent writer.
+//!
+//! The readers take a shared lock for as long as the file is open, and the
+//! writers an exclusive one until the file is finalized. The platforms
+//! without file locks read and write unguarded.
+
+use std::fs::{File, TryLockError};
+use std::io;
+
+/// Take a shared lock of a CDF file to be decoded.
+///
+/// Fails with [`io::ErrorKind::WouldBlock`] if the file is being written.
+#[cfg(feature = "decoder")]
+pub(crate) fn shared(file: &File) -> io::Result<()> {
+    guard(file.try_lock_shared(), "the CDF file is being written")
+}
+
+/// Take an exclusive lock of a CDF file to be encoded.
+///
+/// Fails with [`io::ErrorKind::WouldBlock`] if the file is being read or
+/// written.
+#[cfg(feature = "encoder")]
+pub(crate) fn exclusive(file: &File) -> io::Result<()> {
+    guard(
+        file.try_lock(),
+        "the CDF file is being read or written; encode it with \
+         `Encoder::init_file_atomic` to replace it under its readers",
+    )
+}
+
+fn guard(lock: Result<(), TryLockError>, busy: &str) -> io::Result<()> {
+    match lock {
+        Ok(()) => Ok(()),
+        Err(TryLockError::WouldBlock) => {
+            Err(io::Error::new(io::ErrorKind::WouldBlock, busy))
+        }
+        Err(TryLockError::Error(e))
+            if e.kind() == io::ErrorKind::Unsupported =>
+        {
+            Ok(())
+        }
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
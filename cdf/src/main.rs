@@ -1,18 +1,76 @@
-use std::{env, io, net};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+use std::{env, fmt, io, net};
 
 use clap::Parser;
-use tracing_subscriber::filter::EnvFilter;
+use dusk_cdf::ZkDapConfig;
+use tokio::net::TcpStream;
+use tokio::runtime;
+use toml_base_config::BaseConfig;
+use tracing::field::{Field, Visit};
+use tracing::{span, Level, Subscriber};
+use tracing_subscriber::filter::{EnvFilter, Targets};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
 
 #[derive(Parser, Debug, Default)]
 #[clap(author, version, about)]
 pub struct Args {
     #[clap(long)]
     bind: Option<net::SocketAddr>,
+
+    /// Serve a single client over the standard input and output
+    #[clap(long)]
+    stdio: bool,
+
+    /// Log the fetch, scan and decode operations slower than the threshold
+    #[clap(
+        long,
+        value_name = "MILLIS",
+        num_args = 0..=1,
+        default_missing_value = "100"
+    )]
+    trace_perf: Option<u64>,
+
+    /// Worker threads of the runtime, overriding the config
+    #[clap(long, value_name = "N")]
+    worker_threads: Option<usize>,
+
+    /// Maximum number of threads running the scans of all the sessions,
+    /// overriding the config
+    #[clap(long, value_name = "N")]
+    blocking_threads: Option<usize>,
 }
 
-#[tokio::main]
-async fn main() -> io::Result<()> {
-    let Args { bind } = Args::parse();
+fn main() -> io::Result<()> {
+    let args = Args::parse();
+
+    let config = ZkDapConfig::load().unwrap_or_default();
+
+    let mut runtime = runtime::Builder::new_multi_thread();
+
+    runtime.enable_all();
+
+    if let Some(n) = args.worker_threads.or(config.worker_threads) {
+        runtime.worker_threads(n.max(1));
+    }
+
+    if let Some(n) = args.blocking_threads.or(config.max_blocking_threads) {
+        runtime.max_blocking_threads(n.max(1));
+    }
+
+    runtime.build()?.block_on(serve(args))
+}
+
+async fn serve(args: Args) -> io::Result<()> {
+    let Args {
+        bind,
+        stdio,
+        trace_perf,
+        ..
+    } = args;
 
     let bind = bind.unwrap_or_else(|| {
         net::SocketAddr::new(net::Ipv4Addr::LOCALHOST.into(), 0)
@@ -24,16 +82,123 @@ async fn main() -> io::Result<()> {
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
         .unwrap_or_else(|| EnvFilter::new("info"));
 
-    tracing_subscriber::fmt::Subscriber::builder()
+    let output = tracing_subscriber::fmt::layer()
         .with_writer(std::io::stderr)
-        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_filter(filter);
+
+    // the spans are enabled for the perf layer regardless of the log filter
+    let perf = trace_perf.map(|millis| {
+        SlowSpans {
+            threshold: Duration::from_millis(millis),
+        }
+        .with_filter(Targets::new().with_target("dusk_cdf", Level::TRACE))
+    });
+
+    tracing_subscriber::registry()
+        .with(output)
+        .with(perf)
         .init();
 
-    dusk_cdf::ZkDapBuilder::new(bind)
-        .build()
-        .await?
-        .listen()
-        .await?;
+    let service = dusk_cdf::ZkDapBuilder::new(bind).build().await?;
+
+    if !stdio {
+        return service.listen().await;
+    }
+
+    // the service accepts socket clients only; the stdio is bridged to one
+    let socket = service.local_addr()?;
+
+    tokio::spawn(async move {
+        service.listen().await.ok();
+    });
+
+    let stream = TcpStream::connect(socket).await?;
+    let (mut reader, mut writer) = stream.into_split();
+
+    tokio::select! {
+        r = tokio::io::copy(&mut tokio::io::stdin(), &mut writer) => r?,
+        r = tokio::io::copy(&mut reader, &mut tokio::io::stdout()) => r?,
+    };
 
     Ok(())
 }
+
+/// Layer that logs the spans that took longer than a threshold to close
+struct SlowSpans {
+    threshold: Duration,
+}
+
+/// Start and fields of a span measured by [`SlowSpans`]
+struct Timing {
+    start: Instant,
+    fields: String,
+}
+
+impl Visit for Timing {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let sep = if self.fields.is_empty() { "" } else { " " };
+
+        write!(self.fields, "{}{}={:?}", sep, field.name(), value).ok();
+    }
+}
+
+impl<S> Layer<S> for SlowSpans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &span::Attributes<'_>,
+        id: &span::Id,
+        ctx: Context<'_, S>,
+    ) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let mut timing = Timing {
+            start: Instant::now(),
+            fields: String::new(),
+        };
+
+        attrs.record(&mut timing);
+        span.extensions_mut().insert(timing);
+    }
+
+    fn on_record(
+        &self,
+        id: &span::Id,
+        values: &span::Record<'_>,
+        ctx: Context<'_, S>,
+    ) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<Timing>() {
+                values.record(timing);
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let (elapsed, fields) = match span.extensions().get::<Timing>() {
+            Some(timing) => (timing.start.elapsed(), timing.fields.clone()),
+            None => return,
+        };
+
+        if elapsed >= self.threshold {
+            tracing::warn!(
+                target: "dusk_cdf_dap::perf",
+                "slow {} took {:?}: {}",
+                span.name(),
+                elapsed,
+                fields
+            );
+        }
+    }
+}
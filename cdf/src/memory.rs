@@ -0,0 +1,166 @@
+//! Accounting of the memory held by the caches of a circuit
+
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GateMatch, SourceCounts, SourceId};
+
+/// Memory held by the caches of a circuit, in bytes.
+///
+/// The sizes are estimates of the heap allocations of each cache. The
+/// sources and the witness allocations are always kept; the other caches are
+/// dropped, in the order of their fields, when the total exceeds the
+/// [`CacheLimits::max_memory`](crate::CacheLimits::max_memory) budget, and
+/// rebuilt on their next use.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct MemoryUsage {
+    /// Budget of the caches
+    pub budget: usize,
+    /// Names and contents of the embedded sources
+    pub sources: usize,
+    /// Witness allocations of the debugger, held while the witness steps are
+    /// enabled
+    pub allocations: usize,
+    /// Number of constraints and witnesses of every source
+    pub source_counts: usize,
+    /// Gate patterns recognized in the circuit
+    #[serde(default)]
+    pub patterns: usize,
+    /// Line table of the debugger, used to jump to a source line
+    pub lines: usize,
+    /// Index of the circuit
+    pub index: usize,
+}
+
+impl MemoryUsage {
+    /// Memory held by all the caches
+    pub const fn total(&self) -> usize {
+        self.sources
+            + self.allocations
+            + self.source_counts
+            + self.patterns
+            + self.lines
+            + self.index
+    }
+
+    /// Check if the caches exceed the budget
+    pub const fn is_over_budget(&self) -> bool {
+        self.total() > self.budget
+    }
+}
+
+/// Cache that can be dropped to honor the memory budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Cache {
+    SourceCounts,
+    Patterns,
+    Lines,
+    Index,
+}
+
+impl Cache {
+    /// Caches in the order they are evicted, the cheapest to rebuild first
+    pub const EVICTION: [Self; 4] =
+        [Self::SourceCounts, Self::Patterns, Self::Lines, Self::Index];
+}
+
+/// Estimate of the heap allocations of a value
+pub(crate) trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for usize {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for u64 {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for SourceId {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T> HeapSize for Vec<T>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>()
+            + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T> HeapSize for Arc<[T]>
+where
+    T: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.len() * mem::size_of::<T>()
+            + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<K, V> HeapSize for HashMap<K, V>
+where
+    K: HeapSize,
+    V: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_size() + v.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<A, B> HeapSize for (A, B)
+where
+    A: HeapSize,
+    B: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+impl<A, B, C> HeapSize for (A, B, C)
+where
+    A: HeapSize,
+    B: HeapSize,
+    C: HeapSize,
+{
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size() + self.2.heap_size()
+    }
+}
+
+impl HeapSize for SourceCounts {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+    }
+}
+
+impl HeapSize for GateMatch {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+    }
+}
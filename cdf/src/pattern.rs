@@ -0,0 +1,592 @@
+//! Recognition of the gadgets of a circuit from the selectors of their
+//! consecutive constraints
+
+use std::collections::{BTreeMap, HashMap};
+use std::{fmt, io};
+
+use serde::{Deserialize, Serialize};
+use toml_base_config::BaseConfig;
+
+use crate::{Constant, GateKind, Scalar, Selector, Selectors};
+
+/// Expected value of a selector in a [`GateStep`]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectorValue {
+    /// The selector is disabled
+    Zero,
+    /// The selector holds any value other than zero
+    Nonzero,
+    /// The selector is `1`
+    One,
+    /// The selector is `-1`
+    MinusOne,
+}
+
+impl SelectorValue {
+    /// Check if a selector holds the expected value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Constant, Scalar, SelectorValue};
+    ///
+    /// let one = Scalar::from(Constant::One.bytes());
+    ///
+    /// assert!(SelectorValue::One.matches(&one));
+    /// assert!(SelectorValue::Nonzero.matches(&one));
+    /// assert!(!SelectorValue::Zero.matches(&one));
+    /// ```
+    pub fn matches(&self, value: &Scalar) -> bool {
+        Class::of(value).matches(*self)
+    }
+}
+
+/// Class of the value of a selector, packed into a [`Signature`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Zero = 0,
+    One = 1,
+    MinusOne = 2,
+    Other = 3,
+}
+
+impl Class {
+    fn of(value: &Scalar) -> Self {
+        match Constant::of(value) {
+            Some(Constant::Zero) => Self::Zero,
+            Some(Constant::One) => Self::One,
+            Some(Constant::MinusOne) => Self::MinusOne,
+            _ => Self::Other,
+        }
+    }
+
+    const fn from_bits(bits: u32) -> Self {
+        match bits & 0b11 {
+            0 => Self::Zero,
+            1 => Self::One,
+            2 => Self::MinusOne,
+            _ => Self::Other,
+        }
+    }
+
+    fn matches(self, expected: SelectorValue) -> bool {
+        match expected {
+            SelectorValue::Zero => self == Self::Zero,
+            SelectorValue::Nonzero => self != Self::Zero,
+            SelectorValue::One => self == Self::One,
+            SelectorValue::MinusOne => self == Self::MinusOne,
+        }
+    }
+}
+
+/// Compact form of the selectors of a constraint, two bits per selector, so
+/// the signatures of a whole circuit can be held while matching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Signature {
+    kind: GateKind,
+    classes: u32,
+}
+
+impl Signature {
+    fn new(selectors: &Selectors) -> Self {
+        let classes = Selector::ALL.iter().enumerate().fold(
+            0,
+            |classes, (i, selector)| {
+                classes | (Class::of(selector.of(selectors)) as u32) << (2 * i)
+            },
+        );
+
+        Self {
+            kind: selectors.gate_kind(),
+            classes,
+        }
+    }
+
+    fn class(&self, selector: Selector) -> Class {
+        Class::from_bits(self.classes >> (2 * selector as u32))
+    }
+}
+
+/// Constraint of a [`GatePattern`], matched by its gate kind and the values
+/// of its selectors
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct GateStep {
+    /// Kind of the gate; any kind matches if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<GateKind>,
+    /// Expected values of the selectors; the omitted ones are ignored
+    #[serde(flatten)]
+    pub selectors: BTreeMap<Selector, SelectorValue>,
+}
+
+impl GateStep {
+    /// Step matching a kind of gate
+    pub fn new(kind: GateKind) -> Self {
+        Self {
+            kind: Some(kind),
+            selectors: BTreeMap::new(),
+        }
+    }
+
+    /// Expect a value for a selector
+    pub fn with(mut self, selector: Selector, value: SelectorValue) -> Self {
+        self.selectors.insert(selector, value);
+        self
+    }
+
+    fn matches(&self, signature: &Signature) -> bool {
+        self.kind.map_or(true, |kind| kind == signature.kind)
+            && self.selectors.iter().all(|(selector, value)| {
+                signature.class(*selector).matches(*value)
+            })
+    }
+}
+
+/// Signature of a gadget: the steps matched by its consecutive constraints.
+///
+/// The steps are matched `repeat` times in a row; a greedy pattern keeps
+/// matching them for as long as the following constraints do, such as the
+/// gates of a XOR that depend on the number of bits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GatePattern {
+    /// Name of the gadget, such as `Poseidon round`
+    pub name: String,
+    /// Constraints of the gadget, in order
+    pub steps: Vec<GateStep>,
+    /// Times the steps are matched in a row
+    #[serde(default = "GatePattern::default_repeat")]
+    pub repeat: usize,
+    /// Whether the steps are matched again past `repeat`
+    #[serde(default)]
+    pub greedy: bool,
+}
+
+impl GatePattern {
+    const fn default_repeat() -> usize {
+        1
+    }
+
+    /// Create a pattern matching the steps once
+    pub fn new<N>(name: N, steps: Vec<GateStep>) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            steps,
+            repeat: Self::default_repeat(),
+            greedy: false,
+        }
+    }
+
+    /// Override the times the steps are matched in a row
+    pub fn with_repeat(mut self, repeat: usize) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Match the steps again for as long as the constraints do
+    pub fn with_greedy(mut self, greedy: bool) -> Self {
+        self.greedy = greedy;
+        self
+    }
+
+    /// Number of constraints of the shortest match
+    pub fn window(&self) -> usize {
+        self.steps.len() * self.repeat
+    }
+
+    /// Number of constraints matched from `start`, if any
+    fn match_at(
+        &self,
+        signatures: &[Signature],
+        start: usize,
+    ) -> Option<usize> {
+        if self.window() == 0 {
+            return None;
+        }
+
+        let mut end = start;
+        let mut cycles = 0;
+
+        while self.greedy || cycles < self.repeat {
+            let matched = signatures.get(end..end + self.steps.len()).map_or(
+                false,
+                |cycle| {
+                    self.steps
+                        .iter()
+                        .zip(cycle)
+                        .all(|(step, signature)| step.matches(signature))
+                },
+            );
+
+            if !matched {
+                break;
+            }
+
+            end += self.steps.len();
+            cycles += 1;
+        }
+
+        if cycles < self.repeat {
+            return None;
+        }
+
+        Some(end - start)
+    }
+}
+
+/// Occurrence of a [`GatePattern`] in a circuit
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct GateMatch {
+    /// First constraint of the occurrence
+    pub start: usize,
+    /// Number of constraints of the occurrence
+    pub len: usize,
+    /// Name of the pattern
+    pub name: String,
+    /// Number of the occurrence among the ones of the pattern, from 1
+    pub occurrence: usize,
+}
+
+impl GateMatch {
+    /// Check if the occurrence spans a constraint
+    pub const fn contains(&self, constraint: usize) -> bool {
+        self.start <= constraint && constraint < self.start + self.len
+    }
+}
+
+impl fmt::Display for GateMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.occurrence)
+    }
+}
+
+/// Occurrences of a pattern in a circuit
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct PatternUsage {
+    /// Name of the pattern
+    pub name: String,
+    /// Number of occurrences
+    pub occurrences: usize,
+    /// Constraints spanned by the occurrences
+    pub constraints: usize,
+}
+
+/// Patterns recognized in a circuit, tried in order at every constraint.
+///
+/// The default library holds the signatures of the common gadgets of
+/// `dusk-plonk`; see [`PatternLibrary::builtin`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PatternLibrary {
+    patterns: Vec<GatePattern>,
+}
+
+impl Default for PatternLibrary {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl PatternLibrary {
+    /// Library without patterns
+    pub const fn empty() -> Self {
+        Self { patterns: vec![] }
+    }
+
+    /// Signatures of the common gadgets of `dusk-plonk`:
+    ///
+    /// - `ECC add`: a variable base addition gate, followed by the empty gate
+    ///   holding the resulting point
+    /// - `Poseidon round`: the linear layer of a round of the Hades
+    ///   permutation, five arithmetic gates adding three terms of the state
+    ///   and a round constant
+    /// - `boolean`: `a·a - a = 0`, with `qm = 1` and `qo = -1`
+    /// - `XOR` and `AND`: the runs of logic gates, with `qlogic` set to `-1`
+    ///   and `1` respectively
+    /// - `fixed base mul`: the runs of fixed base addition gates
+    pub fn builtin() -> Self {
+        use SelectorValue::*;
+
+        let linear = GateStep::new(GateKind::Arithmetic)
+            .with(Selector::Qm, Zero)
+            .with(Selector::Ql, Nonzero)
+            .with(Selector::Qr, Nonzero)
+            .with(Selector::Qd, Nonzero)
+            .with(Selector::Qc, Nonzero)
+            .with(Selector::Qo, MinusOne);
+
+        let boolean = GateStep::new(GateKind::Arithmetic)
+            .with(Selector::Qm, One)
+            .with(Selector::Ql, Zero)
+            .with(Selector::Qr, Zero)
+            .with(Selector::Qd, Zero)
+            .with(Selector::Qc, Zero)
+            .with(Selector::Qo, MinusOne);
+
+        let xor =
+            GateStep::new(GateKind::Logic).with(Selector::Qlogic, MinusOne);
+        let and = GateStep::new(GateKind::Logic).with(Selector::Qlogic, One);
+
+        let patterns = vec![
+            GatePattern::new(
+                "ECC add",
+                vec![
+                    GateStep::new(GateKind::VariableGroupAdd),
+                    GateStep::new(GateKind::Empty),
+                ],
+            ),
+            GatePattern::new("Poseidon round", vec![linear]).with_repeat(5),
+            GatePattern::new("boolean", vec![boolean]),
+            GatePattern::new("XOR", vec![xor]).with_greedy(true),
+            GatePattern::new("AND", vec![and]).with_greedy(true),
+            GatePattern::new(
+                "fixed base mul",
+                vec![GateStep::new(GateKind::FixedGroupAdd)],
+            )
+            .with_greedy(true),
+        ];
+
+        Self { patterns }
+    }
+
+    /// Patterns of the library, in the order they are tried
+    pub fn patterns(&self) -> &[GatePattern] {
+        &self.patterns
+    }
+
+    /// Append a pattern, tried after the current ones
+    pub fn with_pattern(&mut self, pattern: GatePattern) -> &mut Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Recognize the patterns in the selectors of consecutive constraints.
+    ///
+    /// At every constraint, the first pattern that matches is taken, and the
+    /// scan resumes after its occurrence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Constant, GateKind, PatternLibrary, Scalar, Selectors};
+    ///
+    /// let one = Scalar::from(Constant::One.bytes());
+    ///
+    /// let boolean = Selectors {
+    ///     qm: one,
+    ///     qo: Scalar::from(Constant::MinusOne.bytes()),
+    ///     qarith: one,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let matches = PatternLibrary::builtin()
+    ///     .recognize([Selectors::default(), boolean, boolean]);
+    ///
+    /// assert_eq!(matches.len(), 2);
+    /// assert_eq!(matches[1].start, 2);
+    /// assert_eq!(matches[1].to_string(), "boolean 2");
+    /// ```
+    pub fn recognize<I>(&self, selectors: I) -> Vec<GateMatch>
+    where
+        I: IntoIterator<Item = Selectors>,
+    {
+        let signatures: Vec<_> =
+            selectors.into_iter().map(|s| Signature::new(&s)).collect();
+
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        let mut matches = vec![];
+        let mut start = 0;
+
+        while start < signatures.len() {
+            let matched = self.patterns.iter().find_map(|pattern| {
+                pattern
+                    .match_at(&signatures, start)
+                    .map(|len| (pattern, len))
+            });
+
+            let (pattern, len) = match matched {
+                Some(matched) => matched,
+                None => {
+                    start += 1;
+                    continue;
+                }
+            };
+
+            let occurrence = occurrences.entry(&pattern.name).or_default();
+
+            *occurrence += 1;
+
+            matches.push(GateMatch {
+                start,
+                len,
+                name: pattern.name.clone(),
+                occurrence: *occurrence,
+            });
+
+            start += len;
+        }
+
+        matches
+    }
+}
+
+/// Gate patterns declared by the user, such as the gadgets of an in-house
+/// library, recognized by the debugger along with the builtin ones.
+///
+/// See [`BaseConfig`] for context. The patterns share the config file of
+/// [`Config`](struct.Config.html). A pattern is declared with its name, the
+/// expected gate kind and selector values of its steps, and the number of
+/// times they repeat; its window spans the steps of every repetition:
+///
+/// ```toml
+/// [[patterns]]
+/// name = "range decomposition"
+/// repeat = 4
+///
+/// [[patterns.steps]]
+/// kind = "range"
+/// qrange = "one"
+/// qc = "zero"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternConfig {
+    /// Whether the builtin patterns are recognized after the declared ones
+    #[serde(default = "PatternConfig::default_builtin")]
+    pub builtin: bool,
+    /// Declared patterns, tried in order before the builtin ones
+    #[serde(default)]
+    pub patterns: Vec<GatePattern>,
+}
+
+impl Default for PatternConfig {
+    fn default() -> Self {
+        Self {
+            builtin: Self::default_builtin(),
+            patterns: vec![],
+        }
+    }
+}
+
+impl PatternConfig {
+    const fn default_builtin() -> bool {
+        true
+    }
+
+    /// Library of the declared patterns, followed by the builtin ones if
+    /// enabled.
+    ///
+    /// A declared pattern that spans no constraint is rejected, as it would
+    /// never match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{GateKind, GatePattern, GateStep, PatternConfig};
+    ///
+    /// let mut config = PatternConfig::default();
+    ///
+    /// config.patterns.push(
+    ///     GatePattern::new("range", vec![GateStep::new(GateKind::Range)])
+    ///         .with_repeat(4),
+    /// );
+    ///
+    /// let library = config.library().expect("valid patterns");
+    ///
+    /// assert_eq!(library.patterns()[0].name, "range");
+    /// assert_eq!(library.patterns()[0].window(), 4);
+    /// ```
+    pub fn library(&self) -> io::Result<PatternLibrary> {
+        if let Some(p) = self.patterns.iter().find(|p| p.window() == 0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the pattern `{}` spans no constraint", p.name),
+            ));
+        }
+
+        let mut patterns = self.patterns.clone();
+
+        if self.builtin {
+            patterns.extend(PatternLibrary::builtin().patterns);
+        }
+
+        Ok(PatternLibrary { patterns })
+    }
+}
+
+// the patterns are declared in the config file of the crate, along with the
+// parameters of the `Config`
+impl BaseConfig for PatternConfig {
+    const PACKAGE: &'static str = env!("CARGO_PKG_NAME");
+}
+
+/// Occurrences and constraints of every pattern, the largest first
+pub(crate) fn usage(matches: &[GateMatch]) -> Vec<PatternUsage> {
+    let mut usage: HashMap<&str, PatternUsage> = HashMap::new();
+
+    for m in matches {
+        let entry = usage.entry(&m.name).or_insert_with(|| PatternUsage {
+            name: m.name.clone(),
+            ..Default::default()
+        });
+
+        entry.occurrences += 1;
+        entry.constraints += m.len;
+    }
+
+    let mut usage: Vec<_> = usage.into_values().collect();
+
+    usage.sort_by(|a, b| {
+        b.constraints.cmp(&a.constraints).then(a.name.cmp(&b.name))
+    });
+
+    usage
+}
+
+/// Occurrence spanning a constraint, in matches sorted by their start
+pub(crate) fn find(
+    matches: &[GateMatch],
+    constraint: usize,
+) -> Option<&GateMatch> {
+    let i = matches.partition_point(|m| m.start <= constraint);
+
+    i.checked_sub(1)
+        .map(|i| &matches[i])
+        .filter(|m| m.contains(constraint))
+}
@@ -1,6 +1,7 @@
-use std::io;
+use std::str::FromStr;
+use std::{fmt, io};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Config, DecodableElement, DecoderContext, Element, EncodableElement,
@@ -9,7 +10,17 @@ use crate::{
 
 /// Polynomial selectors
 #[derive(
-    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct Selectors {
     /// Qm (mult) selector
@@ -38,6 +49,175 @@ pub struct Selectors {
     pub qfixed_add: Scalar,
 }
 
+impl Selectors {
+    /// Arithmetic gate, enabled by `qarith`
+    pub fn is_arith(&self) -> bool {
+        !self.qarith.is_zero()
+    }
+
+    /// Range gate, enabled by `qrange`
+    pub fn is_range(&self) -> bool {
+        !self.qrange.is_zero()
+    }
+
+    /// Logic gate, enabled by `qlogic`
+    pub fn is_logic(&self) -> bool {
+        !self.qlogic.is_zero()
+    }
+
+    /// Fixed base ecc addition gate, enabled by `qfixed_add`
+    pub fn is_fixed_group_add(&self) -> bool {
+        !self.qfixed_add.is_zero()
+    }
+
+    /// Variable base ecc addition gate, enabled by `qgroup_variable`
+    pub fn is_variable_group_add(&self) -> bool {
+        !self.qgroup_variable.is_zero()
+    }
+
+    /// Constraint with a public input term
+    pub fn has_public_input(&self) -> bool {
+        !self.pi.is_zero()
+    }
+
+    /// Classify the gate from its internal selectors.
+    ///
+    /// A gate is expected to enable exactly one internal selector. If none is
+    /// enabled, the constraint is a padding/dummy one; if more than one is
+    /// enabled, the kind can't be determined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{GateKind, Scalar, Selectors};
+    ///
+    /// let mut one = [0u8; Scalar::LEN];
+    /// one[0] = 1;
+    ///
+    /// let mut selectors = Selectors::default();
+    /// assert_eq!(selectors.gate_kind(), GateKind::Empty);
+    ///
+    /// selectors.qrange = Scalar::from(one);
+    /// assert_eq!(selectors.gate_kind(), GateKind::Range);
+    ///
+    /// selectors.qarith = Scalar::from(one);
+    /// assert_eq!(selectors.gate_kind(), GateKind::Ambiguous);
+    /// ```
+    pub fn gate_kind(&self) -> GateKind {
+        let kinds = [
+            (self.is_arith(), GateKind::Arithmetic),
+            (self.is_range(), GateKind::Range),
+            (self.is_logic(), GateKind::Logic),
+            (self.is_fixed_group_add(), GateKind::FixedGroupAdd),
+            (self.is_variable_group_add(), GateKind::VariableGroupAdd),
+        ];
+
+        let mut enabled = kinds.iter().filter(|(e, _)| *e).map(|(_, k)| *k);
+
+        match (enabled.next(), enabled.next()) {
+            (None, _) => GateKind::Empty,
+            (Some(kind), None) => kind,
+            (Some(_), Some(_)) => GateKind::Ambiguous,
+        }
+    }
+}
+
+/// Kind of a gate, as classified by its internal selectors
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub enum GateKind {
+    /// Arithmetic gate (`qarith`)
+    Arithmetic,
+    /// Range check gate (`qrange`)
+    Range,
+    /// Logic gate (`qlogic`)
+    Logic,
+    /// Fixed base ecc addition gate (`qfixed_add`)
+    FixedGroupAdd,
+    /// Variable base ecc addition gate (`qgroup_variable`)
+    VariableGroupAdd,
+    /// No internal selector enabled
+    Empty,
+    /// More than one internal selector enabled
+    Ambiguous,
+}
+
+impl GateKind {
+    /// All the available kinds
+    pub const ALL: [Self; 7] = [
+        Self::Arithmetic,
+        Self::Range,
+        Self::Logic,
+        Self::FixedGroupAdd,
+        Self::VariableGroupAdd,
+        Self::Empty,
+        Self::Ambiguous,
+    ];
+
+    /// Short name of the kind
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Arithmetic => "arith",
+            Self::Range => "range",
+            Self::Logic => "logic",
+            Self::FixedGroupAdd => "fixed_add",
+            Self::VariableGroupAdd => "variable_add",
+            Self::Empty => "empty",
+            Self::Ambiguous => "ambiguous",
+        }
+    }
+}
+
+impl fmt::Display for GateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for GateKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(Self::name).collect();
+
+                format!(
+                    "invalid gate kind `{}`; expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl TryFrom<String> for GateKind {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<GateKind> for String {
+    fn from(kind: GateKind) -> Self {
+        kind.name().into()
+    }
+}
+
 impl Element for Selectors {
     fn len(ctx: &Config) -> usize {
         12 * Scalar::len(ctx)
@@ -105,7 +285,17 @@ impl DecodableElement for Selectors {
 
 /// Polynomial witnesses allocated to a constraint system
 #[derive(
-    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct WiredWitnesses {
     /// Wired `a`
@@ -159,7 +349,17 @@ impl DecodableElement for WiredWitnesses {
 
 /// PLONK polynomial expression representation with its selectors and witnesses.
 #[derive(
-    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct Polynomial {
     /// Selectors of the polynomial
@@ -207,6 +407,44 @@ impl DecodableElement for Polynomial {
 }
 
 impl Polynomial {
+    /// Create a builder for a polynomial, starting with zeroed selectors,
+    /// witnesses wired to `0`, and a failed evaluation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Polynomial, Scalar, Selectors, WiredWitnesses};
+    ///
+    /// let mut one = [0u8; Scalar::LEN];
+    /// one[0] = 1;
+    ///
+    /// let polynomial = Polynomial::builder()
+    ///     .qm(one)
+    ///     .qarith(one)
+    ///     .a(2)
+    ///     .b(3)
+    ///     .o(4)
+    ///     .evaluation(true)
+    ///     .build();
+    ///
+    /// let selectors = Selectors {
+    ///     qm: one.into(),
+    ///     qarith: one.into(),
+    ///     ..Default::default()
+    /// };
+    /// let witnesses = WiredWitnesses {
+    ///     a: 2,
+    ///     b: 3,
+    ///     d: 0,
+    ///     o: 4,
+    /// };
+    ///
+    /// assert_eq!(polynomial, Polynomial::new(selectors, witnesses, true));
+    /// ```
+    pub fn builder() -> PolynomialBuilder {
+        PolynomialBuilder::default()
+    }
+
     /// Create a new polynomial with evaluation to either correct or incorrect
     pub const fn new(
         selectors: Selectors,
@@ -234,4 +472,141 @@ impl Polynomial {
     pub const fn witnesses(&self) -> &WiredWitnesses {
         &self.witnesses
     }
+
+    /// Kind of the gate, as in [`Selectors::gate_kind`]
+    pub fn gate_kind(&self) -> GateKind {
+        self.selectors.gate_kind()
+    }
+}
+
+/// Builder for a [`Polynomial`], created via [`Polynomial::builder`].
+///
+/// Unset fields take their default value.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PolynomialBuilder {
+    polynomial: Polynomial,
+}
+
+impl PolynomialBuilder {
+    /// Set all the selectors
+    pub fn selectors(mut self, selectors: Selectors) -> Self {
+        self.polynomial.selectors = selectors;
+        self
+    }
+
+    /// Set the Qm (mult) selector
+    pub fn qm<S: Into<Scalar>>(mut self, qm: S) -> Self {
+        self.polynomial.selectors.qm = qm.into();
+        self
+    }
+
+    /// Set the Ql (left) selector
+    pub fn ql<S: Into<Scalar>>(mut self, ql: S) -> Self {
+        self.polynomial.selectors.ql = ql.into();
+        self
+    }
+
+    /// Set the Qr (right) selector
+    pub fn qr<S: Into<Scalar>>(mut self, qr: S) -> Self {
+        self.polynomial.selectors.qr = qr.into();
+        self
+    }
+
+    /// Set the Qd (fourth) selector
+    pub fn qd<S: Into<Scalar>>(mut self, qd: S) -> Self {
+        self.polynomial.selectors.qd = qd.into();
+        self
+    }
+
+    /// Set the Qc (constant) selector
+    pub fn qc<S: Into<Scalar>>(mut self, qc: S) -> Self {
+        self.polynomial.selectors.qc = qc.into();
+        self
+    }
+
+    /// Set the Qo (output) selector
+    pub fn qo<S: Into<Scalar>>(mut self, qo: S) -> Self {
+        self.polynomial.selectors.qo = qo.into();
+        self
+    }
+
+    /// Set the public input
+    pub fn pi<S: Into<Scalar>>(mut self, pi: S) -> Self {
+        self.polynomial.selectors.pi = pi.into();
+        self
+    }
+
+    /// Set the Qarith (arithmetic) internal selector
+    pub fn qarith<S: Into<Scalar>>(mut self, qarith: S) -> Self {
+        self.polynomial.selectors.qarith = qarith.into();
+        self
+    }
+
+    /// Set the Qlogic (logical) internal selector
+    pub fn qlogic<S: Into<Scalar>>(mut self, qlogic: S) -> Self {
+        self.polynomial.selectors.qlogic = qlogic.into();
+        self
+    }
+
+    /// Set the Qrange (range check) internal selector
+    pub fn qrange<S: Into<Scalar>>(mut self, qrange: S) -> Self {
+        self.polynomial.selectors.qrange = qrange.into();
+        self
+    }
+
+    /// Set the Qgroup_variable (ecc group variable add) internal selector
+    pub fn qgroup_variable<S: Into<Scalar>>(
+        mut self,
+        qgroup_variable: S,
+    ) -> Self {
+        self.polynomial.selectors.qgroup_variable = qgroup_variable.into();
+        self
+    }
+
+    /// Set the Qgroup_fixed (ecc group fixed add) internal selector
+    pub fn qfixed_add<S: Into<Scalar>>(mut self, qfixed_add: S) -> Self {
+        self.polynomial.selectors.qfixed_add = qfixed_add.into();
+        self
+    }
+
+    /// Set all the wired witnesses
+    pub fn witnesses(mut self, witnesses: WiredWitnesses) -> Self {
+        self.polynomial.witnesses = witnesses;
+        self
+    }
+
+    /// Set the witness wired to `a`
+    pub fn a(mut self, a: usize) -> Self {
+        self.polynomial.witnesses.a = a;
+        self
+    }
+
+    /// Set the witness wired to `b`
+    pub fn b(mut self, b: usize) -> Self {
+        self.polynomial.witnesses.b = b;
+        self
+    }
+
+    /// Set the witness wired to `d` (fourth)
+    pub fn d(mut self, d: usize) -> Self {
+        self.polynomial.witnesses.d = d;
+        self
+    }
+
+    /// Set the witness wired to `o` (output)
+    pub fn o(mut self, o: usize) -> Self {
+        self.polynomial.witnesses.o = o;
+        self
+    }
+
+    /// Set whether the polynomial evaluated to zero
+    pub fn evaluation(mut self, evaluation: bool) -> Self {
+        self.polynomial.evaluation = evaluation;
+        self
+    }
+
+    /// Create the polynomial
+    pub const fn build(self) -> Polynomial {
+        self.polynomial
+    }
 }
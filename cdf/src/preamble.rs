@@ -2,6 +2,8 @@
 
 use std::{io, mem};
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     Config, Constraint, DecodableElement, DecoderContext, Element,
     EncodableElement, EncoderContext, Witness,
@@ -11,7 +13,8 @@ use crate::{
 ///
 /// # Example
 ///
-/// ```
+#[cfg_attr(feature = "decoder", doc = "```")]
+#[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
 /// # fn main() -> std::io::Result<()> {
 /// use dusk_cdf::CircuitDescription;
 ///
@@ -20,7 +23,7 @@ use crate::{
 ///
 /// # Ok(()) }
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Preamble {
     /// Witnesses count
     pub witnesses: usize,
@@ -28,12 +31,21 @@ pub struct Preamble {
     pub constraints: usize,
     /// Configuration parameters for encoding and decoding
     pub config: Config,
+    /// Whether the encoder completed the file. A file that isn't finalized
+    /// was interrupted while being written, and is rejected on open.
+    pub finalized: bool,
 }
 
 impl Preamble {
     /// Serialized length
     pub const LEN: usize = 2 * mem::size_of::<usize>() + Config::LEN;
 
+    /// Flag set in the config byte while the file is being written.
+    ///
+    /// The flag is inverted so files produced before its introduction decode
+    /// as finalized.
+    const PENDING: u8 = 0x80;
+
     /// Create a new preamble instance
     ///
     /// # Example
@@ -55,14 +67,69 @@ impl Preamble {
             witnesses,
             constraints,
             config,
+            finalized: true,
         }
     }
 
+    /// Set the finalized marker of the preamble
+    pub const fn with_finalized(mut self, finalized: bool) -> Self {
+        self.finalized = finalized;
+        self
+    }
+
+    /// Offset of the witnesses section, right after the preamble.
+    ///
+    /// The layout of a CDF file is:
+    ///
+    /// | Section      | Offset                     | Size                    |
+    /// |--------------|----------------------------|-------------------------|
+    /// | Preamble     | `0`                        | [`Preamble::LEN`]       |
+    /// | Witnesses    | [`witnesses_offset`]       | [`witnesses_size`]      |
+    /// | Constraints  | [`constraints_offset`]     | [`constraints_size`]    |
+    /// | Source cache | [`source_cache_offset`]    | remainder of the file   |
+    ///
+    /// Every witness and constraint has a fixed length, defined by the
+    /// [`Config`] of the preamble.
+    ///
+    /// [`witnesses_offset`]: Preamble::witnesses_offset
+    /// [`witnesses_size`]: Preamble::witnesses_size
+    /// [`constraints_offset`]: Preamble::constraints_offset
+    /// [`constraints_size`]: Preamble::constraints_size
+    /// [`source_cache_offset`]: Preamble::source_cache_offset
+    pub const fn witnesses_offset(&self) -> usize {
+        Self::LEN
+    }
+
+    /// Size of the witnesses section, in bytes.
+    ///
+    /// Returns `None` if the size overflows.
+    pub fn witnesses_size(&self) -> Option<usize> {
+        self.witnesses.checked_mul(Witness::len(&self.config))
+    }
+
+    /// Offset of the constraints section, right after the witnesses.
+    ///
+    /// Returns `None` if the offset overflows.
+    pub fn constraints_offset(&self) -> Option<usize> {
+        self.witnesses_size()?.checked_add(self.witnesses_offset())
+    }
+
+    /// Size of the constraints section, in bytes.
+    ///
+    /// Returns `None` if the size overflows.
+    pub fn constraints_size(&self) -> Option<usize> {
+        self.constraints.checked_mul(Constraint::len(&self.config))
+    }
+
     /// Witness offset in CDF, from an index
     ///
+    /// Returns `None` if the index is out of bounds, or if the offset
+    /// overflows.
+    ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Preamble, CircuitDescription};
     ///
@@ -73,15 +140,23 @@ impl Preamble {
     /// # Ok(()) }
     /// ```
     pub fn witness_offset(&self, idx: usize) -> Option<usize> {
-        (idx < self.witnesses)
-            .then(|| Self::LEN + idx * Witness::len(&self.config))
+        if idx >= self.witnesses {
+            return None;
+        }
+
+        idx.checked_mul(Witness::len(&self.config))?
+            .checked_add(self.witnesses_offset())
     }
 
     /// Constraint offset in CDF, from an index
     ///
+    /// Returns `None` if the index is out of bounds, or if the offset
+    /// overflows.
+    ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Preamble, CircuitDescription};
     ///
@@ -92,18 +167,25 @@ impl Preamble {
     /// # Ok(()) }
     /// ```
     pub fn constraint_offset(&self, idx: usize) -> Option<usize> {
-        (idx < self.constraints).then(|| {
-            Self::LEN
-                + self.witnesses * Witness::len(&self.config)
-                + idx * Constraint::len(&self.config)
-        })
+        if idx >= self.constraints {
+            return None;
+        }
+
+        idx.checked_mul(Constraint::len(&self.config))?
+            .checked_add(self.constraints_offset()?)
     }
 
     /// Cache starting position
     ///
+    /// # Panics
+    ///
+    /// Panics if the offset overflows. For untrusted preambles, use
+    /// [`Preamble::checked_source_cache_offset`].
+    ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "decoder", doc = "```")]
+    #[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Preamble, CircuitDescription};
     ///
@@ -114,9 +196,35 @@ impl Preamble {
     /// # Ok(()) }
     /// ```
     pub fn source_cache_offset(&self) -> usize {
-        Self::LEN
-            + self.witnesses * Witness::len(&self.config)
-            + self.constraints * Constraint::len(&self.config)
+        self.checked_source_cache_offset()
+            .expect("the CDF layout overflows")
+    }
+
+    /// Cache starting position, right after the constraints.
+    ///
+    /// Returns `None` if the offset overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Config, Preamble};
+    ///
+    /// let preamble = Preamble::new(2, 3, Config::default());
+    /// let constraints = preamble.constraints_offset().unwrap();
+    /// let size = preamble.constraints_size().unwrap();
+    ///
+    /// assert_eq!(
+    ///     preamble.checked_source_cache_offset(),
+    ///     Some(constraints + size)
+    /// );
+    ///
+    /// let preamble = Preamble::new(usize::MAX, 3, Config::default());
+    ///
+    /// assert_eq!(preamble.checked_source_cache_offset(), None);
+    /// ```
+    pub fn checked_source_cache_offset(&self) -> Option<usize> {
+        self.constraints_size()?
+            .checked_add(self.constraints_offset()?)
     }
 }
 
@@ -126,6 +234,7 @@ impl Default for Preamble {
             witnesses: 1,
             constraints: 0,
             config: Default::default(),
+            finalized: true,
         }
     }
 }
@@ -148,7 +257,12 @@ impl EncodableElement for Preamble {
     fn to_buffer(&self, ctx: &mut EncoderContext, buf: &mut [u8]) {
         let buf = self.witnesses.encode(ctx, buf);
         let buf = self.constraints.encode(ctx, buf);
-        let _ = self.config.encode(ctx, buf);
+
+        self.config.to_buffer(ctx, buf);
+
+        if !self.finalized {
+            buf[0] |= Self::PENDING;
+        }
     }
 }
 
@@ -164,6 +278,8 @@ impl DecodableElement for Preamble {
         let buf = self.constraints.try_decode_in_place(ctx, buf)?;
         let _ = self.config.try_decode_in_place(ctx, buf)?;
 
+        self.finalized = buf[0] & Self::PENDING == 0;
+
         Ok(())
     }
 }
@@ -0,0 +1,31 @@
+//! Curated exports of the types most circuits, tools and frontends need.
+//!
+//! ```
+//! use dusk_cdf::prelude::*;
+//!
+//! let preamble = Preamble::new(1, 0, Config::default());
+//!
+//! assert_eq!(preamble.constraints, 0);
+//! ```
+
+pub use crate::{
+    Config, ConfigBuilder, Constraint, EncodableConstraint, EncodableSource,
+    EncodableWitness, GateKind, Polynomial, Preamble, Scalar, Selectors,
+    Warning, WiredWitnesses, Witness,
+};
+
+#[cfg(feature = "decoder")]
+pub use crate::CircuitDescription;
+
+#[cfg(feature = "encoder")]
+pub use crate::{Encoder, EncoderContextProvider, StreamingEncoder};
+
+#[cfg(feature = "debugger")]
+pub use crate::{
+    Breakpoint, DebuggerBackend, Movement, SharedZkDebugger, State, ZkDebugger,
+};
+
+#[cfg(feature = "dap")]
+pub use crate::{
+    ZkDap, ZkDapBuilder, ZkDapClient, ZkDapConfig, ZkRequest, ZkResponse,
+};
@@ -0,0 +1,452 @@
+//! Static report of a circuit, shared for the review of its failures
+
+use std::path::Path;
+use std::str::FromStr;
+use std::{fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::constraint::Hex;
+use crate::{
+    CircuitDescription, CircuitUsage, Notation, Selector, Warning, Wire,
+};
+
+/// Source lines around a failed constraint in a report
+const EXCERPT_CONTEXT: usize = 3;
+
+/// Format of a report
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// Markdown document
+    #[default]
+    Markdown,
+    /// Standalone HTML page
+    Html,
+}
+
+impl ReportFormat {
+    /// Format implied by the extension of a path: HTML for `.html` and
+    /// `.htm`, markdown otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::ReportFormat;
+    ///
+    /// assert_eq!(ReportFormat::from_path("report.html"), ReportFormat::Html);
+    /// assert_eq!(ReportFormat::from_path("notes.md"), ReportFormat::Markdown);
+    /// ```
+    pub fn from_path<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some(e)
+                if e.eq_ignore_ascii_case("html")
+                    || e.eq_ignore_ascii_case("htm") =>
+            {
+                Self::Html
+            }
+            _ => Self::Markdown,
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!(
+                "invalid report format `{}`; expected markdown or html",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Markdown => f.write_str("markdown"),
+            Self::Html => f.write_str("html"),
+        }
+    }
+}
+
+/// Findings of the full scans of a circuit
+pub(crate) struct Findings {
+    pub failures: Vec<usize>,
+    pub unconstrained: Vec<usize>,
+    pub warnings: Vec<Warning>,
+    pub usage: CircuitUsage,
+}
+
+impl Findings {
+    pub fn scan<S>(
+        cdf: &mut CircuitDescription<S>,
+        limit: usize,
+    ) -> io::Result<Self>
+    where
+        S: io::Read + io::Seek,
+    {
+        let mut failures = vec![];
+        let mut wired = vec![false; cdf.preamble().witnesses];
+
+        for idx in 0..cdf.preamble().constraints {
+            let constraint = cdf.fetch_constraint(idx)?;
+            let polynomial = *constraint.polynomial();
+
+            if !polynomial.evaluation {
+                failures.push(constraint.id());
+            }
+
+            for wire in Wire::ALL {
+                if let Some(w) = wired.get_mut(wire.of(&polynomial.witnesses)) {
+                    *w = true;
+                }
+            }
+        }
+
+        let unconstrained = wired
+            .iter()
+            .enumerate()
+            .filter_map(|(id, wired)| (!wired).then_some(id))
+            .collect();
+
+        let mut warnings = cdf.wirecheck()?;
+        warnings.extend(cdf.scalarcheck()?);
+        warnings.extend(cdf.constcheck()?);
+        warnings.extend_from_slice(cdf.timeline()?.warnings());
+
+        let mut usage = cdf.usage()?;
+        usage.truncate(limit);
+
+        Ok(Self {
+            failures,
+            unconstrained,
+            warnings,
+            usage,
+        })
+    }
+}
+
+/// Markup of the blocks of a report
+struct Document<W> {
+    format: ReportFormat,
+    writer: W,
+}
+
+impl<W> Document<W>
+where
+    W: io::Write,
+{
+    fn begin(&mut self, title: &str) -> io::Result<()> {
+        if self.format == ReportFormat::Html {
+            writeln!(
+                self.writer,
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+                 <title>{}</title>\n<style>{}</style>\n</head>\n<body>",
+                Escape(title),
+                STYLESHEET
+            )?;
+        }
+
+        self.heading(1, title)
+    }
+
+    fn end(&mut self) -> io::Result<()> {
+        if self.format == ReportFormat::Html {
+            writeln!(self.writer, "</body>\n</html>")?;
+        }
+
+        self.writer.flush()
+    }
+
+    fn heading(&mut self, level: usize, text: &str) -> io::Result<()> {
+        match self.format {
+            ReportFormat::Markdown => {
+                writeln!(self.writer, "{} {}\n", "#".repeat(level), text)
+            }
+            ReportFormat::Html => {
+                writeln!(self.writer, "<h{0}>{1}</h{0}>", level, Escape(text))
+            }
+        }
+    }
+
+    fn paragraph(&mut self, text: &str) -> io::Result<()> {
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.writer, "{}\n", text),
+            ReportFormat::Html => {
+                writeln!(self.writer, "<p>{}</p>", Escape(text))
+            }
+        }
+    }
+
+    fn code(&mut self, text: &str) -> io::Result<()> {
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.writer, "`{}`\n", text),
+            ReportFormat::Html => {
+                writeln!(self.writer, "<p><code>{}</code></p>", Escape(text))
+            }
+        }
+    }
+
+    fn excerpt<'a, I>(&mut self, lines: I, current: usize) -> io::Result<()>
+    where
+        I: IntoIterator<Item = (usize, &'a str)>,
+    {
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.writer, "```rust")?,
+            ReportFormat::Html => write!(self.writer, "<pre>")?,
+        }
+
+        for (i, l) in lines {
+            let marker = if i == current { '>' } else { ' ' };
+
+            match self.format {
+                ReportFormat::Markdown => {
+                    writeln!(self.writer, "{} {:>5} | {}", marker, i, l)?
+                }
+                ReportFormat::Html => writeln!(
+                    self.writer,
+                    "{} {:>5} | {}",
+                    Escape(&marker.to_string()),
+                    i,
+                    Escape(l)
+                )?,
+            }
+        }
+
+        match self.format {
+            ReportFormat::Markdown => writeln!(self.writer, "```\n"),
+            ReportFormat::Html => writeln!(self.writer, "</pre>"),
+        }
+    }
+
+    fn table(
+        &mut self,
+        header: &[&str],
+        rows: &[Vec<String>],
+    ) -> io::Result<()> {
+        match self.format {
+            ReportFormat::Markdown => {
+                writeln!(self.writer, "| {} |", header.join(" | "))?;
+                writeln!(self.writer, "|{}", "---|".repeat(header.len()))?;
+
+                for row in rows {
+                    writeln!(self.writer, "| {} |", row.join(" | "))?;
+                }
+
+                writeln!(self.writer)
+            }
+
+            ReportFormat::Html => {
+                write!(self.writer, "<table>\n<tr>")?;
+                for h in header {
+                    write!(self.writer, "<th>{}</th>", Escape(h))?;
+                }
+                writeln!(self.writer, "</tr>")?;
+
+                for row in rows {
+                    write!(self.writer, "<tr>")?;
+                    for cell in row {
+                        write!(self.writer, "<td>{}</td>", Escape(cell))?;
+                    }
+                    writeln!(self.writer, "</tr>")?;
+                }
+
+                writeln!(self.writer, "</table>")
+            }
+        }
+    }
+}
+
+/// Style of the HTML reports
+const STYLESHEET: &str = "\
+    body{font-family:sans-serif;max-width:60em;margin:auto}\
+    table{border-collapse:collapse;margin-bottom:1em}\
+    td,th{border:1px solid #ccc;padding:.2em .6em;text-align:left}\
+    pre{background:#f6f8fa;padding:.6em;overflow-x:auto}";
+
+/// HTML escaped text, valid as XML as well
+pub(crate) struct Escape<'a>(pub &'a str);
+
+impl fmt::Display for Escape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.chars().try_for_each(|c| match c {
+            '&' => f.write_str("&amp;"),
+            '<' => f.write_str("&lt;"),
+            '>' => f.write_str("&gt;"),
+            '"' => f.write_str("&quot;"),
+            c => write!(f, "{}", c),
+        })
+    }
+}
+
+pub(crate) fn write<S, W>(
+    cdf: &mut CircuitDescription<S>,
+    title: &str,
+    format: ReportFormat,
+    limit: usize,
+    writer: W,
+) -> io::Result<()>
+where
+    S: io::Read + io::Seek,
+    W: io::Write,
+{
+    let findings = Findings::scan(cdf, limit)?;
+    let preamble = *cdf.preamble();
+
+    let mut doc = Document { format, writer };
+
+    doc.begin(title)?;
+
+    doc.heading(2, "Summary")?;
+    doc.table(
+        &[
+            "constraints",
+            "witnesses",
+            "sources",
+            "failures",
+            "unconstrained",
+        ],
+        &[vec![
+            preamble.constraints.to_string(),
+            preamble.witnesses.to_string(),
+            cdf.source_names().count().to_string(),
+            findings.failures.len().to_string(),
+            findings.unconstrained.len().to_string(),
+        ]],
+    )?;
+
+    doc.heading(2, "Failed constraints")?;
+    if findings.failures.is_empty() {
+        doc.paragraph("All the constraints evaluate correctly.")?;
+    } else if findings.failures.len() > limit {
+        doc.paragraph(&format!(
+            "Showing the first {} of {} failed constraints.",
+            limit,
+            findings.failures.len()
+        ))?;
+    }
+
+    for id in findings.failures.iter().take(limit) {
+        let resolved = cdf.resolve_constraint(*id)?;
+        let constraint = &resolved.constraint;
+        let polynomial = constraint.polynomial();
+
+        doc.heading(
+            3,
+            &format!(
+                "Constraint #{}: {} gate",
+                constraint.id(),
+                polynomial.gate_kind()
+            ),
+        )?;
+        doc.code(&format!(
+            "{}:{}:{}",
+            constraint.name(),
+            constraint.line(),
+            constraint.col()
+        ))?;
+        doc.excerpt(
+            resolved.excerpt(EXCERPT_CONTEXT),
+            constraint.line() as usize,
+        )?;
+        doc.code(&resolved.equation_in(Notation::Unicode))?;
+
+        let selectors: Vec<_> = Selector::ALL
+            .iter()
+            .map(|s| (s, s.of(&polynomial.selectors)))
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(s, value)| vec![s.to_string(), Hex(value).to_string()])
+            .collect();
+
+        let wires: Vec<_> = Wire::ALL
+            .iter()
+            .map(|w| (w, resolved.wired(*w)))
+            .map(|(w, witness)| {
+                vec![
+                    w.to_string(),
+                    format!("#{}", witness.id()),
+                    Hex(witness.value()).to_string(),
+                ]
+            })
+            .collect();
+
+        doc.table(&["selector", "value"], &selectors)?;
+        doc.table(&["wire", "witness", "value"], &wires)?;
+    }
+
+    doc.heading(2, "Unconstrained witnesses")?;
+    if findings.unconstrained.is_empty() {
+        doc.paragraph("Every witness is wired to a constraint.")?;
+    } else {
+        doc.paragraph(&format!(
+            "{} witnesses aren't wired to any constraint, so their values \
+             aren't checked by the circuit.",
+            findings.unconstrained.len()
+        ))?;
+
+        let mut rows = vec![];
+        for id in findings.unconstrained.iter().take(limit) {
+            let witness = cdf.fetch_witness(*id)?;
+
+            rows.push(vec![
+                format!("#{}", witness.id()),
+                format!("{}:{}", witness.name(), witness.line()),
+            ]);
+        }
+
+        doc.table(&["witness", "source"], &rows)?;
+    }
+
+    doc.heading(2, "Lint findings")?;
+    if findings.warnings.is_empty() {
+        doc.paragraph("The lint checks found nothing.")?;
+    } else {
+        let rows: Vec<_> = findings
+            .warnings
+            .iter()
+            .take(limit)
+            .map(|w| vec![w.to_string()])
+            .collect();
+
+        doc.table(&["finding"], &rows)?;
+    }
+
+    let usage = &findings.usage;
+
+    doc.heading(2, "Sources by failures")?;
+    let rows: Vec<_> = usage
+        .heatmap
+        .iter()
+        .map(|s| {
+            vec![
+                s.name.clone(),
+                s.failures.to_string(),
+                s.constraints.to_string(),
+                format!("{:.2}%", 100.0 * s.failure_rate()),
+            ]
+        })
+        .collect();
+    doc.table(&["source", "failures", "constraints", "rate"], &rows)?;
+
+    doc.heading(2, "Gadgets by constraints")?;
+    let rows: Vec<_> = usage
+        .gadgets
+        .iter()
+        .map(|g| {
+            vec![format!("{}:{}", g.name, g.line), g.constraints.to_string()]
+        })
+        .collect();
+    doc.table(&["gadget", "constraints"], &rows)?;
+
+    doc.end()
+}
@@ -0,0 +1,100 @@
+//! Witnesses wired into the constraints of several sources, revealing the
+//! coupling between gadgets
+
+use std::collections::HashMap;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CircuitDescription, Wire};
+
+/// Witness wired into the constraints of more than one source
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct SharedWire {
+    /// Id of the witness
+    pub witness: usize,
+    /// Number of constraints wiring the witness
+    pub constraints: usize,
+    /// Sources of the constraints wiring the witness, in ascending order
+    pub sources: Vec<String>,
+}
+
+pub(crate) fn shared_wires<S>(
+    cdf: &mut CircuitDescription<S>,
+) -> io::Result<Vec<SharedWire>>
+where
+    S: io::Read + io::Seek,
+{
+    let names: Vec<String> = cdf.source_names().map(String::from).collect();
+    let index: HashMap<&str, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_str(), i))
+        .collect();
+
+    // constraints wiring each witness, and the indexes of their sources
+    let mut wired: HashMap<usize, (usize, Vec<usize>)> = HashMap::new();
+
+    for idx in 0..cdf.preamble().constraints {
+        let constraint = cdf.fetch_constraint(idx)?;
+        let source = match index.get(constraint.name()) {
+            Some(source) => *source,
+            None => continue,
+        };
+
+        let w = constraint.polynomial().witnesses;
+        let mut witnesses: Vec<_> =
+            Wire::ALL.iter().map(|wire| wire.of(&w)).collect();
+
+        witnesses.sort_unstable();
+        witnesses.dedup();
+
+        for witness in witnesses {
+            let (constraints, sources) = wired.entry(witness).or_default();
+
+            *constraints += 1;
+
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+    }
+
+    let mut shared: Vec<_> = wired
+        .into_iter()
+        .filter(|(_, (_, sources))| sources.len() > 1)
+        .map(|(witness, (constraints, sources))| {
+            let mut sources: Vec<_> =
+                sources.into_iter().map(|s| names[s].clone()).collect();
+
+            sources.sort();
+
+            SharedWire {
+                witness,
+                constraints,
+                sources,
+            }
+        })
+        .collect();
+
+    shared.sort_by(|a, b| {
+        b.sources
+            .len()
+            .cmp(&a.sources.len())
+            .then(b.constraints.cmp(&a.constraints))
+            .then(a.witness.cmp(&b.witness))
+    });
+
+    Ok(shared)
+}
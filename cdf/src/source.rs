@@ -1,6 +1,8 @@
+#[cfg(feature = "decoder")]
+use std::collections::HashMap;
 use std::{io, mem};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Config, DecodableElement, DecoderContext, Element, EncodableElement,
@@ -64,7 +66,16 @@ impl DecodableElement for EncodedSource {
 
 /// Source file tripler that can be encoded into a CDF file
 #[derive(
-    Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct EncodableSource {
     line: u64,
@@ -135,6 +146,67 @@ impl EncodableElement for EncodableSource {
     }
 }
 
+/// Interned id of a source file of a CDF file.
+///
+/// The sources are interned by name when the source cache is read, so the
+/// decoded items of a same source file share an id, and can be compared
+/// without comparing their names.
+///
+/// # Example
+///
+#[cfg_attr(feature = "decoder", doc = "```")]
+#[cfg_attr(not(feature = "decoder"), doc = "```ignore")]
+/// # fn main() -> std::io::Result<()> {
+/// use dusk_cdf::CircuitDescription;
+///
+/// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+///
+/// let a = circuit.fetch_constraint(0)?;
+/// let (id, name) = (a.source_id(), a.name().to_string());
+///
+/// let b = circuit.fetch_constraint(1)?;
+///
+/// assert_eq!(id == b.source_id(), name == b.name());
+///
+/// # Ok(()) }
+/// ```
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct SourceId(usize);
+
+impl SourceId {
+    /// Index of the first source of the cache with the name of this one
+    pub const fn index(&self) -> usize {
+        self.0
+    }
+
+    /// Intern the names of the source cache, mapping every source to the
+    /// first one with its name
+    #[cfg(feature = "decoder")]
+    pub(crate) fn intern(names: &[String]) -> Vec<Self> {
+        let mut interned = HashMap::with_capacity(names.len());
+
+        names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                *interned.entry(name.as_str()).or_insert(Self(idx))
+            })
+            .collect()
+    }
+}
+
 /// Source file decoded from a CDF file
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DecodedSource<'a> {
@@ -142,6 +214,7 @@ pub struct DecodedSource<'a> {
     pub(crate) col: u64,
     pub(crate) name: &'a str,
     pub(crate) contents: &'a str,
+    pub(crate) id: SourceId,
 }
 
 impl<'a> Element for DecodedSource<'a> {
@@ -184,8 +257,16 @@ impl<'a> DecodableElement for DecodedSource<'a> {
             )
         })?;
 
+        let id = ctx.fetch_id(contents_index).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "the source id wasn't available in the file cache",
+            )
+        })?;
+
         self.line = line;
         self.col = col;
+        self.id = id;
 
         // the compiler isn't smart enough here to understand that `self` is
         // `'a`; hence the context is also `'a`
@@ -198,3 +279,12 @@ impl<'a> DecodableElement for DecodedSource<'a> {
         Ok(())
     }
 }
+
+#[test]
+#[cfg(feature = "decoder")]
+fn sources_are_interned_by_name() {
+    let names = ["main.rs", "gadget.rs", "main.rs"].map(String::from);
+    let ids = SourceId::intern(&names);
+
+    assert_eq!(ids, [SourceId(0), SourceId(1), SourceId(0)]);
+}
@@ -0,0 +1,260 @@
+//! Terminal styling shared by the CLI tools
+
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::{env, fmt, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::table::{Table, TableStyle};
+
+/// When to color the output of the CLI tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorChoice {
+    /// Color the output if it is a terminal and `NO_COLOR` isn't set
+    Auto,
+    /// Always color the output
+    Always,
+    /// Never color the output
+    Never,
+}
+
+impl Default for ColorChoice {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl ColorChoice {
+    /// Resolve the style of the standard output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::ColorChoice;
+    ///
+    /// assert!(ColorChoice::Always.resolve().is_enabled());
+    /// assert!(!ColorChoice::Never.resolve().is_enabled());
+    /// ```
+    pub fn resolve(self) -> Style {
+        let enabled = match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                let no_color = env::var_os("NO_COLOR")
+                    .map(|v| !v.is_empty())
+                    .unwrap_or(false);
+
+                !no_color && io::stdout().is_terminal()
+            }
+        };
+
+        Style::new(enabled)
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "invalid color choice `{}`; expected never, auto or always",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Always => f.write_str("always"),
+            Self::Never => f.write_str("never"),
+        }
+    }
+}
+
+/// ANSI styling of the outputs; a disabled style leaves the text plain.
+///
+/// The style also defines whether the numbers are displayed with their digits
+/// grouped by thousands, and how the tables are rendered.
+///
+/// # Example
+///
+/// ```
+/// use dusk_cdf::Style;
+///
+/// assert_eq!(Style::PLAIN.failure("fail").to_string(), "fail");
+/// assert_eq!(
+///     Style::new(true).failure("fail").to_string(),
+///     "\x1b[31mfail\x1b[0m"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+    enabled: bool,
+    grouping: bool,
+    table: TableStyle,
+}
+
+impl Style {
+    /// Style that doesn't color the outputs nor group the digits
+    pub const PLAIN: Self = Self::new(false);
+
+    const RED: &'static str = "31";
+    const YELLOW: &'static str = "33";
+    const DIM: &'static str = "2";
+
+    /// Create a new style, coloring the outputs if `enabled`
+    pub const fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            grouping: false,
+            table: TableStyle::Ascii,
+        }
+    }
+
+    /// Flag on whether or not the outputs are colored
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Flag on whether or not the digits of the numbers are grouped
+    pub const fn is_grouping(&self) -> bool {
+        self.grouping
+    }
+
+    /// Group the digits of the numbers by thousands, as in `1,234,567`
+    pub fn with_grouping(&mut self, grouping: bool) -> &mut Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Rendering of the tables
+    pub const fn table_style(&self) -> TableStyle {
+        self.table
+    }
+
+    /// Render the tables with the provided style
+    pub fn with_table_style(&mut self, table: TableStyle) -> &mut Self {
+        self.table = table;
+        self
+    }
+
+    /// Empty table with the provided header, rendered with the table style
+    pub fn table<H, S>(&self, header: H) -> Table
+    where
+        H: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Table::new(self.table, header)
+    }
+
+    /// Integer, with its digits grouped if enabled.
+    ///
+    /// The width, fill and alignment of the formatter are honored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::Style;
+    ///
+    /// let style = *Style::PLAIN.with_grouping(true);
+    ///
+    /// assert_eq!(style.number(1234567).to_string(), "1,234,567");
+    /// assert_eq!(style.number(-1234).to_string(), "-1,234");
+    /// assert_eq!(format!("{:>8}", style.number(20971)), "  20,971");
+    /// assert_eq!(Style::PLAIN.number(1234567).to_string(), "1234567");
+    /// ```
+    pub const fn number<T>(&self, value: T) -> Number<T> {
+        Number {
+            grouping: self.grouping,
+            value,
+        }
+    }
+
+    /// Failed constraints and errors, in red
+    pub const fn failure<T>(&self, value: T) -> Styled<T> {
+        self.styled(Self::RED, value)
+    }
+
+    /// Breakpoints, in yellow
+    pub const fn breakpoint<T>(&self, value: T) -> Styled<T> {
+        self.styled(Self::YELLOW, value)
+    }
+
+    /// Scalar values, dimmed
+    pub const fn scalar<T>(&self, value: T) -> Styled<T> {
+        self.styled(Self::DIM, value)
+    }
+
+    const fn styled<T>(&self, code: &'static str, value: T) -> Styled<T> {
+        let code = if self.enabled { Some(code) } else { None };
+
+        Styled { code, value }
+    }
+}
+
+/// Value displayed with a [`Style`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Styled<T> {
+    code: Option<&'static str>,
+    value: T,
+}
+
+impl<T> fmt::Display for Styled<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "\x1b[{}m{}\x1b[0m", code, self.value),
+            None => self.value.fmt(f),
+        }
+    }
+}
+
+/// Integer displayed with a [`Style`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Number<T> {
+    grouping: bool,
+    value: T,
+}
+
+impl<T> fmt::Display for Number<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plain = self.value.to_string();
+
+        if !self.grouping {
+            return f.pad(&plain);
+        }
+
+        let (sign, digits) = match plain.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", plain.as_str()),
+        };
+
+        let mut grouped = String::with_capacity(plain.len() + digits.len() / 3);
+
+        grouped.push_str(sign);
+
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+
+            grouped.push(c);
+        }
+
+        f.pad(&grouped)
+    }
+}
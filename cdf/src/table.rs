@@ -0,0 +1,241 @@
+//! Tables of the outputs of the CLI tools
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Rendering of the tables of the CLI tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TableStyle {
+    /// Columns aligned with spaces, under a header ruled with `-`
+    Ascii,
+    /// Columns aligned and separated with box-drawing characters
+    Unicode,
+    /// Cells separated by tabs and left unaligned, to be processed by tools
+    /// such as `awk`
+    Tsv,
+}
+
+impl Default for TableStyle {
+    fn default() -> Self {
+        Self::Ascii
+    }
+}
+
+impl FromStr for TableStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(Self::Ascii),
+            "unicode" => Ok(Self::Unicode),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(format!(
+                "invalid table style `{}`; expected ascii, unicode or tsv",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TableStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ascii => f.write_str("ascii"),
+            Self::Unicode => f.write_str("unicode"),
+            Self::Tsv => f.write_str("tsv"),
+        }
+    }
+}
+
+/// Table of an output, rendered under its [`TableStyle`].
+///
+/// The rows are padded or truncated to the columns of the header. A column
+/// is aligned to the right if all of its cells are numbers, or `-` for the
+/// missing ones. The ANSI escape sequences of a styled cell don't count
+/// towards its width.
+///
+/// # Example
+///
+/// ```
+/// use dusk_cdf::{Style, TableStyle};
+///
+/// let mut style = Style::PLAIN;
+/// let mut table = style.table(["constraints", "source"]);
+///
+/// table.row(["26", "main.rs"]).row(["7", "gadget.rs"]);
+///
+/// assert_eq!(
+///     table.to_string(),
+///     "constraints  source\n\
+///      -----------  ---------\n\
+///      \x20        26  main.rs\n\
+///      \x20         7  gadget.rs\n"
+/// );
+///
+/// style.with_table_style(TableStyle::Tsv);
+///
+/// let mut table = style.table(["constraints", "source"]);
+///
+/// table.row(["26", "main.rs"]);
+///
+/// assert_eq!(table.to_string(), "constraints\tsource\n26\tmain.rs\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Table {
+    style: TableStyle,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create an empty table with the provided header
+    pub fn new<H, S>(style: TableStyle, header: H) -> Self
+    where
+        H: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            style,
+            header: header.into_iter().map(Into::into).collect(),
+            rows: vec![],
+        }
+    }
+
+    /// Append a row
+    pub fn row<R, S>(&mut self, row: R) -> &mut Self
+    where
+        R: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut row: Vec<String> = row.into_iter().map(Into::into).collect();
+
+        row.resize(self.header.len(), String::new());
+
+        self.rows.push(row);
+        self
+    }
+
+    /// Flag on whether or not the table has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Rendered lines of the table, starting with its header
+    pub fn lines(&self) -> Vec<String> {
+        let rows = Some(&self.header).into_iter().chain(&self.rows);
+
+        let (separator, rule, cross) = match self.style {
+            TableStyle::Tsv => {
+                return rows.map(|row| row.join("\t")).collect();
+            }
+            TableStyle::Ascii => ("  ", "-", "  "),
+            TableStyle::Unicode => (" │ ", "─", "─┼─"),
+        };
+
+        let columns = 0..self.header.len();
+
+        let widths: Vec<_> = columns
+            .clone()
+            .map(|c| rows.clone().map(|r| width(&r[c])).max().unwrap_or(0))
+            .collect();
+
+        let right: Vec<_> = columns
+            .clone()
+            .map(|c| {
+                !self.rows.is_empty()
+                    && self.rows.iter().all(|r| is_numeric(&r[c]))
+            })
+            .collect();
+
+        let line = |row: &Vec<String>| {
+            let cells: Vec<_> = columns
+                .clone()
+                .map(|c| {
+                    let fill = " ".repeat(widths[c] - width(&row[c]));
+
+                    match right[c] {
+                        true => format!("{}{}", fill, row[c]),
+                        false => format!("{}{}", row[c], fill),
+                    }
+                })
+                .collect();
+
+            cells.join(separator).trim_end().to_string()
+        };
+
+        let ruler: Vec<_> = widths.iter().map(|w| rule.repeat(*w)).collect();
+
+        let mut lines = vec![line(&self.header), ruler.join(cross)];
+
+        lines.extend(self.rows.iter().map(line));
+        lines
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.lines()
+            .iter()
+            .try_for_each(|line| writeln!(f, "{}", line))
+    }
+}
+
+/// Characters of a cell, without its ANSI escape sequences
+fn visible(cell: &str) -> impl Iterator<Item = char> + '_ {
+    let mut escaped = false;
+
+    cell.chars().filter(move |&c| match (escaped, c) {
+        (false, '\x1b') => {
+            escaped = true;
+            false
+        }
+        (true, 'm') => {
+            escaped = false;
+            false
+        }
+        (true, _) => false,
+        (false, _) => true,
+    })
+}
+
+fn width(cell: &str) -> usize {
+    visible(cell).count()
+}
+
+fn is_numeric(cell: &str) -> bool {
+    let mut digits = false;
+
+    let numeric = visible(cell).all(|c| {
+        digits |= c.is_ascii_digit();
+
+        c.is_ascii_digit() || matches!(c, ',' | '.' | '%' | '-')
+    });
+
+    numeric && (digits || cell == "-")
+}
+
+#[test]
+fn unicode_tables_are_aligned_without_escapes() {
+    let mut table = Table::new(TableStyle::Unicode, ["bytes", "source"]);
+
+    table.row(["\x1b[2m1,024\x1b[0m", "main.rs"]).row([
+        "-",
+        "gadget.rs",
+        "ignored",
+    ]);
+
+    assert_eq!(
+        table.lines(),
+        vec![
+            "bytes │ source",
+            "──────┼──────────",
+            "\x1b[2m1,024\x1b[0m │ main.rs",
+            "    - │ gadget.rs",
+        ]
+    );
+
+    assert!(Table::new(TableStyle::Ascii, ["empty"]).lines()[2..].is_empty());
+}
@@ -0,0 +1,148 @@
+//! Ordering of the constraints relative to the allocation of their witnesses
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pattern, CircuitDescription, GateMatch, Warning, Wire, WiredWitnesses,
+};
+
+/// Position of a constraint in the allocation timeline of the witnesses
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct TimelineEntry {
+    /// Id of the constraint
+    pub constraint: usize,
+    /// Maximum id of the witnesses wired to the constraint
+    pub witness: usize,
+    /// Latest constraint after which a wired witness was allocated
+    pub allocated_at: usize,
+}
+
+impl TimelineEntry {
+    /// Check if the constraint references a witness allocated after it
+    pub const fn is_violation(&self) -> bool {
+        self.allocated_at > self.constraint
+    }
+}
+
+/// Constraints of a circuit mapped to the allocation of their wired witnesses.
+///
+/// Witnesses are allocated in the order of their ids. The allocation of a
+/// witness is placed at its originating constraint; a witness without one is
+/// placed along with the preceding witness of the table. A constraint that
+/// wires a witness placed after it is a violation, and often the symptom of a
+/// circuit builder that appends gates before their inputs.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Timeline {
+    entries: Vec<TimelineEntry>,
+    warnings: Vec<Warning>,
+    patterns: Vec<GateMatch>,
+}
+
+impl Timeline {
+    /// Entries of the timeline, indexed by constraint
+    pub fn entries(&self) -> &[TimelineEntry] {
+        &self.entries
+    }
+
+    /// Entries of the constraints that reference witnesses allocated after
+    /// them
+    pub fn violations(&self) -> impl Iterator<Item = &TimelineEntry> {
+        self.entries.iter().filter(|e| e.is_violation())
+    }
+
+    /// Violations of the timeline, one per offending wire
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Gate patterns spanning the violations, in the order of their
+    /// constraints
+    pub fn patterns(&self) -> &[GateMatch] {
+        &self.patterns
+    }
+
+    /// Gate pattern spanning a violating constraint, such as
+    /// `Poseidon round 12`
+    pub fn pattern(&self, constraint: usize) -> Option<&GateMatch> {
+        pattern::find(&self.patterns, constraint)
+    }
+
+    pub(crate) fn set_patterns(&mut self, mut patterns: Vec<GateMatch>) {
+        patterns.dedup();
+
+        self.patterns = patterns;
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        anchors: &[usize],
+        constraint: usize,
+        wires: &WiredWitnesses,
+    ) {
+        let mut entry = TimelineEntry {
+            constraint,
+            ..Default::default()
+        };
+
+        for wire in Wire::ALL {
+            let witness = wire.of(wires);
+
+            // out of bounds wires are reported by the wirecheck
+            let allocated_at = match anchors.get(witness) {
+                Some(a) => *a,
+                None => continue,
+            };
+
+            entry.witness = entry.witness.max(witness);
+            entry.allocated_at = entry.allocated_at.max(allocated_at);
+
+            if allocated_at > constraint {
+                self.warnings.push(Warning::WitnessAllocatedLater {
+                    constraint,
+                    wire,
+                    witness,
+                    allocated_at,
+                });
+            }
+        }
+
+        self.entries.push(entry);
+    }
+}
+
+/// Constraint after which each witness of the table is allocated, indexed by
+/// witness.
+pub(crate) fn anchors<S>(
+    cdf: &mut CircuitDescription<S>,
+) -> io::Result<Vec<usize>>
+where
+    S: io::Read + io::Seek,
+{
+    let eof = cdf.preamble().constraints.saturating_sub(1);
+    let mut anchor = 0;
+    let mut anchors = Vec::with_capacity(cdf.preamble().witnesses);
+
+    for idx in 0..cdf.preamble().witnesses {
+        if let Some(constraint) = cdf.fetch_witness(idx)?.constraint() {
+            anchor = constraint.min(eof);
+        }
+
+        anchors.push(anchor);
+    }
+
+    Ok(anchors)
+}
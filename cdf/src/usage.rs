@@ -0,0 +1,262 @@
+//! Breakdown of the size of a CDF file per source and gadget
+
+use std::collections::HashMap;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pattern, CircuitDescription, Element, EncodableConstraint,
+    EncodableWitness, PatternUsage,
+};
+
+/// Contribution of a source file to the CDF file
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct SourceUsage {
+    /// Path of the source
+    pub name: String,
+    /// Encoded bytes of the source contents and of its witnesses and
+    /// constraints
+    pub bytes: usize,
+    /// Number of constraints defined in the source
+    pub constraints: usize,
+    /// Number of witnesses allocated in the source
+    pub witnesses: usize,
+    /// Number of lines of the source
+    pub lines: usize,
+    /// Number of constraints of the source whose evaluation failed
+    #[serde(default)]
+    pub failures: usize,
+}
+
+impl SourceUsage {
+    /// Average number of constraints per line of the source
+    pub fn density(&self) -> f64 {
+        if self.lines == 0 {
+            0.0
+        } else {
+            self.constraints as f64 / self.lines as f64
+        }
+    }
+
+    /// Fraction of the constraints of the source whose evaluation failed
+    pub fn failure_rate(&self) -> f64 {
+        if self.constraints == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.constraints as f64
+        }
+    }
+}
+
+/// Number of constraints and witnesses attributed to a source
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct SourceCounts {
+    /// Path of the source
+    pub name: String,
+    /// Number of constraints defined in the source
+    pub constraints: usize,
+    /// Number of witnesses allocated in the source
+    pub witnesses: usize,
+}
+
+/// Constraints defined by a single line of a source
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+pub struct GadgetUsage {
+    /// Path of the source
+    pub name: String,
+    /// Line of the source
+    pub line: u64,
+    /// Number of constraints defined by the line
+    pub constraints: usize,
+}
+
+/// Sources and gadgets of a circuit, ordered by their footprint.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct CircuitUsage {
+    /// Sources, ordered by their encoded bytes
+    pub sources: Vec<SourceUsage>,
+    /// Gadgets, ordered by their number of constraints
+    pub gadgets: Vec<GadgetUsage>,
+    /// Sources with failed constraints, ordered by their number of failures
+    #[serde(default)]
+    pub heatmap: Vec<SourceUsage>,
+    /// Gate patterns, ordered by their number of constraints
+    #[serde(default)]
+    pub patterns: Vec<PatternUsage>,
+}
+
+impl CircuitUsage {
+    /// Keep only the first `limit` sources, gadgets, heatmap entries and
+    /// patterns
+    pub fn truncate(&mut self, limit: usize) {
+        self.sources.truncate(limit);
+        self.gadgets.truncate(limit);
+        self.heatmap.truncate(limit);
+        self.patterns.truncate(limit);
+    }
+}
+
+pub(crate) fn counts<S>(
+    cdf: &mut CircuitDescription<S>,
+) -> io::Result<Vec<SourceCounts>>
+where
+    S: io::Read + io::Seek,
+{
+    let mut counts: Vec<_> = cdf
+        .source_names()
+        .map(|name| SourceCounts {
+            name: name.to_string(),
+            ..Default::default()
+        })
+        .collect();
+
+    let index: HashMap<String, usize> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.name.clone(), i))
+        .collect();
+
+    for idx in 0..cdf.preamble().witnesses {
+        let witness = cdf.fetch_witness(idx)?;
+
+        if let Some(i) = index.get(witness.name()) {
+            counts[*i].witnesses += 1;
+        }
+    }
+
+    for idx in 0..cdf.preamble().constraints {
+        let constraint = cdf.fetch_constraint(idx)?;
+
+        if let Some(i) = index.get(constraint.name()) {
+            counts[*i].constraints += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+pub(crate) fn usage<S>(
+    cdf: &mut CircuitDescription<S>,
+) -> io::Result<CircuitUsage>
+where
+    S: io::Read + io::Seek,
+{
+    let config = cdf.preamble().config;
+    let witness_len = EncodableWitness::len(&config);
+    let constraint_len = EncodableConstraint::len(&config);
+
+    let mut sources: HashMap<String, SourceUsage> = cdf
+        .sources()
+        .map(|(name, contents)| {
+            let usage = SourceUsage {
+                name: name.to_string(),
+                bytes: contents.len(),
+                lines: contents.lines().count(),
+                ..Default::default()
+            };
+
+            (name.to_string(), usage)
+        })
+        .collect();
+
+    let mut gadgets: HashMap<(String, u64), usize> = HashMap::new();
+
+    for idx in 0..cdf.preamble().witnesses {
+        let witness = cdf.fetch_witness(idx)?;
+
+        if let Some(source) = sources.get_mut(witness.name()) {
+            source.witnesses += 1;
+            source.bytes += witness_len;
+        }
+    }
+
+    for idx in 0..cdf.preamble().constraints {
+        let constraint = cdf.fetch_constraint(idx)?;
+
+        if let Some(source) = sources.get_mut(constraint.name()) {
+            source.constraints += 1;
+            source.bytes += constraint_len;
+
+            if !constraint.polynomial().is_ok() {
+                source.failures += 1;
+            }
+        }
+
+        *gadgets
+            .entry((constraint.name().to_string(), constraint.line()))
+            .or_default() += 1;
+    }
+
+    let mut sources: Vec<_> = sources.into_values().collect();
+    let mut gadgets: Vec<_> = gadgets
+        .into_iter()
+        .map(|((name, line), constraints)| GadgetUsage {
+            name,
+            line,
+            constraints,
+        })
+        .collect();
+
+    let mut heatmap: Vec<_> =
+        sources.iter().filter(|s| s.failures > 0).cloned().collect();
+
+    heatmap.sort_by(|a, b| {
+        b.failures
+            .cmp(&a.failures)
+            .then(b.failure_rate().total_cmp(&a.failure_rate()))
+            .then(a.name.cmp(&b.name))
+    });
+
+    sources.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.name.cmp(&b.name)));
+    gadgets.sort_by(|a, b| {
+        b.constraints
+            .cmp(&a.constraints)
+            .then(a.name.cmp(&b.name))
+            .then(a.line.cmp(&b.line))
+    });
+
+    let patterns = pattern::usage(cdf.gate_matches()?);
+
+    Ok(CircuitUsage {
+        sources,
+        gadgets,
+        heatmap,
+        patterns,
+    })
+}
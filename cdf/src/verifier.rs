@@ -0,0 +1,113 @@
+//! Consistency of a CDF file with the circuit that is actually proved
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::plonk;
+use crate::{Scalar, Selector, Selectors, Warning};
+
+/// Selectors of the gates of a compiled circuit, in the serde representation
+/// exported along with its verifier data.
+///
+/// This isn't the verifier key of `dusk-plonk`: the key commits to the
+/// selector polynomials, so the selectors of a gate can't be read back from
+/// it, and the crate doesn't depend on `dusk-plonk`. The prover side exports
+/// the selectors of every gate of the compiled circuit when it builds the
+/// key. A check against the commitments of the key itself is left for a
+/// later version.
+///
+/// The gates are indexed as the constraints of the CDF file; the compiled
+/// circuit may pad them up to the size of its domain with empty gates. A
+/// mismatch reveals a CDF file dumped from another version of the circuit
+/// than the one that is proved. The public inputs aren't part of the
+/// compiled circuit, so [`Selector::Pi`] isn't checked.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct VerifierData {
+    /// Selectors of the gates of the compiled circuit, in order
+    pub gates: Vec<Selectors>,
+}
+
+impl VerifierData {
+    /// Parse the verifier data, as JSON if it starts with an object and as
+    /// bincode otherwise
+    pub fn from_slice(bytes: &[u8]) -> io::Result<Self> {
+        plonk::from_json_or_bincode(bytes)
+    }
+
+    /// Read the verifier data from a path
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_slice(&fs::read(path)?)
+    }
+
+    /// Check the selectors of a constraint against the ones of its gate
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Scalar, Selector, Selectors, VerifierData, Warning};
+    ///
+    /// let data = VerifierData {
+    ///     gates: vec![Selectors::default()],
+    /// };
+    ///
+    /// let selectors = Selectors {
+    ///     qc: Scalar::from([1; Scalar::LEN]),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     data.check(0, &selectors),
+    ///     vec![Warning::VerifierSelectorMismatch {
+    ///         constraint: 0,
+    ///         selector: Selector::Qc,
+    ///     }]
+    /// );
+    /// assert_eq!(
+    ///     data.check(1, &selectors),
+    ///     vec![Warning::VerifierGateMissing { constraint: 1 }]
+    /// );
+    /// ```
+    pub fn check(
+        &self,
+        constraint: usize,
+        selectors: &Selectors,
+    ) -> Vec<Warning> {
+        let gate = match self.gates.get(constraint) {
+            Some(gate) => gate,
+            None => return vec![Warning::VerifierGateMissing { constraint }],
+        };
+
+        Selector::ALL
+            .iter()
+            .filter(|s| **s != Selector::Pi && s.of(selectors) != s.of(gate))
+            .map(|selector| Warning::VerifierSelectorMismatch {
+                constraint,
+                selector: *selector,
+            })
+            .collect()
+    }
+
+    /// Check that the gates past the constraints of the CDF file are padding
+    pub(crate) fn check_padding(&self, constraints: usize) -> Vec<Warning> {
+        self.gates
+            .iter()
+            .enumerate()
+            .skip(constraints)
+            .filter(|(_, gate)| {
+                Selector::ALL
+                    .iter()
+                    .filter(|s| **s != Selector::Pi)
+                    .any(|s| s.of(gate) != &Scalar::default())
+            })
+            .map(|(gate, _)| Warning::VerifierGateUnrecorded { gate })
+            .collect()
+    }
+}
@@ -0,0 +1,536 @@
+//! Structured warnings produced while decoding and analyzing a CDF file
+
+use std::fmt;
+
+#[cfg(feature = "decoder")]
+use std::collections::HashSet;
+#[cfg(feature = "decoder")]
+use std::mem;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Scalar, Selectors, WiredWitnesses};
+
+/// Wire of a polynomial
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Wire {
+    /// Wired `a`
+    A,
+    /// Wired `b`
+    B,
+    /// Wired `d` (fourth)
+    D,
+    /// Wired `o` (output)
+    O,
+}
+
+impl Wire {
+    /// All the wires of a polynomial
+    pub const ALL: [Self; 4] = [Self::A, Self::B, Self::D, Self::O];
+
+    /// Index of the witness wired to this wire
+    pub const fn of(&self, witnesses: &WiredWitnesses) -> usize {
+        match self {
+            Self::A => witnesses.a,
+            Self::B => witnesses.b,
+            Self::D => witnesses.d,
+            Self::O => witnesses.o,
+        }
+    }
+}
+
+impl fmt::Display for Wire {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::A => f.write_str("a"),
+            Self::B => f.write_str("b"),
+            Self::D => f.write_str("d"),
+            Self::O => f.write_str("o"),
+        }
+    }
+}
+
+/// Selector of a polynomial
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Selector {
+    /// Qm (mult) selector
+    Qm,
+    /// Ql (left) selector
+    Ql,
+    /// Qr (right) selector
+    Qr,
+    /// Qd (fourth) selector
+    Qd,
+    /// Qc (constant) selector
+    Qc,
+    /// Qo (output) selector
+    Qo,
+    /// Public input
+    Pi,
+    /// Qarith (arithmetic) internal selector
+    Qarith,
+    /// Qlogic (logical) internal selector
+    Qlogic,
+    /// Qrange (range check) internal selector
+    Qrange,
+    /// Qgroup_variable (ecc group variable add) internal selector
+    QgroupVariable,
+    /// Qgroup_fixed (ecc group fixed add) internal selector
+    QfixedAdd,
+}
+
+impl Selector {
+    /// All the selectors of a polynomial
+    pub const ALL: [Self; 12] = [
+        Self::Qm,
+        Self::Ql,
+        Self::Qr,
+        Self::Qd,
+        Self::Qc,
+        Self::Qo,
+        Self::Pi,
+        Self::Qarith,
+        Self::Qlogic,
+        Self::Qrange,
+        Self::QgroupVariable,
+        Self::QfixedAdd,
+    ];
+
+    /// Value of this selector
+    pub const fn of<'a>(&self, selectors: &'a Selectors) -> &'a Scalar {
+        match self {
+            Self::Qm => &selectors.qm,
+            Self::Ql => &selectors.ql,
+            Self::Qr => &selectors.qr,
+            Self::Qd => &selectors.qd,
+            Self::Qc => &selectors.qc,
+            Self::Qo => &selectors.qo,
+            Self::Pi => &selectors.pi,
+            Self::Qarith => &selectors.qarith,
+            Self::Qlogic => &selectors.qlogic,
+            Self::Qrange => &selectors.qrange,
+            Self::QgroupVariable => &selectors.qgroup_variable,
+            Self::QfixedAdd => &selectors.qfixed_add,
+        }
+    }
+
+    /// Name of the selector field
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Qm => "qm",
+            Self::Ql => "ql",
+            Self::Qr => "qr",
+            Self::Qd => "qd",
+            Self::Qc => "qc",
+            Self::Qo => "qo",
+            Self::Pi => "pi",
+            Self::Qarith => "qarith",
+            Self::Qlogic => "qlogic",
+            Self::Qrange => "qrange",
+            Self::QgroupVariable => "qgroup_variable",
+            Self::QfixedAdd => "qfixed_add",
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Well-known scalar, often left in a witness as a placeholder
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Constant {
+    /// `0`
+    Zero,
+    /// `1`
+    One,
+    /// `-1`, the largest scalar of the BLS12-381 scalar field
+    MinusOne,
+    /// Coordinate `u` of the generator of JubJub, the embedded curve of
+    /// `dusk-plonk`
+    GeneratorU,
+    /// Coordinate `v` of the generator of JubJub
+    GeneratorV,
+}
+
+impl Constant {
+    /// All the well-known constants
+    pub const ALL: [Self; 5] = [
+        Self::Zero,
+        Self::One,
+        Self::MinusOne,
+        Self::GeneratorU,
+        Self::GeneratorV,
+    ];
+
+    /// Value of the constant, in little endian
+    pub const fn bytes(&self) -> [u8; Scalar::LEN] {
+        let mut bytes = [0u8; Scalar::LEN];
+
+        match self {
+            Self::Zero => (),
+            Self::One => bytes[0] = 0x01,
+            Self::MinusOne => {
+                bytes = Scalar::MODULUS;
+                bytes[0] = 0x00;
+            }
+            Self::GeneratorU => {
+                bytes = [
+                    0xca, 0xea, 0x7b, 0xec, 0xff, 0xb7, 0xf7, 0x4d, 0xed, 0x54,
+                    0x6c, 0xfd, 0x21, 0xbb, 0x3e, 0x2e, 0xe6, 0xcc, 0xd6, 0x0f,
+                    0x2d, 0xf0, 0xfb, 0xf1, 0xa6, 0x65, 0xac, 0x43, 0x4c, 0x81,
+                    0xd2, 0x3f,
+                ]
+            }
+            Self::GeneratorV => bytes[0] = 0x12,
+        }
+
+        bytes
+    }
+
+    /// Well-known constant equal to `value`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::{Constant, Scalar};
+    ///
+    /// assert_eq!(Constant::of(&Scalar::default()), Some(Constant::Zero));
+    /// assert_eq!(Constant::of(&Scalar::from([0x07; Scalar::LEN])), None);
+    /// ```
+    pub fn of(value: &Scalar) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|c| value.as_ref() == c.bytes().as_slice())
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero => f.write_str("0"),
+            Self::One => f.write_str("1"),
+            Self::MinusOne => f.write_str("-1"),
+            Self::GeneratorU => f.write_str("the u of the JubJub generator"),
+            Self::GeneratorV => f.write_str("the v of the JubJub generator"),
+        }
+    }
+}
+
+/// Inconsistency of the circuit that doesn't prevent it from being debugged
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Warning {
+    /// The wire references a witness that isn't in the witness table
+    WireOutOfBounds {
+        /// Id of the constraint
+        constraint: usize,
+        /// Inconsistent wire
+        wire: Wire,
+        /// Wired witness index
+        witness: usize,
+    },
+    /// The witness table entry referenced by the wire has a different id
+    WireMismatch {
+        /// Id of the constraint
+        constraint: usize,
+        /// Inconsistent wire
+        wire: Wire,
+        /// Wired witness index
+        witness: usize,
+        /// Id of the witness found in the table
+        found: usize,
+    },
+    /// The wire references a witness allocated after the constraint
+    WitnessAllocatedLater {
+        /// Id of the constraint
+        constraint: usize,
+        /// Offending wire
+        wire: Wire,
+        /// Wired witness index
+        witness: usize,
+        /// Constraint after which the witness is allocated
+        allocated_at: usize,
+    },
+    /// The value of the witness isn't a canonical scalar
+    NonCanonicalWitness {
+        /// Index of the witness
+        witness: usize,
+        /// Path of the source file of the witness
+        path: String,
+        /// Line of the witness in the source file
+        line: u64,
+        /// Column of the witness in the source file
+        col: u64,
+    },
+    /// The selector of the constraint isn't a canonical scalar
+    NonCanonicalSelector {
+        /// Id of the constraint
+        constraint: usize,
+        /// Offending selector
+        selector: Selector,
+        /// Path of the source file of the constraint
+        path: String,
+        /// Line of the constraint in the source file
+        line: u64,
+        /// Column of the constraint in the source file
+        col: u64,
+    },
+    /// The constraint record couldn't be decoded and was skipped
+    CorruptConstraint {
+        /// Id of the constraint
+        constraint: usize,
+        /// Offset of the record in the CDF file
+        offset: u64,
+    },
+    /// The name or the contents of the source isn't valid UTF-8, and was
+    /// decoded with replacement characters
+    LossySource {
+        /// Index of the source in the source cache
+        source: usize,
+    },
+    /// The witness holds a well-known constant, but no constant gate fixes
+    /// it; often a placeholder that was never assigned
+    UnfixedConstant {
+        /// Index of the witness
+        witness: usize,
+        /// Constant held by the witness
+        constant: Constant,
+    },
+    /// The selector of the constraint differs from the one of its gate in the
+    /// verifier data of the circuit
+    VerifierSelectorMismatch {
+        /// Id of the constraint
+        constraint: usize,
+        /// Offending selector
+        selector: Selector,
+    },
+    /// The verifier data of the circuit has no gate for the constraint
+    VerifierGateMissing {
+        /// Id of the constraint
+        constraint: usize,
+    },
+    /// The verifier data of the circuit enables the selectors of a gate past
+    /// the constraints of the CDF file
+    VerifierGateUnrecorded {
+        /// Index of the gate
+        gate: usize,
+    },
+}
+
+impl Warning {
+    /// Kind of the warning, as tagged when serialized
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_cdf::Warning;
+    ///
+    /// let warning = Warning::VerifierGateMissing { constraint: 3 };
+    ///
+    /// assert_eq!(warning.kind(), "verifierGateMissing");
+    /// ```
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::WireOutOfBounds { .. } => "wireOutOfBounds",
+            Self::WireMismatch { .. } => "wireMismatch",
+            Self::WitnessAllocatedLater { .. } => "witnessAllocatedLater",
+            Self::NonCanonicalWitness { .. } => "nonCanonicalWitness",
+            Self::NonCanonicalSelector { .. } => "nonCanonicalSelector",
+            Self::CorruptConstraint { .. } => "corruptConstraint",
+            Self::LossySource { .. } => "lossySource",
+            Self::UnfixedConstant { .. } => "unfixedConstant",
+            Self::VerifierSelectorMismatch { .. } => "verifierSelectorMismatch",
+            Self::VerifierGateMissing { .. } => "verifierGateMissing",
+            Self::VerifierGateUnrecorded { .. } => "verifierGateUnrecorded",
+        }
+    }
+
+    /// Id of the constraint that produced the warning, if any
+    pub const fn constraint(&self) -> Option<usize> {
+        match self {
+            Self::WireOutOfBounds { constraint, .. }
+            | Self::WireMismatch { constraint, .. }
+            | Self::WitnessAllocatedLater { constraint, .. }
+            | Self::NonCanonicalSelector { constraint, .. }
+            | Self::CorruptConstraint { constraint, .. }
+            | Self::VerifierSelectorMismatch { constraint, .. }
+            | Self::VerifierGateMissing { constraint } => Some(*constraint),
+
+            Self::NonCanonicalWitness { .. }
+            | Self::LossySource { .. }
+            | Self::UnfixedConstant { .. }
+            | Self::VerifierGateUnrecorded { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WireOutOfBounds {
+                constraint,
+                wire,
+                witness,
+            } => write!(
+                f,
+                "constraint #{}: wire {} references witness #{}, which is \
+                 not in the witness table",
+                constraint, wire, witness
+            ),
+
+            Self::WireMismatch {
+                constraint,
+                wire,
+                witness,
+                found,
+            } => write!(
+                f,
+                "constraint #{}: wire {} references witness #{}, but the \
+                 table contains witness #{}",
+                constraint, wire, witness, found
+            ),
+
+            Self::WitnessAllocatedLater {
+                constraint,
+                wire,
+                witness,
+                allocated_at,
+            } => write!(
+                f,
+                "constraint #{}: wire {} references witness #{}, which is \
+                 allocated after constraint #{}",
+                constraint, wire, witness, allocated_at
+            ),
+
+            Self::NonCanonicalWitness {
+                witness,
+                path,
+                line,
+                col,
+            } => write!(
+                f,
+                "witness #{} at {}:{}:{}: the value isn't a canonical scalar",
+                witness, path, line, col
+            ),
+
+            Self::NonCanonicalSelector {
+                constraint,
+                selector,
+                path,
+                line,
+                col,
+            } => write!(
+                f,
+                "constraint #{} at {}:{}:{}: selector {} isn't a canonical \
+                 scalar",
+                constraint, path, line, col, selector
+            ),
+
+            Self::CorruptConstraint { constraint, offset } => write!(
+                f,
+                "constraint #{}: the record at offset {} is corrupt and was \
+                 skipped",
+                constraint, offset
+            ),
+
+            Self::LossySource { source } => write!(
+                f,
+                "source #{}: invalid utf-8 was replaced by U+FFFD",
+                source
+            ),
+
+            Self::UnfixedConstant { witness, constant } => write!(
+                f,
+                "witness #{}: the value is {}, but no constant gate fixes it",
+                witness, constant
+            ),
+
+            Self::VerifierSelectorMismatch {
+                constraint,
+                selector,
+            } => write!(
+                f,
+                "constraint #{}: selector {} differs from the verifier data",
+                constraint, selector
+            ),
+
+            Self::VerifierGateMissing { constraint } => write!(
+                f,
+                "constraint #{}: the verifier data has no gate for it",
+                constraint
+            ),
+
+            Self::VerifierGateUnrecorded { gate } => write!(
+                f,
+                "gate #{}: the verifier data enables selectors of a gate \
+                 missing from the CDF file",
+                gate
+            ),
+        }
+    }
+}
+
+/// Warnings pending to be reported.
+///
+/// A warning is queued only once, even if the element that produced it is
+/// decoded multiple times.
+#[cfg(feature = "decoder")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Warnings {
+    pending: Vec<Warning>,
+    reported: HashSet<Warning>,
+}
+
+#[cfg(feature = "decoder")]
+impl Warnings {
+    pub fn push(&mut self, warning: Warning) {
+        if self.reported.insert(warning.clone()) {
+            self.pending.push(warning);
+        }
+    }
+
+    pub fn take(&mut self) -> Vec<Warning> {
+        mem::take(&mut self.pending)
+    }
+}
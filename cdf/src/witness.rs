@@ -1,10 +1,11 @@
 use std::io;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     Config, DecodableElement, DecodedSource, DecoderContext, Element,
     EncodableElement, EncodableSource, EncoderContext, Preamble, Scalar,
+    SourceId,
 };
 
 /// Analogous to [`Witness`]. This is a witness that can be encoded into a
@@ -13,7 +14,16 @@ use crate::{
 /// This allows the [`Encoder`](struct.Encoder.html) to encode the constraint
 /// into a cdf file.
 #[derive(
-    Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 pub struct EncodableWitness {
     id: usize,
@@ -134,7 +144,8 @@ impl<'a> Witness<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::ZkDebugger;
     /// use std::fs::File;
@@ -155,7 +166,8 @@ impl<'a> Witness<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::ZkDebugger;
     /// use std::fs::File;
@@ -174,7 +186,8 @@ impl<'a> Witness<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Scalar, ZkDebugger};
     /// use std::fs::File;
@@ -194,7 +207,8 @@ impl<'a> Witness<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Scalar, ZkDebugger};
     /// use std::fs::File;
@@ -213,7 +227,8 @@ impl<'a> Witness<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Scalar, ZkDebugger};
     /// use std::fs::File;
@@ -234,7 +249,8 @@ impl<'a> Witness<'a> {
     ///
     /// # Example
     ///
-    /// ```
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Scalar, ZkDebugger};
     /// use std::fs::File;
@@ -249,11 +265,37 @@ impl<'a> Witness<'a> {
         self.source.name
     }
 
-    /// Source code contents
+    /// Interned id of the source file, shared by the items of a same file
     ///
     /// # Example
     ///
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let mut debugger = ZkDebugger::from_reader(file)?;
+    /// let witness = debugger.fetch_witness(4)?;
+    ///
+    /// let (source, name) = (witness.source_id(), witness.name().to_string());
+    /// let constraint = debugger.fetch_constraint(0)?;
+    ///
+    /// assert_eq!(source == constraint.source_id(), name == constraint.name());
+    ///
+    /// # Ok(()) }
     /// ```
+    pub const fn source_id(&self) -> SourceId {
+        self.source.id
+    }
+
+    /// Source code contents
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "debugger", doc = "```")]
+    #[cfg_attr(not(feature = "debugger"), doc = "```ignore")]
     /// # fn main() -> std::io::Result<()> {
     /// use dusk_cdf::{Scalar, ZkDebugger};
     /// use std::fs::File;
@@ -1,17 +1,43 @@
+mod allocation;
+mod backend;
 mod breakpoint;
+mod focus;
+mod lines;
+mod progress;
+mod shared;
 mod state;
+mod subscription;
+mod until;
 
 use std::fs::File;
 use std::io;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 use std::path::Path;
-
-use crate::{CircuitDescription, Config, Constraint, Preamble, Witness};
-
-use breakpoint::Breakpoints;
-
-pub use breakpoint::Breakpoint;
-pub use state::State;
+use std::sync::Arc;
+
+use crate::memory::{Cache, HeapSize};
+use crate::{
+    CircuitDescription, Config, Constraint, CorruptionPolicy, MemoryUsage,
+    Preamble, ResolvedConstraint, SourceId, Warning, Witness,
+};
+
+use allocation::Allocations;
+use breakpoint::Hit;
+use lines::Lines;
+use subscription::Subscribers;
+
+pub use backend::DebuggerBackend;
+pub use breakpoint::{Breakpoint, Breakpoints};
+pub use focus::Focus;
+pub use progress::ScanProgress;
+pub use shared::{SharedZkDebugger, ZkDebuggerGuard};
+pub use state::{Location, State};
+pub use subscription::{Movement, StateChange};
+pub use until::{CancelToken, Until};
+
+/// Number of constraints visited by a scan between two interruption points
+const SCAN_CHUNK: usize = 4096;
 
 /// The Zk Debugger, it keeps track of breakpoints and the circuit description.
 ///
@@ -26,6 +52,13 @@ pub struct ZkDebugger<S> {
     breakpoints: Breakpoints,
     cdf: CircuitDescription<S>,
     constraint: usize,
+    allocations: Option<Allocations>,
+    allocation: Option<usize>,
+    lines: Option<Lines>,
+    focus: Option<Focus>,
+    focus_failures: bool,
+    paused: Option<(Movement, Anchor)>,
+    subscribers: Subscribers,
 }
 
 impl<S> Deref for ZkDebugger<S> {
@@ -48,6 +81,13 @@ impl<S> From<CircuitDescription<S>> for ZkDebugger<S> {
             breakpoints: Breakpoints::default(),
             cdf,
             constraint: 0,
+            allocations: None,
+            allocation: None,
+            lines: None,
+            focus: None,
+            focus_failures: false,
+            paused: None,
+            subscribers: Subscribers::default(),
         }
     }
 }
@@ -63,6 +103,11 @@ impl<S> ZkDebugger<S> {
         self.cdf.preamble()
     }
 
+    /// Id of the constraint the debugger is currently positioned at
+    pub const fn position(&self) -> usize {
+        self.constraint
+    }
+
     /// Add a breakpoint to the provided source/line.
     ///
     /// # Example
@@ -75,7 +120,8 @@ impl<S> ZkDebugger<S> {
     /// let mut debugger = ZkDebugger::from(circuit);
     /// let breakpoint = Breakpoint {
     ///     source: String::from("xyz"),
-    ///     line: Some(40)   
+    ///     line: Some(40),
+    ///     kind: None,
     /// };
     ///
     /// debugger.add_breakpoint(String::from("xyz"), Some(40));
@@ -91,7 +137,122 @@ impl<S> ZkDebugger<S> {
         source: String,
         line: Option<u64>,
     ) -> usize {
-        self.breakpoints.add(source, line)
+        self.insert_breakpoint(
+            Breakpoint {
+                source,
+                line,
+                kind: None,
+            },
+            false,
+        )
+    }
+
+    /// Add a breakpoint, optionally qualified by a gate kind. A temporary
+    /// breakpoint is removed after its first hit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{Breakpoint, GateKind, ZkDebugger};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let mut breakpoint = Breakpoint {
+    ///     source: String::from("gadget.rs"),
+    ///     line: None,
+    ///     kind: Some(GateKind::Logic),
+    /// };
+    ///
+    /// debugger.insert_breakpoint(breakpoint.clone(), false);
+    ///
+    /// // the circuit has no logic gate
+    /// assert!(debugger.cont()?.breakpoint().is_none());
+    ///
+    /// breakpoint.kind = Some(GateKind::Arithmetic);
+    /// let id = debugger.insert_breakpoint(breakpoint, false);
+    ///
+    /// debugger.goto(0)?;
+    /// assert_eq!(debugger.cont()?.breakpoint(), Some(id));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn insert_breakpoint(
+        &mut self,
+        breakpoint: Breakpoint,
+        temporary: bool,
+    ) -> usize {
+        let len = self.breakpoints.len();
+        let id = if temporary {
+            self.breakpoints.add_temporary(breakpoint.clone())
+        } else {
+            self.breakpoints.insert(breakpoint.clone())
+        };
+
+        if len != self.breakpoints.len() {
+            self.subscribers
+                .notify(|| StateChange::BreakpointAdded { id, breakpoint });
+        }
+
+        id
+    }
+
+    /// Add a temporary breakpoint to the provided source/line, removed after
+    /// its first hit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{State, ZkDebugger};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let id = debugger.add_temporary_breakpoint("gadget.rs".into(), Some(8));
+    ///
+    /// assert_eq!(debugger.cont()?.breakpoint(), Some(id));
+    /// assert_eq!(debugger.fetch_breakpoint(id), None);
+    /// assert!(debugger.cont()?.breakpoint().is_none());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn add_temporary_breakpoint(
+        &mut self,
+        source: String,
+        line: Option<u64>,
+    ) -> usize {
+        self.insert_breakpoint(
+            Breakpoint {
+                source,
+                line,
+                kind: None,
+            },
+            true,
+        )
+    }
+
+    /// Ignore the next `count` hits of a breakpoint, replacing any previous
+    /// count; `0` stops at the next hit.
+    ///
+    /// Returns `false` if the breakpoint doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let id = debugger.add_breakpoint("naive-signature".into(), None);
+    ///
+    /// assert!(debugger.ignore_breakpoint(id, 1));
+    /// assert!(!debugger.ignore_breakpoint(id + 1, 1));
+    ///
+    /// // the hit at line 26 is ignored, stopping at line 28
+    /// assert_eq!(debugger.cont()?.constraint(), Some(16));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn ignore_breakpoint(&mut self, id: usize, count: usize) -> bool {
+        self.breakpoints.ignore(id, count)
     }
 
     /// Remove a breakpoint with the provided id.
@@ -108,7 +269,8 @@ impl<S> ZkDebugger<S> {
     /// let mut debugger = ZkDebugger::from(circuit);
     /// let breakpoint = Breakpoint {
     ///     source: String::from("xyz"),
-    ///     line: Some(40)   
+    ///     line: Some(40),
+    ///     kind: None,
     /// };
     ///
     /// debugger.add_breakpoint(String::from("xyz"), Some(40));
@@ -120,7 +282,51 @@ impl<S> ZkDebugger<S> {
     /// # Ok(()) }
     /// ```
     pub fn remove_breakpoint(&mut self, id: usize) -> Option<Breakpoint> {
-        self.breakpoints.remove(id)
+        let removed = self.breakpoints.remove(id);
+
+        if let Some(breakpoint) = &removed {
+            self.subscribers.notify(|| StateChange::BreakpointRemoved {
+                id,
+                breakpoint: breakpoint.clone(),
+            });
+        }
+
+        removed
+    }
+
+    /// Remove the breakpoints set with the source pattern `source`, or all of
+    /// them if it is `None`, returning their ids in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    ///
+    /// let a = debugger.add_breakpoint("lib.rs".into(), Some(25));
+    /// let b = debugger.add_breakpoint("lib.rs".into(), Some(26));
+    /// let c = debugger.add_breakpoint("gadget.rs".into(), None);
+    ///
+    /// assert_eq!(debugger.remove_breakpoints(Some("lib.rs")), vec![a, b]);
+    /// assert_eq!(debugger.remove_breakpoints(None), vec![c]);
+    /// assert!(debugger.breakpoints().is_empty());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn remove_breakpoints(&mut self, source: Option<&str>) -> Vec<usize> {
+        let mut ids: Vec<_> = self
+            .breakpoints
+            .iter()
+            .filter(|(b, _)| source.map(|s| b.source == s).unwrap_or(true))
+            .map(|(_, id)| *id)
+            .collect();
+
+        ids.sort_unstable();
+        ids.retain(|id| self.remove_breakpoint(*id).is_some());
+
+        ids
     }
 
     /// Fetch a breakpoint from an id returned from `add_breakpoint`.
@@ -135,7 +341,8 @@ impl<S> ZkDebugger<S> {
     /// let mut debugger = ZkDebugger::from(circuit);
     /// let breakpoint = Breakpoint {
     ///     source: String::from("xyz"),
-    ///     line: Some(40)   
+    ///     line: Some(40),
+    ///     kind: None,
     /// };
     ///
     /// debugger.add_breakpoint(String::from("xyz"), Some(40));
@@ -154,7 +361,278 @@ impl<S> ZkDebugger<S> {
 
     /// Remove all breakpoints that matches the source name
     pub fn clear_breakpoints(&mut self, source: &str) {
+        let len = self.breakpoints.len();
+
         self.breakpoints.clear(source);
+
+        if len != self.breakpoints.len() {
+            self.subscribers.notify(|| StateChange::BreakpointsCleared {
+                source: source.to_string(),
+            });
+        }
+    }
+
+    /// Replace the breakpoints repository, returning the previous one.
+    ///
+    /// The ids of the provided breakpoints are preserved, so a repository
+    /// taken from a debugger can be carried over to the one of a reloaded
+    /// circuit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{Breakpoints, ZkDebugger};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let id = debugger.add_breakpoint("gadget.rs".into(), Some(8));
+    ///
+    /// let breakpoints = debugger.replace_breakpoints(Breakpoints::default());
+    /// assert!(debugger.breakpoints().is_empty());
+    ///
+    /// let mut reloaded = ZkDebugger::open("../assets/test.cdf")?;
+    /// reloaded.replace_breakpoints(breakpoints);
+    ///
+    /// assert!(reloaded.fetch_breakpoint(id).is_some());
+    /// assert!(reloaded.add_breakpoint("main.rs".into(), None) > id);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn replace_breakpoints(
+        &mut self,
+        breakpoints: Breakpoints,
+    ) -> Breakpoints {
+        mem::replace(&mut self.breakpoints, breakpoints)
+    }
+
+    /// Ids of the breakpoints, in ascending order, whose source and line
+    /// match no constraint of the circuit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    ///
+    /// let bound = debugger.add_breakpoint("gadget.rs".into(), Some(8));
+    /// let unbound = debugger.add_breakpoint("gadget.rs".into(), Some(9));
+    /// let missing = debugger.add_breakpoint("lib.rs".into(), None);
+    ///
+    /// assert_eq!(debugger.unbound_breakpoints()?, vec![unbound, missing]);
+    /// assert!(debugger.unbound_breakpoints()?.iter().all(|id| *id != bound));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn unbound_breakpoints(&mut self) -> io::Result<Vec<usize>> {
+        if self.lines.is_none() {
+            self.lines.replace(Lines::load(&mut self.cdf)?);
+            self.reclaim(Some(Cache::Lines));
+        }
+
+        let lines = match &self.lines {
+            Some(lines) => lines,
+            None => return Ok(vec![]),
+        };
+
+        Ok(self
+            .breakpoints
+            .sorted()
+            .into_iter()
+            .filter(|(_, b)| !lines.contains(&b.source, b.line))
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// Check if witness allocations are surfaced as steps
+    pub const fn witness_steps(&self) -> bool {
+        self.allocations.is_some()
+    }
+
+    /// Portion of the circuit the navigation is limited to, if any
+    pub const fn focus(&self) -> Option<&Focus> {
+        self.focus.as_ref()
+    }
+
+    /// Check if the failures out of the focus stop the navigation
+    pub const fn focus_failures(&self) -> bool {
+        self.focus_failures
+    }
+
+    /// Limit the navigation to a portion of the circuit, or lift the limit
+    /// with `None`.
+    ///
+    /// [`Self::step`], [`Self::afore`], [`Self::cont`] and [`Self::turn`]
+    /// skip the constraints out of the focus: they never stop at them, and
+    /// report their failures only if `failures` is set. [`Self::goto`] is
+    /// not limited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{Focus, ZkDebugger};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    ///
+    /// debugger.set_focus(Some(Focus::Source("naive-signature".into())), false);
+    ///
+    /// assert_eq!(debugger.step()?.constraint(), Some(15));
+    /// assert_eq!(debugger.step()?.constraint(), Some(16));
+    /// assert!(debugger.step()?.is_end());
+    ///
+    /// debugger.set_focus(Some(Focus::Range { start: 6, end: 8 }), false);
+    ///
+    /// assert_eq!(debugger.afore()?.constraint(), Some(7));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn set_focus(&mut self, focus: Option<Focus>, failures: bool) {
+        self.focus = focus;
+        self.focus_failures = failures;
+    }
+
+    /// Id of the witness allocation the debugger is stopped at, if any.
+    ///
+    /// The debugger stops at witness allocations only when
+    /// [`Self::set_witness_steps`] is enabled.
+    pub fn current_witness(&self) -> Option<usize> {
+        self.allocation
+            .zip(self.allocations.as_ref())
+            .and_then(|(idx, allocations)| allocations.get(idx))
+            .map(|a| a.witness)
+    }
+
+    /// Subscribe to the changes of the debugger, returning the id of the
+    /// subscription.
+    ///
+    /// The subscriber is immediately notified with [`StateChange::Loaded`],
+    /// and then with every navigation and breakpoint mutation. Clones of the
+    /// debugger share its subscribers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{StateChange, ZkDebugger};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let changes = Arc::new(Mutex::new(vec![]));
+    /// let subscriber = Arc::clone(&changes);
+    ///
+    /// debugger.subscribe(move |change: &StateChange| {
+    ///     subscriber.lock().unwrap().push(change.clone());
+    /// });
+    ///
+    /// debugger.step()?;
+    /// debugger.add_breakpoint(String::from("xyz"), Some(40));
+    ///
+    /// assert_eq!(changes.lock().unwrap().len(), 3);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn subscribe<F>(&mut self, subscriber: F) -> usize
+    where
+        F: Fn(&StateChange) + Send + Sync + 'static,
+    {
+        subscriber(&StateChange::Loaded {
+            preamble: *self.preamble(),
+        });
+
+        self.subscribers.add(Arc::new(subscriber))
+    }
+
+    /// Remove a subscription, returning `true` if it was present
+    pub fn unsubscribe(&mut self, id: usize) -> bool {
+        self.subscribers.remove(id)
+    }
+
+    /// Memory held by the caches of the circuit and the debugger.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// assert_eq!(debugger.memory_usage().lines, 0);
+    ///
+    /// debugger.goto_line("gadget.rs", 8)?;
+    /// assert!(debugger.memory_usage().lines > 0);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            allocations: self
+                .allocations
+                .as_ref()
+                .map(HeapSize::heap_size)
+                .unwrap_or_default(),
+            lines: self
+                .lines
+                .as_ref()
+                .map(HeapSize::heap_size)
+                .unwrap_or_default(),
+            ..self.cdf.memory_usage()
+        }
+    }
+
+    /// Drop the caches other than `keep`, in eviction order, while they
+    /// exceed the memory budget
+    fn reclaim(&mut self, keep: Option<Cache>) {
+        for cache in Cache::EVICTION {
+            if !self.memory_usage().is_over_budget() {
+                return;
+            }
+
+            match cache {
+                _ if Some(cache) == keep => (),
+                Cache::Lines => self.lines = None,
+                cache => self.cdf.evict(cache),
+            }
+        }
+    }
+
+    fn navigated(
+        &mut self,
+        movement: Movement,
+        state: io::Result<State>,
+    ) -> io::Result<State> {
+        #[cfg(feature = "tracing")]
+        if let Some(to) = state.as_ref().ok().and_then(State::constraint) {
+            tracing::Span::current().record("to", to);
+        }
+
+        if let Ok(state) = &state {
+            self.subscribers.notify(|| StateChange::Navigated {
+                movement,
+                state: state.clone(),
+            });
+        }
+
+        // the paused scan is resumed only if nothing moved the debugger since
+        if !matches!(state, Ok(State::Paused { .. })) {
+            self.paused = None;
+        }
+
+        // a jump doesn't hit the breakpoint it lands on, so a temporary one
+        // is kept for the next scan
+        let temporary = state
+            .as_ref()
+            .ok()
+            .filter(|_| !matches!(movement, Movement::Goto { .. }))
+            .and_then(State::breakpoint)
+            .filter(|id| self.breakpoints.is_temporary(*id));
+
+        if let Some(id) = temporary {
+            self.remove_breakpoint(id);
+        }
+
+        state
     }
 }
 
@@ -167,6 +645,16 @@ impl ZkDebugger<File> {
     {
         CircuitDescription::open(path).map(Self::from)
     }
+
+    /// Use a path to create a new circuit description with the provided
+    /// decoding configuration. This uses
+    /// [`CircuitDescription::from_reader_with_config`].
+    pub fn open_with_config<P>(path: P, config: Config) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        CircuitDescription::open_with_config(path, config).map(Self::from)
+    }
 }
 
 impl<S> ZkDebugger<S>
@@ -187,7 +675,8 @@ where
     /// let mut debugger = ZkDebugger::from_reader(file)?;
     /// let breakpoint = Breakpoint {
     ///     source: String::from("xyz"),
-    ///     line: Some(40)   
+    ///     line: Some(40),
+    ///     kind: None,
     /// };
     ///
     /// debugger.add_breakpoint(String::from("xyz"), Some(40));
@@ -199,6 +688,52 @@ where
         CircuitDescription::from_reader(source).map(Self::from)
     }
 
+    /// Create a CDF with the provided source and decoding configuration, and
+    /// use it as backend for the debugger.
+    pub fn from_reader_with_config(
+        source: S,
+        config: Config,
+    ) -> io::Result<Self> {
+        CircuitDescription::from_reader_with_config(source, config)
+            .map(Self::from)
+    }
+
+    /// Surface the witness allocations as stoppable steps.
+    ///
+    /// When enabled, [`Self::step`] will stop at every witness allocated
+    /// between the current constraint and the next one, in the order of the
+    /// witness ids, returning [`State::Witness`]. A witness is allocated after
+    /// the constraint that originated it; if it has none, it is allocated
+    /// along with the preceding witness of the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{ZkDebugger, State};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    ///
+    /// debugger.set_witness_steps(true)?;
+    ///
+    /// assert!(matches!(debugger.step()?, State::Witness { .. }));
+    /// assert!(debugger.current_witness().is_some());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn set_witness_steps(&mut self, enabled: bool) -> io::Result<()> {
+        self.allocation = None;
+        self.allocations = if enabled {
+            Some(Allocations::load(&mut self.cdf)?)
+        } else {
+            None
+        };
+
+        self.reclaim(None);
+
+        Ok(())
+    }
+
     /// Attempt to fetch the current constraint from the source.
     ///
     /// # Example
@@ -220,6 +755,30 @@ where
         self.cdf.fetch_constraint(self.constraint)
     }
 
+    /// Attempt to fetch the current constraint, along with the witnesses wired
+    /// to its polynomial.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let mut debugger = ZkDebugger::from_reader(file)?;
+    /// let resolved = debugger.resolve_current_constraint()?;
+    ///
+    /// assert_eq!(resolved.constraint.id(), 0);
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn resolve_current_constraint(
+        &mut self,
+    ) -> io::Result<ResolvedConstraint> {
+        self.cdf.resolve_constraint(self.constraint)
+    }
+
     /// Attempt to read an indexed constraint from the source.
     ///
     /// # Example
@@ -281,11 +840,35 @@ where
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "afore",
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
     pub fn afore(&mut self) -> io::Result<State> {
+        let state = self.move_afore();
+
+        self.navigated(Movement::Afore, state)
+    }
+
+    fn move_afore(&mut self) -> io::Result<State> {
+        self.allocation = None;
+
         let Self {
             breakpoints,
             cdf,
             constraint,
+            focus,
+            focus_failures,
+            ..
         } = self;
 
         let mut idx = *constraint;
@@ -293,9 +876,7 @@ where
             return Ok(State::Beginning);
         }
 
-        let current = cdf.fetch_constraint(idx)?;
-        let source = current.name().to_string();
-        let line = current.line();
+        let anchor = Anchor::fetch(cdf, idx)?;
 
         loop {
             idx -= 1;
@@ -305,30 +886,58 @@ where
                 return Ok(State::Beginning);
             }
 
-            let current = cdf.fetch_constraint(idx)?;
-            let is_invalid = !current.polynomial().evaluation;
-            let different_line =
-                source != current.name() || line != current.line();
+            let current = match visit(cdf, idx)? {
+                Visited::Constraint(current) => current,
+                Visited::Corrupt { offset } => {
+                    match corrupt(cdf, idx, offset) {
+                        Some(state) => {
+                            *constraint = idx;
+                            return Ok(state);
+                        }
+                        None => continue,
+                    }
+                }
+            };
+            let (focused, reported) = focus::visibility(
+                focus.as_ref(),
+                *focus_failures,
+                idx,
+                &current,
+            );
+            let is_invalid = reported && !current.polynomial().evaluation;
+            let different_line = anchor.is_different(&current);
 
             if different_line && is_invalid {
                 *constraint = idx;
-                return Ok(State::InvalidConstraint { id: idx });
+                return Ok(State::InvalidConstraint {
+                    id: idx,
+                    location: Location::from(&current),
+                });
+            }
+
+            if !focused {
+                continue;
             }
 
             if different_line {
-                if let Some(id) = breakpoints.find_breakpoint(&current) {
+                if let Hit::Stop(id) = breakpoints.hit(&current) {
                     *constraint = idx;
-                    return Ok(State::Breakpoint { id });
+                    return Ok(State::Breakpoint {
+                        id,
+                        constraint: idx,
+                        location: Location::from(&current),
+                    });
                 }
             }
 
             if different_line {
-                break;
+                *constraint = idx;
+                return Ok(State::Constraint {
+                    id: idx,
+                    location: Location::from(&current),
+                });
             }
         }
-
-        *constraint = idx;
-        Ok(State::Constraint { id: idx })
     }
 
     /// Continue the execution until EOF, breakpoint, or invalid constraint.
@@ -348,53 +957,250 @@ where
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "cont",
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
     pub fn cont(&mut self) -> io::Result<State> {
-        let Self {
-            breakpoints,
-            cdf,
-            constraint,
-        } = self;
-
-        let mut idx = *constraint;
-        let eof = cdf.preamble().constraints.saturating_sub(1);
-
-        if idx == eof {
-            return Ok(State::End { id: idx });
-        }
-
-        let current = cdf.fetch_constraint(idx)?;
-        let source = current.name().to_string();
-        let line = current.line();
+        let state = self.move_cont();
 
-        loop {
-            idx += 1;
-
-            let current = cdf.fetch_constraint(idx)?;
-            let is_invalid = !current.polynomial().evaluation;
-            let different_line =
-                source != current.name() || line != current.line();
-
-            if different_line && is_invalid {
-                *constraint = idx;
-                return Ok(State::InvalidConstraint { id: idx });
-            }
+        self.navigated(Movement::Cont, state)
+    }
+
+    /// Continue the execution as [`Self::cont`], visiting at most `max`
+    /// constraints.
+    ///
+    /// Returns [`State::Exhausted`] at the last visited constraint if the
+    /// budget runs out before a stop.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{ZkDebugger, State};
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("../assets/test.cdf")?;
+    /// let mut debugger = ZkDebugger::from_reader(file)?;
+    ///
+    /// let state = debugger.cont_max(3)?;
+    ///
+    /// assert!(state.is_exhausted());
+    /// assert_eq!(state.constraint(), Some(3));
+    ///
+    /// assert!(debugger.cont_max(100)?.is_end());
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "cont",
+                max = max,
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
+    pub fn cont_max(&mut self, max: usize) -> io::Result<State> {
+        let mut anchor = self.resumed(Movement::Cont);
+        let state = match self.cont_within(&mut anchor, max) {
+            Ok(Some(state)) => Ok(state),
+            Ok(None) => self.exhausted(),
+            Err(e) => Err(e),
+        };
+
+        self.navigated(Movement::Cont, state)
+    }
+
+    /// Continue the execution as [`Self::cont`], pausing at the first
+    /// interruption point past the deadline or after the token is cancelled.
+    ///
+    /// A paused scan returns [`State::Paused`]; the next [`Self::cont`] or
+    /// [`Self::cont_until`] resumes it with the stop conditions it started
+    /// with, unless the debugger was moved in between.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{CancelToken, State, ZkDebugger};
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let token = CancelToken::new();
+    ///
+    /// // a handler on another thread would cancel a long scan
+    /// token.cancel();
+    ///
+    /// // the circuit is smaller than an interruption chunk
+    /// assert!(debugger.cont_until(token.clone())?.is_end());
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "cont",
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
+    pub fn cont_until<U>(&mut self, until: U) -> io::Result<State>
+    where
+        U: Into<Until>,
+    {
+        let state =
+            self.move_until(Movement::Cont, Self::cont_within, &until.into());
+
+        self.navigated(Movement::Cont, state)
+    }
+
+    fn move_cont(&mut self) -> io::Result<State> {
+        let mut anchor = self.resumed(Movement::Cont);
+
+        loop {
+            if let Some(state) = self.cont_within(&mut anchor, usize::MAX)? {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Continue the execution, visiting at most `budget` constraints.
+    ///
+    /// Returns `None` if the budget is exhausted before a stop. The anchor is
+    /// the source/line the scan started from; it is kept across the calls so
+    /// a paused scan resumes with the same stop conditions.
+    fn cont_within(
+        &mut self,
+        anchor: &mut Option<Anchor>,
+        budget: usize,
+    ) -> io::Result<Option<State>> {
+        self.allocation = None;
+
+        let Self {
+            breakpoints,
+            cdf,
+            constraint,
+            focus,
+            focus_failures,
+            ..
+        } = self;
+
+        let mut idx = *constraint;
+        let eof = cdf.preamble().constraints.saturating_sub(1);
+
+        if idx == eof {
+            return end(cdf, idx).map(Some);
+        }
+
+        let mut from = match anchor {
+            Some(anchor) => *anchor,
+            None => *anchor.insert(Anchor::fetch(cdf, idx)?),
+        };
+
+        for _ in 0..budget {
+            idx += 1;
+
+            let current = match visit(cdf, idx)? {
+                Visited::Constraint(current) => current,
+                Visited::Corrupt { offset } => {
+                    match corrupt(cdf, idx, offset) {
+                        Some(state) => {
+                            *constraint = idx;
+                            return Ok(Some(state));
+                        }
+                        None if idx == eof => {
+                            *constraint = idx;
+                            return Ok(Some(State::End {
+                                id: idx,
+                                location: None,
+                            }));
+                        }
+                        None => continue,
+                    }
+                }
+            };
+            let (focused, reported) = focus::visibility(
+                focus.as_ref(),
+                *focus_failures,
+                idx,
+                &current,
+            );
+            let is_invalid = reported && !current.polynomial().evaluation;
+            let different_line = from.is_different(&current);
+
+            if different_line && is_invalid {
+                *constraint = idx;
+                return Ok(Some(State::InvalidConstraint {
+                    id: idx,
+                    location: Location::from(&current),
+                }));
+            }
 
             if idx == eof {
                 *constraint = idx;
-                return Ok(State::End { id: idx });
+                return Ok(Some(State::End {
+                    id: idx,
+                    location: Some(Location::from(&current)),
+                }));
+            }
+
+            if !focused {
+                continue;
             }
 
             if different_line {
-                if let Some(id) = breakpoints.find_breakpoint(&current) {
-                    *constraint = idx;
-                    return Ok(State::Breakpoint { id });
+                match breakpoints.hit(&current) {
+                    Hit::Stop(id) => {
+                        *constraint = idx;
+                        return Ok(Some(State::Breakpoint {
+                            id,
+                            constraint: idx,
+                            location: Location::from(&current),
+                        }));
+                    }
+
+                    // an ignored hit counts as a stop, so the scan resumes
+                    // past its line
+                    Hit::Ignored => {
+                        from = Anchor::from(&current);
+                        anchor.replace(from);
+                    }
+
+                    Hit::Miss => (),
                 }
             }
         }
+
+        *constraint = idx;
+        Ok(None)
     }
 
     /// Attempt to jump to a given constraint.
     ///
+    /// Landing on a constraint matched by a breakpoint returns
+    /// [`State::Breakpoint`], unless the constraint is invalid, including the
+    /// first and the last constraints; the ignore counts of the breakpoints
+    /// aren't consumed, and the temporary breakpoints aren't removed.
+    ///
     /// # Example
     ///
     /// ```
@@ -406,35 +1212,145 @@ where
     /// let mut debugger = ZkDebugger::from_reader(file)?;
     ///
     /// // goto 7 then go forward one step
-    /// assert_eq!(debugger.goto(7)?, State::Constraint { id : 7 });
-    /// assert_eq!(debugger.step()?, State::Constraint { id : 8 });
+    /// assert_eq!(debugger.goto(7)?.constraint(), Some(7));
+    /// assert_eq!(debugger.step()?.constraint(), Some(8));
+    ///
+    /// let id = debugger.add_breakpoint("gadget.rs".into(), Some(8));
+    ///
+    /// assert_eq!(debugger.goto(8)?.breakpoint(), Some(id));
+    /// assert_eq!(debugger.goto(7)?.breakpoint(), None);
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "goto",
+                constraint = idx,
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
     pub fn goto(&mut self, idx: usize) -> io::Result<State> {
+        let state = self.move_to(idx);
+
+        self.navigated(Movement::Goto { constraint: idx }, state)
+    }
+
+    /// Jump to the first constraint at `line` of a source that matches the
+    /// `source` pattern, as [`Breakpoint::matches`] does.
+    ///
+    /// The line index is built on the first call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::ZkDebugger;
+    ///
+    /// let mut debugger = ZkDebugger::open("../assets/test.cdf")?;
+    ///
+    /// let state = debugger.goto_line("naive-signature/src/gadget.rs", 28)?;
+    ///
+    /// assert_eq!(state.constraint(), Some(16));
+    /// assert_eq!(debugger.goto_line("gadget.rs", 8)?.constraint(), Some(8));
+    /// assert!(debugger.goto_line("gadget.rs", 9).is_err());
+    ///
+    /// # Ok(()) }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "goto",
+                source = source,
+                line = line,
+                from = self.constraint
+            )
+        )
+    )]
+    pub fn goto_line(&mut self, source: &str, line: u64) -> io::Result<State> {
+        if self.lines.is_none() {
+            self.lines.replace(Lines::load(&mut self.cdf)?);
+            self.reclaim(Some(Cache::Lines));
+        }
+
+        let idx = self
+            .lines
+            .as_ref()
+            .and_then(|lines| lines.find(source, line))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no constraint found at {}:{}", source, line),
+                )
+            })?;
+
+        self.goto(idx)
+    }
+
+    fn move_to(&mut self, idx: usize) -> io::Result<State> {
+        self.allocation = None;
+
         let Self {
-            cdf, constraint, ..
+            cdf,
+            constraint,
+            breakpoints,
+            ..
         } = self;
 
-        if idx == 0 {
+        let constraints = cdf.preamble().constraints;
+
+        if idx == 0 && constraints == 0 {
             *constraint = 0;
             return Ok(State::Beginning);
         }
 
-        let current = cdf.fetch_constraint(idx)?;
+        let eof = constraints.saturating_sub(1);
+        let current = match visit(cdf, idx)? {
+            Visited::Constraint(current) => current,
+            Visited::Corrupt { offset } => {
+                *constraint = idx;
+                return Ok(State::Corrupt { id: idx, offset });
+            }
+        };
         let is_invalid = !current.polynomial().evaluation;
+        let location = Location::from(&current);
 
         *constraint = idx;
 
         if is_invalid {
-            return Ok(State::InvalidConstraint { id: idx });
+            return Ok(State::InvalidConstraint { id: idx, location });
+        }
+
+        if let Some(id) = breakpoints.find_breakpoint(&current) {
+            return Ok(State::Breakpoint {
+                id,
+                constraint: idx,
+                location,
+            });
+        }
+
+        if idx == 0 {
+            return Ok(State::Beginning);
         }
 
-        if idx == cdf.preamble().constraints.saturating_sub(1) {
-            return Ok(State::End { id: idx });
+        if idx == eof {
+            return Ok(State::End {
+                id: idx,
+                location: Some(location),
+            });
         }
 
-        Ok(State::Constraint { id: idx })
+        Ok(State::Constraint { id: idx, location })
     }
 
     /// Move to next source/line.
@@ -452,65 +1368,174 @@ where
     /// let file = File::open("../assets/test.cdf")?;
     /// let mut debugger = ZkDebugger::from_reader(file)?;
     ///
-    /// assert_eq!(debugger.step()?, State::Constraint { id : 6 });
-    /// assert_eq!(debugger.step()?, State::Constraint { id : 7 });
+    /// assert_eq!(debugger.step()?.constraint(), Some(6));
+    /// assert_eq!(debugger.step()?.constraint(), Some(7));
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "step",
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
     pub fn step(&mut self) -> io::Result<State> {
+        let state = self.move_step();
+
+        self.navigated(Movement::Step, state)
+    }
+
+    fn move_step(&mut self) -> io::Result<State> {
+        let allocations = match &self.allocations {
+            Some(allocations) => allocations,
+            None => return self.step_constraint(),
+        };
+
+        let from = self.constraint;
+        let resumed = self.allocation.is_some();
+        let next = match self.allocation {
+            Some(idx) => idx + 1,
+            None => allocations.first_from(from),
+        };
+
+        let state = self.step_constraint()?;
+        let to = self.constraint;
+
+        // the allocations of the last constraint are flushed once, when the
+        // end is reached
+        let is_end =
+            matches!(state, State::End { .. }) && (from != to || resumed);
+        let pending = self
+            .allocations
+            .as_ref()
+            .and_then(|allocations| allocations.get(next))
+            .filter(|a| from <= a.constraint && (a.constraint < to || is_end))
+            .copied();
+
+        match pending {
+            Some(allocation) => {
+                self.constraint = from;
+                self.allocation.replace(next);
+
+                Ok(State::Witness {
+                    id: allocation.witness,
+                })
+            }
+
+            None => {
+                self.allocation = None;
+
+                Ok(state)
+            }
+        }
+    }
+
+    fn step_constraint(&mut self) -> io::Result<State> {
         let Self {
             breakpoints,
             cdf,
             constraint,
+            focus,
+            focus_failures,
+            ..
         } = self;
 
         let mut idx = *constraint;
         let eof = cdf.preamble().constraints.saturating_sub(1);
 
         if idx == eof {
-            return Ok(State::End { id: idx });
+            return end(cdf, idx);
         }
 
-        let current = cdf.fetch_constraint(idx)?;
-        let source = current.name().to_string();
-        let line = current.line();
+        let anchor = Anchor::fetch(cdf, idx)?;
 
         loop {
             idx += 1;
 
-            let current = cdf.fetch_constraint(idx)?;
-            let is_invalid = !current.polynomial().evaluation;
-            let different_line =
-                source != current.name() || line != current.line();
+            let current = match visit(cdf, idx)? {
+                Visited::Constraint(current) => current,
+                Visited::Corrupt { offset } => {
+                    match corrupt(cdf, idx, offset) {
+                        Some(state) => {
+                            *constraint = idx;
+                            return Ok(state);
+                        }
+                        None if idx == eof => {
+                            *constraint = idx;
+                            return Ok(State::End {
+                                id: idx,
+                                location: None,
+                            });
+                        }
+                        None => continue,
+                    }
+                }
+            };
+            let (focused, reported) = focus::visibility(
+                focus.as_ref(),
+                *focus_failures,
+                idx,
+                &current,
+            );
+            let is_invalid = reported && !current.polynomial().evaluation;
+            let different_line = anchor.is_different(&current);
 
             if different_line && is_invalid {
                 *constraint = idx;
-                return Ok(State::InvalidConstraint { id: idx });
+                return Ok(State::InvalidConstraint {
+                    id: idx,
+                    location: Location::from(&current),
+                });
             }
 
             if idx == eof {
                 *constraint = idx;
-                return Ok(State::End { id: idx });
+                return Ok(State::End {
+                    id: idx,
+                    location: Some(Location::from(&current)),
+                });
+            }
+
+            if !focused {
+                continue;
             }
 
             if different_line {
-                if let Some(id) = breakpoints.find_breakpoint(&current) {
+                if let Hit::Stop(id) = breakpoints.hit(&current) {
                     *constraint = idx;
-                    return Ok(State::Breakpoint { id });
+                    return Ok(State::Breakpoint {
+                        id,
+                        constraint: idx,
+                        location: Location::from(&current),
+                    });
                 }
             }
 
             if different_line {
-                break;
+                *constraint = idx;
+                return Ok(State::Constraint {
+                    id: idx,
+                    location: Location::from(&current),
+                });
             }
         }
-
-        *constraint = idx;
-        Ok(State::Constraint { id: idx })
     }
 
     /// Reverse the execution until BOF, breakpoint, or invalid constraint.
     ///
+    /// The stops mirror the ones of [`Self::cont`]: the ignored hits of a
+    /// breakpoint are consumed, and a failure of the first constraint is
+    /// reported as [`State::InvalidConstraint`] before the
+    /// [`State::Beginning`], as the one of the last constraint is before the
+    /// [`State::End`].
+    ///
     /// # Example
     ///
     /// ```
@@ -522,65 +1547,382 @@ where
     /// let mut debugger = ZkDebugger::from_reader(file)?;
     /// let breakpoint = Breakpoint {
     ///     source: String::from("xyz"),
-    ///     line: Some(40)   
+    ///     line: Some(40),
+    ///     kind: None,
     /// };
     ///
     /// assert_eq!(debugger.turn()?, State::Beginning);
     ///
     /// # Ok(()) }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "turn",
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
     pub fn turn(&mut self) -> io::Result<State> {
+        let state = self.move_turn();
+
+        self.navigated(Movement::Turn, state)
+    }
+
+    /// Reverse the execution as [`Self::turn`], visiting at most `max`
+    /// constraints.
+    ///
+    /// Counterpart of [`Self::cont_max`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "turn",
+                max = max,
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
+    pub fn turn_max(&mut self, max: usize) -> io::Result<State> {
+        let mut anchor = self.resumed(Movement::Turn);
+        let state = match self.turn_within(&mut anchor, max) {
+            Ok(Some(state)) => Ok(state),
+            Ok(None) => self.exhausted(),
+            Err(e) => Err(e),
+        };
+
+        self.navigated(Movement::Turn, state)
+    }
+
+    /// Scan in chunks until a stop, pausing at the first interruption point
+    /// where the condition is reached
+    fn move_until<F>(
+        &mut self,
+        movement: Movement,
+        within: F,
+        until: &Until,
+    ) -> io::Result<State>
+    where
+        F: Fn(
+            &mut Self,
+            &mut Option<Anchor>,
+            usize,
+        ) -> io::Result<Option<State>>,
+    {
+        let mut anchor = self.resumed(movement);
+
+        loop {
+            if let Some(state) = within(self, &mut anchor, SCAN_CHUNK)? {
+                return Ok(state);
+            }
+
+            if until.reached() {
+                return Ok(self.pause(movement, anchor));
+            }
+        }
+    }
+
+    /// Pause a scan at the current constraint, keeping its anchor so it can
+    /// be resumed
+    fn pause(&mut self, movement: Movement, anchor: Option<Anchor>) -> State {
+        self.paused = anchor.map(|anchor| (movement, anchor));
+
+        State::Paused {
+            id: self.constraint,
+        }
+    }
+
+    /// Anchor of the paused scan, if it runs in the direction of the movement
+    fn resumed(&mut self, movement: Movement) -> Option<Anchor> {
+        match self.paused.take() {
+            Some((paused, anchor)) if paused == movement => Some(anchor),
+            _ => None,
+        }
+    }
+
+    /// State of a bounded scan that ran out of budget at the current
+    /// constraint
+    fn exhausted(&mut self) -> io::Result<State> {
+        let id = self.constraint;
+        let location = match visit(&mut self.cdf, id)? {
+            Visited::Constraint(current) => Some(Location::from(&current)),
+            Visited::Corrupt { .. } => None,
+        };
+
+        Ok(State::Exhausted { id, location })
+    }
+
+    /// Reverse the execution as [`Self::turn`], pausing as
+    /// [`Self::cont_until`] does.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan",
+            level = "debug",
+            skip_all,
+            fields(
+                movement = "turn",
+                from = self.constraint,
+                to = tracing::field::Empty
+            )
+        )
+    )]
+    pub fn turn_until<U>(&mut self, until: U) -> io::Result<State>
+    where
+        U: Into<Until>,
+    {
+        let state =
+            self.move_until(Movement::Turn, Self::turn_within, &until.into());
+
+        self.navigated(Movement::Turn, state)
+    }
+
+    fn move_turn(&mut self) -> io::Result<State> {
+        let mut anchor = self.resumed(Movement::Turn);
+
+        loop {
+            if let Some(state) = self.turn_within(&mut anchor, usize::MAX)? {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Reverse the execution, visiting at most `budget` constraints.
+    ///
+    /// Counterpart of [`Self::cont_within`].
+    fn turn_within(
+        &mut self,
+        anchor: &mut Option<Anchor>,
+        budget: usize,
+    ) -> io::Result<Option<State>> {
+        self.allocation = None;
+
         let Self {
             breakpoints,
             cdf,
             constraint,
+            focus,
+            focus_failures,
+            ..
         } = self;
 
         let mut idx = *constraint;
         if idx == 0 {
-            return Ok(State::Beginning);
+            return Ok(Some(State::Beginning));
         }
 
-        let current = cdf.fetch_constraint(idx)?;
-        let source = current.name().to_string();
-        let line = current.line();
+        let mut from = match anchor {
+            Some(anchor) => *anchor,
+            None => *anchor.insert(Anchor::fetch(cdf, idx)?),
+        };
 
-        loop {
+        for _ in 0..budget {
             idx -= 1;
 
+            let current = match visit(cdf, idx)? {
+                Visited::Constraint(current) => current,
+                Visited::Corrupt { offset } => {
+                    match corrupt(cdf, idx, offset) {
+                        Some(state) => {
+                            *constraint = idx;
+                            return Ok(Some(state));
+                        }
+                        None if idx == 0 => {
+                            *constraint = 0;
+                            return Ok(Some(State::Beginning));
+                        }
+                        None => continue,
+                    }
+                }
+            };
+            let (focused, reported) = focus::visibility(
+                focus.as_ref(),
+                *focus_failures,
+                idx,
+                &current,
+            );
+            let is_invalid = reported && !current.polynomial().evaluation;
+            let different_line = from.is_different(&current);
+
+            // mirroring the end of a forward scan, a failure of the first
+            // constraint is reported before the beginning
+            if different_line && is_invalid {
+                *constraint = idx;
+                return Ok(Some(State::InvalidConstraint {
+                    id: idx,
+                    location: Location::from(&current),
+                }));
+            }
+
             if idx == 0 {
                 *constraint = 0;
-                return Ok(State::Beginning);
+                return Ok(Some(State::Beginning));
             }
 
-            let current = cdf.fetch_constraint(idx)?;
-            let is_invalid = !current.polynomial().evaluation;
-            let different_line =
-                source != current.name() || line != current.line();
-
-            if different_line && is_invalid {
-                *constraint = idx;
-                return Ok(State::InvalidConstraint { id: idx });
+            if !focused {
+                continue;
             }
 
             if different_line {
-                if let Some(id) = breakpoints.find_breakpoint(&current) {
-                    *constraint = idx;
-                    return Ok(State::Breakpoint { id });
+                match breakpoints.hit(&current) {
+                    Hit::Stop(id) => {
+                        *constraint = idx;
+                        return Ok(Some(State::Breakpoint {
+                            id,
+                            constraint: idx,
+                            location: Location::from(&current),
+                        }));
+                    }
+
+                    // an ignored hit counts as a stop, so the scan resumes
+                    // past its line
+                    Hit::Ignored => {
+                        from = Anchor::from(&current);
+                        anchor.replace(from);
+                    }
+
+                    Hit::Miss => (),
                 }
             }
         }
+
+        *constraint = idx;
+        Ok(None)
     }
 }
 
-#[test]
-fn base_operations_wont_panic() -> io::Result<()> {
-    let path = std::env!("CARGO_MANIFEST_DIR");
-    let path = std::path::PathBuf::from(path)
+/// Source/line a scan started from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Anchor {
+    Line(SourceId, u64),
+    /// The scan started from a corrupt constraint, so every constraint is
+    /// on a different line
+    Corrupt,
+}
+
+impl<'a> From<&Constraint<'a>> for Anchor {
+    fn from(constraint: &Constraint<'a>) -> Self {
+        Self::Line(constraint.source_id(), constraint.line())
+    }
+}
+
+impl Anchor {
+    fn fetch<S>(cdf: &mut CircuitDescription<S>, idx: usize) -> io::Result<Self>
+    where
+        S: io::Read + io::Seek,
+    {
+        match visit(cdf, idx)? {
+            Visited::Constraint(current) => Ok(Self::from(&current)),
+            Visited::Corrupt { .. } => Ok(Self::Corrupt),
+        }
+    }
+
+    fn is_different(&self, constraint: &Constraint) -> bool {
+        match self {
+            Self::Line(source, line) => {
+                *source != constraint.source_id() || *line != constraint.line()
+            }
+            Self::Corrupt => true,
+        }
+    }
+}
+
+/// Constraint visited by a navigation
+enum Visited<'a> {
+    Constraint(Constraint<'a>),
+    Corrupt { offset: u64 },
+}
+
+fn visit<S>(
+    cdf: &mut CircuitDescription<S>,
+    idx: usize,
+) -> io::Result<Visited<'_>>
+where
+    S: io::Read + io::Seek,
+{
+    let offset = cdf.preamble().constraint_offset(idx).unwrap_or_default();
+
+    match cdf.fetch_constraint(idx) {
+        Ok(constraint) => Ok(Visited::Constraint(constraint)),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            Ok(Visited::Corrupt {
+                offset: offset as u64,
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Apply the corruption policy of the circuit to a constraint visited by a
+/// scan, returning the state to stop at, if any
+fn corrupt<S>(
+    cdf: &mut CircuitDescription<S>,
+    idx: usize,
+    offset: u64,
+) -> Option<State> {
+    match cdf.preamble().config.corruption {
+        CorruptionPolicy::Stop => Some(State::Corrupt { id: idx, offset }),
+        CorruptionPolicy::Skip => {
+            cdf.push_warning(Warning::CorruptConstraint {
+                constraint: idx,
+                offset,
+            });
+
+            None
+        }
+    }
+}
+
+/// End state of the circuit, located at its last constraint
+fn end<S>(cdf: &mut CircuitDescription<S>, idx: usize) -> io::Result<State>
+where
+    S: io::Read + io::Seek,
+{
+    let location = match cdf.preamble().constraints {
+        0 => None,
+        _ => Some(Location::from(&cdf.fetch_constraint(idx)?)),
+    };
+
+    Ok(State::End { id: idx, location })
+}
+
+/// Path of the CDF file of the test assets
+#[cfg(test)]
+fn test_cdf() -> std::path::PathBuf {
+    std::path::PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
         .parent()
         .expect("failed to updir")
         .join("assets")
-        .join("test.cdf");
+        .join("test.cdf")
+}
+
+/// Flip the evaluation of the constraint `idx` of the CDF file to a failure
+#[cfg(test)]
+fn fail_constraint(bytes: &mut [u8], preamble: &Preamble, idx: usize) {
+    use crate::{Element, Selectors, WiredWitnesses};
+
+    let evaluation = preamble
+        .constraint_offset(idx)
+        .expect("the constraint exists")
+        + usize::len(&preamble.config)
+        + Selectors::len(&preamble.config)
+        + WiredWitnesses::len(&preamble.config);
+
+    bytes[evaluation] = 0;
+}
+
+#[test]
+fn base_operations_wont_panic() -> io::Result<()> {
+    let path = test_cdf();
 
     let mut debugger = ZkDebugger::open(path)?;
 
@@ -592,6 +1934,7 @@ fn base_operations_wont_panic() -> io::Result<()> {
     debugger.fetch_current_constraint()?;
     debugger.fetch_constraint(0)?;
     debugger.fetch_witness(0)?;
+    debugger.resolve_current_constraint()?;
 
     let state = debugger.cont()?;
     assert!(matches!(state, State::End { .. }));
@@ -603,3 +1946,555 @@ fn base_operations_wont_panic() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn witness_steps_surface_every_allocation() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut debugger = ZkDebugger::open(path)?;
+    let mut witnesses = vec![];
+
+    debugger.set_witness_steps(true)?;
+
+    loop {
+        match debugger.step()? {
+            State::Witness { id } => {
+                assert_eq!(debugger.current_witness(), Some(id));
+                witnesses.push(id);
+            }
+            State::End { .. } => break,
+            _ => assert_eq!(debugger.current_witness(), None),
+        }
+    }
+
+    witnesses.sort_unstable();
+    witnesses.dedup();
+
+    assert_eq!(witnesses.len(), debugger.preamble().witnesses);
+
+    debugger.set_witness_steps(false)?;
+    debugger.goto(0)?;
+
+    assert!(!matches!(debugger.step()?, State::Witness { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn states_carry_the_stop_location() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut debugger = ZkDebugger::open(path)?;
+
+    loop {
+        let state = debugger.step()?;
+        let id = state.constraint().expect("witness steps are disabled");
+
+        assert_eq!(id, debugger.position());
+
+        let constraint = debugger.fetch_constraint(id)?;
+        let location = Location::from(&constraint);
+
+        assert_eq!(state.location(), Some(&location));
+        assert_eq!(state.is_invalid(), !constraint.polynomial().evaluation);
+
+        if state.is_end() {
+            break;
+        }
+    }
+
+    let source = debugger.fetch_constraint(3)?.name().to_string();
+    let breakpoint = debugger.add_breakpoint(source, None);
+
+    debugger.goto(0)?;
+
+    match debugger.cont()? {
+        State::Breakpoint { id, constraint, .. } => {
+            assert_eq!(id, breakpoint);
+            assert_eq!(constraint, debugger.position());
+        }
+        state => assert!(state.is_invalid() || state.is_end(), "{}", state),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn subscribers_receive_the_changes() -> io::Result<()> {
+    use std::sync::Mutex;
+
+    let path = test_cdf();
+
+    let mut debugger = ZkDebugger::open(path)?;
+    let changes = Arc::new(Mutex::new(vec![]));
+    let subscriber = Arc::clone(&changes);
+
+    let subscription = debugger.subscribe(move |change: &StateChange| {
+        subscriber
+            .lock()
+            .expect("poisoned lock")
+            .push(change.clone());
+    });
+
+    let state = debugger.goto(5)?;
+    let id = debugger.add_breakpoint("xyz".into(), Some(40));
+
+    debugger.add_breakpoint("xyz".into(), Some(40));
+    debugger.remove_breakpoint(id);
+    debugger.clear_breakpoints("xyz");
+
+    let breakpoint = Breakpoint {
+        source: "xyz".into(),
+        line: Some(40),
+        kind: None,
+    };
+
+    assert_eq!(
+        changes.lock().expect("poisoned lock").as_slice(),
+        &[
+            StateChange::Loaded {
+                preamble: *debugger.preamble(),
+            },
+            StateChange::Navigated {
+                movement: Movement::Goto { constraint: 5 },
+                state,
+            },
+            StateChange::BreakpointAdded {
+                id,
+                breakpoint: breakpoint.clone(),
+            },
+            StateChange::BreakpointRemoved { id, breakpoint },
+        ]
+    );
+
+    assert!(debugger.unsubscribe(subscription));
+    assert!(!debugger.unsubscribe(subscription));
+
+    debugger.step()?;
+
+    assert_eq!(changes.lock().expect("poisoned lock").len(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn paused_scans_resume_with_the_same_stops() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut debugger = ZkDebugger::open(&path)?;
+    let mut paused = ZkDebugger::open(path)?;
+
+    let source = debugger.fetch_constraint(3)?.name().to_string();
+
+    debugger.add_breakpoint(source.clone(), None);
+    paused.add_breakpoint(source, None);
+
+    loop {
+        let state = debugger.cont()?;
+        let mut anchor = None;
+
+        let resumed = loop {
+            if let Some(state) = paused.cont_within(&mut anchor, 1)? {
+                break state;
+            }
+        };
+
+        assert_eq!(state, resumed);
+
+        if state.is_end() {
+            break;
+        }
+    }
+
+    loop {
+        let state = debugger.turn()?;
+        let mut anchor = None;
+
+        let resumed = loop {
+            if let Some(state) = paused.turn_within(&mut anchor, 1)? {
+                break state;
+            }
+        };
+
+        assert_eq!(state, resumed);
+
+        if state == State::Beginning {
+            break;
+        }
+    }
+
+    let shared = SharedZkDebugger::from(debugger);
+
+    assert_eq!(shared.position(), 0);
+    assert_eq!(shared.cont()?, paused.cont()?);
+    assert_eq!(shared.position(), paused.position());
+    assert_eq!(shared.sources().count(), paused.sources().count());
+
+    Ok(())
+}
+
+#[test]
+fn paused_scans_resume_from_their_anchor() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut debugger = ZkDebugger::open(path)?;
+
+    // the hash gadget is called twice from the same line, so a scan that
+    // starts in its first call doesn't stop at its second one
+    let source = debugger.fetch_constraint(8)?.name().to_string();
+
+    let id = debugger.add_breakpoint(source, None);
+
+    debugger.goto(8)?;
+
+    let mut anchor = debugger.resumed(Movement::Cont);
+
+    assert_eq!(debugger.cont_within(&mut anchor, 8)?, None);
+    assert_eq!(
+        debugger.pause(Movement::Cont, anchor),
+        State::Paused { id: 16 }
+    );
+    assert!(debugger.cont()?.is_end());
+
+    // a movement discards the paused scan
+    let mut anchor = None;
+
+    debugger.goto(8)?;
+    debugger.cont_within(&mut anchor, 8)?;
+    debugger.pause(Movement::Cont, anchor);
+    debugger.goto(16)?;
+
+    assert_eq!(debugger.cont()?.breakpoint(), Some(id));
+
+    Ok(())
+}
+
+#[test]
+fn corrupt_constraints_follow_the_policy() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut bytes = std::fs::read(&path)?;
+    let preamble = *ZkDebugger::open(&path)?.preamble();
+
+    let corrupt = 5;
+    let offset = preamble
+        .constraint_offset(corrupt)
+        .expect("the constraint exists");
+    let next = preamble
+        .constraint_offset(corrupt + 1)
+        .expect("the constraint exists");
+
+    bytes[offset..next].iter_mut().for_each(|b| *b = 0xff);
+
+    let expected = State::Corrupt {
+        id: corrupt,
+        offset: offset as u64,
+    };
+
+    let source = io::Cursor::new(bytes.clone());
+    let mut debugger = ZkDebugger::from_reader(source)?;
+
+    debugger.goto(corrupt - 1)?;
+    assert_eq!(debugger.cont()?, expected);
+    assert_eq!(debugger.position(), corrupt);
+
+    debugger.goto(corrupt + 1)?;
+    assert_eq!(debugger.turn()?, expected);
+    assert_eq!(debugger.goto(corrupt)?, expected);
+    assert!(debugger.step()?.constraint() > Some(corrupt));
+
+    let config = *Config::default().with_corruption(CorruptionPolicy::Skip);
+    let source = io::Cursor::new(bytes);
+    let mut debugger = ZkDebugger::from_reader_with_config(source, config)?;
+
+    debugger.goto(corrupt - 1)?;
+
+    loop {
+        let state = debugger.cont()?;
+
+        assert_ne!(state, expected);
+
+        if state.is_end() {
+            break;
+        }
+    }
+
+    assert!(debugger
+        .take_warnings()
+        .contains(&Warning::CorruptConstraint {
+            constraint: corrupt,
+            offset: offset as u64,
+        }));
+
+    Ok(())
+}
+
+#[test]
+fn scans_stop_alike_in_both_directions() -> io::Result<()> {
+    use crate::GateKind;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Stop {
+        Beginning,
+        End,
+        Breakpoint(usize),
+        Invalid(usize),
+    }
+
+    struct Case {
+        /// Constraint whose evaluation is flipped to a failure
+        invalid: Option<usize>,
+        /// Source, line and kind of the breakpoint
+        breakpoint: Option<(&'static str, u64, Option<GateKind>)>,
+        /// Hits of the breakpoint to be ignored
+        ignore: usize,
+        /// Position the scan starts from
+        from: usize,
+        reverse: bool,
+        /// Stops of the consecutive scans
+        stops: &'static [Stop],
+    }
+
+    let path = test_cdf();
+
+    let bytes = std::fs::read(&path)?;
+    let preamble = *ZkDebugger::open(&path)?.preamble();
+    let last = preamble.constraints - 1;
+
+    // the lines 43 and 44 of naive-circuit/src/main.rs, matched by the
+    // `naive-circuit` pattern, are the lines of the constraints 6 and 7; the
+    // constraints 0 to 5 share the line 95, and 25 is alone on 57
+    let cases = [
+        Case {
+            invalid: None,
+            breakpoint: None,
+            ignore: 0,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Beginning, Stop::Beginning],
+        },
+        Case {
+            invalid: None,
+            breakpoint: None,
+            ignore: 0,
+            from: 0,
+            reverse: false,
+            stops: &[Stop::End, Stop::End],
+        },
+        Case {
+            invalid: None,
+            breakpoint: Some(("naive-circuit", 44, None)),
+            ignore: 0,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Breakpoint(7), Stop::Beginning],
+        },
+        Case {
+            invalid: None,
+            breakpoint: Some(("naive-circuit", 43, None)),
+            ignore: 0,
+            from: 0,
+            reverse: false,
+            stops: &[Stop::Breakpoint(6), Stop::End],
+        },
+        Case {
+            invalid: None,
+            breakpoint: Some(("naive-circuit", 44, None)),
+            ignore: 1,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Beginning],
+        },
+        Case {
+            invalid: None,
+            breakpoint: Some(("naive-circuit", 43, None)),
+            ignore: 1,
+            from: 0,
+            reverse: false,
+            stops: &[Stop::End],
+        },
+        Case {
+            invalid: None,
+            breakpoint: Some(("naive-circuit", 44, Some(GateKind::Range))),
+            ignore: 0,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Beginning],
+        },
+        Case {
+            invalid: None,
+            breakpoint: Some(("naive-circuit", 95, None)),
+            ignore: 0,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Breakpoint(5), Stop::Beginning],
+        },
+        Case {
+            invalid: Some(0),
+            breakpoint: None,
+            ignore: 0,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Invalid(0), Stop::Beginning],
+        },
+        Case {
+            invalid: Some(last),
+            breakpoint: None,
+            ignore: 0,
+            from: 0,
+            reverse: false,
+            stops: &[Stop::Invalid(last), Stop::End],
+        },
+        Case {
+            invalid: Some(0),
+            breakpoint: Some(("naive-circuit", 44, None)),
+            ignore: 0,
+            from: last,
+            reverse: true,
+            stops: &[Stop::Breakpoint(7), Stop::Invalid(0), Stop::Beginning],
+        },
+        Case {
+            // the failure shares the line the scan starts from
+            invalid: Some(0),
+            breakpoint: None,
+            ignore: 0,
+            from: 5,
+            reverse: true,
+            stops: &[Stop::Beginning],
+        },
+    ];
+
+    for (i, case) in cases.iter().enumerate() {
+        let mut bytes = bytes.clone();
+
+        if let Some(invalid) = case.invalid {
+            fail_constraint(&mut bytes, &preamble, invalid);
+        }
+
+        let mut debugger = ZkDebugger::from_reader(io::Cursor::new(bytes))?;
+
+        if let Some((source, line, kind)) = case.breakpoint {
+            let breakpoint = Breakpoint {
+                source: source.into(),
+                line: Some(line),
+                kind,
+            };
+
+            let id = debugger.insert_breakpoint(breakpoint, false);
+
+            debugger.ignore_breakpoint(id, case.ignore);
+        }
+
+        debugger.goto(case.from)?;
+
+        for expected in case.stops {
+            let state = match case.reverse {
+                true => debugger.turn()?,
+                false => debugger.cont()?,
+            };
+
+            let stop = match state {
+                State::Beginning => Stop::Beginning,
+                State::End { id, .. } => {
+                    assert_eq!(id, last, "case {}", i);
+                    Stop::End
+                }
+                State::Breakpoint { constraint, .. } => {
+                    Stop::Breakpoint(constraint)
+                }
+                State::InvalidConstraint { id, .. } => Stop::Invalid(id),
+                state => panic!("case {}: unexpected state {}", i, state),
+            };
+
+            assert_eq!(stop, *expected, "case {}", i);
+            assert_eq!(state.constraint().unwrap_or(0), debugger.position());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn goto_lands_on_breakpoints() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut bytes = std::fs::read(&path)?;
+    let preamble = *ZkDebugger::open(&path)?.preamble();
+
+    // naive-circuit/src/main.rs:44, matched by the `naive-circuit` pattern,
+    // is the line of the constraint 7
+    let breakpoint = Breakpoint {
+        source: "naive-circuit".into(),
+        line: Some(44),
+        kind: None,
+    };
+
+    let mut debugger = ZkDebugger::from_reader(io::Cursor::new(bytes.clone()))?;
+    let id = debugger.insert_breakpoint(breakpoint.clone(), false);
+
+    assert_eq!(debugger.goto(6)?.breakpoint(), None);
+    assert!(matches!(
+        debugger.goto(7)?,
+        State::Breakpoint { id: hit, constraint: 7, .. } if hit == id
+    ));
+
+    // the failure of the constraint takes precedence
+    fail_constraint(&mut bytes, &preamble, 7);
+
+    let mut debugger = ZkDebugger::from_reader(io::Cursor::new(bytes))?;
+
+    debugger.insert_breakpoint(breakpoint, false);
+
+    assert!(matches!(
+        debugger.goto(7)?,
+        State::InvalidConstraint { id: 7, .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn goto_checks_the_first_and_last_constraints() -> io::Result<()> {
+    let path = test_cdf();
+
+    let mut bytes = std::fs::read(&path)?;
+    let mut debugger = ZkDebugger::open(&path)?;
+    let preamble = *debugger.preamble();
+    let eof = preamble.constraints - 1;
+
+    for idx in [0, eof] {
+        let constraint = debugger.fetch_constraint(idx)?;
+        let breakpoint = Breakpoint {
+            source: constraint.name().to_string(),
+            line: Some(constraint.line()),
+            kind: None,
+        };
+
+        let id = debugger.insert_breakpoint(breakpoint, true);
+
+        // landing on a temporary breakpoint keeps it
+        for _ in 0..2 {
+            assert!(matches!(
+                debugger.goto(idx)?,
+                State::Breakpoint { id: hit, constraint, .. }
+                    if hit == id && constraint == idx
+            ));
+        }
+
+        debugger.remove_breakpoint(id);
+    }
+
+    assert!(matches!(debugger.goto(0)?, State::Beginning));
+    assert!(debugger.goto(eof)?.is_end());
+
+    fail_constraint(&mut bytes, &preamble, 0);
+
+    let mut debugger = ZkDebugger::from_reader(io::Cursor::new(bytes))?;
+
+    assert!(matches!(
+        debugger.goto(0)?,
+        State::InvalidConstraint { id: 0, .. }
+    ));
+
+    Ok(())
+}
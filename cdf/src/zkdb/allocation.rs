@@ -0,0 +1,62 @@
+use std::io;
+
+use crate::memory::HeapSize;
+use crate::{timeline, CircuitDescription};
+
+/// Allocation of a witness, anchored to the constraint it follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Allocation {
+    /// Constraint after which the witness is allocated
+    pub constraint: usize,
+    /// Index of the allocated witness
+    pub witness: usize,
+}
+
+/// Witness allocation events, ordered by constraint and witness id.
+///
+/// The anchors are the ones of the [`Timeline`](crate::Timeline).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Allocations {
+    items: Vec<Allocation>,
+}
+
+impl Allocations {
+    pub fn load<S>(cdf: &mut CircuitDescription<S>) -> io::Result<Self>
+    where
+        S: io::Read + io::Seek,
+    {
+        let mut items: Vec<_> = timeline::anchors(cdf)?
+            .into_iter()
+            .enumerate()
+            .map(|(witness, constraint)| Allocation {
+                constraint,
+                witness,
+            })
+            .collect();
+
+        items.sort();
+
+        Ok(Self { items })
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&Allocation> {
+        self.items.get(idx)
+    }
+
+    /// Index of the first allocation anchored at or after the constraint
+    pub fn first_from(&self, constraint: usize) -> usize {
+        self.items.partition_point(|a| a.constraint < constraint)
+    }
+}
+
+impl HeapSize for Allocation {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for Allocations {
+    fn heap_size(&self) -> usize {
+        self.items.heap_size()
+    }
+}
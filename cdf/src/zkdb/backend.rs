@@ -0,0 +1,126 @@
+use std::io;
+
+use crate::{EncodableConstraint, EncodableWitness, Preamble};
+
+use super::{Breakpoint, State, ZkDebugger};
+
+/// Debugger operations, agnostic to where the circuit is loaded.
+///
+/// The items are returned in their owned representation so implementations
+/// aren't required to keep a decoder context alive.
+///
+/// [`ZkDebugger`] implements the operations over a local CDF file, and
+/// [`ZkDapClient`](struct.ZkDapClient.html) forwards them to a DAP backend.
+pub trait DebuggerBackend {
+    /// Preamble of the loaded circuit
+    fn preamble(&self) -> io::Result<Preamble>;
+
+    /// Move to previous source/line
+    fn afore(&mut self) -> io::Result<State>;
+
+    /// Continue the execution until EOF, breakpoint, or invalid constraint
+    fn cont(&mut self) -> io::Result<State>;
+
+    /// Jump to a given constraint
+    fn goto(&mut self, idx: usize) -> io::Result<State>;
+
+    /// Move to next source/line
+    fn step(&mut self) -> io::Result<State>;
+
+    /// Reverse the execution until BOF, breakpoint, or invalid constraint
+    fn turn(&mut self) -> io::Result<State>;
+
+    /// Id of the constraint the debugger is currently positioned at
+    fn position(&self) -> io::Result<usize>;
+
+    /// Fetch the constraint the debugger is currently positioned at
+    fn current_constraint(&mut self) -> io::Result<EncodableConstraint>;
+
+    /// Fetch an indexed constraint
+    fn constraint(&mut self, idx: usize) -> io::Result<EncodableConstraint>;
+
+    /// Fetch an indexed witness
+    fn witness(&mut self, idx: usize) -> io::Result<EncodableWitness>;
+
+    /// Add a breakpoint to the provided source/line, returning its id
+    fn add_breakpoint(
+        &mut self,
+        source: String,
+        line: Option<u64>,
+    ) -> io::Result<usize>;
+
+    /// Remove a breakpoint, returning `true` if it was present
+    fn remove_breakpoint(&mut self, id: usize) -> io::Result<bool>;
+
+    /// Breakpoints of the debugger with their ids, ordered by id
+    fn breakpoints(&self) -> io::Result<Vec<(usize, Breakpoint)>>;
+}
+
+impl<S> DebuggerBackend for ZkDebugger<S>
+where
+    S: io::Read + io::Seek,
+{
+    fn preamble(&self) -> io::Result<Preamble> {
+        Ok(*ZkDebugger::preamble(self))
+    }
+
+    fn afore(&mut self) -> io::Result<State> {
+        ZkDebugger::afore(self)
+    }
+
+    fn cont(&mut self) -> io::Result<State> {
+        ZkDebugger::cont(self)
+    }
+
+    fn goto(&mut self, idx: usize) -> io::Result<State> {
+        ZkDebugger::goto(self, idx)
+    }
+
+    fn step(&mut self) -> io::Result<State> {
+        ZkDebugger::step(self)
+    }
+
+    fn turn(&mut self) -> io::Result<State> {
+        ZkDebugger::turn(self)
+    }
+
+    fn position(&self) -> io::Result<usize> {
+        Ok(ZkDebugger::position(self))
+    }
+
+    fn current_constraint(&mut self) -> io::Result<EncodableConstraint> {
+        self.fetch_current_constraint()
+            .map(EncodableConstraint::from)
+    }
+
+    fn constraint(&mut self, idx: usize) -> io::Result<EncodableConstraint> {
+        self.fetch_constraint(idx).map(EncodableConstraint::from)
+    }
+
+    fn witness(&mut self, idx: usize) -> io::Result<EncodableWitness> {
+        self.fetch_witness(idx).map(EncodableWitness::from)
+    }
+
+    fn add_breakpoint(
+        &mut self,
+        source: String,
+        line: Option<u64>,
+    ) -> io::Result<usize> {
+        Ok(ZkDebugger::add_breakpoint(self, source, line))
+    }
+
+    fn remove_breakpoint(&mut self, id: usize) -> io::Result<bool> {
+        Ok(ZkDebugger::remove_breakpoint(self, id).is_some())
+    }
+
+    fn breakpoints(&self) -> io::Result<Vec<(usize, Breakpoint)>> {
+        let mut breakpoints: Vec<_> = ZkDebugger::breakpoints(self)
+            .iter()
+            .map(|(breakpoint, id)| (*id, breakpoint.clone()))
+            .collect();
+
+        breakpoints.sort_by_key(|(id, _)| *id);
+
+        Ok(breakpoints)
+    }
+}
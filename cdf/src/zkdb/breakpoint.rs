@@ -1,14 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
-use crate::Constraint;
+use serde::{Deserialize, Serialize};
+
+use crate::{Constraint, GateKind};
 
 /// A single breakpoint in code. A `Breakpoint` has a source pattern which
 /// triggers the breakpoint and the line number.
 ///
 /// The [`ZkDebugger`](struct.ZkDebugger.html) struct stores the breakpoints for
 /// debugging.
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+///
+/// The gate kind was added in 0.6; a breakpoint built from its source and
+/// line alone can leave the kind to its default, matching any gate.
+///
+/// # Example
+///
+/// ```
+/// use dusk_cdf::Breakpoint;
+///
+/// let breakpoint = Breakpoint {
+///     source: String::from("gadget.rs"),
+///     line: Some(8),
+///     ..Default::default()
+/// };
+///
+/// assert!(breakpoint.kind.is_none());
+/// ```
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
 pub struct Breakpoint {
     /// Source pattern that will trigger the breakpoint.
     pub source: String,
@@ -16,6 +37,10 @@ pub struct Breakpoint {
     /// incidence of `source` will trigger the breakpoint, regardless of
     /// the line.
     pub line: Option<u64>,
+    /// Kind of the gates that will trigger the breakpoint. If `None`, any
+    /// gate will trigger the breakpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<GateKind>,
 }
 
 impl Breakpoint {
@@ -28,6 +53,7 @@ impl Breakpoint {
     /// let breakpoint = Breakpoint {
     ///     source: String::from("xyz"),
     ///     line: Some(40),
+    ///     kind: None,
     /// };
     ///
     /// assert!(breakpoint.matches("xyz", 40));
@@ -39,14 +65,64 @@ impl Breakpoint {
                 None => true,
             }
     }
+
+    /// Check if the constraint matches the source, line and gate kind of the
+    /// breakpoint.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{Breakpoint, CircuitDescription, GateKind};
+    ///
+    /// let mut circuit = CircuitDescription::open("../assets/test.cdf")?;
+    /// let constraint = circuit.fetch_constraint(8)?;
+    ///
+    /// let mut breakpoint = Breakpoint {
+    ///     source: String::from("gadget.rs"),
+    ///     line: None,
+    ///     kind: Some(GateKind::Arithmetic),
+    /// };
+    ///
+    /// assert!(breakpoint.matches_constraint(&constraint));
+    ///
+    /// breakpoint.kind = Some(GateKind::Range);
+    /// assert!(!breakpoint.matches_constraint(&constraint));
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn matches_constraint(&self, constraint: &Constraint) -> bool {
+        self.matches(constraint.name(), constraint.line())
+            && match self.kind {
+                Some(k) => k == constraint.polynomial().selectors.gate_kind(),
+                None => true,
+            }
+    }
+}
+
+/// Outcome of the breakpoints that match a visited constraint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Hit {
+    /// No breakpoint matches the constraint
+    Miss,
+    /// The matching breakpoints consumed a hit to be ignored
+    Ignored,
+    /// The breakpoint with the provided id stops the execution
+    Stop(usize),
 }
 
 /// A collection of breakpoints, the debugger keeps track of the breakpoints
 /// using this struct.
+///
+/// A breakpoint can be temporary, being removed by the debugger after its
+/// first hit, and can have a number of hits to be ignored before it stops the
+/// execution.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Breakpoints {
     next_id: usize,
     breakpoints: HashMap<Breakpoint, usize>,
+    temporary: HashSet<usize>,
+    ignore: HashMap<usize, usize>,
 }
 
 impl Default for Breakpoints {
@@ -54,6 +130,8 @@ impl Default for Breakpoints {
         Self {
             next_id: 1,
             breakpoints: HashMap::default(),
+            temporary: HashSet::default(),
+            ignore: HashMap::default(),
         }
     }
 }
@@ -69,8 +147,18 @@ impl Deref for Breakpoints {
 impl Breakpoints {
     /// Add a breakpoint to the collection of breakpoints.
     pub fn add(&mut self, source: String, line: Option<u64>) -> usize {
-        let breakpoint = Breakpoint { source, line };
+        self.insert(Breakpoint {
+            source,
+            line,
+            kind: None,
+        })
+    }
 
+    /// Add a breakpoint, along with its gate kind, to the collection of
+    /// breakpoints.
+    ///
+    /// An identical existing breakpoint keeps its id.
+    pub fn insert(&mut self, breakpoint: Breakpoint) -> usize {
         let id = *self.breakpoints.entry(breakpoint).or_insert(self.next_id);
 
         if id >= self.next_id {
@@ -80,6 +168,48 @@ impl Breakpoints {
         id
     }
 
+    /// Add a breakpoint that is removed after its first hit.
+    ///
+    /// If an identical breakpoint already exists, with the same source, line
+    /// and kind, its id is returned and it stays permanent. A breakpoint that
+    /// differs only by its kind is added as a new temporary breakpoint.
+    pub fn add_temporary(&mut self, breakpoint: Breakpoint) -> usize {
+        let len = self.breakpoints.len();
+        let id = self.insert(breakpoint);
+
+        if len != self.breakpoints.len() {
+            self.temporary.insert(id);
+        }
+
+        id
+    }
+
+    /// Check if the breakpoint is removed after its first hit.
+    pub fn is_temporary(&self, id: usize) -> bool {
+        self.temporary.contains(&id)
+    }
+
+    /// Ignore the next `count` hits of a breakpoint, replacing any previous
+    /// count. Returns `false` if the breakpoint doesn't exist.
+    pub fn ignore(&mut self, id: usize, count: usize) -> bool {
+        if self.find_breakpoint_from_id(id).is_none() {
+            return false;
+        }
+
+        if count == 0 {
+            self.ignore.remove(&id);
+        } else {
+            self.ignore.insert(id, count);
+        }
+
+        true
+    }
+
+    /// Remaining hits of a breakpoint to be ignored
+    pub fn ignore_count(&self, id: usize) -> usize {
+        self.ignore.get(&id).copied().unwrap_or(0)
+    }
+
     /// Remove a breakpoint from the collection of breakpoints.
     pub fn remove(&mut self, id: usize) -> Option<Breakpoint> {
         let removed = self
@@ -90,24 +220,74 @@ impl Breakpoints {
 
         if let Some(b) = &removed {
             self.breakpoints.remove(b);
+            self.temporary.remove(&id);
+            self.ignore.remove(&id);
         }
 
         removed
     }
 
     /// Find a breakpoint from the collection of breakpoints given constraint.
-    /// The name of the constraint is used as the source pattern
+    /// The name of the constraint is used as the source pattern. Among
+    /// several matches, the lowest id is returned, as the scans stop at.
     pub fn find_breakpoint<'a>(
         &self,
         constraint: &Constraint<'a>,
     ) -> Option<usize> {
-        let source = constraint.name();
-        let line = constraint.line();
-
         self.breakpoints
-            .keys()
-            .find(|b| b.matches(source, line))
-            .and_then(|b| self.breakpoints.get(b).copied())
+            .iter()
+            .filter(|(b, _)| b.matches_constraint(constraint))
+            .map(|(_, id)| *id)
+            .min()
+    }
+
+    /// Hit the breakpoints that match the constraint, stopping at the lowest
+    /// id among the ones without hits left to be ignored.
+    ///
+    /// Every matching breakpoint with hits left to be ignored consumes one of
+    /// them instead of stopping.
+    pub(crate) fn hit<'a>(&mut self, constraint: &Constraint<'a>) -> Hit {
+        let Self {
+            breakpoints,
+            ignore,
+            ..
+        } = self;
+
+        let mut matched = false;
+
+        let stop = breakpoints
+            .iter()
+            .filter(|(b, _)| b.matches_constraint(constraint))
+            .map(|(_, id)| *id)
+            .inspect(|_| matched = true)
+            .filter(|id| match ignore.get_mut(id) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    false
+                }
+                Some(_) => {
+                    ignore.remove(id);
+                    false
+                }
+                None => true,
+            })
+            .min();
+
+        match stop {
+            Some(id) => Hit::Stop(id),
+            None if matched => Hit::Ignored,
+            None => Hit::Miss,
+        }
+    }
+
+    /// Breakpoints along with their ids, in ascending order of id.
+    pub fn sorted(&self) -> Vec<(usize, &Breakpoint)> {
+        let mut sorted: Vec<_> =
+            self.breakpoints.iter().map(|(b, id)| (*id, b)).collect();
+
+        sorted.sort_unstable_by_key(|(id, _)| *id);
+
+        sorted
     }
 
     /// Find a breakpoint by its id.
@@ -121,5 +301,15 @@ impl Breakpoints {
     pub fn clear(&mut self, source: &str) {
         self.breakpoints
             .retain(|b, _| !source.contains(b.source.as_str()));
+
+        let Self {
+            breakpoints,
+            temporary,
+            ignore,
+            ..
+        } = self;
+
+        temporary.retain(|id| breakpoints.values().any(|i| i == id));
+        ignore.retain(|id, _| breakpoints.values().any(|i| i == id));
     }
 }
@@ -0,0 +1,57 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Constraint;
+
+/// Portion of the circuit the navigation is limited to.
+///
+/// The constraints out of the focus are skipped: the debugger never stops at
+/// them, and reports their failures only when asked to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Focus {
+    /// Constraints of the sources that match the pattern, as a
+    /// [`Breakpoint`](crate::Breakpoint) does
+    Source(String),
+    /// Constraints with an id in `start..end`
+    Range {
+        /// First constraint of the focus
+        start: usize,
+        /// Constraint past the end of the focus
+        end: usize,
+    },
+}
+
+impl Focus {
+    /// Check if the constraint with the provided id is in focus
+    pub fn contains(&self, idx: usize, constraint: &Constraint) -> bool {
+        match self {
+            Self::Source(pattern) => constraint.name().contains(pattern),
+            Self::Range { start, end } => (*start..*end).contains(&idx),
+        }
+    }
+}
+
+impl fmt::Display for Focus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source(pattern) => f.write_str(pattern),
+            Self::Range { start, end } => write!(f, "{}..{}", start, end),
+        }
+    }
+}
+
+/// Flags on whether a navigation may stop at the constraint, and whether it
+/// reports its failure
+pub(crate) fn visibility(
+    focus: Option<&Focus>,
+    failures: bool,
+    idx: usize,
+    constraint: &Constraint,
+) -> (bool, bool) {
+    match focus {
+        Some(f) if !f.contains(idx, constraint) => (false, failures),
+        _ => (true, true),
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::io;
+
+use crate::memory::HeapSize;
+use crate::CircuitDescription;
+
+/// First constraint of every source line, indexed by line
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Lines {
+    lines: HashMap<u64, Vec<(String, usize)>>,
+}
+
+impl Lines {
+    pub fn load<S>(cdf: &mut CircuitDescription<S>) -> io::Result<Self>
+    where
+        S: io::Read + io::Seek,
+    {
+        let mut lines: HashMap<u64, Vec<(String, usize)>> = HashMap::new();
+
+        #[cfg(feature = "index")]
+        if let Some(index) = cdf.index() {
+            for (name, line, idx) in index.lines() {
+                lines.entry(line).or_default().push((name.to_string(), idx));
+            }
+
+            return Ok(Self { lines });
+        }
+
+        for idx in 0..cdf.preamble().constraints {
+            let constraint = match cdf.fetch_constraint(idx) {
+                Ok(c) => c,
+                // a corrupt record has no location to be found at
+                Err(e) if e.kind() == io::ErrorKind::InvalidData => continue,
+                Err(e) => return Err(e),
+            };
+
+            let sources = lines.entry(constraint.line()).or_default();
+
+            if !sources.iter().any(|(name, _)| name == constraint.name()) {
+                sources.push((constraint.name().to_string(), idx));
+            }
+        }
+
+        Ok(Self { lines })
+    }
+
+    /// First constraint at `line` of the sources whose name contains `source`
+    pub fn find(&self, source: &str, line: u64) -> Option<usize> {
+        self.lines
+            .get(&line)?
+            .iter()
+            .filter(|(name, _)| name.contains(source))
+            .map(|(_, idx)| *idx)
+            .min()
+    }
+
+    /// Check if a source whose name contains `source` has a constraint at
+    /// `line`, or at any line if `None`
+    pub fn contains(&self, source: &str, line: Option<u64>) -> bool {
+        match line {
+            Some(line) => self.find(source, line).is_some(),
+            None => self
+                .lines
+                .values()
+                .flatten()
+                .any(|(name, _)| name.contains(source)),
+        }
+    }
+}
+
+impl HeapSize for Lines {
+    fn heap_size(&self) -> usize {
+        self.lines.heap_size()
+    }
+}
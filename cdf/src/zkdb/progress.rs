@@ -0,0 +1,115 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Progress of a running scan of a [`SharedZkDebugger`], as of its last
+/// interruption point.
+///
+/// The total is the number of constraints between the start of the scan and
+/// the end of the circuit in its direction, from the preamble, so a scan that
+/// stops at a breakpoint finishes ahead of its estimate.
+///
+/// [`SharedZkDebugger`]: crate::SharedZkDebugger
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use dusk_cdf::ScanProgress;
+///
+/// let progress = ScanProgress {
+///     visited: 50_000,
+///     total: 200_000,
+///     elapsed: Duration::from_secs(2),
+/// };
+///
+/// assert_eq!(progress.percentage(), 25);
+/// assert_eq!(progress.throughput(), Some(25_000.0));
+/// assert_eq!(progress.eta(), Some(Duration::from_secs(6)));
+/// assert_eq!(
+///     progress.to_string(),
+///     "50000/200000 constraints (25%), 25000 constraints/s, 6s left"
+/// );
+/// ```
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub struct ScanProgress {
+    /// Constraints visited since the start of the scan
+    pub visited: usize,
+    /// Constraints to be visited by the scan, at most
+    pub total: usize,
+    /// Time since the start of the scan
+    pub elapsed: Duration,
+}
+
+impl ScanProgress {
+    /// Visited constraints per second, if any time elapsed
+    pub fn throughput(&self) -> Option<f64> {
+        let elapsed = self.elapsed.as_secs_f64();
+
+        (elapsed > 0.0).then(|| self.visited as f64 / elapsed)
+    }
+
+    /// Estimated time to visit the remaining constraints at the current
+    /// throughput, if any constraint was visited
+    pub fn eta(&self) -> Option<Duration> {
+        let throughput = self.throughput().filter(|t| *t > 0.0)?;
+        let remaining = self.total.saturating_sub(self.visited);
+
+        Some(Duration::from_secs_f64(remaining as f64 / throughput))
+    }
+
+    /// Visited share of the total, from 0 to 100
+    pub fn percentage(&self) -> u64 {
+        match self.total {
+            0 => 100,
+            total => {
+                (self.visited.min(total) as u128 * 100 / total as u128) as u64
+            }
+        }
+    }
+}
+
+impl fmt::Display for ScanProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} constraints ({}%)",
+            self.visited,
+            self.total,
+            self.percentage()
+        )?;
+
+        if let Some(throughput) = self.throughput() {
+            write!(f, ", {:.0} constraints/s", throughput)?;
+        }
+
+        if let Some(eta) = self.eta() {
+            write!(f, ", {}s left", eta.as_secs_f64().ceil() as u64)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Start of a running scan, from which its progress is computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ScanStart {
+    pub started: Instant,
+    pub position: usize,
+    pub total: usize,
+}
+
+impl ScanStart {
+    /// Progress of the scan, now at `position`
+    pub fn progress(&self, position: usize) -> ScanProgress {
+        ScanProgress {
+            visited: position.abs_diff(self.position).min(self.total),
+            total: self.total,
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
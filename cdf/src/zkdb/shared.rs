@@ -0,0 +1,405 @@
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use crate::{EncodableConstraint, EncodableWitness, Preamble};
+
+use super::progress::ScanStart;
+use super::{
+    Anchor, Breakpoint, CancelToken, DebuggerBackend, Movement, ScanProgress,
+    State, Until, ZkDebugger, SCAN_CHUNK,
+};
+
+/// Immutable data of the loaded circuit, readable without locking the
+/// debugger.
+///
+/// The sources are shared with the debugger rather than copied.
+#[derive(Debug)]
+struct Metadata {
+    preamble: Preamble,
+    names: Arc<[String]>,
+    contents: Arc<[String]>,
+}
+
+/// A [`ZkDebugger`] that can be shared across threads.
+///
+/// The preamble, the sources and the position are available without waiting
+/// for the debugger, so they remain responsive while a scan is running. The
+/// scans of [`Self::cont`] and [`Self::turn`] release the debugger every few
+/// thousand constraints, and pause at that point if [`Self::interrupt`] was
+/// called; their progress is reported by [`Self::progress`] meanwhile.
+#[derive(Debug)]
+pub struct SharedZkDebugger<S> {
+    debugger: Arc<Mutex<ZkDebugger<S>>>,
+    metadata: Arc<Metadata>,
+    position: Arc<AtomicUsize>,
+    interrupted: CancelToken,
+    scan: Arc<Mutex<Option<ScanStart>>>,
+}
+
+impl<S> Clone for SharedZkDebugger<S> {
+    fn clone(&self) -> Self {
+        Self {
+            debugger: Arc::clone(&self.debugger),
+            metadata: Arc::clone(&self.metadata),
+            position: Arc::clone(&self.position),
+            interrupted: self.interrupted.clone(),
+            scan: Arc::clone(&self.scan),
+        }
+    }
+}
+
+impl<S> From<ZkDebugger<S>> for SharedZkDebugger<S> {
+    fn from(debugger: ZkDebugger<S>) -> Self {
+        let (names, contents) = debugger.shared_sources();
+        let metadata = Metadata {
+            preamble: *debugger.preamble(),
+            names,
+            contents,
+        };
+
+        Self {
+            position: Arc::new(AtomicUsize::new(debugger.position())),
+            debugger: Arc::new(Mutex::new(debugger)),
+            metadata: Arc::new(metadata),
+            interrupted: CancelToken::new(),
+            scan: Arc::default(),
+        }
+    }
+}
+
+impl<S> SharedZkDebugger<S> {
+    /// Preamble of the loaded circuit
+    pub fn preamble(&self) -> &Preamble {
+        &self.metadata.preamble
+    }
+
+    /// Path and contents of the sources of the loaded circuit
+    pub fn sources(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.metadata
+            .names
+            .iter()
+            .map(|path| path.as_str())
+            .zip(self.metadata.contents.iter().map(|c| c.as_str()))
+    }
+
+    /// Id of the constraint the debugger is positioned at.
+    ///
+    /// During a scan, this is the position of its last interruption point.
+    pub fn position(&self) -> usize {
+        self.position.load(Ordering::Acquire)
+    }
+
+    /// Pause the running scan at its next interruption point
+    pub fn interrupt(&self) {
+        self.interrupted.cancel();
+    }
+
+    /// Token cancelled by [`Self::interrupt`], to be handed to a signal
+    /// handler
+    pub fn interrupt_token(&self) -> CancelToken {
+        self.interrupted.clone()
+    }
+
+    /// Progress of the running scan of [`Self::cont`] or [`Self::turn`], as
+    /// of its last interruption point, if any scan is running
+    pub fn progress(&self) -> Option<ScanProgress> {
+        let scan = *self.scan.lock().unwrap_or_else(PoisonError::into_inner);
+
+        scan.map(|s| s.progress(self.position()))
+    }
+
+    /// Exclusive access to the debugger.
+    ///
+    /// The position is published when the guard is dropped.
+    pub fn lock(&self) -> io::Result<ZkDebuggerGuard<'_, S>> {
+        let guard = self.debugger.lock().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                "the debugger lock is poisoned",
+            )
+        })?;
+
+        Ok(ZkDebuggerGuard {
+            guard,
+            position: &self.position,
+        })
+    }
+}
+
+impl<S> SharedZkDebugger<S>
+where
+    S: io::Read + io::Seek,
+{
+    /// [`ZkDebugger::afore`] over the shared debugger
+    pub fn afore(&self) -> io::Result<State> {
+        self.lock()?.afore()
+    }
+
+    /// [`ZkDebugger::cont`], interruptible via [`Self::interrupt`].
+    ///
+    /// An interrupted scan returns [`State::Paused`], leaving the debugger at
+    /// the constraint where it was paused; the next scan resumes it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use dusk_cdf::{SharedZkDebugger, ZkDebugger};
+    /// use std::thread;
+    ///
+    /// let debugger = ZkDebugger::open("../assets/test.cdf")?;
+    /// let debugger = SharedZkDebugger::from(debugger);
+    ///
+    /// let scan = debugger.clone();
+    /// let scan = thread::spawn(move || scan.cont());
+    ///
+    /// // the metadata is available while the scan runs
+    /// assert_ne!(debugger.preamble().constraints, 0);
+    ///
+    /// scan.join().expect("the scan panicked")?;
+    ///
+    /// // the progress is reported only while the scan runs
+    /// assert!(debugger.progress().is_none());
+    ///
+    /// # Ok(()) }
+    /// ```
+    pub fn cont(&self) -> io::Result<State> {
+        self.scan(Movement::Cont, ZkDebugger::cont_within, usize::MAX, None)
+    }
+
+    /// [`ZkDebugger::cont_max`], interruptible via [`Self::interrupt`].
+    pub fn cont_max(&self, max: usize) -> io::Result<State> {
+        self.scan(Movement::Cont, ZkDebugger::cont_within, max, None)
+    }
+
+    /// [`ZkDebugger::cont_until`], also paused by [`Self::interrupt`].
+    pub fn cont_until<U>(&self, until: U) -> io::Result<State>
+    where
+        U: Into<Until>,
+    {
+        let until = until.into();
+
+        self.scan(
+            Movement::Cont,
+            ZkDebugger::cont_within,
+            usize::MAX,
+            Some(&until),
+        )
+    }
+
+    /// [`ZkDebugger::goto`] over the shared debugger
+    pub fn goto(&self, idx: usize) -> io::Result<State> {
+        self.lock()?.goto(idx)
+    }
+
+    /// [`ZkDebugger::goto_line`] over the shared debugger
+    pub fn goto_line(&self, source: &str, line: u64) -> io::Result<State> {
+        self.lock()?.goto_line(source, line)
+    }
+
+    /// [`ZkDebugger::step`] over the shared debugger
+    pub fn step(&self) -> io::Result<State> {
+        self.lock()?.step()
+    }
+
+    /// [`ZkDebugger::turn`], interruptible via [`Self::interrupt`].
+    ///
+    /// Behaves as [`Self::cont`] when interrupted.
+    pub fn turn(&self) -> io::Result<State> {
+        self.scan(Movement::Turn, ZkDebugger::turn_within, usize::MAX, None)
+    }
+
+    /// [`ZkDebugger::turn_max`], interruptible via [`Self::interrupt`].
+    pub fn turn_max(&self, max: usize) -> io::Result<State> {
+        self.scan(Movement::Turn, ZkDebugger::turn_within, max, None)
+    }
+
+    /// [`ZkDebugger::turn_until`], also paused by [`Self::interrupt`].
+    pub fn turn_until<U>(&self, until: U) -> io::Result<State>
+    where
+        U: Into<Until>,
+    {
+        let until = until.into();
+
+        self.scan(
+            Movement::Turn,
+            ZkDebugger::turn_within,
+            usize::MAX,
+            Some(&until),
+        )
+    }
+
+    fn scan<F>(
+        &self,
+        movement: Movement,
+        within: F,
+        budget: usize,
+        until: Option<&Until>,
+    ) -> io::Result<State>
+    where
+        F: Fn(
+            &mut ZkDebugger<S>,
+            &mut Option<Anchor>,
+            usize,
+        ) -> io::Result<Option<State>>,
+    {
+        self.interrupted.reset();
+
+        let position = self.position();
+        let total = match movement {
+            Movement::Turn => position,
+            _ => self.preamble().constraints.saturating_sub(position),
+        };
+
+        self.scan
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(ScanStart {
+                started: Instant::now(),
+                position,
+                total: total.min(budget),
+            });
+
+        let state = self.scan_chunks(movement, within, budget, until);
+
+        self.scan
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        state
+    }
+
+    fn scan_chunks<F>(
+        &self,
+        movement: Movement,
+        within: F,
+        budget: usize,
+        until: Option<&Until>,
+    ) -> io::Result<State>
+    where
+        F: Fn(
+            &mut ZkDebugger<S>,
+            &mut Option<Anchor>,
+            usize,
+        ) -> io::Result<Option<State>>,
+    {
+        let mut anchor = self.lock()?.resumed(movement);
+        let mut budget = budget;
+
+        loop {
+            let mut debugger = self.lock()?;
+            let chunk = budget.min(SCAN_CHUNK);
+
+            if let Some(state) = within(&mut debugger, &mut anchor, chunk)? {
+                return debugger.navigated(movement, Ok(state));
+            }
+
+            budget -= chunk;
+
+            if budget == 0 {
+                let state = debugger.exhausted();
+
+                return debugger.navigated(movement, state);
+            }
+
+            if self.interrupted.reset() || until.map_or(false, Until::reached) {
+                let state = debugger.pause(movement, anchor);
+
+                return debugger.navigated(movement, Ok(state));
+            }
+        }
+    }
+}
+
+impl<S> DebuggerBackend for SharedZkDebugger<S>
+where
+    S: io::Read + io::Seek,
+{
+    fn preamble(&self) -> io::Result<Preamble> {
+        Ok(*SharedZkDebugger::preamble(self))
+    }
+
+    fn afore(&mut self) -> io::Result<State> {
+        SharedZkDebugger::afore(self)
+    }
+
+    fn cont(&mut self) -> io::Result<State> {
+        SharedZkDebugger::cont(self)
+    }
+
+    fn goto(&mut self, idx: usize) -> io::Result<State> {
+        SharedZkDebugger::goto(self, idx)
+    }
+
+    fn step(&mut self) -> io::Result<State> {
+        SharedZkDebugger::step(self)
+    }
+
+    fn turn(&mut self) -> io::Result<State> {
+        SharedZkDebugger::turn(self)
+    }
+
+    fn position(&self) -> io::Result<usize> {
+        Ok(SharedZkDebugger::position(self))
+    }
+
+    fn current_constraint(&mut self) -> io::Result<EncodableConstraint> {
+        DebuggerBackend::current_constraint(&mut *self.lock()?)
+    }
+
+    fn constraint(&mut self, idx: usize) -> io::Result<EncodableConstraint> {
+        DebuggerBackend::constraint(&mut *self.lock()?, idx)
+    }
+
+    fn witness(&mut self, idx: usize) -> io::Result<EncodableWitness> {
+        DebuggerBackend::witness(&mut *self.lock()?, idx)
+    }
+
+    fn add_breakpoint(
+        &mut self,
+        source: String,
+        line: Option<u64>,
+    ) -> io::Result<usize> {
+        Ok(self.lock()?.add_breakpoint(source, line))
+    }
+
+    fn remove_breakpoint(&mut self, id: usize) -> io::Result<bool> {
+        Ok(self.lock()?.remove_breakpoint(id).is_some())
+    }
+
+    fn breakpoints(&self) -> io::Result<Vec<(usize, Breakpoint)>> {
+        DebuggerBackend::breakpoints(&*self.lock()?)
+    }
+}
+
+/// Exclusive access to the debugger of a [`SharedZkDebugger`]
+#[derive(Debug)]
+pub struct ZkDebuggerGuard<'a, S> {
+    guard: MutexGuard<'a, ZkDebugger<S>>,
+    position: &'a AtomicUsize,
+}
+
+impl<'a, S> Deref for ZkDebuggerGuard<'a, S> {
+    type Target = ZkDebugger<S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<'a, S> DerefMut for ZkDebuggerGuard<'a, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, S> Drop for ZkDebuggerGuard<'a, S> {
+    fn drop(&mut self) {
+        self.position
+            .store(self.guard.position(), Ordering::Release);
+    }
+}
@@ -1,5 +1,40 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Constraint;
+
+/// Source location of the constraint the debugger stopped at
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Location {
+    /// Name of the source file
+    pub source: String,
+    /// Line of the source file
+    pub line: u64,
+    /// Column of the source file
+    pub col: u64,
+}
+
+impl<'a> From<&Constraint<'a>> for Location {
+    fn from(constraint: &Constraint<'a>) -> Self {
+        Self {
+            source: constraint.name().to_string(),
+            line: constraint.line(),
+            col: constraint.col(),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.source, self.line, self.col)
+    }
+}
+
 /// State describind a mutation of the zk debugger
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+#[non_exhaustive]
 pub enum State {
     /// BOF of the CDF backend
     ///
@@ -9,20 +44,173 @@ pub enum State {
     Constraint {
         /// Id of the constraint
         id: usize,
+        /// Source location of the constraint
+        location: Location,
     },
     /// Hit a constraint that evaluated to false
     InvalidConstraint {
         /// Id of the constraint
         id: usize,
+        /// Source location of the constraint
+        location: Location,
+    },
+    /// Hit a constraint record that couldn't be decoded
+    ///
+    /// Only produced with [`CorruptionPolicy::Stop`](crate::CorruptionPolicy)
+    Corrupt {
+        /// Id of the constraint
+        id: usize,
+        /// Offset of the record in the CDF file
+        offset: u64,
     },
     /// Hit a breakpoint
     Breakpoint {
         /// Id of the breakpoint
         id: usize,
+        /// Id of the constraint that triggered the breakpoint
+        constraint: usize,
+        /// Source location of the constraint
+        location: Location,
+    },
+    /// Stopped at a witness allocation
+    ///
+    /// Only produced when the witness steps are enabled
+    Witness {
+        /// Id of the witness
+        id: usize,
+    },
+    /// A scan was paused before a stop, and resumes from the constraint with
+    /// the same stop conditions
+    Paused {
+        /// Id of the constraint the scan was paused at
+        id: usize,
+    },
+    /// Visited the budget of constraints of a bounded scan without a stop
+    Exhausted {
+        /// Id of the last visited constraint
+        id: usize,
+        /// Source location of the constraint, unless it is corrupt
+        location: Option<Location>,
     },
     /// EOF of the CDF backend
     End {
-        /// Id of the breakpoint
+        /// Id of the last constraint
         id: usize,
+        /// Source location of the last constraint, if the circuit has any
+        location: Option<Location>,
     },
 }
+
+impl State {
+    /// Id of the constraint the debugger stopped at, if the state refers to
+    /// one
+    pub const fn constraint(&self) -> Option<usize> {
+        match self {
+            Self::Beginning => Some(0),
+            Self::Constraint { id, .. }
+            | Self::InvalidConstraint { id, .. }
+            | Self::Corrupt { id, .. }
+            | Self::Paused { id }
+            | Self::Exhausted { id, .. }
+            | Self::End { id, .. } => Some(*id),
+            Self::Breakpoint { constraint, .. } => Some(*constraint),
+            Self::Witness { .. } => None,
+        }
+    }
+
+    /// Source location of the constraint the debugger stopped at, if known
+    pub const fn location(&self) -> Option<&Location> {
+        match self {
+            Self::Constraint { location, .. }
+            | Self::InvalidConstraint { location, .. }
+            | Self::Breakpoint { location, .. } => Some(location),
+            Self::Exhausted { location, .. } | Self::End { location, .. } => {
+                location.as_ref()
+            }
+            Self::Beginning
+            | Self::Corrupt { .. }
+            | Self::Witness { .. }
+            | Self::Paused { .. } => None,
+        }
+    }
+
+    /// Id of the breakpoint that stopped the execution, if any
+    pub const fn breakpoint(&self) -> Option<usize> {
+        match self {
+            Self::Breakpoint { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Check if the execution stopped at a constraint that evaluated to false
+    pub const fn is_invalid(&self) -> bool {
+        matches!(self, Self::InvalidConstraint { .. })
+    }
+
+    /// Check if a scan was paused before a stop
+    pub const fn is_paused(&self) -> bool {
+        matches!(self, Self::Paused { .. })
+    }
+
+    /// Check if a bounded scan visited its budget without a stop
+    pub const fn is_exhausted(&self) -> bool {
+        matches!(self, Self::Exhausted { .. })
+    }
+
+    /// Check if the execution reached the end of the circuit
+    pub const fn is_end(&self) -> bool {
+        matches!(self, Self::End { .. })
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Beginning => write!(f, "beginning of the circuit"),
+            Self::Constraint { id, location } => {
+                write!(f, "constraint {} at {}", id, location)
+            }
+            Self::InvalidConstraint { id, location } => {
+                write!(f, "invalid constraint {} at {}", id, location)
+            }
+            Self::Corrupt { id, offset } => {
+                write!(f, "corrupt constraint {} at offset {}", id, offset)
+            }
+            Self::Breakpoint {
+                id,
+                constraint,
+                location,
+            } => write!(
+                f,
+                "breakpoint {} at constraint {} ({})",
+                id, constraint, location
+            ),
+            Self::Witness { id } => write!(f, "allocation of witness {}", id),
+            Self::Paused { id } => {
+                write!(f, "scan paused at constraint {}", id)
+            }
+            Self::Exhausted {
+                id,
+                location: Some(location),
+            } => write!(
+                f,
+                "scan budget exhausted at constraint {} ({})",
+                id, location
+            ),
+            Self::Exhausted { id, location: None } => {
+                write!(f, "scan budget exhausted at constraint {}", id)
+            }
+            Self::End {
+                id,
+                location: Some(location),
+            } => write!(
+                f,
+                "end of the circuit at constraint {} ({})",
+                id, location
+            ),
+            Self::End { id, location: None } => {
+                write!(f, "end of the circuit at constraint {}", id)
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Preamble;
+
+use super::{Breakpoint, State};
+
+/// Operation that moved the debugger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Movement {
+    /// [`ZkDebugger::afore`](crate::ZkDebugger::afore)
+    Afore,
+    /// [`ZkDebugger::cont`](crate::ZkDebugger::cont)
+    Cont,
+    /// [`ZkDebugger::goto`](crate::ZkDebugger::goto)
+    Goto {
+        /// Target constraint
+        constraint: usize,
+    },
+    /// [`ZkDebugger::step`](crate::ZkDebugger::step)
+    Step,
+    /// [`ZkDebugger::turn`](crate::ZkDebugger::turn)
+    Turn,
+}
+
+/// Change of the zk debugger, emitted to its subscribers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "camelCase")]
+pub enum StateChange {
+    /// A circuit is loaded in the debugger
+    Loaded {
+        /// Preamble of the circuit
+        preamble: Preamble,
+    },
+    /// The debugger moved to a new state
+    Navigated {
+        /// Operation that moved the debugger
+        movement: Movement,
+        /// Resulting state
+        state: State,
+    },
+    /// A breakpoint was added
+    BreakpointAdded {
+        /// Id of the breakpoint
+        id: usize,
+        /// Added breakpoint
+        breakpoint: Breakpoint,
+    },
+    /// A breakpoint was removed
+    BreakpointRemoved {
+        /// Id of the breakpoint
+        id: usize,
+        /// Removed breakpoint
+        breakpoint: Breakpoint,
+    },
+    /// The breakpoints matching a source were removed
+    BreakpointsCleared {
+        /// Cleared source
+        source: String,
+    },
+}
+
+type Subscriber = Arc<dyn Fn(&StateChange) + Send + Sync>;
+
+/// Callbacks notified of the debugger changes, in subscription order
+#[derive(Clone)]
+pub(crate) struct Subscribers {
+    next_id: usize,
+    subscribers: Vec<(usize, Subscriber)>,
+}
+
+impl Default for Subscribers {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            subscribers: vec![],
+        }
+    }
+}
+
+impl fmt::Debug for Subscribers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscribers")
+            .field("next_id", &self.next_id)
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+impl Subscribers {
+    pub fn add(&mut self, subscriber: Subscriber) -> usize {
+        let id = self.next_id;
+
+        self.next_id += 1;
+        self.subscribers.push((id, subscriber));
+
+        id
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        let len = self.subscribers.len();
+
+        self.subscribers.retain(|(idx, _)| *idx != id);
+
+        len != self.subscribers.len()
+    }
+
+    /// Notify the subscribers; the change is built only if there is any
+    pub fn notify<F>(&self, change: F)
+    where
+        F: FnOnce() -> StateChange,
+    {
+        if self.subscribers.is_empty() {
+            return;
+        }
+
+        let change = change();
+
+        self.subscribers.iter().for_each(|(_, s)| s(&change));
+    }
+}
@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Flag that pauses the scans observing it, shareable across threads.
+///
+/// The clones of a token share the flag, so one of them can be handed to a
+/// signal handler while the scan runs on another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Create a token that isn't cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pause the scans observing the token at their next interruption point
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Check if the token is cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Clear the cancellation, returning `true` if it was set
+    pub fn reset(&self) -> bool {
+        self.cancelled.swap(false, Ordering::AcqRel)
+    }
+}
+
+/// Condition that pauses a scan of
+/// [`ZkDebugger::cont_until`](crate::ZkDebugger::cont_until)
+#[derive(Debug, Clone)]
+pub enum Until {
+    /// Pause once the instant is past
+    Deadline(Instant),
+    /// Pause once the token is cancelled
+    Cancelled(CancelToken),
+}
+
+impl Until {
+    /// Check if the scan should pause.
+    ///
+    /// A cancelled token stays cancelled until [`CancelToken::reset`], so it
+    /// pauses every scan observing it in the meantime.
+    pub fn reached(&self) -> bool {
+        match self {
+            Self::Deadline(deadline) => Instant::now() >= *deadline,
+            Self::Cancelled(token) => token.is_cancelled(),
+        }
+    }
+}
+
+impl From<Instant> for Until {
+    fn from(deadline: Instant) -> Self {
+        Self::Deadline(deadline)
+    }
+}
+
+impl From<Duration> for Until {
+    fn from(timeout: Duration) -> Self {
+        Self::Deadline(Instant::now() + timeout)
+    }
+}
+
+impl From<CancelToken> for Until {
+    fn from(token: CancelToken) -> Self {
+        Self::Cancelled(token)
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "dap")]
+
 use std::path::PathBuf;
 use std::{env, io};
 
@@ -158,6 +160,7 @@ async fn initialize_works() -> io::Result<()> {
                     instruction_reference: None,
                     offset: None,
                 },
+                kind: None,
             })
             .into(),
         )
@@ -225,11 +228,125 @@ fn request_encode_decode() {
                 instruction_reference: None,
                 offset: None,
             },
+            kind: None,
+        },
+        ZkRequest::AddTemporaryBreakpoint {
+            breakpoint: dap_reactor::prelude::Breakpoint {
+                id: None,
+                verified: true,
+                message: None,
+                source: None,
+                line: Some(20),
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_reference: None,
+                offset: None,
+            },
+            kind: Some(GateKind::Range),
         },
         ZkRequest::RemoveBreakpoint { id: 48 },
+        ZkRequest::AddBreakpoints {
+            breakpoints: vec![dap_reactor::prelude::Breakpoint {
+                id: None,
+                verified: true,
+                message: None,
+                source: None,
+                line: Some(25),
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_reference: None,
+                offset: None,
+            }],
+            kind: Some(GateKind::Arithmetic),
+        },
+        ZkRequest::RemoveBreakpoints { source: None },
+        ZkRequest::RemoveBreakpoints {
+            source: Some("lib.rs".into()),
+        },
         ZkRequest::LoadCdf { path: "foo".into() },
         ZkRequest::SourceContents,
+        ZkRequest::SourceList { filter: None },
+        ZkRequest::SourceList {
+            filter: Some("*.rs".into()),
+        },
+        ZkRequest::Sources {
+            filter: Some("src/**".into()),
+            offset: 3,
+            limit: Some(10),
+            known: vec![],
+        },
+        ZkRequest::Sources {
+            filter: None,
+            offset: 0,
+            limit: None,
+            known: vec![ZkSourceInfo {
+                path: "foo".into(),
+                size: 3,
+                hash: "bar".into(),
+            }],
+        },
+        ZkRequest::SourceChunk {
+            path: "foo".into(),
+            offset: 1024,
+            len: 4096,
+        },
         ZkRequest::Witness { id: 38 },
+        ZkRequest::ContinueMax {
+            max: 1000,
+            reverse: false,
+        },
+        ZkRequest::ContinueMax {
+            max: 10,
+            reverse: true,
+        },
+        ZkRequest::Focus {
+            focus: Some(Focus::Source("gadget.rs".into())),
+            failures: false,
+        },
+        ZkRequest::Focus {
+            focus: Some(Focus::Range { start: 3, end: 8 }),
+            failures: true,
+        },
+        ZkRequest::Focus {
+            focus: None,
+            failures: false,
+        },
+        ZkRequest::Top { limit: 10 },
+        ZkRequest::WireCheck,
+        ZkRequest::Timeline,
+        ZkRequest::ScalarCheck,
+        ZkRequest::ConstCheck,
+        ZkRequest::SharedWires,
+        ZkRequest::Locals,
+        ZkRequest::Frontier,
+        ZkRequest::Memory,
+        ZkRequest::Snippet,
+        ZkRequest::Equation {
+            notation: dusk_cdf::Notation::Latex,
+        },
+        ZkRequest::Backend {
+            call: BackendCall::Step,
+        },
+        ZkRequest::Backend {
+            call: BackendCall::Goto { constraint: 3 },
+        },
+        ZkRequest::Backend {
+            call: BackendCall::AddBreakpoint {
+                source: "foo".into(),
+                line: Some(7),
+            },
+        },
+        ZkRequest::Pause,
+        ZkRequest::Batch { requests: vec![] },
+        ZkRequest::Batch {
+            requests: vec![
+                ZkRequest::Witness { id: 3 },
+                ZkRequest::Witness { id: 5 },
+                ZkRequest::Locals,
+            ],
+        },
     ];
 
     for case in cases {
@@ -261,13 +378,30 @@ fn response_encode_decode() {
             id: 92,
             removed: true,
         },
+        ZkResponse::AddBreakpoints { ids: vec![3, 4] },
+        ZkResponse::RemoveBreakpoints { ids: vec![] },
         ZkResponse::LoadCdf,
         ZkResponse::SourceContents {
-            sources: vec![ZkSource {
+            sources: vec![ZkSource::new("foo", "bar")],
+        },
+        ZkResponse::SourceList {
+            sources: vec![ZkSourceInfo {
                 path: "foo".into(),
-                contents: "bar".into(),
+                size: 3,
+                hash: "baz".into(),
             }],
         },
+        ZkResponse::Sources {
+            sources: vec![ZkSource::new("foo", "bar")],
+            unchanged: vec!["baz".into()],
+            total: 5,
+        },
+        ZkResponse::SourceChunk {
+            path: "foo".into(),
+            offset: 1024,
+            contents: "bar".into(),
+            size: 8192,
+        },
         ZkResponse::Witness {
             witness: ZkWitness {
                 id: 92,
@@ -277,6 +411,190 @@ fn response_encode_decode() {
                 line: 19,
             },
         },
+        ZkResponse::ContinueMax {
+            state: State::Exhausted {
+                id: 1000,
+                location: Some(Location {
+                    source: "foo".into(),
+                    line: 18,
+                    col: 4,
+                }),
+            },
+        },
+        ZkResponse::ContinueMax {
+            state: State::End {
+                id: 25,
+                location: None,
+            },
+        },
+        ZkResponse::Focus {
+            focus: Some(Focus::Range { start: 3, end: 8 }),
+        },
+        ZkResponse::Focus { focus: None },
+        ZkResponse::Top {
+            usage: CircuitUsage {
+                sources: vec![SourceUsage {
+                    name: "foo".into(),
+                    bytes: 4096,
+                    constraints: 20,
+                    witnesses: 12,
+                    lines: 80,
+                    failures: 3,
+                }],
+                gadgets: vec![GadgetUsage {
+                    name: "foo".into(),
+                    line: 18,
+                    constraints: 9,
+                }],
+                heatmap: vec![SourceUsage {
+                    name: "foo".into(),
+                    bytes: 4096,
+                    constraints: 20,
+                    witnesses: 12,
+                    lines: 80,
+                    failures: 3,
+                }],
+                patterns: vec![PatternUsage {
+                    name: "Poseidon round".into(),
+                    occurrences: 2,
+                    constraints: 10,
+                }],
+            },
+        },
+        ZkResponse::WireCheck {
+            warnings: vec![
+                Warning::WireOutOfBounds {
+                    constraint: 3,
+                    wire: Wire::O,
+                    witness: 92,
+                },
+                Warning::WireMismatch {
+                    constraint: 4,
+                    wire: Wire::A,
+                    witness: 1,
+                    found: 2,
+                },
+            ],
+        },
+        ZkResponse::Timeline {
+            warnings: vec![Warning::WitnessAllocatedLater {
+                constraint: 7,
+                wire: Wire::B,
+                witness: 12,
+                allocated_at: 9,
+            }],
+            patterns: vec![GateMatch {
+                start: 6,
+                len: 2,
+                name: "ECC add".into(),
+                occurrence: 3,
+            }],
+        },
+        ZkResponse::ScalarCheck {
+            warnings: vec![
+                Warning::NonCanonicalWitness {
+                    witness: 3,
+                    path: "lib.rs".into(),
+                    line: 4,
+                    col: 9,
+                },
+                Warning::NonCanonicalSelector {
+                    constraint: 8,
+                    selector: Selector::Qc,
+                    path: "lib.rs".into(),
+                    line: 12,
+                    col: 5,
+                },
+            ],
+        },
+        ZkResponse::ConstCheck {
+            warnings: vec![Warning::UnfixedConstant {
+                witness: 5,
+                constant: Constant::GeneratorU,
+            }],
+        },
+        ZkResponse::SharedWires {
+            wires: vec![SharedWire {
+                witness: 7,
+                constraints: 3,
+                sources: vec!["gadget.rs".into(), "lib.rs".into()],
+            }],
+        },
+        ZkResponse::Locals {
+            constraint: 28,
+            witnesses: vec![ZkWitness {
+                id: 92,
+                constraint: Some(28),
+                value: "foo".into(),
+                source: "bar".into(),
+                line: 19,
+            }],
+        },
+        ZkResponse::Locals {
+            constraint: 3,
+            witnesses: vec![],
+        },
+        ZkResponse::Frontier {
+            frontier: Frontier::default(),
+        },
+        ZkResponse::Memory {
+            usage: MemoryUsage {
+                budget: 1 << 30,
+                sources: 2048,
+                ..Default::default()
+            },
+        },
+        ZkResponse::Snippet {
+            constraint: 3,
+            markdown: String::from("### Constraint #3\n"),
+        },
+        ZkResponse::Equation {
+            constraint: 3,
+            equation: String::from("q_C = 0\nq_C = 0x01\n"),
+        },
+        ZkResponse::Backend {
+            reply: BackendReply::State {
+                state: dusk_cdf::State::Breakpoint {
+                    id: 2,
+                    constraint: 7,
+                    location: dusk_cdf::Location {
+                        source: String::from("src/main.rs"),
+                        line: 12,
+                        col: 4,
+                    },
+                },
+            },
+        },
+        ZkResponse::Backend {
+            reply: BackendReply::Preamble {
+                preamble: Preamble::new(3, 5, Config::default()),
+            },
+        },
+        ZkResponse::Backend {
+            reply: BackendReply::Constraint {
+                constraint: EncodableConstraint::default(),
+            },
+        },
+        ZkResponse::Backend {
+            reply: BackendReply::Breakpoints {
+                breakpoints: vec![(
+                    1,
+                    dusk_cdf::Breakpoint {
+                        source: "foo".into(),
+                        line: None,
+                        kind: Some(GateKind::Range),
+                    },
+                )],
+            },
+        },
+        ZkResponse::Pause,
+        ZkResponse::Batch {
+            responses: vec![
+                Ok(ZkResponse::GotoLine { id: 4 }),
+                Err("the witness 99 doesn't exist".into()),
+                Ok(ZkResponse::LoadCdf),
+            ],
+        },
     ];
 
     for case in cases {
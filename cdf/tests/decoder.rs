@@ -1,14 +1,22 @@
+#![cfg(all(feature = "encoder", feature = "decoder"))]
+
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use dusk_cdf::*;
 
-#[test]
-fn decoder_works() {
-    let asset = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+/// Path of the CDF file of the test assets
+fn test_cdf() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .parent()
         .expect("failed to find root workspace dir")
         .join("assets")
         .join("test.cdf")
+}
+
+#[test]
+fn decoder_works() {
+    let asset = test_cdf()
         .canonicalize()
         .expect("failed to find CDF test asset");
 
@@ -38,3 +46,1166 @@ fn decoder_works() {
     cdf.fetch_constraint(cdf.preamble().constraints)
         .expect_err("constraint doesn't exist in the set");
 }
+
+#[test]
+fn wirecheck_reports_inconsistent_wires() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    // the witness at index 1 is registered with id 5
+    let witnesses = vec![
+        EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+        EncodableWitness::new(5, None, Scalar::default(), source.clone()),
+    ];
+
+    // `o` points beyond the witness table
+    let polynomial = Polynomial::builder().a(0).b(1).d(0).o(7).build();
+    let constraints = vec![EncodableConstraint::new(0, polynomial, source)];
+
+    // the encoder would reject the ids and the wires
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    )
+    .validate_ids(false)
+    .validate_wires(false);
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let expected = vec![
+        Warning::WireOutOfBounds {
+            constraint: 0,
+            wire: Wire::O,
+            witness: 7,
+        },
+        Warning::WireMismatch {
+            constraint: 0,
+            wire: Wire::B,
+            witness: 1,
+            found: 5,
+        },
+    ];
+
+    let mut cdf = CircuitDescription::from_reader(cursor.clone())
+        .expect("failed to decode circuit");
+
+    assert_eq!(cdf.wirecheck().expect("failed to check wires"), expected);
+
+    // decoding won't check the wires unless configured
+    cdf.fetch_constraint(0).expect("failed to read constraint");
+    assert!(cdf.take_warnings().is_empty());
+
+    let config = *Config::default().with_check_wires(true);
+    let mut cdf = CircuitDescription::from_reader_with_config(cursor, config)
+        .expect("failed to decode circuit");
+
+    cdf.fetch_constraint(0).expect("failed to read constraint");
+    cdf.fetch_constraint(0).expect("failed to read constraint");
+
+    // warnings are reported only once
+    assert_eq!(cdf.take_warnings(), expected);
+    assert!(cdf.take_warnings().is_empty());
+}
+
+#[test]
+fn timeline_reports_witnesses_allocated_later() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    // witness 1 is the output of constraint 1
+    let witnesses = vec![
+        EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+        EncodableWitness::new(1, Some(1), Scalar::default(), source.clone()),
+    ];
+
+    // constraint 0 consumes witness 1 before it is allocated
+    let constraints = vec![
+        EncodableConstraint::new(
+            0,
+            Polynomial::builder().a(0).b(1).build(),
+            source.clone(),
+        ),
+        EncodableConstraint::new(1, Polynomial::builder().o(1).build(), source),
+    ];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let timeline = cdf.timeline().expect("failed to build the timeline");
+
+    let expected = TimelineEntry {
+        constraint: 0,
+        witness: 1,
+        allocated_at: 1,
+    };
+
+    assert_eq!(timeline.entries().len(), 2);
+    assert_eq!(timeline.violations().collect::<Vec<_>>(), vec![&expected]);
+    assert_eq!(
+        timeline.warnings(),
+        &[Warning::WitnessAllocatedLater {
+            constraint: 0,
+            wire: Wire::B,
+            witness: 1,
+            allocated_at: 1,
+        }]
+    );
+}
+
+#[test]
+fn locals_lists_the_witnesses_created_by_a_constraint() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    let witnesses = vec![
+        EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+        EncodableWitness::new(1, Some(1), Scalar::default(), source.clone()),
+        EncodableWitness::new(2, Some(0), Scalar::default(), source.clone()),
+        EncodableWitness::new(3, Some(1), Scalar::default(), source.clone()),
+    ];
+
+    let constraints = vec![
+        EncodableConstraint::new(0, Polynomial::default(), source.clone()),
+        EncodableConstraint::new(1, Polynomial::default(), source),
+    ];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let ids = |cdf: &mut CircuitDescription<_>, idx| {
+        cdf.locals(idx)
+            .expect("failed to read the locals")
+            .iter()
+            .map(|w| w.id())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(ids(&mut cdf, 0), vec![2]);
+    assert_eq!(ids(&mut cdf, 1), vec![1, 3]);
+}
+
+#[test]
+fn frontier_traces_the_wires_to_the_earliest_failure() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    let witnesses = (0..6)
+        .map(|i| {
+            EncodableWitness::new(i, None, Scalar::default(), source.clone())
+        })
+        .collect::<Vec<_>>();
+
+    // 1 fails computing witness 2, that 3 consumes, failing in turn with 4
+    // over it; witness 5 is wired to the passing constraint 2 only
+    let gate = |a, b, o, ok| {
+        Polynomial::builder()
+            .a(a)
+            .b(b)
+            .d(0)
+            .o(o)
+            .evaluation(ok)
+            .build()
+    };
+
+    let constraints = vec![
+        EncodableConstraint::new(0, gate(0, 0, 1, true), source.clone()),
+        EncodableConstraint::new(1, gate(1, 0, 2, false), source.clone()),
+        EncodableConstraint::new(2, gate(0, 0, 5, true), source.clone()),
+        EncodableConstraint::new(3, gate(2, 0, 3, false), source.clone()),
+        EncodableConstraint::new(4, gate(3, 5, 4, false), source.clone()),
+        EncodableConstraint::new(5, gate(0, 0, 0, true), source),
+    ];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let frontier = cdf.frontier(4).expect("failed to trace the frontier");
+    let entry = |wire, witness, constraint| FrontierEntry {
+        wire,
+        witness,
+        constraint,
+    };
+
+    assert_eq!(frontier.constraint(), 4);
+    assert_eq!(
+        frontier.entries(),
+        &[
+            entry(Wire::A, 3, 1),
+            entry(Wire::B, 5, 4),
+            entry(Wire::D, 0, 1),
+            entry(Wire::O, 4, 4),
+        ]
+    );
+    assert_eq!(frontier.earliest(), Some(1));
+
+    let frontier = cdf.frontier(5).expect("failed to trace the frontier");
+
+    assert!(frontier.entries().is_empty());
+    assert_eq!(frontier.earliest(), None);
+}
+
+#[test]
+fn scalarcheck_reports_non_canonical_scalars() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    let modulus = Scalar::from(Scalar::MODULUS);
+    let witnesses = vec![
+        EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+        EncodableWitness::new(1, None, modulus, source.clone()),
+    ];
+
+    let polynomial = Polynomial::builder().qc(modulus).build();
+    let constraints = vec![EncodableConstraint::new(0, polynomial, source)];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let expected = vec![
+        Warning::NonCanonicalWitness {
+            witness: 1,
+            path: "lib.rs".into(),
+            line: 1,
+            col: 0,
+        },
+        Warning::NonCanonicalSelector {
+            constraint: 0,
+            selector: Selector::Qc,
+            path: "lib.rs".into(),
+            line: 1,
+            col: 0,
+        },
+    ];
+
+    assert_eq!(
+        cdf.scalarcheck().expect("failed to check scalars"),
+        expected
+    );
+}
+
+#[test]
+fn decoder_rejects_overflowing_layout() {
+    let mut bytes = vec![];
+
+    bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+    bytes.extend_from_slice(&1u64.to_le_bytes());
+    bytes.push(0);
+
+    let cursor = std::io::Cursor::new(bytes);
+
+    CircuitDescription::from_reader(cursor)
+        .expect_err("the layout of the preamble overflows");
+}
+
+#[test]
+fn encoder_file_matches_cursor() {
+    let dir = tempdir::TempDir::new("dusk-cdf").expect("failed to create dir");
+    let path = dir.path().join("circuit.cdf");
+
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let witnesses: Vec<_> = (0..3)
+        .map(|id| {
+            EncodableWitness::new(id, None, Scalar::default(), source.clone())
+        })
+        .collect();
+    let constraints: Vec<EncodableConstraint> = vec![];
+
+    let mut provider = HashMap::new();
+    provider.insert("lib.rs".to_string(), "fn main() {}");
+
+    let mut file = Encoder::init_file(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+        &path,
+    )
+    .expect("failed to create file");
+
+    assert!(file.is_durable());
+
+    let n = file.write_all(provider.clone()).expect("failed to encode");
+
+    let mut cursor = Encoder::init_cursor(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+    )
+    .flush_policy(FlushPolicy::Never);
+
+    assert!(!cursor.is_durable());
+
+    cursor.write_all(provider).expect("failed to encode");
+
+    let bytes = std::fs::read(&path).expect("failed to read file");
+
+    assert_eq!(bytes.len(), n);
+    assert_eq!(bytes, cursor.into_inner().into_inner());
+
+    let mut cdf = CircuitDescription::open(&path).expect("failed to open");
+
+    assert_eq!(cdf.preamble().witnesses, witnesses.len());
+    cdf.fetch_witness(2).expect("failed to read witness");
+}
+
+#[test]
+fn decoder_rejects_unfinalized_file() {
+    let dir = tempdir::TempDir::new("dusk-cdf").expect("failed to create dir");
+    let path = dir.path().join("circuit.cdf");
+
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let witnesses =
+        vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    let constraints: Vec<EncodableConstraint> = vec![];
+
+    let mut provider = HashMap::new();
+    provider.insert("lib.rs".to_string(), "fn main() {}");
+
+    let mut encoder = Encoder::init_file_atomic(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+        &path,
+    )
+    .expect("failed to create file");
+
+    // nothing is written to the target path until the encoding succeeds
+    assert!(!path.exists());
+
+    encoder.write_all(provider).expect("failed to encode");
+
+    assert!(!dir.path().join("circuit.cdf.tmp").exists());
+
+    let mut bytes = std::fs::read(&path).expect("failed to read file");
+
+    CircuitDescription::from_reader(std::io::Cursor::new(bytes.clone()))
+        .expect("the file is finalized");
+
+    // simulate an interrupted encoding, setting the pending flag stored in
+    // the last byte of the preamble
+    bytes[Preamble::LEN - 1] |= 0x80;
+
+    CircuitDescription::from_reader(std::io::Cursor::new(bytes))
+        .expect_err("the file isn't finalized");
+}
+
+#[test]
+fn file_is_locked_while_written() {
+    let dir = tempdir::TempDir::new("dusk-cdf").expect("failed to create dir");
+    let path = dir.path().join("circuit.cdf");
+
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let witnesses =
+        vec![EncodableWitness::new(0, None, Scalar::default(), source)];
+    let constraints: Vec<EncodableConstraint> = vec![];
+
+    let mut provider = HashMap::new();
+    provider.insert("lib.rs".to_string(), "fn main() {}");
+
+    let mut encoder = Encoder::init_file(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+        &path,
+    )
+    .expect("failed to create file");
+
+    let busy =
+        CircuitDescription::open(&path).expect_err("the file is being written");
+
+    assert_eq!(busy.kind(), std::io::ErrorKind::WouldBlock);
+
+    encoder
+        .write_all(provider.clone())
+        .expect("failed to encode");
+
+    let circuit = CircuitDescription::open(&path).expect("failed to open");
+
+    // a reader can't be torn by an encoding in place, only replaced
+    let busy = Encoder::init_file(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+        &path,
+    )
+    .expect_err("the file is being read");
+
+    assert_eq!(busy.kind(), std::io::ErrorKind::WouldBlock);
+
+    Encoder::init_file_atomic(
+        Config::default(),
+        witnesses.iter(),
+        constraints.iter(),
+        &path,
+    )
+    .expect("failed to create file")
+    .write_all(provider)
+    .expect("failed to encode");
+
+    assert_eq!(circuit.preamble().witnesses, witnesses.len());
+}
+
+#[test]
+fn decoder_errors_locate_the_corrupt_record() {
+    let asset = test_cdf();
+
+    let mut bytes = std::fs::read(&asset).expect("failed to read test asset");
+    let preamble = *CircuitDescription::open(&asset)
+        .expect("failed to read test asset")
+        .preamble();
+
+    let witness = preamble.witness_offset(3).expect("the witness exists");
+    let constraint = preamble
+        .constraint_offset(5)
+        .expect("the constraint exists");
+    let next = preamble
+        .constraint_offset(6)
+        .expect("the constraint exists");
+    let len = preamble.witness_offset(4).expect("the witness exists") - witness;
+
+    bytes[witness..witness + len]
+        .iter_mut()
+        .for_each(|b| *b = 0xff);
+    bytes[constraint..next].iter_mut().for_each(|b| *b = 0xff);
+
+    let cursor = std::io::Cursor::new(bytes);
+    let mut cdf =
+        CircuitDescription::from_reader(cursor).expect("failed to load");
+
+    let e = cdf
+        .fetch_constraint(5)
+        .expect_err("the constraint is corrupt");
+
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert!(e
+        .to_string()
+        .starts_with(&format!("constraint #5 at offset {:#x}: ", constraint)));
+
+    let e = cdf
+        .fetch_witnesses(&[2, 3])
+        .expect_err("the witness is corrupt");
+
+    assert!(e
+        .to_string()
+        .starts_with(&format!("witness #3 at offset {:#x}: ", witness)));
+}
+
+#[test]
+fn decoder_utf8_policy_applies_to_the_sources() {
+    let asset = test_cdf();
+
+    let mut bytes = std::fs::read(&asset).expect("failed to read test asset");
+    let cache = CircuitDescription::open(&asset)
+        .expect("failed to read test asset")
+        .preamble()
+        .source_cache_offset();
+
+    // skip the array and string markers of the first source name
+    bytes[cache + 4] = 0xff;
+
+    let cursor = std::io::Cursor::new(bytes.clone());
+    let e = CircuitDescription::from_reader(cursor)
+        .expect_err("the source name isn't valid utf-8");
+
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert!(e.to_string().contains("invalid utf-8 in source name #0"));
+
+    let config = *Config::default().with_utf8(Utf8Policy::Lenient);
+    let cursor = std::io::Cursor::new(bytes);
+    let mut cdf = CircuitDescription::from_reader_with_config(cursor, config)
+        .expect("the lenient policy replaces the invalid bytes");
+
+    let name = cdf.source_names().next().expect("the source exists");
+
+    assert!(name.contains(char::REPLACEMENT_CHARACTER));
+    assert_eq!(
+        cdf.take_warnings(),
+        vec![Warning::LossySource { source: 0 }]
+    );
+}
+
+#[test]
+fn decoder_enforces_the_cache_limits() {
+    let asset = test_cdf();
+
+    let load = |limits: CacheLimits| {
+        let config = *Config::default().with_cache_limits(limits);
+
+        CircuitDescription::open_with_config(&asset, config)
+    };
+
+    let cdf = load(CacheLimits::DEFAULT).expect("failed to load");
+    let sources = cdf.source_names().count();
+    let largest = cdf
+        .source_sizes()
+        .map(|(_, size)| size)
+        .max()
+        .expect("the circuit has sources");
+
+    load(CacheLimits {
+        max_sources: sources,
+        max_source_size: largest,
+        ..CacheLimits::DEFAULT
+    })
+    .expect("the cache is within the limits");
+
+    let limits = [
+        CacheLimits {
+            max_sources: sources - 1,
+            ..CacheLimits::DEFAULT
+        },
+        CacheLimits {
+            max_source_size: largest - 1,
+            ..CacheLimits::DEFAULT
+        },
+        CacheLimits {
+            max_cache_size: largest,
+            ..CacheLimits::DEFAULT
+        },
+    ];
+
+    for limits in limits {
+        let e = load(limits).expect_err("the cache exceeds the limits");
+
+        assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            e.to_string().contains("above the limit")
+                || e.to_string().contains("exceeds the limit")
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "index")]
+fn decoder_loads_the_index_matching_the_file() {
+    let asset = test_cdf();
+
+    let dir = tempdir::TempDir::new("dusk-cdf").expect("failed to create dir");
+    let path = dir.path().join("circuit.cdf");
+
+    std::fs::copy(&asset, &path).expect("failed to copy test asset");
+
+    let mut cdf = CircuitDescription::open(&path).expect("failed to load");
+    assert!(cdf.index().is_none());
+
+    let index = cdf.build_index().expect("failed to build index").clone();
+    let file = std::fs::File::create(CircuitIndex::sidecar(&path))
+        .expect("failed to create index file");
+
+    index.write(file).expect("failed to write index");
+
+    let mut cdf = CircuitDescription::open(&path).expect("failed to load");
+    assert_eq!(cdf.index(), Some(&index));
+
+    let counts = cdf.source_counts().expect("failed to count").to_vec();
+    assert_eq!(counts, index.source_counts());
+
+    let constraint = cdf.fetch_constraint(8).expect("failed to fetch");
+    let a = constraint.polynomial().witnesses.a;
+    assert!(index.wired(a).contains(&8));
+
+    // a changed source doesn't match the digest of the index
+    let mut bytes = std::fs::read(&path).expect("failed to read file");
+    let last = bytes.last_mut().expect("the file isn't empty");
+
+    *last = if *last == b' ' { b'\n' } else { b' ' };
+    std::fs::write(&path, bytes).expect("failed to write file");
+
+    let cdf = CircuitDescription::open(&path).expect("failed to load");
+    assert!(cdf.index().is_none());
+}
+
+#[test]
+fn usage_ranks_the_sources_by_failures() {
+    let a = EncodableSource::new(1, 0, "a.rs".into());
+    let b = EncodableSource::new(1, 0, "b.rs".into());
+    let c = EncodableSource::new(1, 0, "c.rs".into());
+    let provider: HashMap<String, String> = ["a.rs", "b.rs", "c.rs"]
+        .into_iter()
+        .map(|name| (name.to_string(), "fn main() {}\n".to_string()))
+        .collect();
+
+    let witnesses =
+        vec![EncodableWitness::new(0, None, Scalar::default(), a.clone())];
+
+    let ok = Polynomial::builder().evaluation(true).build();
+    let failed = Polynomial::builder().evaluation(false).build();
+    let constraints = vec![
+        EncodableConstraint::new(0, failed, a.clone()),
+        EncodableConstraint::new(1, ok, a),
+        EncodableConstraint::new(2, failed, b.clone()),
+        EncodableConstraint::new(3, failed, b),
+        EncodableConstraint::new(4, ok, c),
+    ];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf =
+        CircuitDescription::from_reader(cursor).expect("failed to decode");
+    let usage = cdf.usage().expect("failed to compute usage");
+
+    let heatmap: Vec<_> = usage
+        .heatmap
+        .iter()
+        .map(|s| (s.failures, s.failure_rate()))
+        .collect();
+
+    // the source without failures is left out of the heatmap
+    assert_eq!(heatmap, vec![(2, 1.0), (1, 0.5)]);
+    assert!(usage.heatmap[0].name.ends_with("b.rs"));
+    assert!(usage.heatmap[1].name.ends_with("a.rs"));
+}
+
+#[test]
+#[cfg(feature = "debugger")]
+fn decoder_evicts_the_caches_over_the_memory_budget() {
+    let asset = test_cdf();
+
+    let mut cdf = CircuitDescription::open(&asset).expect("failed to load");
+    let counts = cdf.source_counts().expect("failed to count").to_vec();
+    let usage = cdf.memory_usage();
+
+    assert!(usage.source_counts > 0);
+    assert_eq!(usage.total(), usage.sources + usage.source_counts);
+
+    // the budget holds the sources, but not the counts along with them
+    let config = *Config::default().with_cache_limits(CacheLimits {
+        max_memory: usage.sources,
+        ..CacheLimits::DEFAULT
+    });
+
+    let mut debugger =
+        ZkDebugger::open_with_config(&asset, config).expect("failed to load");
+
+    assert_eq!(debugger.source_counts().expect("failed to count"), counts);
+    assert!(debugger.memory_usage().source_counts > 0);
+
+    // loading the line table evicts the counts, which are rebuilt on use
+    debugger
+        .goto_line("gadget.rs", 8)
+        .expect("failed to goto line");
+
+    let usage = debugger.memory_usage();
+
+    assert!(usage.lines > 0);
+    assert_eq!(usage.source_counts, 0);
+    assert_eq!(debugger.source_counts().expect("failed to count"), counts);
+}
+
+#[test]
+fn report_lists_the_failures_and_unconstrained_witnesses() {
+    let source = EncodableSource::new(1, 0, "gadget.rs".into());
+    let provider: HashMap<String, String> =
+        [("gadget.rs".to_string(), "assert!(a < b);\n".to_string())]
+            .into_iter()
+            .collect();
+
+    let witnesses = vec![
+        EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+        EncodableWitness::new(1, None, Scalar::default(), source.clone()),
+    ];
+
+    let failed = Polynomial::builder().evaluation(false).build();
+    let constraints = vec![EncodableConstraint::new(0, failed, source)];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf =
+        CircuitDescription::from_reader(cursor).expect("failed to decode");
+
+    let mut markdown = vec![];
+    cdf.report("circuit", ReportFormat::Markdown, 10, &mut markdown)
+        .expect("failed to write the markdown report");
+    let markdown = String::from_utf8(markdown).expect("invalid utf-8");
+
+    assert!(markdown.contains("### Constraint #0"));
+    assert!(markdown.contains(">     1 | assert!(a < b);"));
+    assert!(markdown.contains("| #1 |"));
+
+    let mut html = vec![];
+    cdf.report("circuit", ReportFormat::Html, 10, &mut html)
+        .expect("failed to write the html report");
+    let html = String::from_utf8(html).expect("invalid utf-8");
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("assert!(a &lt; b);"));
+    assert!(html.contains("<td>#1</td>"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn constcheck_reports_unfixed_constants() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    let mut one = [0u8; Scalar::LEN];
+    let mut seven = [0u8; Scalar::LEN];
+    one[0] = 1;
+    seven[0] = 7;
+    let (one, seven) = (Scalar::from(one), Scalar::from(seven));
+
+    let witnesses = vec![
+        EncodableWitness::new(0, None, Scalar::default(), source.clone()),
+        EncodableWitness::new(1, None, one, source.clone()),
+        EncodableWitness::new(2, None, seven, source.clone()),
+        EncodableWitness::new(3, None, one, source.clone()),
+    ];
+
+    // ql·a = 0 fixes the zero witness
+    let polynomial = Polynomial::builder().ql(one).a(0).build();
+    let constraints = vec![EncodableConstraint::new(0, polynomial, source)];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let expected = vec![
+        Warning::UnfixedConstant {
+            witness: 1,
+            constant: Constant::One,
+        },
+        Warning::UnfixedConstant {
+            witness: 3,
+            constant: Constant::One,
+        },
+    ];
+
+    assert_eq!(
+        cdf.constcheck().expect("failed to check constants"),
+        expected
+    );
+
+    let entropy = cdf.witness_entropy().expect("failed to compute entropy");
+
+    assert_eq!(entropy.witnesses, 4);
+    assert_eq!(entropy.distinct, 3);
+    assert_eq!(
+        entropy.repeated,
+        vec![RepeatedValue {
+            value: one,
+            witnesses: 2,
+            constant: Some(Constant::One),
+        }]
+    );
+    assert!(entropy.bits < entropy.max_bits());
+}
+
+#[test]
+fn shared_wires_span_several_sources() {
+    let lib = EncodableSource::new(1, 0, "lib.rs".into());
+    let gadget = EncodableSource::new(1, 0, "gadget.rs".into());
+    let provider: HashMap<String, String> = [
+        ("lib.rs".to_string(), "fn main() {}\n".to_string()),
+        ("gadget.rs".to_string(), "fn gadget() {}\n".to_string()),
+    ]
+    .into();
+
+    let witnesses: Vec<_> = (0..4)
+        .map(|i| EncodableWitness::new(i, None, Scalar::default(), lib.clone()))
+        .collect();
+
+    // witness 2 couples both sources
+    let constraints = vec![
+        EncodableConstraint::new(
+            0,
+            Polynomial::builder().a(1).b(2).build(),
+            lib.clone(),
+        ),
+        EncodableConstraint::new(
+            1,
+            Polynomial::builder().a(2).b(3).build(),
+            gadget.clone(),
+        ),
+        EncodableConstraint::new(
+            2,
+            Polynomial::builder().a(3).b(3).build(),
+            gadget,
+        ),
+    ];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    // the unwired `d` and `o` wires point to the witness 0
+    let expected = vec![
+        SharedWire {
+            witness: 0,
+            constraints: 3,
+            sources: vec!["gadget.rs".into(), "lib.rs".into()],
+        },
+        SharedWire {
+            witness: 2,
+            constraints: 2,
+            sources: vec!["gadget.rs".into(), "lib.rs".into()],
+        },
+    ];
+
+    assert_eq!(
+        cdf.shared_wires().expect("failed to list shared wires"),
+        expected
+    );
+}
+
+#[test]
+fn legacy_files_are_converted_on_open() {
+    fn word(bytes: &mut Vec<u8>, word: u64) {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+
+    fn source(bytes: &mut Vec<u8>, line: u64, path: &str) {
+        word(bytes, line);
+        word(bytes, 1);
+
+        let mut padded = [0u8; 1024];
+        padded[..path.len()].copy_from_slice(path.as_bytes());
+        bytes.extend_from_slice(&padded);
+    }
+
+    let dir = tempdir::TempDir::new("dusk-cdf").expect("failed to create dir");
+    let main = dir.path().join("main.rs");
+    let main = main.display().to_string();
+
+    std::fs::write(&main, "fn main() {\n    gate();\n}\n")
+        .expect("failed to write the source");
+
+    // preamble, as in `assets/cdf.bnf`
+    let mut legacy = vec![];
+    word(&mut legacy, 2);
+    word(&mut legacy, 1);
+
+    for (id, value) in [(0, 3u8), (1, 5u8)] {
+        word(&mut legacy, id);
+        legacy.extend_from_slice(&[value; 32]);
+        source(&mut legacy, 1, &main);
+    }
+
+    word(&mut legacy, 0);
+
+    // qm, ql, qr, qd, qc, qo and pi
+    for selector in 0..7u8 {
+        legacy.extend_from_slice(&[selector; 32]);
+    }
+
+    // a, b, d and o
+    for index in [1, 0, 0, 1] {
+        word(&mut legacy, index);
+        legacy.push(0);
+        word(&mut legacy, 0);
+        legacy.extend_from_slice(&[0; 32]);
+    }
+
+    legacy.push(1);
+    source(&mut legacy, 2, &main);
+
+    let path = dir.path().join("legacy.cdf");
+
+    std::fs::write(&path, &legacy).expect("failed to write the legacy file");
+
+    let mut circuit =
+        LegacyCircuit::open(&path).expect("failed to read the legacy file");
+
+    assert_eq!(circuit.witnesses(), 2);
+    assert_eq!(circuit.constraints(), 1);
+    assert_eq!(
+        circuit.fetch_witness(1).expect("failed to fetch").value(),
+        &Scalar::from([5; 32])
+    );
+
+    let mut cdf =
+        CircuitDescription::open(&path).expect("failed to open legacy file");
+
+    assert_eq!(cdf.preamble().witnesses, 2);
+    assert_eq!(cdf.preamble().constraints, 1);
+
+    let constraint = cdf.fetch_constraint(0).expect("failed to fetch");
+    let polynomial = constraint.polynomial();
+
+    assert_eq!(polynomial.witnesses.a, 1);
+    assert_eq!(polynomial.witnesses.o, 1);
+    assert_eq!(polynomial.selectors.qc, Scalar::from([4; 32]));
+    assert!(polynomial.evaluation);
+    assert_eq!(constraint.line(), 2);
+    assert!(constraint.contents().contains("gate();"));
+
+    let converted = dir.path().join("converted.cdf");
+
+    circuit
+        .convert_file(Config::default(), &converted)
+        .expect("failed to convert");
+
+    let cdf =
+        CircuitDescription::open(converted).expect("failed to open converted");
+
+    assert_eq!(cdf.preamble().constraints, 1);
+}
+
+#[test]
+fn gate_matches_name_the_gadgets() {
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    let one = Scalar::from(Constant::One.bytes());
+    let minus_one = Scalar::from(Constant::MinusOne.bytes());
+
+    let boolean = Polynomial::builder().qm(one).qo(minus_one).qarith(one);
+    let xor = Polynomial::builder().qlogic(minus_one);
+
+    let witnesses = vec![EncodableWitness::new(
+        0,
+        None,
+        Scalar::default(),
+        source.clone(),
+    )];
+
+    // a boolean constraint, an XOR spanning 3 gates and a padding gate
+    let constraints = vec![
+        EncodableConstraint::new(0, boolean.build(), source.clone()),
+        EncodableConstraint::new(1, xor.build(), source.clone()),
+        EncodableConstraint::new(2, xor.build(), source.clone()),
+        EncodableConstraint::new(3, xor.build(), source.clone()),
+        EncodableConstraint::new(4, Polynomial::builder().build(), source),
+    ];
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let labels: Vec<_> = cdf
+        .gate_matches()
+        .expect("failed to recognize the patterns")
+        .iter()
+        .map(|m| (m.start, m.len, m.to_string()))
+        .collect();
+
+    assert_eq!(
+        labels,
+        vec![(0, 1, "boolean 1".to_string()), (1, 3, "XOR 1".to_string())]
+    );
+
+    let xor = cdf.gate_match(2).expect("failed to fetch the match");
+
+    assert_eq!(xor.map(|m| m.start), Some(1));
+    assert_eq!(cdf.gate_match(4).expect("failed to fetch the match"), None);
+    assert!(cdf.memory_usage().patterns > 0);
+
+    let usage = cdf.usage().expect("failed to compute usage");
+
+    assert_eq!(usage.patterns[0].name, "XOR");
+    assert_eq!(usage.patterns[0].constraints, 3);
+    assert_eq!(usage.patterns[1].name, "boolean");
+
+    cdf.set_pattern_library(PatternLibrary::empty());
+
+    assert!(cdf.gate_matches().expect("failed to recognize").is_empty());
+}
+
+#[test]
+fn declared_patterns_precede_the_builtin_ones() {
+    use toml_base_config::BaseConfig;
+
+    let source = EncodableSource::new(1, 0, "lib.rs".into());
+    let provider: HashMap<String, String> =
+        [("lib.rs".to_string(), "fn main() {}\n".to_string())].into();
+
+    let minus_one = Scalar::from(Constant::MinusOne.bytes());
+    let xor = Polynomial::builder().qlogic(minus_one).build();
+
+    let witnesses = vec![EncodableWitness::new(
+        0,
+        None,
+        Scalar::default(),
+        source.clone(),
+    )];
+
+    let constraints = (0..3)
+        .map(|id| EncodableConstraint::new(id, xor, source.clone()))
+        .collect::<Vec<_>>();
+
+    let mut encoder = Encoder::init_cursor(
+        Config::default(),
+        witnesses.into_iter(),
+        constraints.into_iter(),
+    );
+
+    encoder
+        .write_all(provider)
+        .expect("failed to encode circuit");
+
+    let mut cursor = encoder.into_inner();
+    cursor.set_position(0);
+
+    let mut cdf = CircuitDescription::from_reader(cursor)
+        .expect("failed to decode circuit");
+
+    let dir = tempdir::TempDir::new("dusk-cdf").expect("failed to create dir");
+    let path = dir.path().join("patterns.toml");
+
+    std::fs::write(
+        &path,
+        r#"
+[[patterns]]
+name = "nibble xor"
+repeat = 2
+
+[[patterns.steps]]
+kind = "logic"
+qlogic = "minus_one"
+"#,
+    )
+    .expect("failed to write the patterns");
+
+    let config =
+        PatternConfig::load_path(&path).expect("failed to load the patterns");
+    let library = config.library().expect("invalid patterns");
+
+    assert!(config.builtin);
+    assert_eq!(library.patterns()[0].window(), 2);
+
+    cdf.set_pattern_library(library);
+
+    let labels: Vec<_> = cdf
+        .gate_matches()
+        .expect("failed to recognize the patterns")
+        .iter()
+        .map(|m| (m.start, m.len, m.to_string()))
+        .collect();
+
+    // the remaining gate falls back to the builtin XOR
+    assert_eq!(
+        labels,
+        vec![
+            (0, 2, "nibble xor 1".to_string()),
+            (2, 1, "XOR 1".to_string())
+        ]
+    );
+
+    let empty = PatternConfig {
+        builtin: false,
+        patterns: vec![GatePattern::new("empty", vec![])],
+    };
+
+    assert!(empty.library().is_err());
+}
@@ -0,0 +1,51 @@
+//! Regenerate the CDF of the naive circuit and debug it end-to-end.
+
+use std::process::Command;
+
+use dusk_cdf::{State, ZkDebugger};
+use tempdir::TempDir;
+
+/// Public inputs (key, signature) and private inputs of the circuit
+const MIN_WITNESSES: usize = 2 + 7;
+
+#[test]
+fn naive_circuit_cdf_is_debuggable() {
+    let dir = TempDir::new("naive-circuit").expect("failed to create temp dir");
+    let cdf = dir.path().join("naive.cdf");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_naive-circuit"))
+        .env("CDF_OUTPUT", &cdf)
+        .output()
+        .expect("failed to execute the circuit");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("proof evaluation: true"), "{}", stdout);
+
+    let mut debugger =
+        ZkDebugger::open(&cdf).expect("failed to open the generated CDF");
+
+    let preamble = *debugger.preamble();
+
+    assert!(preamble.witnesses >= MIN_WITNESSES);
+    assert_ne!(preamble.constraints, 0);
+
+    // a valid proof implies all the constraints evaluate
+    let state = debugger.cont().expect("failed to continue");
+
+    assert!(state.is_end());
+    assert_eq!(state.constraint(), Some(preamble.constraints - 1));
+
+    let state = debugger.turn().expect("failed to reverse");
+
+    assert_eq!(state, State::Beginning);
+
+    let warnings = debugger.wirecheck().expect("failed to check the wires");
+
+    assert!(warnings.is_empty(), "{:?}", warnings);
+
+    for idx in 0..preamble.witnesses {
+        debugger.fetch_witness(idx).expect("failed to read witness");
+    }
+}
@@ -1,36 +1,82 @@
+mod adapter;
+mod compare;
 mod config;
 mod context;
+mod export;
 mod input;
+mod journal;
 mod output;
+mod render;
 
-use std::{io, net};
+use std::fs::File;
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::{io, net, panic};
 
 use crate::args::ParsedArgs;
 use crate::commands::Command;
+use crate::plugin::DebuggerPlugin;
+use crate::recovery::{Recovered, Recovery};
+use crate::script;
 use dap_reactor::prelude::{
-    Event, Source as DapSource, StackTraceArguments, StackTraceResponse,
-    ThreadReason, VariablesResponse,
+    Event, OutputCategory, Source as DapSource, StackTraceArguments,
+    StackTraceResponse, ThreadReason, VariablesResponse,
 };
 use dap_reactor::prelude::{SourceReference, StackFrame};
 use dap_reactor::protocol::ProtocolResponseError;
-use dap_reactor::reactor::{Client, ClientBuilder, ClientResponse};
+use dap_reactor::reactor::{
+    Client, ClientBuilder, ClientRequest, ClientResponse,
+};
 use dap_reactor::request::Request;
 use dap_reactor::response::Response;
-use dusk_cdf::{ZkRequest, ZkResponse};
+use dusk_cdf::{
+    CancelToken, ZkDapBuilder, ZkDapClient, ZkDapLocal, ZkRequest, ZkResponse,
+    ZkWitness,
+};
+use tokio::runtime::Handle;
 use tokio::sync::mpsc;
-use tokio::time;
+use tokio::{signal, task, time};
 use toml_base_config::BaseConfig;
 
+use adapter::Adapter;
+use compare::Compare;
 use config::Config;
 use context::Context;
+use export::Export;
 use input::Input;
 
 pub use output::{Output, Source};
+pub use render::{plain_source, Capabilities, RenderMode, Renderer};
+
+/// Requests, responses and events channels of a DAP backend
+type Channels = (
+    mpsc::Sender<ClientRequest>,
+    mpsc::Receiver<Response>,
+    mpsc::Receiver<Event>,
+);
+
+/// Delivery of the requests of a [`BackendClient`] to the DAP backend
+type Transport = Box<dyn Fn(ZkRequest) -> io::Result<ZkResponse> + Send>;
+
+/// Client of the DAP backend of the session, shared by its position and
+/// breakpoints
+type BackendClient = ZkDapClient<Transport>;
 
 pub struct App {
     context: Context,
     input: Input,
     outputs: mpsc::Receiver<Output>,
+    compare: Option<Compare>,
+    interrupt: CancelToken,
+    /// Recovery of the loaded file, shared with the autosave task and the
+    /// panic hook so they follow the file opened by the session
+    recovery: Arc<Mutex<Recovery>>,
+    /// Whether the sessions are saved for recovery
+    autosave: bool,
+    /// Entries of the journal recorded before the file of the recovery was
+    /// opened, left out of its session
+    recovery_start: usize,
+    _adapter: Option<Adapter>,
 }
 
 impl App {
@@ -42,6 +88,9 @@ impl App {
         context: Context,
         mut events: mpsc::Receiver<Event>,
     ) {
+        let renderer = context.config().renderer();
+        let mut progress = String::new();
+
         while let Some(event) = events.recv().await {
             let mut result = None;
 
@@ -62,12 +111,30 @@ impl App {
                     reason: ThreadReason::Started,
                     ..
                 } => {
+                    // only the sources that aren't cached under their path
+                    // will be transferred
+                    let known = context.known_sources().await;
+
                     result.replace(
-                        context.send_request(ZkRequest::SourceContents).await,
+                        context
+                            .send_request(ZkRequest::Sources {
+                                filter: None,
+                                offset: 0,
+                                limit: None,
+                                known,
+                            })
+                            .await,
                     );
                 }
 
-                Event::Stopped { thread_id, .. } => {
+                Event::Stopped {
+                    thread_id,
+                    description,
+                    text,
+                    ..
+                } => {
+                    context.record_state(description, text).await;
+
                     result.replace(
                         context
                             .send_request(Request::StackTrace {
@@ -82,6 +149,58 @@ impl App {
                     );
                 }
 
+                Event::Output {
+                    category, output, ..
+                } => {
+                    let output = match category {
+                        Some(OutputCategory::Stderr) => Output {
+                            contents: None,
+                            console: vec![],
+                            error: vec![output],
+                        },
+                        _ => Output {
+                            contents: None,
+                            console: vec![output],
+                            error: vec![],
+                        },
+                    };
+
+                    result.replace(context.send_output(output).await);
+                }
+
+                Event::ProgressStart {
+                    title,
+                    message,
+                    percentage,
+                    ..
+                } => {
+                    progress = title;
+
+                    result.replace(renderer.progress(
+                        &mut io::stdout(),
+                        &progress,
+                        percentage.unwrap_or_default(),
+                        &message.unwrap_or_default(),
+                    ));
+                }
+
+                Event::ProgressUpdate {
+                    message,
+                    percentage,
+                    ..
+                } => {
+                    result.replace(renderer.progress(
+                        &mut io::stdout(),
+                        &progress,
+                        percentage.unwrap_or_default(),
+                        &message.unwrap_or_default(),
+                    ));
+                }
+
+                Event::ProgressEnd { .. } => {
+                    result.replace(renderer.end_progress(&mut io::stdout()));
+                }
+
                 Event::Thread {
                     reason: ThreadReason::Exited,
                     ..
@@ -106,14 +225,68 @@ impl App {
         }
     }
 
+    /// Connect to a DAP backend listening on a socket
+    async fn connect(socket: net::SocketAddr) -> io::Result<Channels> {
+        let Client {
+            responses: mut client_responses,
+            events,
+            requests,
+            ..
+        } = ClientBuilder::new().connect(socket).await?;
+
+        let (responses_tx, responses) = mpsc::channel(50);
+
+        tokio::spawn(async move {
+            while let Some(ClientResponse { response, .. }) =
+                client_responses.recv().await
+            {
+                if responses_tx.send(response).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok((requests, responses, events))
+    }
+
+    /// Run a DAP backend in-process, without binding a socket
+    fn connect_local() -> Channels {
+        let ZkDapLocal {
+            requests: local_requests,
+            responses,
+            events,
+        } = ZkDapBuilder::new(()).build_local();
+
+        let (requests, mut requests_rx) = mpsc::channel(50);
+
+        tokio::spawn(async move {
+            while let Some(ClientRequest { request, .. }) =
+                requests_rx.recv().await
+            {
+                if local_requests.send(request).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        (requests, responses, events)
+    }
+
     async fn handle_responses(
         context: Context,
-        mut responses: mpsc::Receiver<ClientResponse>,
+        mut responses: mpsc::Receiver<Response>,
     ) {
-        while let Some(ClientResponse { response, .. }) = responses.recv().await
-        {
+        while let Some(response) = responses.recv().await {
+            // a response awaited by its caller isn't displayed
+            if let Some(reply) = context.take_reply().await {
+                reply.send(response).ok();
+                continue;
+            }
+
             let mut result: Option<io::Result<()>> = None;
             let mut custom: Option<ZkResponse> = None;
+            let config = context.config();
+            let style = config.style();
 
             match response {
                 Response::Custom { body } => {
@@ -171,9 +344,22 @@ impl App {
                     let mut output = Output::default();
 
                     for v in variables {
+                        let value = match (v.name.as_str(), v.r#type.as_deref())
+                        {
+                            ("Evaluation", _) if v.value == "false" => {
+                                style.failure(&v.value).to_string()
+                            }
+                            (_, Some("scalar")) => {
+                                let value = config.display_scalar(&v.value);
+
+                                style.scalar(value).to_string()
+                            }
+                            _ => v.value.clone(),
+                        };
+
                         output.merge(Output {
                             contents: None,
-                            console: vec![format!("{}: {}", v.name, v.value)],
+                            console: vec![format!("{}: {}", v.name, value)],
                             error: vec![],
                         });
                     }
@@ -190,15 +376,24 @@ impl App {
                     context.unlock_contents().await;
                 }
 
+                Some(ZkResponse::Sources {
+                    sources, unchanged, ..
+                }) => {
+                    context.replace_contents(sources, &unchanged).await;
+                    context.unlock_contents().await;
+                }
+
                 Some(ZkResponse::AddBreakpoint { id }) => {
                     result.replace(
                         context
                             .send_output(Output {
                                 contents: None,
-                                console: vec![format!(
-                                    "breakpoint added: #{}",
-                                    id
-                                )],
+                                console: vec![style
+                                    .breakpoint(format!(
+                                        "breakpoint added: #{}",
+                                        id
+                                    ))
+                                    .to_string()],
                                 error: vec![],
                             })
                             .await,
@@ -212,10 +407,12 @@ impl App {
                                 contents: None,
                                 console: removed
                                     .then(|| {
-                                        vec![format!(
-                                            "breakpoint #{} removed",
-                                            id
-                                        )]
+                                        vec![style
+                                            .breakpoint(format!(
+                                                "breakpoint #{} removed",
+                                                id
+                                            ))
+                                            .to_string()]
                                     })
                                     .unwrap_or_default(),
                                 error: (!removed)
@@ -231,7 +428,508 @@ impl App {
                     );
                 }
 
+                Some(ZkResponse::AddBreakpoints { ids }) => {
+                    let ids: Vec<_> =
+                        ids.iter().map(|id| format!("#{}", id)).collect();
+                    let output = Output::console(
+                        style
+                            .breakpoint(format!(
+                                "breakpoints added: {}",
+                                ids.join(", ")
+                            ))
+                            .to_string(),
+                    );
+
+                    result.replace(context.send_output(output).await);
+                }
+
+                Some(ZkResponse::RemoveBreakpoints { ids }) => {
+                    let ids: Vec<_> =
+                        ids.iter().map(|id| format!("#{}", id)).collect();
+                    let output = if ids.is_empty() {
+                        Output::console("no breakpoint removed")
+                    } else {
+                        Output::console(
+                            style
+                                .breakpoint(format!(
+                                    "breakpoints removed: {}",
+                                    ids.join(", ")
+                                ))
+                                .to_string(),
+                        )
+                    };
+
+                    result.replace(context.send_output(output).await);
+                }
+
+                Some(ZkResponse::IgnoreBreakpoint { id, count, ignored }) => {
+                    let output = if ignored {
+                        Output::console(
+                            style
+                                .breakpoint(format!(
+                                    "breakpoint #{} ignores its next {} hits",
+                                    id, count
+                                ))
+                                .to_string(),
+                        )
+                    } else {
+                        Output::error(format!("breakpoint #{} not found", id))
+                    };
+
+                    result.replace(context.send_output(output).await);
+                }
+
+                Some(ZkResponse::Focus { focus }) => {
+                    let console = match focus {
+                        Some(f) => format!("navigation limited to {}", f),
+                        None => "navigation limit lifted".into(),
+                    };
+
+                    result.replace(
+                        context.send_output(Output::console(console)).await,
+                    );
+                }
+
+                Some(ZkResponse::WireCheck { warnings }) => {
+                    let console = if warnings.is_empty() {
+                        vec!["no inconsistent wires found".into()]
+                    } else {
+                        warnings
+                            .iter()
+                            .map(|w| style.failure(w).to_string())
+                            .collect()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Timeline { warnings, patterns }) => {
+                    let console = if warnings.is_empty() {
+                        vec!["no witness allocated after its constraint found"
+                            .into()]
+                    } else {
+                        warnings
+                            .iter()
+                            .map(|w| {
+                                let warning = style.failure(w).to_string();
+                                let pattern = w.constraint().and_then(|c| {
+                                    patterns.iter().find(|m| m.contains(c))
+                                });
+
+                                match pattern {
+                                    Some(m) => format!("{} ({})", warning, m),
+                                    None => warning,
+                                }
+                            })
+                            .collect()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::ScalarCheck { warnings }) => {
+                    let console = if warnings.is_empty() {
+                        vec!["no non-canonical scalar found".into()]
+                    } else {
+                        warnings
+                            .iter()
+                            .map(|w| style.failure(w).to_string())
+                            .collect()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::ConstCheck { warnings }) => {
+                    let console = if warnings.is_empty() {
+                        vec!["no unfixed constant found".into()]
+                    } else {
+                        warnings
+                            .iter()
+                            .map(|w| style.failure(w).to_string())
+                            .collect()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::SharedWires { wires }) => {
+                    let console = if wires.is_empty() {
+                        vec!["no witness is shared by several sources".into()]
+                    } else {
+                        let mut table =
+                            style.table(["witness", "constraints", "sources"]);
+
+                        for w in &wires {
+                            table.row([
+                                style.number(w.witness).to_string(),
+                                style.number(w.constraints).to_string(),
+                                w.sources.join(", "),
+                            ]);
+                        }
+
+                        table.lines()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Top { usage }) => {
+                    let mut sources = style.table([
+                        "bytes",
+                        "constraints",
+                        "witnesses",
+                        "constraints/line",
+                        "source",
+                    ]);
+
+                    for s in &usage.sources {
+                        sources.row([
+                            style.number(s.bytes).to_string(),
+                            style.number(s.constraints).to_string(),
+                            style.number(s.witnesses).to_string(),
+                            format!("{:.2}", s.density()),
+                            s.name.clone(),
+                        ]);
+                    }
+
+                    let mut gadgets = style.table(["constraints", "gadget"]);
+
+                    for g in &usage.gadgets {
+                        gadgets.row([
+                            style.number(g.constraints).to_string(),
+                            format!("{}:{}", g.name, g.line),
+                        ]);
+                    }
+
+                    let mut console = vec!["sources by size:".to_string()];
+
+                    console.extend(sources.lines());
+                    console.push("gadgets by constraints:".to_string());
+                    console.extend(gadgets.lines());
+
+                    if !usage.patterns.is_empty() {
+                        let mut patterns = style.table([
+                            "constraints",
+                            "occurrences",
+                            "pattern",
+                        ]);
+
+                        for p in &usage.patterns {
+                            patterns.row([
+                                style.number(p.constraints).to_string(),
+                                style.number(p.occurrences).to_string(),
+                                p.name.clone(),
+                            ]);
+                        }
+
+                        console.push("gadgets by pattern:".to_string());
+                        console.extend(patterns.lines());
+                    }
+
+                    if !usage.heatmap.is_empty() {
+                        let mut heatmap = style.table([
+                            "failures",
+                            "rate",
+                            "constraints",
+                            "source",
+                        ]);
+
+                        for s in &usage.heatmap {
+                            heatmap.row([
+                                style.number(s.failures).to_string(),
+                                format!("{:.2}%", 100.0 * s.failure_rate()),
+                                style.number(s.constraints).to_string(),
+                                s.name.clone(),
+                            ]);
+                        }
+
+                        console.push("sources by failures:".to_string());
+                        console.extend(heatmap.lines());
+                    }
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Locals {
+                    constraint,
+                    witnesses,
+                }) => {
+                    let console = if witnesses.is_empty() {
+                        vec![format!(
+                            "no witness created by constraint #{}",
+                            constraint
+                        )]
+                    } else {
+                        witnesses
+                            .into_iter()
+                            .map(|w| ZkWitness {
+                                value: config.display_scalar(&w.value),
+                                ..w
+                            })
+                            .map(|w| format!("{:?}", w))
+                            .collect()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Memory { usage }) => {
+                    let caches = [
+                        ("sources", usage.sources),
+                        ("allocations", usage.allocations),
+                        ("source counts", usage.source_counts),
+                        ("patterns", usage.patterns),
+                        ("lines", usage.lines),
+                        ("index", usage.index),
+                        ("total", usage.total()),
+                    ];
+
+                    let mut table = style.table(["bytes", "cache"]);
+
+                    for (name, bytes) in caches {
+                        table.row([
+                            style.number(bytes).to_string(),
+                            name.into(),
+                        ]);
+                    }
+
+                    let budget = match usage.budget {
+                        usize::MAX => "-".to_string(),
+                        budget => style.number(budget).to_string(),
+                    };
+
+                    table.row([budget, "budget".to_string()]);
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console: table.lines(),
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Snippet {
+                    constraint,
+                    markdown,
+                }) => {
+                    let output = match context.take_export().await {
+                        Some(export) => match export.write(&markdown) {
+                            Ok(destination) => Output::console(format!(
+                                "constraint #{} exported to {}",
+                                constraint, destination
+                            )),
+                            Err(e) => Output::error(format!(
+                                "failed to export constraint #{}: {}",
+                                constraint, e
+                            )),
+                        },
+                        None => Output::console(markdown),
+                    };
+
+                    result.replace(context.send_output(output).await);
+                }
+
+                Some(ZkResponse::Equation { equation, .. }) => {
+                    result.replace(
+                        context.send_output(Output::console(equation)).await,
+                    );
+                }
+
+                Some(ZkResponse::Frontier { frontier }) => {
+                    let traced = frontier.constraint();
+                    let console = if frontier.entries().is_empty() {
+                        vec![format!("constraint #{} didn't fail", traced)]
+                    } else {
+                        frontier
+                            .entries()
+                            .iter()
+                            .map(|e| {
+                                let wire = format!(
+                                    "{} (witness #{})",
+                                    e.wire, e.witness
+                                );
+
+                                match e.is_upstream(traced) {
+                                    true => format!(
+                                        "{}: first failed at constraint #{}",
+                                        wire,
+                                        style.failure(e.constraint)
+                                    ),
+                                    false => {
+                                        format!("{}: no earlier failure", wire)
+                                    }
+                                }
+                            })
+                            .collect()
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Publics {
+                    publics,
+                    mismatches,
+                }) => {
+                    let console = match mismatches {
+                        Some(m) if m.is_empty() => vec![format!(
+                            "the {} public inputs match the proof artifacts",
+                            style.number(publics.len())
+                        )],
+                        Some(m) => m
+                            .iter()
+                            .map(|m| style.failure(m).to_string())
+                            .collect(),
+                        None if publics.is_empty() => {
+                            vec!["no constraint records a public input".into()]
+                        }
+                        None => publics
+                            .iter()
+                            .enumerate()
+                            .map(|(i, p)| {
+                                format!(
+                                    "{:>6} constraint #{}  {}",
+                                    i,
+                                    style.number(p.constraint),
+                                    style.scalar(
+                                        config.scalars.format(&p.value)
+                                    )
+                                )
+                            })
+                            .collect(),
+                    };
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
+                Some(ZkResponse::Raw {
+                    constraint,
+                    offset,
+                    bytes,
+                }) => {
+                    let mut console = vec![format!(
+                        "constraint #{} at offset {:#x}, {} bytes",
+                        style.number(constraint),
+                        offset,
+                        style.number(bytes.len())
+                    )];
+
+                    // hexdump rows of 16 bytes, addressed by file offset
+                    console.extend(bytes.chunks(16).enumerate().map(
+                        |(i, row)| {
+                            let hex: Vec<_> = row
+                                .iter()
+                                .map(|b| format!("{:02x}", b))
+                                .collect();
+                            let ascii: String = row
+                                .iter()
+                                .map(|b| match b.is_ascii_graphic() {
+                                    true => *b as char,
+                                    false => '.',
+                                })
+                                .collect();
+
+                            format!(
+                                "{:08x}  {:<47}  |{}|",
+                                offset + i * 16,
+                                hex.join(" "),
+                                ascii
+                            )
+                        },
+                    ));
+
+                    result.replace(
+                        context
+                            .send_output(Output {
+                                contents: None,
+                                console,
+                                error: vec![],
+                            })
+                            .await,
+                    );
+                }
+
                 Some(ZkResponse::Witness { witness }) => {
+                    let witness = ZkWitness {
+                        value: config.display_scalar(&witness.value),
+                        ..witness
+                    };
+
                     result.replace(
                         context
                             .send_output(Output {
@@ -253,44 +951,148 @@ impl App {
     }
 
     pub async fn load(args: ParsedArgs) -> io::Result<Self> {
-        let ParsedArgs { path, attach } = args;
-        let config = Config::load()?;
+        Self::load_with(args, None).await
+    }
 
-        let input = Input::try_from(&config)?;
+    /// Load the app reading the commands from the provided lines instead of
+    /// the prompt, so a recorded session can be replayed
+    pub async fn replay<I>(args: ParsedArgs, lines: I) -> io::Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self::load_with(args, Some(lines.into_iter().collect())).await
+    }
 
-        let socket = match attach {
-            Some(socket) => socket,
+    async fn load_with(
+        args: ParsedArgs,
+        lines: Option<Vec<String>>,
+    ) -> io::Result<Self> {
+        let ParsedArgs {
+            path,
+            attach,
+            dap_command,
+            color,
+            compare,
+        } = args;
+        let mut config = Config::load()?;
 
-            None => {
-                let ip = net::Ipv4Addr::LOCALHOST;
-                let port = 0;
-                let socket = net::SocketAddrV4::new(ip, port);
+        if let Some(color) = color {
+            config.render.color = color;
+        }
+
+        let path = path.map(|p| p.display().to_string());
 
-                let service =
-                    dusk_cdf::ZkDapBuilder::new(socket).build().await?;
-                let socket = service.local_addr()?;
+        // a replayed session isn't saved for recovery
+        let autosave = config.autosave_interval().filter(|_| lines.is_none());
+        let recovery = match (&path, autosave) {
+            (Some(path), Some(_)) => Recovery::load(path),
+            _ => Recovery::default(),
+        };
+
+        let mut input = match lines {
+            Some(lines) => Input::scripted(&config, lines)?,
+            None => Input::try_from(&config)?,
+        };
 
-                tokio::spawn(async move {
-                    service.listen().await.ok();
-                });
+        if let Some(recovered) = recovery.recovered() {
+            if Self::offer_recovery(&recovered)? {
+                let commands: Vec<_> = recovered
+                    .commands
+                    .into_iter()
+                    .filter(|line| Self::is_restorable(&input, line))
+                    .collect();
 
-                socket
+                input.queue(commands);
+            } else if let Err(e) = recovery.discard() {
+                eprintln!("failed to discard the recovered session: {}", e);
             }
+        }
+
+        let adapter = match dap_command {
+            Some(command) => Some(Adapter::spawn(&command).await?),
+            None => None,
         };
 
-        let Client {
-            responses,
-            events,
-            requests,
-            ..
-        } = ClientBuilder::new().connect(socket).await?;
+        let (requests, responses, events) = match (attach, &adapter) {
+            (Some(socket), _) => Self::connect(socket).await?,
+
+            (None, Some(adapter)) => Self::connect(adapter.socket()).await?,
+
+            // without a remote adapter, the backend runs in-process
+            (None, None) => Self::connect_local(),
+        };
 
         let (outputs_tx, outputs) = mpsc::channel(50);
 
         let context = Context::new(config, requests, outputs_tx);
 
         if let Some(path) = path {
-            context.replace_path(path.display().to_string()).await?;
+            if let Err(e) = input.recent().push(path.as_str()) {
+                eprintln!("failed to record the recent files: {}", e);
+            }
+
+            context.replace_path(path).await?;
+        }
+
+        // the prompt reads ctrl+c as a key, so the signal is raised only while
+        // a command runs; it interrupts the command instead of the process,
+        // pausing the scan of the backend
+        let interrupt = CancelToken::new();
+        let i = interrupt.clone();
+        let c = context.clone();
+
+        tokio::spawn(async move {
+            while signal::ctrl_c().await.is_ok() {
+                i.cancel();
+
+                if let Err(e) = c.send_request(ZkRequest::Pause).await {
+                    c.send_error_output(e).await;
+                }
+            }
+        });
+
+        let compare = compare
+            .map(|p| Compare::open(p.display().to_string(), interrupt.clone()))
+            .transpose()?;
+
+        if let (Some(compare), Some(path)) = (&compare, context.path().await) {
+            let output = compare.load(path, context.config().style()).await;
+
+            context.send_output(output).await?;
+        }
+
+        let recovery = Arc::new(Mutex::new(recovery));
+
+        if let Some(autosave) = autosave {
+            let r = Arc::clone(&recovery);
+
+            tokio::spawn(async move {
+                let mut interval = time::interval(autosave);
+
+                loop {
+                    interval.tick().await;
+
+                    let saved =
+                        r.lock().unwrap_or_else(PoisonError::into_inner).save();
+
+                    if let Err(e) = saved {
+                        eprintln!("failed to save the session: {}", e);
+                    }
+                }
+            });
+
+            // the latest snapshot outlives a panic of the session. the lock
+            // isn't awaited, as the panicking thread may hold it
+            let r = Arc::clone(&recovery);
+            let hook = panic::take_hook();
+
+            panic::set_hook(Box::new(move |info| {
+                if let Ok(r) = r.try_lock() {
+                    r.save().ok();
+                }
+
+                hook(info);
+            }));
         }
 
         let c = context.clone();
@@ -309,11 +1111,82 @@ impl App {
             context,
             input,
             outputs,
+            compare,
+            interrupt,
+            recovery,
+            autosave: autosave.is_some(),
+            recovery_start: 0,
+            _adapter: adapter,
         };
 
         Ok(app)
     }
 
+    /// Propose to restore the session recovered for the CDF file.
+    ///
+    /// Nothing is proposed if the session isn't interactive.
+    fn offer_recovery(recovered: &Recovered) -> io::Result<bool> {
+        let stdin = io::stdin();
+
+        if !stdin.is_terminal() {
+            return Ok(false);
+        }
+
+        let elapsed = recovered.saved.elapsed().unwrap_or_default().as_secs();
+        let elapsed = match elapsed {
+            s if s < 60 => format!("{}s", s),
+            s if s < 3600 => format!("{}m", s / 60),
+            s if s < 86400 => format!("{}h", s / 3600),
+            s => format!("{}d", s / 86400),
+        };
+
+        print!(
+            "restore the {} commands of the session saved {} ago? [Y/n] ",
+            recovered.commands.len(),
+            elapsed
+        );
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+
+        if stdin.lock().read_line(&mut answer)? == 0 {
+            return Ok(false);
+        }
+
+        Ok(matches!(answer.trim(), "" | "y" | "Y" | "yes"))
+    }
+
+    /// A restored command is replayed unless it leaves the recovered file or
+    /// writes outside the session
+    fn is_restorable(input: &Input, line: &str) -> bool {
+        !matches!(
+            input.parse(line),
+            Ok(Some(
+                Command::Open { .. }
+                    | Command::Reopen { .. }
+                    | Command::Copy
+                    | Command::ExportView { .. }
+            ))
+        )
+    }
+
+    /// Keep the journal of the session as the snapshot to be saved to its
+    /// recovery file
+    async fn snapshot(&self) {
+        let journal = self.context.journal().await.skip(self.recovery_start);
+        let recovery = self.lock_recovery();
+        let mut session = vec![];
+
+        // the session is headed by the path the recovery file is keyed by
+        if journal.write_session(recovery.cdf(), &mut session).is_ok() {
+            recovery.snapshot(String::from_utf8_lossy(&session).into_owned());
+        }
+    }
+
+    fn lock_recovery(&self) -> MutexGuard<'_, Recovery> {
+        self.recovery.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Empty the pending outputs
     pub async fn flush_output(&mut self) -> Option<Output> {
         time::sleep(self.context.config().render_delay()).await;
@@ -327,21 +1200,365 @@ impl App {
         Some(output)
     }
 
+    /// Register a custom command
+    pub fn register_plugin<P>(&mut self, plugin: P)
+    where
+        P: DebuggerPlugin + 'static,
+    {
+        self.input.register_plugin(Arc::new(plugin));
+    }
+
+    /// Run an analysis script over the backend of the session
+    async fn run_script(&mut self, script: String) -> Output {
+        let interrupt = self.interrupt.clone();
+
+        self.run_backend(move |backend| script::run(backend, script, interrupt))
+            .await
+            .unwrap_or_else(|e| {
+                Output::error(format!("failed to run script: {}", e))
+            })
+    }
+
+    /// Client of the DAP backend of the session, to be used from a blocking
+    /// task.
+    ///
+    /// Its requests are sent over the channels of the session, so the
+    /// movements are displayed as the ones of the prompt.
+    fn backend(&self) -> BackendClient {
+        let context = self.context.clone();
+        let handle = Handle::current();
+
+        let transport: Transport = Box::new(move |request| {
+            match handle.block_on(context.call(request))? {
+                Response::Custom { body } => {
+                    ZkResponse::try_from(body.as_ref())
+                }
+
+                Response::Error {
+                    command,
+                    error: ProtocolResponseError { message, .. },
+                } => Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    message.unwrap_or_else(|| {
+                        format!("error in command '{}'", command)
+                    }),
+                )),
+
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "unexpected response from the backend",
+                )),
+            }
+        });
+
+        ZkDapClient::new(transport)
+    }
+
+    /// Execute a blocking task over the backend of the session.
+    ///
+    /// The outputs of the movements of the task are collected while it runs,
+    /// so they don't hold the responses it awaits, and merged ahead of its
+    /// own output.
+    async fn run_backend<F>(&mut self, task: F) -> io::Result<Output>
+    where
+        F: FnOnce(BackendClient) -> io::Result<Output> + Send + 'static,
+    {
+        let backend = self.backend();
+        let mut task = task::spawn_blocking(move || task(backend));
+        let mut output = Output::default();
+
+        let result = loop {
+            tokio::select! {
+                result = &mut task => break result,
+                Some(o) = self.outputs.recv() => output.merge(o),
+            }
+        };
+
+        let result = result
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .and_then(|output| output)?;
+
+        output.merge(result);
+
+        Ok(output)
+    }
+
+    /// Execute a registered plugin over the backend of the session
+    async fn run_plugin(&mut self, name: String, args: Vec<String>) -> Output {
+        let plugin = match self.input.plugin(&name) {
+            Some(p) => p,
+            None => return Output::error(format!("unknown plugin: {}", name)),
+        };
+
+        self.run_backend(move |mut backend| {
+            plugin.execute(&mut backend, &args).map(Output::console)
+        })
+        .await
+        .unwrap_or_else(|e| {
+            Output::error(format!("failed to execute {}: {}", name, e))
+        })
+    }
+
+    /// Open a CDF file, recording it as the most recent one
+    async fn open(&mut self, path: String) -> Output {
+        let mut output = Output::console(format!("opening {}", path));
+
+        // the journal holds the commands over the previous file, so the
+        // session of the new one is saved from this command on, to the
+        // recovery file of the new one
+        self.recovery_start = self.context.journal().await.len();
+
+        {
+            let mut recovery = self.lock_recovery();
+
+            if let Err(e) = recovery.discard() {
+                output.merge(Output::error(format!(
+                    "failed to discard the recovery file: {}",
+                    e
+                )));
+            }
+
+            *recovery = match self.autosave {
+                true => Recovery::load(&path),
+                false => Recovery::default(),
+            };
+        }
+
+        if let Err(e) = self.input.recent().push(path.as_str()) {
+            output.merge(Output::error(format!(
+                "failed to record the recent files: {}",
+                e
+            )));
+        }
+
+        if let Err(e) = self.context.replace_path(path.clone()).await {
+            output.merge(Output::error(format!(
+                "error sending request to backend: {}",
+                e
+            )));
+
+            return output;
+        }
+
+        if let Some(o) = self.flush_output().await {
+            output.merge(o);
+        }
+
+        if let Some(compare) = &self.compare {
+            let style = self.context.config().style();
+
+            output.merge(compare.load(path, style).await);
+        }
+
+        output
+    }
+
+    /// Path of a recently opened file; without an index, the latest one that
+    /// isn't currently loaded
+    async fn recent_path(&self, index: Option<usize>) -> io::Result<String> {
+        let entries = self.input.recent().entries();
+
+        let path = match index {
+            Some(i) => entries.get(i).cloned(),
+            None => {
+                let current = self.context.path().await;
+
+                entries
+                    .iter()
+                    .find(|e| Some(e.as_str()) != current.as_deref())
+                    .cloned()
+            }
+        };
+
+        path.ok_or_else(|| {
+            if entries.is_empty() {
+                return io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no file was opened recently",
+                );
+            }
+
+            let listing = entries.iter().enumerate().fold(
+                String::from("no such recent file. recent files:"),
+                |mut s, (i, e)| {
+                    s.push_str(&format!("\n{:>3} {}", i, e));
+                    s
+                },
+            );
+
+            io::Error::new(io::ErrorKind::NotFound, listing)
+        })
+    }
+
+    /// Define, print or list the aliases of the session
+    fn alias(
+        &mut self,
+        name: Option<String>,
+        expansion: Option<String>,
+    ) -> Output {
+        match (name, expansion) {
+            (Some(name), Some(expansion)) => {
+                self.input.set_alias(&name, &expansion);
+
+                Output::default()
+            }
+
+            (Some(name), None) => match self.input.aliases().get(&name) {
+                Some(e) => Output::console(format!("{} = {}", name, e)),
+                None => Output::error(format!("no such alias: {}", name)),
+            },
+
+            (None, _) if self.input.aliases().is_empty() => {
+                Output::console("no aliases defined")
+            }
+
+            (None, _) => Output::console(
+                self.input
+                    .aliases()
+                    .iter()
+                    .map(|(name, e)| format!("{} = {}", name, e))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        }
+    }
+
+    /// Command of the journal by its number, along with its line
+    async fn rerun(&self, index: usize) -> io::Result<(String, Command)> {
+        let journal = self.context.journal().await;
+        let line =
+            journal
+                .get(index)
+                .map(|e| e.command.clone())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no command #{} in the history", index),
+                    )
+                })?;
+
+        let command = self.input.parse(&line)?.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "empty command")
+        })?;
+
+        Ok((line, command))
+    }
+
+    /// List the journal of the session, or write it to a session file
+    async fn history(&self, path: Option<String>) -> Output {
+        let journal = self.context.journal().await;
+
+        let path = match path {
+            Some(p) => p,
+            None if journal.is_empty() => {
+                return Output::console("no command was executed")
+            }
+            None => return Output::console(journal.to_string()),
+        };
+
+        let cdf = self.context.path().await.unwrap_or_default();
+        let written = File::create(&path).and_then(|file| {
+            let mut writer = io::BufWriter::new(file);
+
+            journal.write_session(&cdf, &mut writer)?;
+            writer.flush()
+        });
+
+        match written {
+            Ok(()) => Output::console(format!("history written to {}", path)),
+            Err(e) => Output::error(format!("failed to write {}: {}", path, e)),
+        }
+    }
+
     /// Analogous to iterator next, but async
     pub async fn next_output(&mut self) -> Option<Output> {
+        match self.execute_next().await {
+            Some(output) => {
+                self.snapshot().await;
+
+                Some(output)
+            }
+
+            // the session quits cleanly, so there is nothing to recover
+            None => {
+                if let Err(e) = self.lock_recovery().discard() {
+                    eprintln!("failed to discard the recovery file: {}", e);
+                }
+
+                None
+            }
+        }
+    }
+
+    async fn execute_next(&mut self) -> Option<Output> {
         let command = match self.input.next() {
             Some(Command::Quit) | None => return None,
             Some(c) => c,
         };
 
-        if matches!(command, Command::Help) {
-            return Some(Output {
-                contents: None,
-                console: vec![self.input.help()],
-                error: vec![],
-            });
+        let (line, command) = match command {
+            Command::Rerun { index } => match self.rerun(index).await {
+                Ok((_, Command::Quit)) => return None,
+                Ok(rerun) => rerun,
+                Err(e) => return Some(Output::error(e.to_string())),
+            },
+            c => (self.input.last_line().unwrap_or_default().into(), c),
+        };
+
+        if !matches!(command, Command::History { .. } | Command::Help { .. }) {
+            self.context.record_command(line).await;
         }
 
+        self.interrupt.reset();
+
+        match command {
+            Command::History { path } => return Some(self.history(path).await),
+
+            Command::Help { topic: None } => {
+                return Some(Output::console(self.input.help()))
+            }
+
+            Command::Alias { name, expansion } => {
+                return Some(self.alias(name, expansion))
+            }
+
+            Command::Help { topic: Some(topic) } => {
+                return Some(match self.input.help_topic(&topic) {
+                    Ok(help) => Output::console(help),
+                    Err(e) => Output::error(e.to_string()),
+                })
+            }
+
+            Command::Script { path } => {
+                return Some(self.run_script(path).await)
+            }
+
+            Command::Plugin { name, args } => {
+                return Some(self.run_plugin(name, args).await)
+            }
+
+            Command::Open { path } => return Some(self.open(path).await),
+
+            Command::Reopen { index } => {
+                return Some(match self.recent_path(index).await {
+                    Ok(path) => self.open(path).await,
+                    Err(e) => Output::error(e.to_string()),
+                })
+            }
+
+            Command::Copy => {
+                self.context.replace_export(Export::Clipboard).await
+            }
+
+            Command::ExportView { ref path } => {
+                self.context.replace_export(Export::File(path.into())).await
+            }
+
+            _ => (),
+        }
+
+        let mirrored = self.compare.as_ref().map(|_| command.clone());
+
         if let Err(e) = self.context.receive_command(command).await {
             return Some(Output {
                 contents: None,
@@ -350,6 +1567,16 @@ impl App {
             });
         }
 
-        self.flush_output().await
+        let mut output = self.flush_output().await;
+
+        if let (Some(compare), Some(command)) = (&self.compare, mirrored) {
+            let style = self.context.config().style();
+
+            if let Some(o) = compare.follow(command, style).await {
+                output.get_or_insert_with(Output::default).merge(o);
+            }
+        }
+
+        output
     }
 }
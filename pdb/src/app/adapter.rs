@@ -0,0 +1,95 @@
+use std::process::Stdio;
+use std::{io, net};
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+/// DAP adapter spawned as a child process, speaking the protocol over its
+/// stdio.
+///
+/// The DAP client connects to sockets only, so the stdio of the adapter is
+/// bridged to a loopback socket that accepts a single connection. The child is
+/// killed when the adapter is dropped.
+#[derive(Debug)]
+pub struct Adapter {
+    socket: net::SocketAddr,
+    _child: Child,
+}
+
+impl Adapter {
+    /// Spawn the command line, split as shell words
+    pub async fn spawn(command: &str) -> io::Result<Self> {
+        let mut words = shellwords::split(command)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .into_iter();
+
+        let program = words.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the DAP command is empty",
+            )
+        })?;
+
+        // the stderr would be printed over the rendered sources
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| missing("stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| missing("stdout"))?;
+
+        let ip = net::Ipv4Addr::LOCALHOST;
+        let listener = TcpListener::bind(net::SocketAddrV4::new(ip, 0)).await?;
+        let socket = listener.local_addr()?;
+
+        tokio::spawn(async move {
+            bridge(listener, stdin, stdout).await.ok();
+        });
+
+        Ok(Self {
+            socket,
+            _child: child,
+        })
+    }
+
+    /// Socket bridged to the stdio of the adapter
+    pub const fn socket(&self) -> net::SocketAddr {
+        self.socket
+    }
+}
+
+fn missing(stream: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::BrokenPipe,
+        format!("the {} of the DAP adapter isn't available", stream),
+    )
+}
+
+/// Forward the bytes between the first accepted connection and the adapter
+/// until either side is closed
+async fn bridge(
+    listener: TcpListener,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+) -> io::Result<()> {
+    let (stream, _) = listener.accept().await?;
+    let (reader, writer) = stream.into_split();
+
+    tokio::select! {
+        r = forward(reader, stdin) => r,
+        r = forward(stdout, writer) => r,
+    }
+}
+
+async fn forward<R, W>(mut reader: R, mut writer: W) -> io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    tokio::io::copy(&mut reader, &mut writer).await.map(|_| ())
+}
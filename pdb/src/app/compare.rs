@@ -0,0 +1,247 @@
+use std::fs::File;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use dusk_cdf::{
+    Breakpoint, CancelToken, CircuitDescription, Config, Style, ZkDebugger,
+};
+use tokio::task;
+use toml_base_config::BaseConfig;
+
+use crate::commands::Command;
+
+use super::Output;
+
+/// Second CDF file navigated in lockstep with the debugged one.
+///
+/// The navigation commands are mirrored by a local debugger over the debugged
+/// file to track its position, and the constraint at that position is compared
+/// field by field with the one of the second file.
+///
+/// The mirrored scans pause once the interrupt token is cancelled.
+#[derive(Clone)]
+pub struct Compare {
+    path: String,
+    interrupt: CancelToken,
+    inner: Arc<Mutex<CompareInner>>,
+}
+
+struct CompareInner {
+    left: Option<ZkDebugger<File>>,
+    right: CircuitDescription<File>,
+    first: Option<usize>,
+}
+
+impl Compare {
+    /// Open the second CDF file
+    pub fn open(path: String, interrupt: CancelToken) -> io::Result<Self> {
+        let right = CircuitDescription::open(&path)?;
+        let inner = CompareInner {
+            left: None,
+            right,
+            first: None,
+        };
+
+        Ok(Self {
+            path,
+            interrupt,
+            inner: Arc::new(Mutex::new(inner)),
+        })
+    }
+
+    /// Load the debugged file, searching for the first divergent constraint
+    pub async fn load(&self, left: String, style: Style) -> Output {
+        self.run(move |path, inner| {
+            let mut left = ZkDebugger::open_with_config(left, Config::load()?)?;
+            let divergence = left.first_divergence(&mut inner.right)?;
+
+            inner.first = divergence.constraint();
+
+            let console = match inner.first {
+                Some(idx) => format!(
+                    "comparing with {}: {}",
+                    path,
+                    style.failure(format!(
+                        "first divergence at constraint #{}",
+                        idx
+                    ))
+                ),
+                None => {
+                    format!("comparing with {}: no divergent constraint", path)
+                }
+            };
+
+            inner.left.replace(left);
+
+            Ok(Output::console(console))
+        })
+        .await
+    }
+
+    /// Mirror a command over the debugged file, comparing the constraint it
+    /// moved to.
+    ///
+    /// Commands that don't navigate produce no output.
+    pub async fn follow(
+        &self,
+        command: Command,
+        style: Style,
+    ) -> Option<Output> {
+        let navigates = matches!(
+            command,
+            Command::Afore
+                | Command::Continue
+                | Command::ContinueMax { .. }
+                | Command::Goto { .. }
+                | Command::GotoLine { .. }
+                | Command::Next
+                | Command::Restart
+                | Command::Turn
+        );
+
+        let interrupt = self.interrupt.clone();
+
+        let output = self
+            .run(move |path, inner| {
+                let CompareInner { left, right, first } = inner;
+                let left = left.as_mut().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        "no CDF file is loaded",
+                    )
+                })?;
+
+                let mut paused = false;
+
+                match command {
+                    Command::Afore => left.afore().map(|_| ())?,
+                    Command::Continue => {
+                        paused = left.cont_until(interrupt)?.is_paused()
+                    }
+                    Command::ContinueMax {
+                        max,
+                        reverse: false,
+                    } => left.cont_max(max).map(|_| ())?,
+                    Command::ContinueMax { max, reverse: true } => {
+                        left.turn_max(max).map(|_| ())?
+                    }
+                    Command::Goto { id } => left.goto(id).map(|_| ())?,
+                    Command::GotoLine { source, line } => {
+                        left.goto_line(&source, line).map(|_| ())?
+                    }
+                    Command::Next => left.step().map(|_| ())?,
+                    Command::Restart => left.goto(0).map(|_| ())?,
+                    Command::Turn => {
+                        paused = left.turn_until(interrupt)?.is_paused()
+                    }
+
+                    Command::Breakpoint { source, line, kind } => {
+                        let breakpoint = Breakpoint { source, line, kind };
+
+                        left.insert_breakpoint(breakpoint, false);
+                    }
+
+                    Command::TemporaryBreakpoint { source, line, kind } => {
+                        let breakpoint = Breakpoint { source, line, kind };
+
+                        left.insert_breakpoint(breakpoint, true);
+                    }
+
+                    Command::Delete { id } => {
+                        left.remove_breakpoint(id);
+                    }
+
+                    Command::Ignore { id, count } => {
+                        left.ignore_breakpoint(id, count);
+                    }
+
+                    Command::Focus { focus, failures } => {
+                        left.set_focus(focus, failures);
+                    }
+
+                    _ => (),
+                }
+
+                if !navigates {
+                    return Ok(Output::default());
+                }
+
+                let idx = left.position();
+
+                if paused {
+                    return Ok(Output::console(format!(
+                        "{}: scan interrupted at constraint #{}",
+                        path, idx
+                    )));
+                }
+
+                if idx >= right.preamble().constraints {
+                    return Ok(Output::console(format!(
+                        "{}: constraint #{} is past its end",
+                        path, idx
+                    )));
+                }
+
+                let divergences = left.divergences(right, idx)?;
+
+                if divergences.is_empty() {
+                    return Ok(Output::console(format!(
+                        "{}: constraint #{} matches",
+                        path, idx
+                    )));
+                }
+
+                let mut console = vec![];
+
+                if *first == Some(idx) {
+                    console.push(
+                        style
+                            .failure(format!("{}: first divergence", path))
+                            .to_string(),
+                    );
+                }
+
+                console.extend(
+                    divergences.iter().map(|d| style.failure(d).to_string()),
+                );
+
+                Ok(Output {
+                    contents: None,
+                    console,
+                    error: vec![],
+                })
+            })
+            .await;
+
+        navigates.then_some(output)
+    }
+
+    /// Execute a blocking task over the compared files
+    async fn run<F>(&self, task: F) -> Output
+    where
+        F: FnOnce(&str, &mut CompareInner) -> io::Result<Output>
+            + Send
+            + 'static,
+    {
+        let path = self.path.clone();
+        let inner = Arc::clone(&self.inner);
+
+        task::spawn_blocking(move || {
+            let mut inner = lock(&inner)?;
+
+            task(&path, &mut inner)
+        })
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+        .and_then(|output| output)
+        .unwrap_or_else(|e| Output::error(format!("failed to compare: {}", e)))
+    }
+}
+
+fn lock(
+    inner: &Mutex<CompareInner>,
+) -> io::Result<MutexGuard<'_, CompareInner>> {
+    inner
+        .lock()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+}
@@ -1,9 +1,13 @@
+use std::collections::BTreeMap;
 use std::time;
 
+use dusk_cdf::{ColorChoice, ScalarFormat, Style, TableStyle};
 use rustyline::Config as RustylineConfig;
 use serde::{Deserialize, Serialize};
 use toml_base_config::BaseConfig;
 
+use super::render::{Capabilities, RenderMode, Renderer};
+
 /// Readline configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Readline {
@@ -80,6 +84,20 @@ pub struct Render {
     pub line_numbers: bool,
     pub theme: String,
     pub timeout: u64,
+    #[serde(default)]
+    pub color: ColorChoice,
+    #[serde(default = "Render::default_thousands_separators")]
+    pub thousands_separators: bool,
+    #[serde(default)]
+    pub mode: RenderMode,
+    #[serde(default)]
+    pub table_style: TableStyle,
+}
+
+impl Render {
+    const fn default_thousands_separators() -> bool {
+        true
+    }
 }
 
 impl Default for Render {
@@ -96,15 +114,41 @@ impl Default for Render {
             }
             .to_string(),
             timeout: 500,
+            color: ColorChoice::Auto,
+            thousands_separators: Self::default_thousands_separators(),
+            mode: RenderMode::Auto,
+            table_style: TableStyle::Ascii,
         }
     }
 }
 
+/// Session persistence parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// Interval between the saves of a modified session to its recovery
+    /// file, in seconds. `0` disables the recovery files
+    pub autosave: u64,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self { autosave: 30 }
+    }
+}
+
 /// App configuration
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
     pub readline: Readline,
     pub render: Render,
+    #[serde(default)]
+    pub session: Session,
+    /// Representation of the displayed and entered scalars
+    #[serde(default)]
+    pub scalars: ScalarFormat,
+    /// Command shortcuts, mapped to the command line they expand to
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
 }
 
 impl Config {
@@ -122,6 +166,46 @@ impl Config {
     pub const fn render_timeout(&self) -> time::Duration {
         time::Duration::from_millis(self.render.timeout)
     }
+
+    /// Return the configured interval of the session saves, if enabled
+    pub const fn autosave_interval(&self) -> Option<time::Duration> {
+        match self.session.autosave {
+            0 => None,
+            secs => Some(time::Duration::from_secs(secs)),
+        }
+    }
+
+    /// Scalar received from the backend, in the configured representation.
+    /// A value the backend didn't encode as a scalar is left as is.
+    pub fn display_scalar(&self, value: &str) -> String {
+        ScalarFormat::default()
+            .parse(value)
+            .map(|scalar| self.scalars.format(&scalar))
+            .unwrap_or_else(|_| value.to_string())
+    }
+
+    /// Renderer of the outputs, resolved from the render mode
+    pub fn renderer(&self) -> Renderer {
+        self.render.mode.resolve(Capabilities::detect())
+    }
+
+    /// Style of the console outputs, resolved from the color choice. The
+    /// plain renderer is colored only if the colors are forced, and the
+    /// digits of the TSV tables aren't grouped so they can be processed.
+    pub fn style(&self) -> Style {
+        let mut style = match self.renderer() {
+            Renderer::Plain if self.render.color != ColorChoice::Always => {
+                Style::PLAIN
+            }
+            _ => self.render.color.resolve(),
+        };
+
+        let table = self.render.table_style;
+        let grouping =
+            self.render.thousands_separators && table != TableStyle::Tsv;
+
+        *style.with_grouping(grouping).with_table_style(table)
+    }
 }
 
 impl BaseConfig for Config {
@@ -1,20 +1,27 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::{fmt, io};
 
 use dap_reactor::reactor::ClientRequest;
-use dusk_cdf::ZkSource;
-use tokio::sync::{mpsc, RwLock};
+use dap_reactor::response::Response;
+use dusk_cdf::{ZkSource, ZkSourceInfo};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
 
 use crate::commands::Command;
 
 use super::config::Config;
+use super::export::Export;
+use super::journal::Journal;
 use super::Output;
 
 #[derive(Clone)]
 pub struct Context {
     config: Config,
     requests: mpsc::Sender<ClientRequest>,
+    /// Requests in flight, in the order they were sent, along with the caller
+    /// awaiting the response of each one. The backend answers every request,
+    /// in order.
+    awaiting: Arc<Mutex<VecDeque<Option<oneshot::Sender<Response>>>>>,
     outputs: mpsc::Sender<Output>,
     contents_lock: mpsc::Sender<()>,
     inner: Arc<RwLock<ContextInner>>,
@@ -34,6 +41,7 @@ impl Context {
         Self {
             config,
             requests,
+            awaiting: Arc::default(),
             outputs,
             contents_lock,
             inner,
@@ -75,31 +83,94 @@ impl Context {
             inner.locked = false;
         }
 
-        inner.contents.get(name).cloned()
+        inner.contents.get(name).map(|s| s.contents.clone())
+    }
+
+    /// Paths and content hashes of the cached sources
+    pub async fn known_sources(&self) -> Vec<ZkSourceInfo> {
+        self.inner
+            .read()
+            .await
+            .contents
+            .values()
+            .map(|s| ZkSourceInfo {
+                path: s.path.clone(),
+                size: s.contents.len(),
+                hash: s.hash.clone(),
+            })
+            .collect()
     }
 
     pub async fn replace_contents_batch<C>(&self, contents: C)
     where
         C: IntoIterator<Item = ZkSource>,
     {
-        let contents = contents
-            .into_iter()
-            .map(|ZkSource { path, contents }| (path, contents));
-
         let mut inner = self.inner.write().await;
 
         inner.contents.clear();
-        inner.contents.extend(contents);
+        inner.extend_contents(contents);
+    }
+
+    /// Replace the cache with the sources of a loaded CDF file, keeping the
+    /// cached ones the backend reported as `unchanged`, so the sources of a
+    /// previous file don't survive a reload
+    pub async fn replace_contents<C>(&self, contents: C, unchanged: &[String])
+    where
+        C: IntoIterator<Item = ZkSource>,
+    {
+        let mut inner = self.inner.write().await;
+
+        inner.contents.retain(|path, _| unchanged.contains(path));
+        inner.extend_contents(contents);
     }
 
     pub async fn send_request<R>(&self, request: R) -> io::Result<()>
     where
         R: Into<ClientRequest>,
     {
+        self.send_awaited(request, None).await
+    }
+
+    /// Send a request, returning its response to the caller instead of
+    /// displaying it
+    pub async fn call<R>(&self, request: R) -> io::Result<Response>
+    where
+        R: Into<ClientRequest>,
+    {
+        let (reply, response) = oneshot::channel();
+
+        self.send_awaited(request, Some(reply)).await?;
+
+        response
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
+    }
+
+    /// Take the caller awaiting the next response, if it isn't displayed
+    pub async fn take_reply(&self) -> Option<oneshot::Sender<Response>> {
+        self.awaiting.lock().await.pop_front().flatten()
+    }
+
+    async fn send_awaited<R>(
+        &self,
+        request: R,
+        reply: Option<oneshot::Sender<Response>>,
+    ) -> io::Result<()>
+    where
+        R: Into<ClientRequest>,
+    {
+        // the request is queued while it's sent, so its response can't be
+        // taken before
+        let mut awaiting = self.awaiting.lock().await;
+
         self.requests
             .send_timeout(request.into(), self.config.render_timeout())
             .await
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        awaiting.push_back(reply);
+
+        Ok(())
     }
 
     pub async fn send_output<O>(&self, output: O) -> io::Result<()>
@@ -125,6 +196,39 @@ impl Context {
         .ok();
     }
 
+    /// Set the destination of the next markdown snippet
+    pub async fn replace_export(&self, export: Export) {
+        self.inner.write().await.export.replace(export);
+    }
+
+    /// Take the destination of the received markdown snippet
+    pub async fn take_export(&self) -> Option<Export> {
+        self.inner.write().await.export.take()
+    }
+
+    /// Record an executed command line in the journal of the session
+    pub async fn record_command(&self, command: String) {
+        self.inner.write().await.journal.push(command);
+    }
+
+    /// Record the state reached by the latest command of the journal
+    pub async fn record_state(
+        &self,
+        state: Option<String>,
+        location: Option<String>,
+    ) {
+        self.inner
+            .write()
+            .await
+            .journal
+            .record_state(state, location);
+    }
+
+    /// Commands executed in the session, along with the states they reached
+    pub async fn journal(&self) -> Journal {
+        self.inner.read().await.journal.clone()
+    }
+
     pub async fn lock_contents(&self) {
         self.inner.write().await.locked = true;
     }
@@ -138,8 +242,10 @@ impl Context {
 struct ContextInner {
     path: Option<String>,
     locked: bool,
-    contents: HashMap<String, String>,
+    contents: HashMap<String, ZkSource>,
     contents_lock: mpsc::Receiver<()>,
+    export: Option<Export>,
+    journal: Journal,
 }
 
 impl ContextInner {
@@ -149,8 +255,19 @@ impl ContextInner {
             locked: false,
             contents: HashMap::new(),
             contents_lock,
+            export: None,
+            journal: Journal::default(),
         }
     }
+
+    pub fn extend_contents<C>(&mut self, contents: C)
+    where
+        C: IntoIterator<Item = ZkSource>,
+    {
+        let contents = contents.into_iter().map(|s| (s.path.clone(), s));
+
+        self.contents.extend(contents);
+    }
 }
 
 #[tokio::test]
@@ -188,10 +305,7 @@ async fn context_base_functions_works() -> io::Result<()> {
 
     assert_eq!(cmd_reqs, r);
 
-    let source = ZkSource {
-        path: "foo".into(),
-        contents: "bar".into(),
-    };
+    let source = ZkSource::new("foo", "bar");
 
     context.replace_contents_batch(vec![source.clone()]).await;
 
@@ -202,6 +316,36 @@ async fn context_base_functions_works() -> io::Result<()> {
 
     assert_eq!(source.contents, contents);
 
+    let known = context.known_sources().await;
+
+    assert_eq!(known.len(), 1);
+    assert_eq!(
+        (known[0].path.as_str(), known[0].hash.as_str()),
+        ("foo", source.hash.as_str())
+    );
+
+    let updated = ZkSource::new("foo", "baz");
+    let renamed = ZkSource::new("qux", "bar");
+
+    context
+        .replace_contents(vec![updated.clone()], &["qux".into()])
+        .await;
+
+    let contents = context
+        .contents(&source.path)
+        .await
+        .expect("failed to fetch contents");
+
+    assert_eq!(updated.contents, contents);
+
+    context.replace_contents(vec![renamed.clone()], &[]).await;
+
+    assert_eq!(
+        context.contents(&renamed.path).await,
+        Some(renamed.contents)
+    );
+    assert_eq!(context.contents(&source.path).await, None);
+
     let output = Output::console("foo");
 
     context.send_output(output.clone()).await?;
@@ -221,3 +365,44 @@ async fn context_base_functions_works() -> io::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn awaited_responses_are_returned_to_their_caller() -> io::Result<()> {
+    use dap_reactor::request::Request;
+
+    let config = Config::default();
+    let (requests_tx, mut requests) = mpsc::channel(50);
+    let (outputs_tx, _outputs) = mpsc::channel(50);
+
+    let context = Context::new(config, requests_tx, outputs_tx);
+
+    context.send_request(Request::Threads).await?;
+
+    let c = context.clone();
+    let call = tokio::spawn(async move { c.call(Request::Threads).await });
+
+    for _ in 0..2 {
+        requests.recv().await.expect("expected request");
+    }
+
+    // the response of the first request is displayed
+    assert!(context.take_reply().await.is_none());
+
+    let response = Response::Custom { body: None };
+
+    context
+        .take_reply()
+        .await
+        .expect("the call awaits its response")
+        .send(response.clone())
+        .expect("failed to reply");
+
+    let replied = call
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??;
+
+    assert_eq!(replied, response);
+    assert!(context.take_reply().await.is_none());
+
+    Ok(())
+}
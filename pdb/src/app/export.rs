@@ -0,0 +1,63 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crossterm::tty::IsTty;
+
+/// Destination of the markdown snippet of a constraint
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Export {
+    /// Clipboard of the terminal
+    Clipboard,
+    /// File, replaced if it exists
+    File(PathBuf),
+}
+
+impl Export {
+    /// Write the snippet to the destination, returning a description of it
+    pub fn write(&self, markdown: &str) -> io::Result<String> {
+        match self {
+            Self::Clipboard => {
+                let mut stdout = io::stdout();
+
+                if !stdout.is_tty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "the clipboard requires a terminal; use export-view <FILE> instead",
+                    ));
+                }
+
+                // OSC 52 sets the clipboard of the terminal, including the
+                // terminals of a remote session
+                write!(
+                    stdout,
+                    "\x1b]52;c;{}\x07",
+                    base64::encode(markdown.as_bytes())
+                )?;
+                stdout.flush()?;
+
+                Ok("the clipboard".into())
+            }
+
+            Self::File(path) => {
+                fs::write(path, markdown)?;
+
+                Ok(path.display().to_string())
+            }
+        }
+    }
+}
+
+#[test]
+fn export_writes_the_snippet_to_a_file() -> io::Result<()> {
+    let dir = tempdir::TempDir::new("pdb-export")?;
+    let path = dir.path().join("constraint.md");
+    let markdown = "### Constraint #3\n";
+
+    let destination = Export::File(path.clone()).write(markdown)?;
+
+    assert_eq!(destination, path.display().to_string());
+    assert_eq!(fs::read_to_string(&path)?, markdown);
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
+use std::collections::{BTreeMap, VecDeque};
 use std::path::PathBuf;
 use std::{fs, io};
 
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 
-use crate::commands::{Command, CommandParser};
+use crate::commands::{Command, CommandParser, Instruction};
+use crate::plugin::Plugin;
+use crate::recent::Recent;
 
 use super::config::Config;
 
@@ -13,18 +16,123 @@ pub struct Input {
     bell: String,
     history: Option<PathBuf>,
     parser: CommandParser,
+    scripted: Option<VecDeque<String>>,
+    queued: VecDeque<String>,
+    last_line: Option<String>,
 }
 
 impl Input {
+    /// Read the provided lines instead of prompting the user, as a recorded
+    /// session is replayed. The history and the recent files of the user are
+    /// left untouched.
+    pub fn scripted<I>(config: &Config, lines: I) -> io::Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let parser =
+            CommandParser::default().with_aliases(config.aliases.clone());
+
+        let mut rl = Editor::<CommandParser>::with_config(config.rustyline())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        rl.set_helper(Some(parser.clone()));
+
+        Ok(Self {
+            rl,
+            bell: String::new(),
+            history: None,
+            parser,
+            scripted: Some(lines.into_iter().collect()),
+            queued: VecDeque::new(),
+            last_line: None,
+        })
+    }
+
     pub fn help(&self) -> String {
-        self.parser
-            .instructions()
-            .iter()
-            .fold(String::new(), |mut s, i| {
+        let help = self.parser.instructions().iter().fold(
+            String::new(),
+            |mut s, i| {
                 s.push_str(&format!("{} - {}\n", i.syntax(), i.help()));
                 s
+            },
+        );
+
+        let help = self.parser.plugins().iter().fold(help, |mut s, p| {
+            s.push_str(&format!("{} - {}\n", p.syntax(), p.help()));
+            s
+        });
+
+        self.parser.aliases().iter().fold(help, |mut s, (name, e)| {
+            s.push_str(&format!("{} - alias of `{}`\n", name, e));
+            s
+        })
+    }
+
+    /// Aliases of the session, mapped to the command line they expand to
+    pub const fn aliases(&self) -> &BTreeMap<String, String> {
+        self.parser.aliases()
+    }
+
+    /// Define an alias for the session. An empty expansion removes it.
+    pub fn set_alias(&mut self, name: &str, expansion: &str) {
+        if let Some(helper) = self.rl.helper_mut() {
+            helper.set_alias(name, expansion);
+        }
+
+        self.parser.set_alias(name, expansion);
+    }
+
+    /// Long help of a command, or of a plugin
+    pub fn help_topic(&self, topic: &str) -> io::Result<String> {
+        if let Some(plugin) = self.parser.plugin(topic) {
+            return Ok(format!("{}\n\n{}\n", plugin.syntax(), plugin.help()));
+        }
+
+        self.parser
+            .instruction(topic)
+            .map(Instruction::long_help)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no such command: {}", topic),
+                )
             })
     }
+
+    /// Line of the latest parsed command, with its alias expanded
+    pub fn last_line(&self) -> Option<&str> {
+        self.last_line.as_deref()
+    }
+
+    /// Read the provided lines before the next ones, as a recovered session
+    /// is restored
+    pub fn queue<I>(&mut self, lines: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.queued.extend(lines);
+    }
+
+    /// Parse a command line, as typed on the prompt
+    pub fn parse(&self, line: &str) -> io::Result<Option<Command>> {
+        self.parser.parse(line)
+    }
+
+    pub const fn recent(&self) -> &Recent {
+        self.parser.recent()
+    }
+
+    pub fn plugin(&self, name: &str) -> Option<Plugin> {
+        self.parser.plugin(name).cloned()
+    }
+
+    pub fn register_plugin(&mut self, plugin: Plugin) {
+        if let Some(helper) = self.rl.helper_mut() {
+            helper.register_plugin(plugin.clone());
+        }
+
+        self.parser.register_plugin(plugin);
+    }
 }
 
 impl Iterator for Input {
@@ -32,11 +140,23 @@ impl Iterator for Input {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            match self.rl.readline(&self.bell) {
+            let line = match (self.queued.pop_front(), &mut self.scripted) {
+                (Some(line), _) => Ok(line),
+                (None, Some(lines)) => Ok(lines.pop_front()?),
+                (None, None) => self.rl.readline(&self.bell),
+            };
+
+            match line {
                 Ok(line) => match self.parser.parse(&line) {
                     Ok(Some(Command::Quit)) => return None,
 
-                    Ok(Some(c)) => return Some(c),
+                    Ok(Some(c)) => {
+                        let line = self.parser.expand(&line);
+
+                        self.last_line.replace(line.trim().to_string());
+
+                        return Some(c);
+                    }
 
                     Ok(None) => (),
 
@@ -63,7 +183,9 @@ impl TryFrom<&Config> for Input {
     fn try_from(config: &Config) -> io::Result<Self> {
         let line = config.rustyline();
         let bell = format!("{} ", '\u{03c0}');
-        let parser = CommandParser::default();
+        let parser = CommandParser::default()
+            .with_recent(Recent::load())
+            .with_aliases(config.aliases.clone());
 
         let mut rl = Editor::<CommandParser>::with_config(line)
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
@@ -101,6 +223,9 @@ impl TryFrom<&Config> for Input {
             bell,
             history,
             parser,
+            scripted: None,
+            queued: VecDeque::new(),
+            last_line: None,
         })
     }
 }
@@ -135,3 +260,16 @@ fn help_works() {
 
     input.help();
 }
+
+#[test]
+fn help_topic_works() {
+    use toml_base_config::BaseConfig;
+
+    let config = Config::load().expect("failed to load config");
+    let input = Input::try_from(&config).expect("failed to load input");
+
+    let help = input.help_topic("tb").expect("failed to fetch the help");
+    assert!(help.starts_with("tbreak "));
+
+    assert!(input.help_topic("xyz").is_err());
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::io::{self, Write};
+
+/// Command executed in the session, along with the state it reached
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// Command line, with its alias expanded
+    pub command: String,
+    /// State the debugger stopped at, if the command moved it
+    pub state: Option<String>,
+    /// Source location of the state, as `NAME:LINE:COLUMN`
+    pub location: Option<String>,
+}
+
+/// Sequence of the commands executed in the session, numbered from 1
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Journal {
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    /// Record an executed command
+    pub fn push<C>(&mut self, command: C)
+    where
+        C: Into<String>,
+    {
+        self.entries.push(Entry {
+            command: command.into(),
+            ..Entry::default()
+        });
+    }
+
+    /// Record the state reached by the latest command, replacing any
+    /// previous one as a scan stops at its last state
+    pub fn record_state(
+        &mut self,
+        state: Option<String>,
+        location: Option<String>,
+    ) {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.state = state;
+            entry.location = location;
+        }
+    }
+
+    /// Entry by its number, the first being `1`
+    pub fn get(&self, number: usize) -> Option<&Entry> {
+        number.checked_sub(1).and_then(|i| self.entries.get(i))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Journal of the entries recorded after the first `n` ones
+    pub fn skip(&self, n: usize) -> Self {
+        Self {
+            entries: self.entries.iter().skip(n).cloned().collect(),
+        }
+    }
+
+    /// Write the journal as a session of `tests/sessions`: every command is
+    /// prefixed by `> `, and followed by the source it stopped at
+    pub fn write_session<W>(&self, cdf: &str, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "# Session over {}", cdf)?;
+
+        for entry in &self.entries {
+            writeln!(writer, "> {}", entry.command)?;
+
+            // the rendered source is matched by its name and line
+            let location = entry
+                .location
+                .as_deref()
+                .and_then(|l| l.rsplit_once(':'))
+                .map(|(l, _)| l);
+
+            if let Some(location) = location {
+                writeln!(writer, "@ {}", location)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Journal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            write!(f, "{:>4}  {}", i + 1, entry.command)?;
+
+            if let Some(state) = &entry.state {
+                write!(f, "  -> {}", state)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn journal_is_written_as_a_session() -> io::Result<()> {
+    let mut journal = Journal::default();
+
+    journal.push("goto 16");
+    journal.record_state(
+        Some("constraint 16 at gadget.rs:28:5".into()),
+        Some("gadget.rs:28:5".into()),
+    );
+    journal.push("print");
+
+    assert_eq!(journal.get(1).map(|e| e.command.as_str()), Some("goto 16"));
+    assert!(journal.get(0).is_none());
+    assert!(journal.get(3).is_none());
+
+    let listing = journal.to_string();
+
+    assert!(
+        listing.contains("   1  goto 16  -> constraint 16 at gadget.rs:28:5\n")
+    );
+    assert!(listing.contains("   2  print\n"));
+
+    let mut session = vec![];
+    journal.write_session("test.cdf", &mut session)?;
+
+    assert_eq!(
+        String::from_utf8(session).expect("invalid utf-8"),
+        "# Session over test.cdf\n> goto 16\n@ gadget.rs:28\n> print\n"
+    );
+
+    let mut session = vec![];
+    journal.skip(1).write_session("test.cdf", &mut session)?;
+
+    assert_eq!(
+        String::from_utf8(session).expect("invalid utf-8"),
+        "# Session over test.cdf\n> print\n"
+    );
+
+    Ok(())
+}
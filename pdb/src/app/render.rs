@@ -0,0 +1,331 @@
+use std::io::{self, Write};
+use std::{env, fmt, str::FromStr};
+
+use crossterm::tty::IsTty;
+use crossterm::{cursor, queue, terminal};
+use serde::{Deserialize, Serialize};
+
+use super::Source;
+
+/// Configured choice of the renderer
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    /// Pick the renderer from the capabilities of the terminal
+    #[default]
+    Auto,
+    /// Always take over the terminal
+    Terminal,
+    /// Always print plain text
+    Plain,
+}
+
+impl RenderMode {
+    /// Select the renderer for a terminal with the provided capabilities
+    pub const fn resolve(self, capabilities: Capabilities) -> Renderer {
+        match self {
+            Self::Terminal => Renderer::Terminal,
+            Self::Plain => Renderer::Plain,
+            Self::Auto if capabilities.is_interactive() => Renderer::Terminal,
+            Self::Auto => Renderer::Plain,
+        }
+    }
+}
+
+impl FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "terminal" => Ok(Self::Terminal),
+            "plain" => Ok(Self::Plain),
+            _ => Err(format!(
+                "invalid render mode `{}`; expected auto, terminal or plain",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RenderMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => f.write_str("auto"),
+            Self::Terminal => f.write_str("terminal"),
+            Self::Plain => f.write_str("plain"),
+        }
+    }
+}
+
+/// Capabilities of the terminal the outputs are rendered to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// The standard output is a terminal
+    pub tty: bool,
+    /// The terminal interprets the ANSI escape sequences. Windows consoles
+    /// without virtual terminal processing don't, and print them verbatim.
+    pub ansi: bool,
+    /// The terminal declares itself as `dumb`, unable to move the cursor
+    pub dumb: bool,
+}
+
+impl Capabilities {
+    /// Probe the capabilities of the standard output
+    pub fn detect() -> Self {
+        let tty = io::stdout().is_tty();
+        let dumb = env::var("TERM").map(|t| t == "dumb").unwrap_or(false);
+
+        #[cfg(windows)]
+        let ansi = crossterm::ansi_support::supports_ansi();
+        #[cfg(not(windows))]
+        let ansi = true;
+
+        Self { tty, ansi, dumb }
+    }
+
+    /// Check if the terminal supports the alternate screen, the clears and
+    /// the colors of the terminal renderer
+    pub const fn is_interactive(&self) -> bool {
+        self.tty && self.ansi && !self.dumb
+    }
+}
+
+/// Backend of the console outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Renderer {
+    /// Alternate screen, cleared before every source listing highlighted by
+    /// `bat`
+    Terminal,
+    /// Plain text appended to the output, without escape sequences
+    Plain,
+}
+
+impl Renderer {
+    /// Check if the renderer prints plain text
+    pub const fn is_plain(&self) -> bool {
+        matches!(self, Self::Plain)
+    }
+
+    /// Take over the terminal
+    pub fn enter<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        match self {
+            Self::Terminal => {
+                queue!(
+                    w,
+                    terminal::EnterAlternateScreen,
+                    cursor::MoveTo(0, 0)
+                )?;
+                w.flush()
+            }
+            Self::Plain => Ok(()),
+        }
+    }
+
+    /// Restore the terminal
+    pub fn leave<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        match self {
+            Self::Terminal => {
+                queue!(w, terminal::LeaveAlternateScreen)?;
+                w.flush()
+            }
+            Self::Plain => Ok(()),
+        }
+    }
+
+    /// Prepare the terminal for a new source listing
+    pub fn clear<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        match self {
+            Self::Terminal => {
+                queue!(
+                    w,
+                    terminal::Clear(terminal::ClearType::All),
+                    cursor::MoveTo(1, 1)
+                )?;
+                w.flush()
+            }
+            Self::Plain => Ok(()),
+        }
+    }
+
+    /// Draw the progress bar of a running scan over the current line. The
+    /// plain text outputs aren't overwritten, so they get no bar.
+    pub fn progress<W>(
+        &self,
+        w: &mut W,
+        title: &str,
+        percentage: u64,
+        message: &str,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        match self {
+            Self::Terminal => {
+                let filled = percentage.min(100) as usize / 5;
+
+                queue!(
+                    w,
+                    cursor::MoveToColumn(0),
+                    terminal::Clear(terminal::ClearType::CurrentLine)
+                )?;
+                write!(
+                    w,
+                    "{} [{}{}] {}",
+                    title,
+                    "#".repeat(filled),
+                    "-".repeat(20 - filled),
+                    message
+                )?;
+                w.flush()
+            }
+            Self::Plain => Ok(()),
+        }
+    }
+
+    /// Erase the progress bar of a finished scan
+    pub fn end_progress<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        match self {
+            Self::Terminal => {
+                queue!(
+                    w,
+                    cursor::MoveToColumn(0),
+                    terminal::Clear(terminal::ClearType::CurrentLine)
+                )?;
+                w.flush()
+            }
+            Self::Plain => Ok(()),
+        }
+    }
+}
+
+/// Write the lines of a source within `margin` lines of the highlighted one,
+/// marking it with `>`.
+pub fn plain_source<W>(
+    w: &mut W,
+    source: &Source,
+    margin: usize,
+    line_numbers: bool,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let Source {
+        name,
+        contents,
+        line,
+    } = source;
+
+    let start = line.saturating_sub(margin).max(1);
+    let end = line.saturating_add(margin);
+    let width = end.to_string().len();
+
+    writeln!(w, "{}", name)?;
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .skip(start - 1)
+        .take_while(|(n, _)| *n <= end)
+        .try_for_each(|(n, l)| {
+            let marker = if n == *line { '>' } else { ' ' };
+
+            match line_numbers {
+                true => writeln!(w, "{} {:>width$} | {}", marker, n, l),
+                false => writeln!(w, "{} {}", marker, l),
+            }
+        })
+}
+
+#[test]
+fn auto_falls_back_to_plain() {
+    let interactive = Capabilities {
+        tty: true,
+        ansi: true,
+        dumb: false,
+    };
+
+    // windows console without virtual terminal processing
+    let no_ansi = Capabilities {
+        ansi: false,
+        ..interactive
+    };
+
+    let redirected = Capabilities {
+        tty: false,
+        ..interactive
+    };
+
+    let dumb = Capabilities {
+        dumb: true,
+        ..interactive
+    };
+
+    assert_eq!(RenderMode::Auto.resolve(interactive), Renderer::Terminal);
+    assert_eq!(RenderMode::Auto.resolve(no_ansi), Renderer::Plain);
+    assert_eq!(RenderMode::Auto.resolve(redirected), Renderer::Plain);
+    assert_eq!(RenderMode::Auto.resolve(dumb), Renderer::Plain);
+
+    assert_eq!(RenderMode::Terminal.resolve(no_ansi), Renderer::Terminal);
+    assert_eq!(RenderMode::Plain.resolve(interactive), Renderer::Plain);
+}
+
+#[test]
+fn plain_renderer_writes_no_escape_sequences() -> io::Result<()> {
+    let source = Source {
+        name: "gadget.rs".into(),
+        contents: (1..=20).map(|i| format!("line {}\n", i)).collect(),
+        line: 10,
+    };
+
+    let mut out = vec![];
+
+    Renderer::Plain.enter(&mut out)?;
+    Renderer::Plain.clear(&mut out)?;
+    Renderer::Plain.progress(&mut out, "Continuing", 25, "1/4")?;
+    Renderer::Plain.end_progress(&mut out)?;
+    plain_source(&mut out, &source, 2, true)?;
+    Renderer::Plain.leave(&mut out)?;
+
+    let out = String::from_utf8(out).expect("the output is utf-8");
+    let lines: Vec<_> = out.lines().collect();
+
+    assert!(!out.contains('\x1b'));
+    assert_eq!(
+        lines,
+        vec![
+            "gadget.rs",
+            "   8 | line 8",
+            "   9 | line 9",
+            "> 10 | line 10",
+            "  11 | line 11",
+            "  12 | line 12",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mode_round_trips() {
+    for mode in [RenderMode::Auto, RenderMode::Terminal, RenderMode::Plain] {
+        assert_eq!(mode.to_string().parse::<RenderMode>(), Ok(mode));
+    }
+
+    assert!("fancy".parse::<RenderMode>().is_err());
+}
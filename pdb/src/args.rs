@@ -1,41 +1,154 @@
-use std::path::PathBuf;
-use std::{io, net};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io, net};
 
 use clap::Parser;
+use dusk_cdf::ColorChoice;
+
+/// Environment variable with the output path of the circuit examples
+pub const CDF_OUTPUT: &str = "CDF_OUTPUT";
 
 /// PLONK debugger CLI
 #[derive(Parser, Debug, Default)]
 #[clap(author, version, about)]
 pub struct Args {
-    /// CDF file path
+    /// CDF file path. If absent, `CDF_OUTPUT` and then the newest
+    /// `target/*.cdf` are proposed
     #[clap(value_parser)]
     path: Option<PathBuf>,
 
     /// DAP backend to attach
     #[clap(long)]
     attach: Option<net::SocketAddr>,
+
+    /// DAP adapter command to spawn, speaking the protocol over its stdio
+    #[clap(long, conflicts_with = "attach")]
+    dap_command: Option<String>,
+
+    /// When to color the output: never, auto or always. Overrides the
+    /// configured one
+    #[clap(long)]
+    color: Option<ColorChoice>,
+
+    /// CDF file to navigate in lockstep, comparing its constraints with the
+    /// debugged ones
+    #[clap(long, value_parser)]
+    compare: Option<PathBuf>,
 }
 
 impl Args {
-    /// Resolve a command
+    /// Resolve a command, without proposing any CDF file if the path is
+    /// absent. See [`ParsedArgs::discover`].
     pub fn resolve(self) -> io::Result<ParsedArgs> {
-        let Args { path, attach } = self;
+        let Args {
+            path,
+            attach,
+            dap_command,
+            color,
+            compare,
+        } = self;
 
-        let path = match path {
-            Some(p) => Some(p.canonicalize()?),
-            None => None,
-        };
+        let path = path.map(|p| p.canonicalize()).transpose()?;
+        let compare = compare.map(|p| p.canonicalize()).transpose()?;
 
-        Ok(ParsedArgs { path, attach })
+        Ok(ParsedArgs {
+            path,
+            attach,
+            dap_command,
+            color,
+            compare,
+        })
     }
 }
 
+/// Propose the discovered CDF files until one is confirmed.
+///
+/// Nothing is proposed if the session isn't interactive.
+fn propose() -> io::Result<Option<PathBuf>> {
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() {
+        return Ok(None);
+    }
+
+    let output = env::var_os(CDF_OUTPUT).map(PathBuf::from);
+    let dir = env::current_dir()?;
+
+    for candidate in candidates(output, &dir) {
+        print!("open {}? [Y/n] ", candidate.display());
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+
+        if stdin.lock().read_line(&mut answer)? == 0 {
+            break;
+        }
+
+        match answer.trim() {
+            "" | "y" | "Y" | "yes" => {
+                return candidate.canonicalize().map(Some)
+            }
+            _ => (),
+        }
+    }
+
+    Ok(None)
+}
+
+/// CDF files to propose when no path is provided: the `output` file, then the
+/// `target/*.cdf` files of `dir`, newest first
+fn candidates(output: Option<PathBuf>, dir: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<_> = fs::read_dir(dir.join("target"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "cdf"))
+        .filter_map(|path| {
+            let modified = path.metadata().and_then(|m| m.modified()).ok()?;
+
+            Some((modified, path))
+        })
+        .collect();
+
+    found.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut candidates: Vec<_> =
+        output.filter(|p| p.is_file()).into_iter().collect();
+
+    for (_, path) in found {
+        if !candidates.contains(&path) {
+            candidates.push(path);
+        }
+    }
+
+    candidates
+}
+
 /// Parsed arguments for the CLI
 pub struct ParsedArgs {
     /// Path to the CDF file
     pub path: Option<PathBuf>,
-    /// Socket to attach. Will bind to localhost if absent
+    /// Socket to attach. Will run the backend in-process if absent
     pub attach: Option<net::SocketAddr>,
+    /// Command of a DAP adapter to spawn as a child process
+    pub dap_command: Option<String>,
+    /// Color choice, replacing the configured one
+    pub color: Option<ColorChoice>,
+    /// CDF file navigated in lockstep with the debugged one
+    pub compare: Option<PathBuf>,
+}
+
+impl ParsedArgs {
+    /// Propose the CDF files found in `CDF_OUTPUT` and `target/*.cdf` if no
+    /// path was provided, reading the confirmation from the standard input.
+    pub fn discover(mut self) -> io::Result<Self> {
+        if self.path.is_none() {
+            self.path = propose()?;
+        }
+
+        Ok(self)
+    }
 }
 
 #[test]
@@ -44,3 +157,35 @@ fn parse_default_args_wont_panic() {
         .resolve()
         .expect("failed to parse default args");
 }
+
+#[test]
+fn candidates_propose_the_output_then_the_newest() -> io::Result<()> {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempdir::TempDir::new("pdb-candidates")?;
+    let target = dir.path().join("target");
+
+    fs::create_dir(&target)?;
+
+    let output = dir.path().join("output.cdf");
+    let old = target.join("old.cdf");
+    let new = target.join("new.cdf");
+    let other = target.join("notes.txt");
+
+    let now = SystemTime::now();
+
+    for (path, age) in [(&output, 0), (&old, 60), (&new, 30), (&other, 0)] {
+        fs::File::create(path)?.set_modified(now - Duration::from_secs(age))?;
+    }
+
+    assert_eq!(
+        candidates(Some(output.clone()), dir.path()),
+        vec![output, new.clone(), old.clone()]
+    );
+
+    let missing = dir.path().join("missing.cdf");
+
+    assert_eq!(candidates(Some(missing), dir.path()), vec![new, old]);
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 mod command;
 mod instruction;
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::io;
+use std::sync::Arc;
 
 use rustyline::completion::FilenameCompleter;
 use rustyline::hint::Hinter;
@@ -11,6 +14,9 @@ use rustyline_derive::{Completer, Helper, Highlighter, Validator};
 pub use command::Command;
 pub use instruction::Instruction;
 
+use crate::plugin::{DebuggerPlugin, Plugin};
+use crate::recent::Recent;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ParsedLine {
     Resolved {
@@ -20,6 +26,10 @@ pub enum ParsedLine {
         instruction: Instruction,
         completion: String,
     },
+    CompletablePlugin {
+        name: String,
+        completion: String,
+    },
     Invalid,
     Empty,
 }
@@ -28,6 +38,9 @@ pub enum ParsedLine {
 #[derive(Completer, Helper, Validator, Highlighter)]
 pub struct CommandParser {
     instructions: Vec<Instruction>,
+    plugins: Vec<Plugin>,
+    aliases: BTreeMap<String, String>,
+    recent: Recent,
     filename_completer: FilenameCompleter,
 }
 
@@ -36,12 +49,118 @@ impl CommandParser {
     pub fn instructions(&self) -> &[Instruction] {
         &self.instructions
     }
+
+    /// Fetch the instruction invoked by a token, which can be a prefix of
+    /// the instruction token
+    pub fn instruction(&self, token: &str) -> Option<&Instruction> {
+        self.instructions
+            .iter()
+            .find(|i| i.complete_unary(token).is_some())
+    }
+
+    /// Return all registered plugins
+    pub fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+
+    /// Fetch a registered plugin by its name
+    pub fn plugin(&self, name: &str) -> Option<&Plugin> {
+        self.plugins.iter().find(|p| p.name() == name)
+    }
+
+    /// Return the aliases, mapped to the command line they expand to
+    pub const fn aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
+    }
+
+    /// Define an alias, replacing any previous one with the same name. An
+    /// empty expansion removes the alias.
+    pub fn set_alias<N, E>(&mut self, name: N, expansion: E)
+    where
+        N: Into<String>,
+        E: Into<String>,
+    {
+        let name = name.into();
+        let expansion = expansion.into();
+
+        if expansion.trim().is_empty() {
+            self.aliases.remove(&name);
+        } else {
+            self.aliases.insert(name, expansion);
+        }
+    }
+
+    /// Replace the aliases, returning the updated parser
+    pub fn with_aliases(mut self, aliases: BTreeMap<String, String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Expand the alias invoked by the first token of a line, appending the
+    /// remaining arguments to its expansion.
+    ///
+    /// An alias is matched by its exact name, and the expansion isn't
+    /// expanded again.
+    pub fn expand<'a>(&self, line: &'a str) -> Cow<'a, str> {
+        let trimmed = line.trim_start();
+        let (token, rest) = trimmed
+            .split_once(char::is_whitespace)
+            .unwrap_or((trimmed, ""));
+
+        match self.aliases.get(token) {
+            Some(expansion) if rest.is_empty() && !line.ends_with(' ') => {
+                Cow::Owned(expansion.clone())
+            }
+            Some(expansion) => Cow::Owned(format!("{} {}", expansion, rest)),
+            None => Cow::Borrowed(line),
+        }
+    }
+
+    /// Recently opened files, completed for `open`
+    pub const fn recent(&self) -> &Recent {
+        &self.recent
+    }
+
+    /// Replace the recently opened files, returning the updated parser
+    pub fn with_recent(mut self, recent: Recent) -> Self {
+        self.recent = recent;
+        self
+    }
+
+    /// Register a plugin, replacing any previous one with the same name
+    pub fn register_plugin(&mut self, plugin: Plugin) {
+        self.plugins.retain(|p| p.name() != plugin.name());
+        self.plugins.push(plugin);
+    }
+
+    /// Register a plugin, returning the updated parser
+    pub fn with_plugin<P>(mut self, plugin: P) -> Self
+    where
+        P: DebuggerPlugin + 'static,
+    {
+        self.register_plugin(Arc::new(plugin));
+        self
+    }
+
+    fn find_plugin_prefix(&self, token: &str) -> Option<&Plugin> {
+        self.plugins.iter().find(|p| p.name().starts_with(token))
+    }
+
+    fn plugin_command(plugin: &Plugin, tokens: &[String]) -> Command {
+        Command::Plugin {
+            name: plugin.name().into(),
+            args: tokens[1..].to_vec(),
+        }
+    }
 }
 
 impl Clone for CommandParser {
     fn clone(&self) -> Self {
         Self {
             instructions: self.instructions.clone(),
+            plugins: self.plugins.clone(),
+            aliases: self.aliases.clone(),
+            recent: self.recent.clone(),
             filename_completer: FilenameCompleter::new(),
         }
     }
@@ -57,7 +176,10 @@ impl Hinter for CommandParser {
         _ctx: &Context<'_>,
     ) -> Option<Self::Hint> {
         self.parse_completable(line).ok().and_then(|c| match c {
-            ParsedLine::Completable { completion, .. } => Some(completion),
+            ParsedLine::Completable { completion, .. }
+            | ParsedLine::CompletablePlugin { completion, .. } => {
+                Some(completion)
+            }
             _ => None,
         })
     }
@@ -66,21 +188,10 @@ impl Hinter for CommandParser {
 impl Default for CommandParser {
     fn default() -> Self {
         Self {
-            instructions: vec![
-                Instruction::Afore,
-                Instruction::Breakpoint,
-                Instruction::Continue,
-                Instruction::Delete,
-                Instruction::Goto,
-                Instruction::Help,
-                Instruction::Next,
-                Instruction::Open,
-                Instruction::Print,
-                Instruction::Restart,
-                Instruction::Turn,
-                Instruction::Quit,
-                Instruction::Witness,
-            ],
+            instructions: Instruction::ALL.to_vec(),
+            plugins: vec![],
+            aliases: BTreeMap::new(),
+            recent: Recent::default(),
             filename_completer: FilenameCompleter::new(),
         }
     }
@@ -94,6 +205,8 @@ impl CommandParser {
 
     /// Attempt to parse a command, providing completion information
     pub fn parse_completable(&self, line: &str) -> io::Result<ParsedLine> {
+        let line = self.expand(line);
+        let line = line.as_ref();
         let ends_with_space = line.ends_with(' ');
         let tokens = Self::split(line)?;
 
@@ -125,13 +238,23 @@ impl CommandParser {
                 }
                 None => (),
             }
+
+            if let Some(plugin) = self.find_plugin_prefix(&tokens[0]) {
+                return Ok(ParsedLine::CompletablePlugin {
+                    name: plugin.name().into(),
+                    completion: plugin.name()[tokens[0].len()..].to_string(),
+                });
+            }
         }
 
-        let instruction = match self
-            .instructions
-            .iter()
-            .find(|i| i.complete_unary(&tokens[0]).is_some())
-        {
+        if let Some(plugin) = self.plugin(&tokens[0]) {
+            return Ok(ParsedLine::CompletablePlugin {
+                name: plugin.name().into(),
+                completion: String::new(),
+            });
+        }
+
+        let instruction = match self.instruction(&tokens[0]) {
             Some(i) => i,
             None => return Ok(ParsedLine::Invalid),
         };
@@ -149,63 +272,114 @@ impl CommandParser {
 
     /// Attempt to parse a command
     pub fn parse(&self, line: &str) -> io::Result<Option<Command>> {
-        let tokens = Self::split(line)?;
+        let tokens = Self::split(&self.expand(line))?;
 
         if tokens.is_empty() {
             return Ok(None);
         }
 
-        let instruction = match self
-            .instructions
-            .iter()
-            .find(|i| i.complete_unary(&tokens[0]).is_some())
-        {
-            Some(i) => i,
-            None => {
-                return Err(io::Error::new(
+        // `!N` runs a command of the history again
+        if let Some(index) = tokens[0].strip_prefix('!') {
+            return match (index.parse(), tokens.len()) {
+                (Ok(index), 1) => Ok(Some(Command::Rerun { index })),
+                _ => Err(io::Error::new(
                     io::ErrorKind::InvalidInput,
-                    "the provided instruction is invalid",
-                ))
-            }
+                    "expected the number of a command of the history, as `!N`",
+                )),
+            };
+        }
+
+        if let Some(plugin) = self.plugin(&tokens[0]) {
+            return Ok(Some(Self::plugin_command(plugin, &tokens)));
+        }
+
+        let instruction = match self.instruction(&tokens[0]) {
+            Some(i) => i,
+            None => match self.find_plugin_prefix(&tokens[0]) {
+                Some(plugin) => {
+                    return Ok(Some(Self::plugin_command(plugin, &tokens)))
+                }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "the provided instruction is invalid",
+                    ))
+                }
+            },
         };
 
         if let Some(command) = instruction.resolve_unary() {
-            return Ok(Some(command));
+            if tokens.len() == 1 || !instruction.has_optional_argument() {
+                return Ok(Some(command));
+            }
         }
 
-        if tokens.len() != 2 {
+        if !instruction.accepts_arguments(tokens.len() - 1) {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
                 format!(
-                    "single argument expected. syntax: {}",
+                    "unexpected number of arguments. syntax: {}",
                     instruction.syntax()
                 ),
             ));
         }
 
-        Command::try_from_binary(instruction, &tokens[1]).map(Some)
+        Command::try_from_binary(instruction, &tokens[1..].join(" ")).map(Some)
     }
 }
 
 #[test]
 fn validate_return_all_instructions() {
-    let flag = 0b1111111111111;
+    let flag: u64 = 0b1111111111111111111111111111111111;
     let result = CommandParser::default().instructions().iter().fold(
         0,
         |bit, instruction| match instruction {
-            Instruction::Afore => bit | 0b1000000000000,
-            Instruction::Breakpoint => bit | 0b0100000000000,
-            Instruction::Continue => bit | 0b0010000000000,
-            Instruction::Delete => bit | 0b0001000000000,
-            Instruction::Goto => bit | 0b0000100000000,
-            Instruction::Help => bit | 0b0000010000000,
-            Instruction::Next => bit | 0b0000001000000,
-            Instruction::Open => bit | 0b0000000100000,
-            Instruction::Print => bit | 0b0000000010000,
-            Instruction::Restart => bit | 0b0000000001000,
-            Instruction::Turn => bit | 0b0000000000100,
-            Instruction::Quit => bit | 0b0000000000010,
-            Instruction::Witness => bit | 0b0000000000001,
+            Instruction::Afore => bit | 0b1000000000000000000000000000000000,
+            Instruction::Breakpoint => {
+                bit | 0b0100000000000000000000000000000000
+            }
+            Instruction::Continue => bit | 0b0010000000000000000000000000000000,
+            Instruction::Delete => bit | 0b0001000000000000000000000000000000,
+            Instruction::Goto => bit | 0b0000100000000000000000000000000000,
+            Instruction::Help => bit | 0b0000010000000000000000000000000000,
+            Instruction::Next => bit | 0b0000001000000000000000000000000000,
+            Instruction::Open => bit | 0b0000000100000000000000000000000000,
+            Instruction::Print => bit | 0b0000000010000000000000000000000000,
+            Instruction::Restart => bit | 0b0000000001000000000000000000000000,
+            Instruction::Turn => bit | 0b0000000000100000000000000000000000,
+            Instruction::Quit => bit | 0b0000000000010000000000000000000000,
+            Instruction::Witness => bit | 0b0000000000001000000000000000000000,
+            Instruction::Script => bit | 0b0000000000000100000000000000000000,
+            Instruction::WireCheck => {
+                bit | 0b0000000000000010000000000000000000
+            }
+            Instruction::Timeline => bit | 0b0000000000000001000000000000000000,
+            Instruction::ScalarCheck => {
+                bit | 0b0000000000000000100000000000000000
+            }
+            Instruction::Top => bit | 0b0000000000000000010000000000000000,
+            Instruction::Reopen => bit | 0b0000000000000000001000000000000000,
+            Instruction::TemporaryBreakpoint => {
+                bit | 0b0000000000000000000100000000000000
+            }
+            Instruction::Ignore => bit | 0b0000000000000000000010000000000000,
+            Instruction::Focus => bit | 0b0000000000000000000001000000000000,
+            Instruction::Locals => bit | 0b0000000000000000000000100000000000,
+            Instruction::Frontier => bit | 0b0000000000000000000000010000000000,
+            Instruction::Alias => bit | 0b0000000000000000000000001000000000,
+            Instruction::Memory => bit | 0b0000000000000000000000000100000000,
+            Instruction::Copy => bit | 0b0000000000000000000000000010000000,
+            Instruction::ExportView => {
+                bit | 0b0000000000000000000000000001000000
+            }
+            Instruction::ConstCheck => {
+                bit | 0b0000000000000000000000000000100000
+            }
+            Instruction::History => bit | 0b0000000000000000000000000000010000,
+            Instruction::Shared => bit | 0b0000000000000000000000000000001000,
+            Instruction::Publics => bit | 0b0000000000000000000000000000000100,
+            Instruction::Raw => bit | 0b0000000000000000000000000000000010,
+            Instruction::Latex => bit | 0b0000000000000000000000000000000001,
         },
     );
     assert_eq!(flag, result);
@@ -218,6 +392,34 @@ fn validate_return_all_instructions() {
 
     assert_eq!(a.len(), b.len());
 }
+#[test]
+fn aliases_expand_before_parsing() {
+    let mut parser = CommandParser::default();
+
+    parser.set_alias("n", "next");
+    parser.set_alias("b", "breakpoint gadget.rs");
+    parser.set_alias("w", "witness");
+
+    assert_eq!(parser.expand("n"), "next");
+    assert_eq!(parser.expand("w 3"), "witness 3");
+    assert_eq!(parser.expand("ne"), "ne");
+
+    assert_eq!(parser.parse("n").unwrap(), Some(Command::Next));
+    assert_eq!(
+        parser.parse("w 3").unwrap(),
+        Some(Command::Witness { id: 3 })
+    );
+    assert!(matches!(
+        parser.parse("b").unwrap(),
+        Some(Command::Breakpoint { line: None, .. })
+    ));
+
+    parser.set_alias("n", "");
+
+    assert!(parser.aliases().get("n").is_none());
+    assert_eq!(parser.expand("n"), "n");
+}
+
 #[test]
 fn validate_parse_completable() {
     let cases_instructions = vec![
@@ -264,8 +466,69 @@ fn validate_parse_completable() {
 #[test]
 fn validate_parse() {
     let parser = CommandParser::default();
-    let cases_error = vec!["aaa", "open "];
-    let cases_ok = vec!["", "quit", "open Cargo.toml"];
+    let cases_error = vec![
+        "aaa",
+        "open ",
+        "reopen x",
+        "ignore 1",
+        "ignore 1 x",
+        "tbreak",
+        "break gadget.rs --kind",
+        "break gadget.rs --kind foo",
+        "break gadget.rs --type range",
+        "break --kind range",
+        "tbreak lib.rs:25 lib.rs:26",
+        "focus gadget.rs --all",
+        "focus 10..20 --failures x",
+        "continue 10",
+        "continue --max",
+        "turn --max x",
+        "export-view",
+        "!x",
+        "!1 2",
+        "publics --all",
+        "raw x",
+        "raw 1 2",
+    ];
+    let cases_ok = vec![
+        "",
+        "quit",
+        "open Cargo.toml",
+        "reopen",
+        "reopen 2",
+        "tbreak gadget.rs:12",
+        "ignore 1 10",
+        "break gadget.rs --kind range",
+        "break lib.rs:25 lib.rs:26 gadget.rs",
+        "delete all",
+        "delete lib.rs",
+        "focus",
+        "focus gadget.rs",
+        "focus 10..20 --failures",
+        "locals",
+        "frontier",
+        "memory",
+        "copy",
+        "export-view constraint.md",
+        "constcheck",
+        "shared",
+        "publics",
+        "publics --compare",
+        "raw",
+        "raw 42",
+        "history",
+        "history debug.session",
+        "!3",
+        "help",
+        "help breakpoint",
+        "alias",
+        "alias n",
+        "alias n = next",
+        "alias n =",
+        "continue",
+        "continue --max 100",
+        "turn --max 10",
+    ];
 
     for cases in cases_error.into_iter() {
         let result_err = parser.parse(cases);
@@ -277,3 +540,62 @@ fn validate_parse() {
         assert!(result_ok.is_ok());
     }
 }
+
+#[test]
+fn validate_parse_plugin() {
+    use dusk_cdf::DebuggerBackend;
+
+    struct Echo;
+
+    impl DebuggerPlugin for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn syntax(&self) -> &str {
+            "echo [ARGS]"
+        }
+
+        fn execute(
+            &self,
+            _backend: &mut dyn DebuggerBackend,
+            args: &[String],
+        ) -> io::Result<String> {
+            Ok(args.join(" "))
+        }
+    }
+
+    let parser = CommandParser::default().with_plugin(Echo);
+
+    assert_eq!(parser.plugins().len(), 1);
+    assert!(parser.plugin("echo").is_some());
+
+    let command = parser.parse("echo foo bar").unwrap();
+    let expected = Command::Plugin {
+        name: "echo".into(),
+        args: vec!["foo".into(), "bar".into()],
+    };
+
+    assert_eq!(command, Some(expected));
+
+    let command = parser.parse("ec").unwrap();
+    let expected = Command::Plugin {
+        name: "echo".into(),
+        args: vec![],
+    };
+
+    assert_eq!(command, Some(expected));
+
+    let completion = parser.parse_completable("ec").unwrap();
+    let expected = ParsedLine::CompletablePlugin {
+        name: "echo".into(),
+        completion: "ho".into(),
+    };
+
+    assert_eq!(completion, expected);
+
+    // registering a plugin with the same name replaces the previous one
+    let parser = parser.with_plugin(Echo);
+
+    assert_eq!(parser.plugins().len(), 1);
+}
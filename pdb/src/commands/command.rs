@@ -7,7 +7,7 @@ use dap_reactor::prelude::{
     ReverseContinueArguments, Source, StepBackArguments, VariablesArguments,
 };
 use dap_reactor::request::Request;
-use dusk_cdf::ZkRequest;
+use dusk_cdf::{Focus, GateKind, Notation, ZkRequest};
 
 use super::Instruction;
 
@@ -23,21 +23,54 @@ pub enum Command {
         /// Optional line. If empty, will stop whenever the source file is
         /// opened
         line: Option<u64>,
+        /// Optional gate kind. If empty, will stop at any gate
+        kind: Option<GateKind>,
+    },
+    /// Set a breakpoint at every location, in a single request
+    Breakpoints {
+        /// Source patterns, along with their optional lines
+        locations: Vec<(String, Option<u64>)>,
+        /// Optional gate kind of all the breakpoints
+        kind: Option<GateKind>,
     },
     /// Continue the execution of the program
     Continue,
+    /// Continue or reverse the execution, visiting at most `max` constraints
+    ContinueMax {
+        /// Maximum number of constraints to visit
+        max: usize,
+        /// Reverse the execution instead
+        reverse: bool,
+    },
     /// Delete a breakpoint
     Delete {
         /// Id of the breakpoint
         id: usize,
     },
+    /// Delete the breakpoints of a source, in a single request
+    DeleteBreakpoints {
+        /// Source pattern the breakpoints were set with. If empty, will
+        /// delete all of them
+        source: Option<String>,
+    },
     /// Jump to a constraint
     Goto {
         /// Id of the constraint
         id: usize,
     },
+    /// Jump to the first constraint of a source line
+    GotoLine {
+        /// Source pattern
+        source: String,
+        /// Line of the source
+        line: u64,
+    },
     /// Print the help menu
-    Help,
+    Help {
+        /// Command or plugin to print the long help of. If empty, will list
+        /// all of them
+        topic: Option<String>,
+    },
     /// Execute to next constraint
     Next,
     /// Open a CDF file
@@ -58,6 +91,108 @@ pub enum Command {
         /// Id of the witness
         id: usize,
     },
+    /// List the wires inconsistent with the witness table
+    WireCheck,
+    /// List the constraints referencing witnesses allocated after them
+    Timeline,
+    /// List the stored scalars that aren't canonical
+    ScalarCheck,
+    /// List the witnesses holding well-known constants that no constant gate
+    /// fixes
+    ConstCheck,
+    /// List the witnesses wired into the constraints of more than one source
+    SharedWires,
+    /// List the public inputs recorded by the constraints
+    Publics {
+        /// Compare them with the public input vector of the proof artifacts
+        compare: bool,
+    },
+    /// Print the byte offset and a hexdump of the encoded constraint
+    Raw {
+        /// Id of the constraint, or `None` for the current one
+        id: Option<usize>,
+    },
+    /// Print the sources and gadgets with the largest footprint
+    Top {
+        /// Maximum number of sources and gadgets
+        limit: usize,
+    },
+    /// Run an analysis script over the loaded circuit
+    Script {
+        /// Script path
+        path: String,
+    },
+    /// Open a recently opened CDF file
+    Reopen {
+        /// Position in the list of recent files, the latest being `0`. If
+        /// empty, will open the latest file other than the current one
+        index: Option<usize>,
+    },
+    /// Set a new breakpoint that is removed after its first hit
+    TemporaryBreakpoint {
+        /// Source pattern
+        source: String,
+        /// Optional line. If empty, will stop whenever the source file is
+        /// opened
+        line: Option<u64>,
+        /// Optional gate kind. If empty, will stop at any gate
+        kind: Option<GateKind>,
+    },
+    /// Ignore the next hits of a breakpoint
+    Ignore {
+        /// Id of the breakpoint
+        id: usize,
+        /// Number of hits to be ignored
+        count: usize,
+    },
+    /// Limit the navigation to a portion of the circuit
+    Focus {
+        /// Portion of the circuit. If empty, the limit is lifted
+        focus: Option<Focus>,
+        /// Report the failures out of the focus
+        failures: bool,
+    },
+    /// List the witnesses created by the current constraint
+    Locals,
+    /// Trace the wires of the current failed constraint to the earliest
+    /// failure they depend on
+    Frontier,
+    /// Print the memory held by the caches of the debugger
+    Memory,
+    /// Copy the current constraint to the clipboard as a markdown snippet
+    Copy,
+    /// Print the equation of the current constraint as LaTeX
+    Latex,
+    /// Write the current constraint to a file as a markdown snippet
+    ExportView {
+        /// File path
+        path: String,
+    },
+    /// List the commands of the session, or write them to a session file
+    History {
+        /// Session file path. If empty, will list the commands
+        path: Option<String>,
+    },
+    /// Execute a command of the session again
+    Rerun {
+        /// Number of the command in the history, the first being `1`
+        index: usize,
+    },
+    /// Define or list the aliases of the session
+    Alias {
+        /// Name of the alias. If empty, will list all of them
+        name: Option<String>,
+        /// Command line the alias expands to. If empty, will print the
+        /// alias
+        expansion: Option<String>,
+    },
+    /// Execute a registered plugin
+    Plugin {
+        /// Name of the plugin
+        name: String,
+        /// Arguments provided by the user
+        args: Vec<String>,
+    },
 }
 
 impl Command {
@@ -68,38 +203,178 @@ impl Command {
         arg: &str,
     ) -> io::Result<Self> {
         match instruction {
-            Instruction::Open => PathBuf::from(arg)
-                .canonicalize()
+            Instruction::Open => resolve_newest(arg)
                 .map(|path| path.display().to_string())
                 .map(|path| Self::Open { path }),
 
+            Instruction::Script => PathBuf::from(arg)
+                .canonicalize()
+                .map(|path| path.display().to_string())
+                .map(|path| Self::Script { path }),
+
             Instruction::Breakpoint => {
-                let mut args = arg.split(':');
+                let (mut locations, kind) = parse_breakpoints(arg)?;
 
-                let source = args
-                    .next()
-                    .unwrap_or("split always generate a first element")
-                    .into();
+                match locations.len() {
+                    1 => {
+                        let (source, line) = locations.remove(0);
 
-                let line = args.next().map(u64::from_str).transpose().map_err(
-                    |e| io::Error::new(io::ErrorKind::InvalidInput, e),
-                )?;
+                        Ok(Self::Breakpoint { source, line, kind })
+                    }
+                    _ => Ok(Self::Breakpoints { locations, kind }),
+                }
+            }
 
-                Ok(Self::Breakpoint { source, line })
+            Instruction::TemporaryBreakpoint => {
+                let (mut locations, kind) = parse_breakpoints(arg)?;
+
+                if locations.len() != 1 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("syntax: {}", instruction.syntax()),
+                    ));
+                }
+
+                let (source, line) = locations.remove(0);
+
+                Ok(Self::TemporaryBreakpoint { source, line, kind })
             }
 
-            Instruction::Delete => usize::from_str(arg)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-                .map(|id| Self::Delete { id }),
+            Instruction::Focus => {
+                let mut args = arg.split_whitespace();
+                let focus = args.next().map(parse_focus);
 
-            Instruction::Goto => usize::from_str(arg)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-                .map(|id| Self::Goto { id }),
+                let failures = match args.next() {
+                    None => false,
+                    Some("--failures") => true,
+                    Some(_) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("syntax: {}", instruction.syntax()),
+                        ))
+                    }
+                };
+
+                Ok(Self::Focus { focus, failures })
+            }
+
+            Instruction::Ignore => {
+                let (id, count) = arg.split_once(' ').ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("syntax: {}", instruction.syntax()),
+                    )
+                })?;
+
+                let id = usize::from_str(id).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidInput, e)
+                })?;
+
+                let count = usize::from_str(count.trim()).map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidInput, e)
+                })?;
+
+                Ok(Self::Ignore { id, count })
+            }
+
+            Instruction::Continue | Instruction::Turn => {
+                let max = arg
+                    .strip_prefix("--max ")
+                    .map(|max| usize::from_str(max.trim()))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("syntax: {}", instruction.syntax()),
+                        )
+                    })?
+                    .map_err(|e| {
+                        io::Error::new(io::ErrorKind::InvalidInput, e)
+                    })?;
+
+                Ok(Self::ContinueMax {
+                    max,
+                    reverse: matches!(instruction, Instruction::Turn),
+                })
+            }
+
+            Instruction::Delete => match (arg, usize::from_str(arg)) {
+                (_, Ok(id)) => Ok(Self::Delete { id }),
+                ("all", _) => Ok(Self::DeleteBreakpoints { source: None }),
+                (source, _) => Ok(Self::DeleteBreakpoints {
+                    source: Some(source.into()),
+                }),
+            },
+
+            Instruction::Goto => match arg.rsplit_once(':') {
+                Some((source, line)) => u64::from_str(line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                    .map(|line| Self::GotoLine {
+                        source: source.into(),
+                        line,
+                    }),
+
+                None => usize::from_str(arg)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                    .map(|id| Self::Goto { id }),
+            },
 
             Instruction::Witness => usize::from_str(arg)
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
                 .map(|id| Self::Witness { id }),
 
+            Instruction::ExportView => {
+                Ok(Self::ExportView { path: arg.into() })
+            }
+
+            Instruction::Publics => match arg.trim() {
+                "--compare" => Ok(Self::Publics { compare: true }),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("syntax: {}", instruction.syntax()),
+                )),
+            },
+
+            Instruction::Raw => usize::from_str(arg)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                .map(|id| Self::Raw { id: Some(id) }),
+
+            Instruction::Top => usize::from_str(arg)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                .map(|limit| Self::Top { limit }),
+
+            Instruction::Alias => {
+                let (name, expansion) = match arg.split_once('=') {
+                    Some((name, expansion)) => {
+                        (name.trim(), Some(expansion.trim().into()))
+                    }
+                    None => (arg.trim(), None),
+                };
+
+                if name.is_empty() || name.contains(char::is_whitespace) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("syntax: {}", instruction.syntax()),
+                    ));
+                }
+
+                Ok(Self::Alias {
+                    name: Some(name.into()),
+                    expansion,
+                })
+            }
+
+            Instruction::Help => Ok(Self::Help {
+                topic: Some(arg.trim().into()),
+            }),
+
+            Instruction::Reopen => usize::from_str(arg)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                .map(|index| Self::Reopen { index: Some(index) }),
+
+            Instruction::History => Ok(Self::History {
+                path: Some(arg.into()),
+            }),
+
             _ => Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
                 format!(
@@ -111,6 +386,124 @@ impl Command {
     }
 }
 
+/// Source patterns with their optional lines, and optional gate kind of a
+/// `NAME[:LINE]... [--kind KIND]` argument
+#[allow(clippy::type_complexity)]
+fn parse_breakpoints(
+    arg: &str,
+) -> io::Result<(Vec<(String, Option<u64>)>, Option<GateKind>)> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let option = || {
+        invalid(String::from(
+            "the only option of a breakpoint is --kind <KIND>",
+        ))
+    };
+
+    let mut args = arg.split_whitespace();
+    let mut locations = vec![];
+    let mut kind = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--kind" if kind.is_none() => {
+                let k = args.next().ok_or_else(option)?;
+
+                kind.replace(GateKind::from_str(k).map_err(invalid)?);
+            }
+
+            a if a.starts_with("--") => return Err(option()),
+
+            // the kind closes the arguments
+            _ if kind.is_some() => return Err(option()),
+
+            location => {
+                let mut location = location.split(':');
+
+                let source = location
+                    .next()
+                    .unwrap_or("split always generate a first element")
+                    .into();
+
+                let line =
+                    location.next().map(u64::from_str).transpose().map_err(
+                        |e| io::Error::new(io::ErrorKind::InvalidInput, e),
+                    )?;
+
+                locations.push((source, line));
+            }
+        }
+    }
+
+    if locations.is_empty() {
+        return Err(invalid(String::from(
+            "a breakpoint requires a location as NAME[:LINE]",
+        )));
+    }
+
+    Ok((locations, kind))
+}
+
+/// Constraint range of a `START..END` argument, or a source pattern
+fn parse_focus(arg: &str) -> Focus {
+    let range = arg.split_once("..").and_then(|(start, end)| {
+        Some((usize::from_str(start).ok()?, usize::from_str(end).ok()?))
+    });
+
+    match range {
+        Some((start, end)) => Focus::Range { start, end },
+        None => Focus::Source(arg.into()),
+    }
+}
+
+/// Breakpoint of the DAP requests
+fn dap_breakpoint(source: String, line: Option<u64>) -> Breakpoint {
+    Breakpoint {
+        id: None,
+        verified: true,
+        message: None,
+        source: Some(Source {
+            name: Some(source),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: vec![],
+            adapter_data: None,
+            checksums: vec![],
+        }),
+        line,
+        column: None,
+        end_line: line,
+        end_column: None,
+        instruction_reference: None,
+        offset: None,
+    }
+}
+
+/// Canonical path of a file; a glob pattern resolves to its newest match
+fn resolve_newest(pattern: &str) -> io::Result<PathBuf> {
+    if !pattern.contains(&['*', '?', '['][..]) {
+        return PathBuf::from(pattern).canonicalize();
+    }
+
+    glob::glob(pattern)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let modified = path.metadata().and_then(|m| m.modified()).ok()?;
+
+            Some((modified, path))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no file matches `{}`", pattern),
+            )
+        })
+        .and_then(|(_, path)| path.canonicalize())
+}
+
 impl IntoIterator for Command {
     type Item = Request;
     type IntoIter = vec::IntoIter<Request>;
@@ -126,28 +519,42 @@ impl IntoIterator for Command {
             }]
             .into_iter(),
 
-            Command::Breakpoint { source, line } => {
+            Command::Breakpoint { source, line, kind } => {
                 vec![ZkRequest::AddBreakpoint {
-                    breakpoint: Breakpoint {
-                        id: None,
-                        verified: true,
-                        message: None,
-                        source: Some(Source {
-                            name: Some(source),
-                            source_reference: None,
-                            presentation_hint: None,
-                            origin: None,
-                            sources: vec![],
-                            adapter_data: None,
-                            checksums: vec![],
-                        }),
-                        line,
-                        column: None,
-                        end_line: line,
-                        end_column: None,
-                        instruction_reference: None,
-                        offset: None,
-                    },
+                    breakpoint: dap_breakpoint(source, line),
+                    kind,
+                }
+                .into()]
+                .into_iter()
+            }
+
+            Command::Breakpoints { locations, kind } => {
+                let breakpoints = locations
+                    .into_iter()
+                    .map(|(source, line)| dap_breakpoint(source, line))
+                    .collect();
+
+                vec![ZkRequest::AddBreakpoints { breakpoints, kind }.into()]
+                    .into_iter()
+            }
+
+            Command::TemporaryBreakpoint { source, line, kind } => {
+                vec![ZkRequest::AddTemporaryBreakpoint {
+                    breakpoint: dap_breakpoint(source, line),
+                    kind,
+                }
+                .into()]
+                .into_iter()
+            }
+
+            Command::Focus { focus, failures } => {
+                vec![ZkRequest::Focus { focus, failures }.into()].into_iter()
+            }
+
+            Command::Ignore { id, count } => {
+                vec![ZkRequest::IgnoreBreakpoint {
+                    id: id as u64,
+                    count: count as u64,
                 }
                 .into()]
                 .into_iter()
@@ -161,11 +568,19 @@ impl IntoIterator for Command {
             }]
             .into_iter(),
 
+            Command::ContinueMax { max, reverse } => {
+                vec![ZkRequest::ContinueMax { max, reverse }.into()].into_iter()
+            }
+
             Command::Delete { id } => {
                 vec![ZkRequest::RemoveBreakpoint { id: id as u64 }.into()]
                     .into_iter()
             }
 
+            Command::DeleteBreakpoints { source } => {
+                vec![ZkRequest::RemoveBreakpoints { source }.into()].into_iter()
+            }
+
             Command::Goto { id } => vec![Request::Goto {
                 arguments: GotoArguments {
                     thread_id: 0,
@@ -174,7 +589,11 @@ impl IntoIterator for Command {
             }]
             .into_iter(),
 
-            Command::Help => vec![].into_iter(),
+            Command::GotoLine { source, line } => {
+                vec![ZkRequest::GotoLine { source, line }.into()].into_iter()
+            }
+
+            Command::Help { .. } | Command::Alias { .. } => vec![].into_iter(),
 
             Command::Next => {
                 vec![Request::Next { arguments: None }].into_iter()
@@ -193,7 +612,7 @@ impl IntoIterator for Command {
                     supports_variable_paging: false,
                     supports_run_in_terminal_request: false,
                     supports_memory_references: false,
-                    supports_progress_reporting: false,
+                    supports_progress_reporting: true,
                     supports_invalidated_event: false,
                     supports_memory_event: false,
                     supports_args_can_be_interpreted_by_shell: false,
@@ -231,6 +650,56 @@ impl IntoIterator for Command {
             Command::Witness { id } => {
                 vec![ZkRequest::Witness { id }.into()].into_iter()
             }
+
+            Command::WireCheck => vec![ZkRequest::WireCheck.into()].into_iter(),
+
+            Command::Timeline => vec![ZkRequest::Timeline.into()].into_iter(),
+
+            Command::Locals => vec![ZkRequest::Locals.into()].into_iter(),
+
+            Command::Frontier => vec![ZkRequest::Frontier.into()].into_iter(),
+
+            Command::Memory => vec![ZkRequest::Memory.into()].into_iter(),
+
+            Command::Copy | Command::ExportView { .. } => {
+                vec![ZkRequest::Snippet.into()].into_iter()
+            }
+
+            Command::Latex => vec![ZkRequest::Equation {
+                notation: Notation::Latex,
+            }
+            .into()]
+            .into_iter(),
+
+            Command::ScalarCheck => {
+                vec![ZkRequest::ScalarCheck.into()].into_iter()
+            }
+
+            Command::ConstCheck => {
+                vec![ZkRequest::ConstCheck.into()].into_iter()
+            }
+
+            Command::SharedWires => {
+                vec![ZkRequest::SharedWires.into()].into_iter()
+            }
+
+            Command::Publics { compare } => {
+                vec![ZkRequest::Publics { compare }.into()].into_iter()
+            }
+
+            Command::Raw { id } => {
+                vec![ZkRequest::Raw { id }.into()].into_iter()
+            }
+
+            Command::Top { limit } => {
+                vec![ZkRequest::Top { limit }.into()].into_iter()
+            }
+
+            Command::Script { .. }
+            | Command::Reopen { .. }
+            | Command::History { .. }
+            | Command::Rerun { .. }
+            | Command::Plugin { .. } => vec![].into_iter(),
         }
     }
 }
@@ -262,6 +731,42 @@ fn try_from_binary_open_works() {
     assert_eq!(c, command);
 }
 
+#[test]
+fn try_from_binary_open_resolves_globs_to_the_newest() -> io::Result<()> {
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempdir::TempDir::new("pdb-open")?;
+    let now = SystemTime::now();
+
+    for (name, age) in [("run-1.cdf", 60), ("run-2.cdf", 0), ("run-3.cdf", 30)]
+    {
+        fs::File::create(dir.path().join(name))?
+            .set_modified(now - Duration::from_secs(age))?;
+    }
+
+    let pattern = dir.path().join("run-*.cdf");
+    let pattern = pattern.to_str().expect("the path is utf-8");
+
+    let command = Command::try_from_binary(&Instruction::Open, pattern)?;
+    let newest = dir.path().join("run-2.cdf").canonicalize()?;
+
+    assert_eq!(
+        command,
+        Command::Open {
+            path: newest.display().to_string(),
+        }
+    );
+
+    let pattern = dir.path().join("none-*.cdf");
+    let pattern = pattern.to_str().expect("the path is utf-8");
+
+    Command::try_from_binary(&Instruction::Open, pattern)
+        .expect_err("no file matches the pattern");
+
+    Ok(())
+}
+
 #[test]
 fn try_from_binary_breakpoint_works() {
     let source = String::from("lib.rs");
@@ -276,6 +781,7 @@ fn try_from_binary_breakpoint_works() {
     let b = Command::Breakpoint {
         source: source.clone(),
         line: None,
+        kind: None,
     };
 
     assert_eq!(b, breakpoint);
@@ -287,18 +793,72 @@ fn try_from_binary_breakpoint_works() {
     )
     .expect("failed to create breakpoint command");
 
+    let b = Command::Breakpoint {
+        source: source.clone(),
+        line: Some(line),
+        kind: None,
+    };
+
+    assert_eq!(b, breakpoint);
+
+    Command::try_from_binary(&Instruction::Breakpoint, "lib.rs --kind foo")
+        .expect_err("kind must be valid");
+    Command::try_from_binary(&Instruction::Breakpoint, "lib.rs --type range")
+        .expect_err("kind is the only option");
+
+    let breakpoint = Command::try_from_binary(
+        &Instruction::Breakpoint,
+        &format!("{}:{} --kind range", source, line),
+    )
+    .expect("failed to create breakpoint command");
+
     let b = Command::Breakpoint {
         source,
         line: Some(line),
+        kind: Some(GateKind::Range),
     };
 
     assert_eq!(b, breakpoint);
+
+    let breakpoints = Command::try_from_binary(
+        &Instruction::Breakpoint,
+        "lib.rs:25 lib.rs:26 gadgets.rs --kind arith",
+    )
+    .expect("failed to create breakpoints command");
+
+    let b = Command::Breakpoints {
+        locations: vec![
+            ("lib.rs".into(), Some(25)),
+            ("lib.rs".into(), Some(26)),
+            ("gadgets.rs".into(), None),
+        ],
+        kind: Some(GateKind::Arithmetic),
+    };
+
+    assert_eq!(b, breakpoints);
+
+    Command::try_from_binary(&Instruction::Breakpoint, "lib.rs --kind arith x")
+        .expect_err("the kind closes the arguments");
+    Command::try_from_binary(&Instruction::TemporaryBreakpoint, "a.rs b.rs")
+        .expect_err("a temporary breakpoint has a single location");
 }
 
 #[test]
 fn try_from_binary_delete_works() {
-    Command::try_from_binary(&Instruction::Delete, "xx")
-        .expect_err("delete should be numeric");
+    let all = Command::try_from_binary(&Instruction::Delete, "all")
+        .expect("failed to create delete command");
+
+    assert_eq!(all, Command::DeleteBreakpoints { source: None });
+
+    let source = Command::try_from_binary(&Instruction::Delete, "lib.rs")
+        .expect("failed to create delete command");
+
+    assert_eq!(
+        source,
+        Command::DeleteBreakpoints {
+            source: Some("lib.rs".into())
+        }
+    );
 
     let id = 2387;
     let delete =
@@ -309,6 +869,85 @@ fn try_from_binary_delete_works() {
     assert_eq!(d, delete);
 }
 
+#[test]
+fn try_from_binary_temporary_breakpoint_works() {
+    let breakpoint = Command::try_from_binary(
+        &Instruction::TemporaryBreakpoint,
+        "gadget.rs:12",
+    )
+    .expect("failed to create temporary breakpoint command");
+
+    let b = Command::TemporaryBreakpoint {
+        source: "gadget.rs".into(),
+        line: Some(12),
+        kind: None,
+    };
+
+    assert_eq!(b, breakpoint);
+}
+
+#[test]
+fn try_from_binary_focus_works() {
+    Command::try_from_binary(&Instruction::Focus, "gadget.rs --all")
+        .expect_err("failures is the only option");
+
+    let focus = Command::try_from_binary(&Instruction::Focus, "gadget.rs")
+        .expect("failed to create focus command");
+    let f = Command::Focus {
+        focus: Some(Focus::Source("gadget.rs".into())),
+        failures: false,
+    };
+
+    assert_eq!(f, focus);
+
+    let focus =
+        Command::try_from_binary(&Instruction::Focus, "3..8 --failures")
+            .expect("failed to create focus command");
+    let f = Command::Focus {
+        focus: Some(Focus::Range { start: 3, end: 8 }),
+        failures: true,
+    };
+
+    assert_eq!(f, focus);
+}
+
+#[test]
+fn try_from_binary_continue_max_works() {
+    Command::try_from_binary(&Instruction::Continue, "10")
+        .expect_err("the budget takes the max flag");
+
+    let cont = Command::try_from_binary(&Instruction::Continue, "--max 10")
+        .expect("failed to create continue command");
+    let c = Command::ContinueMax {
+        max: 10,
+        reverse: false,
+    };
+
+    assert_eq!(c, cont);
+
+    let turn = Command::try_from_binary(&Instruction::Turn, "--max 10")
+        .expect("failed to create turn command");
+    let t = Command::ContinueMax {
+        max: 10,
+        reverse: true,
+    };
+
+    assert_eq!(t, turn);
+}
+
+#[test]
+fn try_from_binary_ignore_works() {
+    Command::try_from_binary(&Instruction::Ignore, "1")
+        .expect_err("ignore requires a count");
+    Command::try_from_binary(&Instruction::Ignore, "1 xx")
+        .expect_err("count should be numeric");
+
+    let ignore = Command::try_from_binary(&Instruction::Ignore, "1 10")
+        .expect("failed to create ignore command");
+
+    assert_eq!(Command::Ignore { id: 1, count: 10 }, ignore);
+}
+
 #[test]
 fn try_from_binary_goto_works() {
     Command::try_from_binary(&Instruction::Goto, "xx")
@@ -320,6 +959,18 @@ fn try_from_binary_goto_works() {
     let g = Command::Goto { id };
 
     assert_eq!(g, goto);
+
+    Command::try_from_binary(&Instruction::Goto, "gadget.rs:xx")
+        .expect_err("goto line should be numeric");
+
+    let goto = Command::try_from_binary(&Instruction::Goto, "gadget.rs:12")
+        .expect("failed to create goto line command");
+    let g = Command::GotoLine {
+        source: "gadget.rs".into(),
+        line: 12,
+    };
+
+    assert_eq!(g, goto);
 }
 
 #[test]
@@ -336,19 +987,106 @@ fn try_from_binary_witness_works() {
     assert_eq!(w, witness);
 }
 
+#[test]
+fn try_from_binary_reopen_works() {
+    Command::try_from_binary(&Instruction::Reopen, "xx")
+        .expect_err("reopen should be numeric");
+
+    let reopen = Command::try_from_binary(&Instruction::Reopen, "2")
+        .expect("failed to create reopen command");
+
+    assert_eq!(Command::Reopen { index: Some(2) }, reopen);
+}
+
+#[test]
+fn try_from_binary_alias_works() {
+    Command::try_from_binary(&Instruction::Alias, "n x = next")
+        .expect_err("the alias name should be a single token");
+
+    Command::try_from_binary(&Instruction::Alias, "= next")
+        .expect_err("the alias name is mandatory");
+
+    let alias = |name: &str, expansion: Option<&str>| Command::Alias {
+        name: Some(name.into()),
+        expansion: expansion.map(String::from),
+    };
+
+    let cases = vec![
+        ("n = next", alias("n", Some("next"))),
+        (
+            "b=breakpoint gadget.rs",
+            alias("b", Some("breakpoint gadget.rs")),
+        ),
+        ("n =", alias("n", Some(""))),
+        ("n", alias("n", None)),
+    ];
+
+    for (arg, expected) in cases {
+        let command = Command::try_from_binary(&Instruction::Alias, arg)
+            .expect("failed to create alias command");
+
+        assert_eq!(command, expected);
+    }
+}
+
 #[test]
 fn command_generates_requests() {
     Command::Afore.into_iter().next().expect("req");
     Command::Breakpoint {
         source: "foo".into(),
         line: None,
+        kind: Some(GateKind::Logic),
     }
     .into_iter()
     .next()
     .expect("req");
     Command::Continue.into_iter().next().expect("req");
+    Command::ContinueMax {
+        max: 10,
+        reverse: true,
+    }
+    .into_iter()
+    .next()
+    .expect("req");
     Command::Delete { id: 83 }.into_iter().next().expect("req");
+    Command::DeleteBreakpoints { source: None }
+        .into_iter()
+        .next()
+        .expect("req");
+    Command::Breakpoints {
+        locations: vec![("lib.rs".into(), Some(25))],
+        kind: None,
+    }
+    .into_iter()
+    .next()
+    .expect("req");
     Command::Goto { id: 83 }.into_iter().next().expect("req");
+    Command::TemporaryBreakpoint {
+        source: "foo".into(),
+        line: None,
+        kind: None,
+    }
+    .into_iter()
+    .next()
+    .expect("req");
+    Command::Ignore { id: 83, count: 2 }
+        .into_iter()
+        .next()
+        .expect("req");
+    Command::Focus {
+        focus: None,
+        failures: false,
+    }
+    .into_iter()
+    .next()
+    .expect("req");
+    Command::GotoLine {
+        source: "foo".into(),
+        line: 83,
+    }
+    .into_iter()
+    .next()
+    .expect("req");
     Command::Next.into_iter().next().expect("req");
     Command::Open { path: "foo".into() }
         .into_iter()
@@ -358,6 +1096,40 @@ fn command_generates_requests() {
     Command::Restart.into_iter().next().expect("req");
     Command::Quit.into_iter().next().expect("req");
     Command::Witness { id: 83 }.into_iter().next().expect("req");
+    Command::WireCheck.into_iter().next().expect("req");
+    Command::Timeline.into_iter().next().expect("req");
+    Command::Locals.into_iter().next().expect("req");
+    Command::Frontier.into_iter().next().expect("req");
+    Command::Memory.into_iter().next().expect("req");
+    Command::Copy.into_iter().next().expect("req");
+    Command::Latex.into_iter().next().expect("req");
+    Command::ExportView { path: "foo".into() }
+        .into_iter()
+        .next()
+        .expect("req");
+    Command::ScalarCheck.into_iter().next().expect("req");
+    Command::ConstCheck.into_iter().next().expect("req");
+    Command::SharedWires.into_iter().next().expect("req");
+    Command::Publics { compare: true }
+        .into_iter()
+        .next()
+        .expect("req");
+    Command::Raw { id: None }.into_iter().next().expect("req");
+    Command::Top { limit: 10 }.into_iter().next().expect("req");
 
-    assert!(Command::Help.into_iter().next().is_none());
+    assert!(Command::Help { topic: None }.into_iter().next().is_none());
+    assert!(Command::Alias {
+        name: None,
+        expansion: None,
+    }
+    .into_iter()
+    .next()
+    .is_none());
+    assert!(Command::Script { path: "foo".into() }
+        .into_iter()
+        .next()
+        .is_none());
+    assert!(Command::Reopen { index: None }.into_iter().next().is_none());
+    assert!(Command::History { path: None }.into_iter().next().is_none());
+    assert!(Command::Rerun { index: 1 }.into_iter().next().is_none());
 }
@@ -15,61 +15,113 @@ pub enum Instruction {
     Turn = 0x0a,
     Quit = 0x0b,
     Witness = 0x0c,
+    Script = 0x0d,
+    WireCheck = 0x0e,
+    Timeline = 0x0f,
+    ScalarCheck = 0x10,
+    Top = 0x11,
+    Reopen = 0x12,
+    TemporaryBreakpoint = 0x13,
+    Ignore = 0x14,
+    Focus = 0x15,
+    Locals = 0x16,
+    Frontier = 0x17,
+    Alias = 0x18,
+    Memory = 0x19,
+    Copy = 0x1a,
+    ExportView = 0x1b,
+    ConstCheck = 0x1c,
+    History = 0x1d,
+    Shared = 0x1e,
+    Publics = 0x1f,
+    Raw = 0x20,
+    Latex = 0x21,
 }
 
 impl Instruction {
+    /// All the instructions, in the order of their discriminants
+    pub const ALL: [Self; 34] = [
+        Self::Afore,
+        Self::Breakpoint,
+        Self::Continue,
+        Self::Delete,
+        Self::Goto,
+        Self::Help,
+        Self::Next,
+        Self::Open,
+        Self::Print,
+        Self::Restart,
+        Self::Turn,
+        Self::Quit,
+        Self::Witness,
+        Self::Script,
+        Self::WireCheck,
+        Self::Timeline,
+        Self::ScalarCheck,
+        Self::Top,
+        Self::Reopen,
+        Self::TemporaryBreakpoint,
+        Self::Ignore,
+        Self::Focus,
+        Self::Locals,
+        Self::Frontier,
+        Self::Alias,
+        Self::Memory,
+        Self::Copy,
+        Self::ExportView,
+        Self::ConstCheck,
+        Self::History,
+        Self::Shared,
+        Self::Publics,
+        Self::Raw,
+        Self::Latex,
+    ];
+
+    /// Documentation and parsing metadata of the instruction
+    pub fn metadata(&self) -> &'static Metadata {
+        &METADATA[*self as usize]
+    }
+
     pub fn help(&self) -> &'static str {
-        match self {
-            Instruction::Afore => "go to the previous constraint",
-            Instruction::Breakpoint => "set a new breakpoint. the name pattern doesn't have to be an exact match to the source name.",
-            Instruction::Continue => "continue normal execution until next error",
-            Instruction::Delete => "remove a breakpoint.",
-            Instruction::Goto => "jump to a constraint",
-            Instruction::Help => "print the help menu",
-            Instruction::Next => "go to the next constraint",
-            Instruction::Open => "open a file",
-            Instruction::Print => "print constraint data",
-            Instruction::Restart => "restart the execution of a circuit",
-            Instruction::Turn => "reverse the execution of the circuit",
-            Instruction::Quit => "terminate the session",
-            Instruction::Witness => "print information about a witness",
-        }
+        self.metadata().help
     }
 
     pub fn syntax(&self) -> &'static str {
-        match self {
-            Instruction::Afore => "afore",
-            Instruction::Breakpoint => "breakpoint <NAME>[:LINE]",
-            Instruction::Continue => "continue",
-            Instruction::Delete => "delete <NUMBER>",
-            Instruction::Goto => "goto <NUMBER>",
-            Instruction::Help => "help",
-            Instruction::Next => "next",
-            Instruction::Open => "open <FILE>",
-            Instruction::Print => "print",
-            Instruction::Restart => "restart",
-            Instruction::Turn => "turn",
-            Instruction::Quit => "quit",
-            Instruction::Witness => "witness <NUMBER>",
-        }
+        self.metadata().syntax
     }
 
     pub fn token(&self) -> &'static str {
-        match self {
-            Instruction::Afore => "afore",
-            Instruction::Breakpoint => "breakpoint",
-            Instruction::Continue => "continue",
-            Instruction::Delete => "delete",
-            Instruction::Goto => "goto",
-            Instruction::Help => "help",
-            Instruction::Next => "next",
-            Instruction::Open => "open",
-            Instruction::Print => "print",
-            Instruction::Restart => "restart",
-            Instruction::Turn => "turn",
-            Instruction::Quit => "quit",
-            Instruction::Witness => "witness",
+        self.metadata().token
+    }
+
+    /// Long help of the instruction, with its examples and related
+    /// instructions
+    pub fn long_help(&self) -> String {
+        let Metadata {
+            syntax,
+            help,
+            details,
+            examples,
+            related,
+            ..
+        } = self.metadata();
+
+        let mut s = format!("{}\n\n{}\n\n{}\n", syntax, help, details);
+
+        if !examples.is_empty() {
+            s.push_str("\nexamples:\n");
+            examples
+                .iter()
+                .for_each(|e| s.push_str(&format!("  {}\n", e)));
         }
+
+        if !related.is_empty() {
+            let related: Vec<_> = related.iter().map(|i| i.token()).collect();
+
+            s.push_str(&format!("\nsee also: {}\n", related.join(", ")));
+        }
+
+        s
     }
 
     pub fn complete_unary(&self, token: &str) -> Option<&str> {
@@ -85,42 +137,457 @@ impl Instruction {
             })
     }
 
+    /// Flag on whether the argument of the instruction can be omitted
+    pub const fn has_optional_argument(&self) -> bool {
+        matches!(
+            self,
+            Instruction::Continue
+                | Instruction::Turn
+                | Instruction::Reopen
+                | Instruction::Focus
+                | Instruction::Help
+                | Instruction::Alias
+                | Instruction::History
+                | Instruction::Raw
+        )
+    }
+
+    /// Check if a binary instruction accepts the number of arguments
+    pub const fn accepts_arguments(&self, n: usize) -> bool {
+        match self {
+            Instruction::Breakpoint => n >= 1,
+            Instruction::TemporaryBreakpoint => n == 1 || n == 3,
+            Instruction::Continue | Instruction::Turn | Instruction::Ignore => {
+                n == 2
+            }
+            Instruction::Focus => n == 1 || n == 2,
+            Instruction::Alias => n >= 1,
+            _ => n == 1,
+        }
+    }
+
     pub fn complete_binary(
         &self,
         parser: &CommandParser,
         token: &str,
     ) -> Option<String> {
         match self {
+            // the recently opened files take precedence over the file system
             Instruction::Open => parser
-                .filename_completer
-                .complete_path(token, token.len())
-                .ok()
-                .and_then(|(_, pairs)| {
-                    pairs
-                        .first()
-                        .map(|pair| pair.replacement[token.len()..].to_string())
-                }),
+                .recent
+                .entries()
+                .into_iter()
+                .find(|e| e.len() > token.len() && e.starts_with(token))
+                .map(|e| e[token.len()..].to_string())
+                .or_else(|| Self::complete_path(parser, token)),
+
+            Instruction::Script
+            | Instruction::ExportView
+            | Instruction::History => Self::complete_path(parser, token),
+
+            Instruction::Help => parser
+                .instructions()
+                .iter()
+                .map(|i| i.metadata().token)
+                .chain(parser.plugins().iter().map(|p| p.name()))
+                .find(|t| t.len() > token.len() && t.starts_with(token))
+                .map(|t| t[token.len()..].to_string()),
 
             _ => None,
         }
     }
 
+    fn complete_path(parser: &CommandParser, token: &str) -> Option<String> {
+        parser
+            .filename_completer
+            .complete_path(token, token.len())
+            .ok()
+            .and_then(|(_, pairs)| {
+                pairs
+                    .first()
+                    .map(|pair| pair.replacement[token.len()..].to_string())
+            })
+    }
+
     /// Resolve an unary token
     pub fn resolve_unary(&self) -> Option<Command> {
         match self {
             Instruction::Afore => Some(Command::Afore),
             Instruction::Continue => Some(Command::Continue),
-            Instruction::Help => Some(Command::Help),
+            Instruction::Help => Some(Command::Help { topic: None }),
             Instruction::Next => Some(Command::Next),
             Instruction::Print => Some(Command::Print),
             Instruction::Restart => Some(Command::Restart),
             Instruction::Turn => Some(Command::Turn),
             Instruction::Quit => Some(Command::Quit),
+            Instruction::WireCheck => Some(Command::WireCheck),
+            Instruction::Timeline => Some(Command::Timeline),
+            Instruction::ScalarCheck => Some(Command::ScalarCheck),
+            Instruction::Reopen => Some(Command::Reopen { index: None }),
+            Instruction::Focus => Some(Command::Focus {
+                focus: None,
+                failures: false,
+            }),
+            Instruction::Locals => Some(Command::Locals),
+            Instruction::Frontier => Some(Command::Frontier),
+            Instruction::Alias => Some(Command::Alias {
+                name: None,
+                expansion: None,
+            }),
+            Instruction::Memory => Some(Command::Memory),
+            Instruction::Copy => Some(Command::Copy),
+            Instruction::ConstCheck => Some(Command::ConstCheck),
+            Instruction::History => Some(Command::History { path: None }),
+            Instruction::Shared => Some(Command::SharedWires),
+            Instruction::Publics => Some(Command::Publics { compare: false }),
+            Instruction::Raw => Some(Command::Raw { id: None }),
+            Instruction::Latex => Some(Command::Latex),
             _ => None,
         }
     }
 }
 
+/// Documentation and parsing metadata of an instruction.
+///
+/// The parser, the completion and the help menu are all driven by
+/// [`Instruction::ALL`] and this table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Metadata {
+    /// Described instruction
+    pub instruction: Instruction,
+    /// Token that invokes the instruction; any of its prefixes is accepted
+    pub token: &'static str,
+    /// Syntax of the arguments
+    pub syntax: &'static str,
+    /// Short description, listed by `help`
+    pub help: &'static str,
+    /// Long description, printed by `help <COMMAND>`
+    pub details: &'static str,
+    /// Example invocations
+    pub examples: &'static [&'static str],
+    /// Related instructions
+    pub related: &'static [Instruction],
+}
+
+static METADATA: [Metadata; Instruction::ALL.len()] = [
+    Metadata {
+        instruction: Instruction::Afore,
+        token: "afore",
+        syntax: "afore",
+        help: "go to the previous constraint",
+        details: "moves one constraint back, skipping the constraints out of the focus. it doesn't stop at breakpoints.",
+        examples: &["afore"],
+        related: &[Instruction::Next, Instruction::Turn],
+    },
+    Metadata {
+        instruction: Instruction::Breakpoint,
+        token: "breakpoint",
+        syntax: "breakpoint <NAME>[:LINE]... [--kind KIND]",
+        help: "set new breakpoints. the name pattern doesn't have to be an exact match to the source name. the kind restricts them to a gate type, such as range or logic.",
+        details: "continue and turn stop at the constraints of the sources whose name contains NAME, at LINE if provided. several locations are set in a single request, sharing the kind. the kinds are arith, range, logic, fixed_add, variable_add, empty and ambiguous. the id of every breakpoint is printed, to be used with delete and ignore.",
+        examples: &[
+            "breakpoint gadget.rs",
+            "breakpoint gadget.rs:12",
+            "breakpoint lib.rs:25 lib.rs:26 gadget.rs",
+            "breakpoint gadget.rs --kind range",
+        ],
+        related: &[
+            Instruction::TemporaryBreakpoint,
+            Instruction::Delete,
+            Instruction::Ignore,
+            Instruction::Continue,
+        ],
+    },
+    Metadata {
+        instruction: Instruction::Continue,
+        token: "continue",
+        syntax: "continue [--max N]",
+        help: "continue normal execution until next error. --max stops after visiting N constraints.",
+        details: "visits the next constraints until a failed constraint, a breakpoint or the end of the circuit. ctrl+c interrupts the scan, returning to the prompt.",
+        examples: &["continue", "continue --max 1000"],
+        related: &[Instruction::Turn, Instruction::Breakpoint, Instruction::Focus],
+    },
+    Metadata {
+        instruction: Instruction::Delete,
+        token: "delete",
+        syntax: "delete <NUMBER | NAME | all>",
+        help: "remove a breakpoint, the breakpoints set with a name, or all of them.",
+        details: "the id of a breakpoint is printed when it is set. NAME is matched exactly against the name the breakpoints were set with, such as lib.rs for `breakpoint lib.rs:25`.",
+        examples: &["delete 1", "delete lib.rs", "delete all"],
+        related: &[Instruction::Breakpoint, Instruction::TemporaryBreakpoint],
+    },
+    Metadata {
+        instruction: Instruction::Goto,
+        token: "goto",
+        syntax: "goto <NUMBER | SOURCE:LINE>",
+        help: "jump to a constraint, or to the first constraint of a source line",
+        details: "the source is matched as the breakpoints are, and the line index is built on its first use.",
+        examples: &["goto 42", "goto gadget.rs:12"],
+        related: &[Instruction::Next, Instruction::Afore, Instruction::Print],
+    },
+    Metadata {
+        instruction: Instruction::Help,
+        token: "help",
+        syntax: "help [COMMAND]",
+        help: "print the help menu, or the long help of a command",
+        details: "the command can be abbreviated as on the prompt, and can also be the name of a plugin.",
+        examples: &["help", "help breakpoint", "help tb"],
+        related: &[],
+    },
+    Metadata {
+        instruction: Instruction::Next,
+        token: "next",
+        syntax: "next",
+        help: "go to the next constraint",
+        details: "moves one constraint forward, skipping the constraints out of the focus. it doesn't stop at breakpoints.",
+        examples: &["next"],
+        related: &[Instruction::Afore, Instruction::Continue],
+    },
+    Metadata {
+        instruction: Instruction::Open,
+        token: "open",
+        syntax: "open <FILE | GLOB>",
+        help: "open a file, or the newest match of a glob",
+        details: "the opened files are remembered, and completed by open before the file system.",
+        examples: &["open target/circuit.cdf", "open 'target/*.cdf'"],
+        related: &[Instruction::Reopen],
+    },
+    Metadata {
+        instruction: Instruction::Print,
+        token: "print",
+        syntax: "print",
+        help: "print constraint data",
+        details: "prints the selectors, the wired witnesses and the evaluation of the current constraint.",
+        examples: &["print"],
+        related: &[Instruction::Witness, Instruction::Locals],
+    },
+    Metadata {
+        instruction: Instruction::Restart,
+        token: "restart",
+        syntax: "restart",
+        help: "restart the execution of a circuit",
+        details: "moves to the first constraint, keeping the breakpoints.",
+        examples: &["restart"],
+        related: &[Instruction::Continue, Instruction::Goto],
+    },
+    Metadata {
+        instruction: Instruction::Turn,
+        token: "turn",
+        syntax: "turn [--max N]",
+        help: "reverse the execution of the circuit. --max stops after visiting N constraints.",
+        details: "visits the previous constraints until a failed constraint, a breakpoint or the start of the circuit. ctrl+c interrupts the scan, returning to the prompt.",
+        examples: &["turn", "turn --max 1000"],
+        related: &[Instruction::Continue, Instruction::Breakpoint],
+    },
+    Metadata {
+        instruction: Instruction::Quit,
+        token: "quit",
+        syntax: "quit",
+        help: "terminate the session",
+        details: "ctrl+d terminates the session as well.",
+        examples: &["quit"],
+        related: &[],
+    },
+    Metadata {
+        instruction: Instruction::Witness,
+        token: "witness",
+        syntax: "witness <NUMBER>",
+        help: "print information about a witness",
+        details: "prints the value of the witness, the source line that declared it and the constraint that created it, if any.",
+        examples: &["witness 7"],
+        related: &[Instruction::Locals, Instruction::Print],
+    },
+    Metadata {
+        instruction: Instruction::Script,
+        token: "script",
+        syntax: "script <FILE>",
+        help: "run an analysis script over the loaded circuit",
+        details: "the script is run over the session, moving its position and sharing its breakpoints, and its output is printed. ctrl+c terminates it.",
+        examples: &["script analysis.rhai"],
+        related: &[],
+    },
+    Metadata {
+        instruction: Instruction::WireCheck,
+        token: "wirecheck",
+        syntax: "wirecheck",
+        help: "list the wires inconsistent with the witness table",
+        details: "reports the wires pointing beyond the witness table, and the wired values that differ from the witness they point to.",
+        examples: &["wirecheck"],
+        related: &[Instruction::Timeline, Instruction::ScalarCheck],
+    },
+    Metadata {
+        instruction: Instruction::Timeline,
+        token: "timeline",
+        syntax: "timeline",
+        help: "list the constraints referencing witnesses allocated after them",
+        details: "a witness is allocated at the constraint that created it. a constraint wiring a witness allocated later is often a gate appended before its inputs.",
+        examples: &["timeline"],
+        related: &[Instruction::WireCheck, Instruction::Locals],
+    },
+    Metadata {
+        instruction: Instruction::ScalarCheck,
+        token: "scalarcheck",
+        syntax: "scalarcheck",
+        help: "list the stored scalars that aren't canonical",
+        details: "reports the witness values and the selectors that aren't smaller than the modulus of the scalar field.",
+        examples: &["scalarcheck"],
+        related: &[Instruction::WireCheck, Instruction::ConstCheck],
+    },
+    Metadata {
+        instruction: Instruction::Top,
+        token: "top",
+        syntax: "top <NUMBER>",
+        help: "print the sources and gadgets with the largest footprint, and the sources with the most failures",
+        details: "NUMBER limits each of the listings.",
+        examples: &["top 10"],
+        related: &[Instruction::Focus],
+    },
+    Metadata {
+        instruction: Instruction::Reopen,
+        token: "reopen",
+        syntax: "reopen [N]",
+        help: "open the previous file, or the Nth most recent one (0 is the latest)",
+        details: "without arguments, flips back to the latest file other than the current one, such as between the ok and wrong outputs of a circuit.",
+        examples: &["reopen", "reopen 2"],
+        related: &[Instruction::Open],
+    },
+    Metadata {
+        instruction: Instruction::TemporaryBreakpoint,
+        token: "tbreak",
+        syntax: "tbreak <NAME>[:LINE] [--kind KIND]",
+        help: "set a new breakpoint that is removed after its first hit.",
+        details: "accepts the same arguments as breakpoint.",
+        examples: &["tbreak gadget.rs:12", "tbreak main.rs --kind logic"],
+        related: &[Instruction::Breakpoint, Instruction::Ignore],
+    },
+    Metadata {
+        instruction: Instruction::Ignore,
+        token: "ignore",
+        syntax: "ignore <NUMBER> <COUNT>",
+        help: "ignore the next COUNT hits of a breakpoint. 0 stops at the next hit.",
+        details: "a hit is counted whenever continue or turn would stop at the breakpoint; handy to step over the first iterations of a loop.",
+        examples: &["ignore 1 10", "ignore 1 0"],
+        related: &[Instruction::Breakpoint, Instruction::TemporaryBreakpoint],
+    },
+    Metadata {
+        instruction: Instruction::Focus,
+        token: "focus",
+        syntax: "focus [<NAME | START..END> [--failures]]",
+        help: "limit the navigation to a source or to a constraint range, reporting the failures out of it only with --failures. without arguments, lift the limit.",
+        details: "next, afore, continue and turn skip the constraints out of the focus.",
+        examples: &["focus gadget.rs", "focus 100..200 --failures", "focus"],
+        related: &[Instruction::Continue, Instruction::Top],
+    },
+    Metadata {
+        instruction: Instruction::Locals,
+        token: "locals",
+        syntax: "locals",
+        help: "list the witnesses created by the current constraint",
+        details: "lists the witnesses whose origin is the current constraint, that is, the values created by its gate or gadget.",
+        examples: &["locals"],
+        related: &[Instruction::Witness, Instruction::Timeline],
+    },
+    Metadata {
+        instruction: Instruction::Frontier,
+        token: "frontier",
+        syntax: "frontier",
+        help: "trace the wires of the current failed constraint back to the earliest failed constraint they depend on",
+        details: "the wires are followed through the preceding failed constraints sharing their witnesses. the earliest failure of a wire is where its value went wrong.",
+        examples: &["frontier"],
+        related: &[Instruction::Goto, Instruction::Continue],
+    },
+    Metadata {
+        instruction: Instruction::Alias,
+        token: "alias",
+        syntax: "alias [NAME [= COMMAND]]",
+        help: "define a shortcut for a command. without arguments, list the aliases.",
+        details: "a line starting with NAME runs COMMAND, followed by the rest of the line. the aliases of the [aliases] table of the config are always defined; the ones defined here last for the session. an empty COMMAND removes the alias.",
+        examples: &["alias", "alias n = next", "alias b = breakpoint gadget.rs", "alias n ="],
+        related: &[Instruction::Help],
+    },
+    Metadata {
+        instruction: Instruction::Memory,
+        token: "memory",
+        syntax: "memory",
+        help: "print the memory held by the caches of the debugger",
+        details: "breaks the usage down by cache, along with the budget set by max_memory in the [cache_limits] of the config. the source counts, the line table and the index are dropped when the budget is exceeded, and rebuilt on their next use.",
+        examples: &["memory"],
+        related: &[Instruction::Top],
+    },
+    Metadata {
+        instruction: Instruction::Copy,
+        token: "copy",
+        syntax: "copy",
+        help: "copy the current constraint to the clipboard as a markdown snippet",
+        details: "the snippet contains the source excerpt, the equation, the selectors and the wires of the constraint. the clipboard is set with the OSC 52 escape sequence, supported by most terminals, also over ssh.",
+        examples: &["copy"],
+        related: &[Instruction::ExportView, Instruction::Print],
+    },
+    Metadata {
+        instruction: Instruction::ExportView,
+        token: "export-view",
+        syntax: "export-view <FILE>",
+        help: "write the current constraint to a file as a markdown snippet",
+        details: "the snippet is the one of copy, and an existing file is replaced. handy to paste a failure into an issue or a code review.",
+        examples: &["export-view constraint.md"],
+        related: &[Instruction::Copy, Instruction::Print],
+    },
+    Metadata {
+        instruction: Instruction::ConstCheck,
+        token: "constcheck",
+        syntax: "constcheck",
+        help: "list the witnesses holding well-known constants that no constant gate fixes",
+        details: "the constants are 0, 1, -1 and the coordinates of the JubJub generator. a witness holding one of them without a gate such as ql·a + qc = 0 fixing it is often a placeholder that was never assigned.",
+        examples: &["constcheck"],
+        related: &[Instruction::ScalarCheck, Instruction::Witness],
+    },
+    Metadata {
+        instruction: Instruction::History,
+        token: "history",
+        syntax: "history [FILE]",
+        help: "list the commands of the session along with the states they reached; !N runs the Nth one again",
+        details: "the commands are numbered from 1, with their aliases expanded. with FILE, the journal is written as a session: every command prefixed by `> `, followed by the source it stopped at, so the path to the current position can be replayed.",
+        examples: &["history", "!3", "history debug.session"],
+        related: &[Instruction::Alias, Instruction::Script],
+    },
+    Metadata {
+        instruction: Instruction::Shared,
+        token: "shared",
+        syntax: "shared",
+        help: "list the witnesses wired into the constraints of more than one source",
+        details: "every witness is listed with the number of constraints wiring it and their sources, the most shared first. a witness shared by several gadgets couples them, and often explains a failure surfacing far from the gadget that computed it.",
+        examples: &["shared"],
+        related: &[Instruction::Frontier, Instruction::Witness],
+    },
+    Metadata {
+        instruction: Instruction::Publics,
+        token: "publics",
+        syntax: "publics [--compare]",
+        help: "list the public inputs recorded by the constraints. with --compare, diff them against the public input vector of the proof",
+        details: "the public inputs are the non-zero pi selectors, in the order of their constraints. the public input vector is read from the .cdfp file written by the prover next to the CDF file; a public input appended out of order shifts every following position, a common cause of verification failures.",
+        examples: &["publics", "publics --compare"],
+        related: &[Instruction::Goto, Instruction::Print],
+    },
+    Metadata {
+        instruction: Instruction::Raw,
+        token: "raw",
+        syntax: "raw [ID]",
+        help: "print the byte offset and a hexdump of the encoded constraint, the current one if ID is absent",
+        details: "the bytes are read as stored in the CDF file, without decoding them, so the ones of a corrupt constraint are printed as well. the rows are addressed by their offset in the file; handy to debug the integration of an encoder.",
+        examples: &["raw", "raw 42"],
+        related: &[Instruction::Print, Instruction::Goto],
+    },
+    Metadata {
+        instruction: Instruction::Latex,
+        token: "latex",
+        syntax: "latex",
+        help: "print the equation of the current constraint as LaTeX",
+        details: "the equation of the nonzero terms, such as q_M \\cdot a \\cdot b + q_C = 0, is followed by the values of its selectors and witnesses in an aligned environment, ready to paste into a paper or a math-enabled issue. a failed constraint is rendered as an inequation.",
+        examples: &["latex"],
+        related: &[Instruction::Copy, Instruction::Print],
+    },
+];
+
 #[test]
 fn complete_unary_works() {
     vec![
@@ -137,6 +604,27 @@ fn complete_unary_works() {
         Instruction::Turn,
         Instruction::Quit,
         Instruction::Witness,
+        Instruction::Script,
+        Instruction::WireCheck,
+        Instruction::Timeline,
+        Instruction::ScalarCheck,
+        Instruction::Top,
+        Instruction::Reopen,
+        Instruction::TemporaryBreakpoint,
+        Instruction::Ignore,
+        Instruction::Focus,
+        Instruction::Locals,
+        Instruction::Frontier,
+        Instruction::Alias,
+        Instruction::Memory,
+        Instruction::Copy,
+        Instruction::ExportView,
+        Instruction::ConstCheck,
+        Instruction::History,
+        Instruction::Shared,
+        Instruction::Publics,
+        Instruction::Raw,
+        Instruction::Latex,
     ]
     .into_iter()
     .for_each(|t| {
@@ -169,6 +657,58 @@ fn complete_binary_works() {
     assert_eq!("oml", completion);
 }
 
+#[test]
+fn complete_binary_prefers_recent_files() -> std::io::Result<()> {
+    use crate::recent::Recent;
+
+    let dir = tempdir::TempDir::new("pdb-complete")?;
+    let recent = Recent::load_from(dir.path().join("recent"));
+
+    recent.push("/tmp/output_wrong.cdf")?;
+    recent.push("/tmp/output_ok.cdf")?;
+
+    let parser = CommandParser::default().with_recent(recent);
+
+    let completion =
+        Instruction::Open.complete_binary(&parser, "/tmp/output_w");
+    assert_eq!(completion.as_deref(), Some("rong.cdf"));
+
+    let completion = Instruction::Open.complete_binary(&parser, "");
+    assert_eq!(completion.as_deref(), Some("/tmp/output_ok.cdf"));
+
+    Ok(())
+}
+
+#[test]
+fn metadata_matches_the_instructions() {
+    Instruction::ALL
+        .iter()
+        .enumerate()
+        .for_each(|(i, instruction)| {
+            assert_eq!(*instruction as usize, i);
+            assert_eq!(instruction.metadata().instruction, *instruction);
+        });
+}
+
+#[test]
+fn long_help_lists_examples_and_related() {
+    let help = Instruction::Breakpoint.long_help();
+
+    assert!(help.starts_with(Instruction::Breakpoint.syntax()));
+    assert!(help.contains("  breakpoint gadget.rs:12\n"));
+    assert!(help.contains("see also: tbreak, delete, ignore, continue"));
+}
+
+#[test]
+fn complete_binary_completes_help_topics() {
+    let parser = CommandParser::default();
+
+    let completion = Instruction::Help.complete_binary(&parser, "tbr");
+    assert_eq!(completion.as_deref(), Some("eak"));
+
+    assert!(Instruction::Help.complete_binary(&parser, "xyz").is_none());
+}
+
 #[test]
 fn help_generates_output() {
     Instruction::Afore.help();
@@ -184,6 +724,12 @@ fn help_generates_output() {
     Instruction::Turn.help();
     Instruction::Quit.help();
     Instruction::Witness.help();
+    Instruction::Script.help();
+    Instruction::WireCheck.help();
+    Instruction::Timeline.help();
+    Instruction::ScalarCheck.help();
+    Instruction::Top.help();
+    Instruction::Reopen.help();
 
     Instruction::Afore.syntax();
     Instruction::Breakpoint.syntax();
@@ -198,6 +744,12 @@ fn help_generates_output() {
     Instruction::Turn.syntax();
     Instruction::Quit.syntax();
     Instruction::Witness.syntax();
+    Instruction::Script.syntax();
+    Instruction::WireCheck.syntax();
+    Instruction::Timeline.syntax();
+    Instruction::ScalarCheck.syntax();
+    Instruction::Top.syntax();
+    Instruction::Reopen.syntax();
 }
 
 #[test]
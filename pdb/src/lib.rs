@@ -1,9 +1,16 @@
 pub mod app;
 pub mod args;
 pub mod commands;
+pub mod plugin;
+pub mod recent;
+pub mod recovery;
+pub mod script;
 
 pub mod prelude {
     pub use crate::app::*;
     pub use crate::args::*;
     pub use crate::commands::*;
+    pub use crate::plugin::*;
+    pub use crate::recent::*;
+    pub use crate::recovery::*;
 }
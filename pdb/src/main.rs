@@ -3,7 +3,6 @@ use std::io;
 use bat::line_range::{LineRange, LineRanges};
 use bat::PrettyPrinter;
 use clap::Parser;
-use crossterm::{cursor, execute, queue, terminal};
 
 use dusk_pdb::prelude::*;
 
@@ -19,42 +18,48 @@ fn render(
     } = output;
 
     let config = app.config();
-
-    if let Some(Source {
-        name,
-        contents,
-        line,
-    }) = contents
-    {
-        queue!(
-            stdout,
-            terminal::Clear(terminal::ClearType::All),
-            cursor::MoveTo(1, 1)
-        )?;
-
-        println!("{}", name);
-
-        let margin = config.render.margin;
-        let range = LineRanges::from(vec![LineRange::new(
-            line.saturating_sub(margin),
-            line.saturating_add(margin),
-        )]);
-
-        PrettyPrinter::new()
-            .input_from_bytes(contents.as_bytes())
-            .language("rust")
-            .header(config.render.header)
-            .grid(config.render.grid)
-            .line_numbers(config.render.line_numbers)
-            .line_ranges(range)
-            .highlight(line)
-            .theme(&config.render.theme)
-            .print()
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    let style = config.style();
+    let renderer = config.renderer();
+    let margin = config.render.margin;
+
+    match contents {
+        Some(source) if renderer.is_plain() => {
+            plain_source(stdout, &source, margin, config.render.line_numbers)?;
+        }
+
+        Some(Source {
+            name,
+            contents,
+            line,
+        }) => {
+            renderer.clear(stdout)?;
+
+            println!("{}", name);
+
+            let range = LineRanges::from(vec![LineRange::new(
+                line.saturating_sub(margin),
+                line.saturating_add(margin),
+            )]);
+
+            PrettyPrinter::new()
+                .input_from_bytes(contents.as_bytes())
+                .language("rust")
+                .header(config.render.header)
+                .grid(config.render.grid)
+                .line_numbers(config.render.line_numbers)
+                .line_ranges(range)
+                .highlight(line)
+                .theme(&config.render.theme)
+                .colored_output(style.is_enabled())
+                .print()
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        }
+
+        None => (),
     }
 
     for error in error {
-        println!("{}", error);
+        println!("{}", style.failure(error));
     }
 
     for console in console {
@@ -66,12 +71,13 @@ fn render(
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
-    let args = Args::parse().resolve()?;
+    let args = Args::parse().resolve()?.discover()?;
     let mut app = App::load(args).await?;
 
     let mut stdout = io::stdout();
+    let renderer = app.config().renderer();
 
-    execute!(stdout, terminal::EnterAlternateScreen, cursor::MoveTo(0, 0))?;
+    renderer.enter(&mut stdout)?;
 
     if let Some(output) = app.flush_output().await {
         render(&mut stdout, &app, output)?;
@@ -81,7 +87,7 @@ async fn main() -> io::Result<()> {
         render(&mut stdout, &app, output)?;
     }
 
-    execute!(stdout, terminal::LeaveAlternateScreen)?;
+    renderer.leave(&mut stdout)?;
 
     println!("bye!");
 
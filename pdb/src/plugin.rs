@@ -0,0 +1,78 @@
+use std::io;
+use std::sync::Arc;
+
+use dusk_cdf::DebuggerBackend;
+
+/// A custom command that can be registered into the debugger.
+///
+/// Plugins are resolved by their name, showing up in the help menu and in the
+/// command completion. They are executed over the backend of the session,
+/// sharing its position and breakpoints, so the movements of a plugin are
+/// displayed as the ones of the prompt.
+///
+/// # Example
+///
+/// ```
+/// use std::io;
+///
+/// use dusk_cdf::DebuggerBackend;
+/// use dusk_pdb::prelude::*;
+///
+/// struct Count;
+///
+/// impl DebuggerPlugin for Count {
+///     fn name(&self) -> &str {
+///         "count"
+///     }
+///
+///     fn syntax(&self) -> &str {
+///         "count"
+///     }
+///
+///     fn help(&self) -> &str {
+///         "print the number of constraints of the circuit"
+///     }
+///
+///     fn execute(
+///         &self,
+///         backend: &mut dyn DebuggerBackend,
+///         _args: &[String],
+///     ) -> io::Result<String> {
+///         backend.preamble().map(|p| p.constraints.to_string())
+///     }
+/// }
+///
+/// let parser = CommandParser::default().with_plugin(Count);
+/// let command = parser.parse("count").expect("valid command");
+///
+/// assert_eq!(
+///     command,
+///     Some(Command::Plugin {
+///         name: "count".into(),
+///         args: vec![],
+///     })
+/// );
+/// ```
+pub trait DebuggerPlugin: Send + Sync {
+    /// Token that triggers the plugin
+    fn name(&self) -> &str;
+
+    /// Syntax description, displayed in the help menu
+    fn syntax(&self) -> &str;
+
+    /// Short description, displayed in the help menu
+    fn help(&self) -> &str {
+        ""
+    }
+
+    /// Execute the command with the arguments provided by the user, returning
+    /// the contents to be displayed in the console.
+    fn execute(
+        &self,
+        backend: &mut dyn DebuggerBackend,
+        args: &[String],
+    ) -> io::Result<String>;
+}
+
+/// Shared reference to a registered plugin
+pub type Plugin = Arc<dyn DebuggerPlugin>;
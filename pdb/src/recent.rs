@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::{fs, io};
+
+/// Most recently opened CDF files of the user, most recent first.
+///
+/// The list is persisted one path per line, and the clones share the same
+/// entries so the completion of the prompt follows the opened files.
+#[derive(Debug, Clone, Default)]
+pub struct Recent {
+    path: Option<PathBuf>,
+    entries: Arc<RwLock<Vec<String>>>,
+}
+
+impl Recent {
+    /// Maximum number of remembered files
+    pub const CAPACITY: usize = 10;
+
+    /// Load the list stored in the config dir of the user. Any failure yields
+    /// an empty list.
+    pub fn load() -> Self {
+        dirs::config_dir()
+            .map(|p| p.join(env!("CARGO_PKG_NAME")).join("recent"))
+            .map(Self::load_from)
+            .unwrap_or_default()
+    }
+
+    /// Load the list persisted in the provided file. A missing file yields an
+    /// empty list.
+    pub fn load_from<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+
+        let entries = fs::read_to_string(&path)
+            .map(|contents| {
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .take(Self::CAPACITY)
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            path: Some(path),
+            entries: Arc::new(RwLock::new(entries)),
+        }
+    }
+
+    /// Remembered files, most recent first
+    pub fn entries(&self) -> Vec<String> {
+        self.entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Move the file to the front of the list, persisting it
+    pub fn push<E>(&self, entry: E) -> io::Result<()>
+    where
+        E: Into<String>,
+    {
+        let entry = entry.into();
+
+        let entries = {
+            let mut entries =
+                self.entries.write().unwrap_or_else(PoisonError::into_inner);
+
+            entries.retain(|e| e != &entry);
+            entries.insert(0, entry);
+            entries.truncate(Self::CAPACITY);
+            entries.clone()
+        };
+
+        let path = match &self.path {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents = entries.iter().fold(String::new(), |mut s, e| {
+            s.push_str(e);
+            s.push('\n');
+            s
+        });
+
+        fs::write(path, contents)
+    }
+}
+
+#[test]
+fn push_moves_to_front_and_persists() -> io::Result<()> {
+    let dir = tempdir::TempDir::new("pdb-recent")?;
+    let path = dir.path().join("pdb").join("recent");
+
+    let recent = Recent::load_from(&path);
+
+    assert!(recent.entries().is_empty());
+
+    recent.push("ok.cdf")?;
+    recent.push("wrong.cdf")?;
+    recent.push("ok.cdf")?;
+
+    assert_eq!(recent.entries(), vec!["ok.cdf", "wrong.cdf"]);
+    assert_eq!(Recent::load_from(&path).entries(), recent.entries());
+
+    (0..Recent::CAPACITY * 2).try_for_each(|i| recent.push(i.to_string()))?;
+
+    let entries = Recent::load_from(&path).entries();
+
+    assert_eq!(entries.len(), Recent::CAPACITY);
+    assert_eq!(entries[0], (Recent::CAPACITY * 2 - 1).to_string());
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+use std::{fs, io};
+
+/// Recovery file of the session over a CDF file, offered for restore on the
+/// next launch over the same file if the session didn't quit cleanly.
+///
+/// The session is saved as the journal of `history`, so restoring it replays
+/// its commands. The latest snapshot is kept in memory until
+/// [`Recovery::save`] writes it, so a panic hook can save it without
+/// awaiting the session; the clones share the same snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct Recovery {
+    cdf: String,
+    path: Option<PathBuf>,
+    pending: Arc<Mutex<Option<String>>>,
+}
+
+/// Session read from a recovery file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recovered {
+    /// Time of the latest save of the session
+    pub saved: SystemTime,
+    /// Command lines of the session, with their aliases expanded
+    pub commands: Vec<String>,
+}
+
+impl Recovery {
+    /// Recovery of the CDF file, stored in the data local dir of the user.
+    /// Without such dir, nothing is saved.
+    ///
+    /// The file is keyed by its canonical path, so the session is recovered
+    /// whatever the path the file is opened with.
+    pub fn load(cdf: &str) -> Self {
+        let cdf = fs::canonicalize(cdf)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| cdf.into());
+
+        dirs::data_local_dir()
+            .map(|p| p.join(env!("CARGO_PKG_NAME")).join("recovery"))
+            .map(|dir| Self::load_from(dir, &cdf))
+            .unwrap_or_default()
+    }
+
+    /// Recovery of the CDF file, stored in the provided dir
+    pub fn load_from<P>(dir: P, cdf: &str) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        // the file is named after the path of the CDF file; as distinct
+        // paths can map to the same name, the session records its path too
+        let name: String = cdf
+            .chars()
+            .map(|c| match c {
+                c if c.is_ascii_alphanumeric() => c,
+                '.' | '-' | '_' => c,
+                _ => '%',
+            })
+            .collect();
+
+        Self {
+            cdf: cdf.into(),
+            path: Some(dir.as_ref().join(name)),
+            pending: Arc::default(),
+        }
+    }
+
+    /// Path of the CDF file the session is saved for
+    pub fn cdf(&self) -> &str {
+        &self.cdf
+    }
+
+    /// Replace the snapshot to be saved by the provided session, as written
+    /// by the journal
+    pub fn snapshot(&self, session: String) {
+        if self.path.is_some() {
+            self.pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .replace(session);
+        }
+    }
+
+    /// Write the pending snapshot, if the session was modified since the
+    /// latest save. Returns `true` if a snapshot was written.
+    pub fn save(&self) -> io::Result<bool> {
+        let path = match &self.path {
+            Some(p) => p,
+            None => return Ok(false),
+        };
+
+        let session = match self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            Some(s) => s,
+            None => return Ok(false),
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // a session dying while saving must not corrupt the previous save
+        let partial = path.with_extension("partial");
+
+        fs::write(&partial, session)?;
+        fs::rename(partial, path)?;
+
+        Ok(true)
+    }
+
+    /// Session saved for the CDF file, if any
+    pub fn recovered(&self) -> Option<Recovered> {
+        let path = self.path.as_ref()?;
+
+        let contents = fs::read_to_string(path).ok()?;
+        let mut lines = contents.lines();
+
+        if lines.next()? != format!("# Session over {}", self.cdf) {
+            return None;
+        }
+
+        let commands: Vec<_> = lines
+            .filter_map(|l| l.strip_prefix("> "))
+            .map(String::from)
+            .collect();
+
+        if commands.is_empty() {
+            return None;
+        }
+
+        let saved = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+
+        Some(Recovered { saved, commands })
+    }
+
+    /// Drop the pending snapshot and remove the saved session, as the session
+    /// quits cleanly
+    pub fn discard(&self) -> io::Result<()> {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        match &self.path {
+            Some(path) => match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn saved_session_is_recovered_until_discarded() -> io::Result<()> {
+    let dir = tempdir::TempDir::new("pdb-recovery")?;
+    let cdf = "/circuits/test.cdf";
+
+    let recovery = Recovery::load_from(dir.path(), cdf);
+
+    assert!(!recovery.save()?);
+    assert!(recovery.recovered().is_none());
+
+    recovery.snapshot(format!(
+        "# Session over {}\n> breakpoint gadget.rs:28\n> continue\n@ gadget.rs:28\n",
+        cdf
+    ));
+
+    // the clone of a panic hook shares the snapshot
+    assert!(recovery.clone().save()?);
+    assert!(!recovery.save()?);
+
+    let recovered = Recovery::load_from(dir.path(), cdf)
+        .recovered()
+        .expect("the session was saved");
+
+    assert_eq!(
+        recovered.commands,
+        vec!["breakpoint gadget.rs:28", "continue"]
+    );
+    assert!(recovered.saved <= SystemTime::now());
+
+    // another file mapped to the same name isn't offered the session
+    let other = Recovery::load_from(dir.path(), "/circuits%test.cdf");
+
+    assert!(other.recovered().is_none());
+
+    recovery.discard()?;
+
+    assert!(recovery.recovered().is_none());
+    assert!(Recovery::default().save().is_ok());
+
+    Ok(())
+}
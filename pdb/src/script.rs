@@ -0,0 +1,377 @@
+//! Ad-hoc analysis scripts executed over the loaded CDF.
+//!
+//! Scripts are written in [Rhai](https://rhai.rs) and run over the backend of
+//! the interactive session, sharing its position and breakpoints.
+//!
+//! The following functions are available:
+//!
+//! - `afore()`, `cont()`, `step()`, `turn()`, `goto(id)`: navigate the
+//!   circuit, returning a state map with `state` and `id`
+//! - `constraint()`, `constraint(id)`: fetch the current or an indexed
+//!   constraint, including its `gate` kind
+//! - `witness(id)`: fetch an indexed witness
+//! - `constraints()`, `witnesses()`: number of items in the circuit
+//! - `breakpoint(source)`, `breakpoint(source, line)`: add a breakpoint,
+//!   returning its id
+//! - `delete(id)`: remove a breakpoint
+//!
+//! Everything printed by the script is captured into the console output.
+
+use std::io;
+use std::path::Path;
+
+use dusk_cdf::{CancelToken, DebuggerBackend};
+
+use crate::app::Output;
+
+/// Execute the script over the provided backend.
+///
+/// Script errors, including the ones of the backend, are reported in the
+/// error section of the returned output. Cancelling the token terminates the
+/// script once the running call to the backend returns.
+#[cfg(feature = "script")]
+pub fn run<B, Q>(
+    backend: B,
+    script: Q,
+    interrupt: CancelToken,
+) -> io::Result<Output>
+where
+    B: DebuggerBackend + 'static,
+    Q: AsRef<Path>,
+{
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use rhai::{Engine, EvalAltResult};
+
+    let debugger = Rc::new(RefCell::new(backend));
+    let console = Rc::new(RefCell::new(vec![]));
+
+    let mut engine = Engine::new();
+
+    let c = console.clone();
+    engine.on_print(move |s| c.borrow_mut().push(s.to_string()));
+
+    engine.on_progress(move |_| {
+        interrupt.is_cancelled().then(|| "interrupted".into())
+    });
+
+    engine::register(&mut engine, &debugger);
+
+    let result = engine.run_file(script.as_ref().to_path_buf());
+
+    let mut output = Output {
+        contents: None,
+        console: console.take(),
+        error: vec![],
+    };
+
+    match result.map_err(|e| *e) {
+        Ok(()) => (),
+
+        Err(EvalAltResult::ErrorTerminated(..)) => {
+            let interrupted = match debugger.borrow().position() {
+                Ok(position) => {
+                    format!("script interrupted at constraint #{}", position)
+                }
+                Err(_) => String::from("script interrupted"),
+            };
+
+            output.error.push(interrupted);
+        }
+
+        Err(e) => output.error.push(format!("script error: {}", e)),
+    }
+
+    Ok(output)
+}
+
+/// Execute the script over the provided backend.
+///
+/// Always fails since pdb was compiled without the `script` feature.
+#[cfg(not(feature = "script"))]
+pub fn run<B, Q>(
+    _backend: B,
+    _script: Q,
+    _interrupt: CancelToken,
+) -> io::Result<Output>
+where
+    B: DebuggerBackend + 'static,
+    Q: AsRef<Path>,
+{
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "pdb was compiled without scripting support (feature `script`)",
+    ))
+}
+
+#[cfg(feature = "script")]
+mod engine {
+    use std::cell::RefCell;
+    use std::io;
+    use std::rc::Rc;
+
+    use dusk_cdf::{
+        DebuggerBackend, EncodableConstraint, EncodableWitness, Scalar, State,
+    };
+    use rhai::{Dynamic, Engine, EvalAltResult, Map, INT};
+
+    type Shared<B> = Rc<RefCell<B>>;
+    type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+    pub fn register<B>(engine: &mut Engine, debugger: &Shared<B>)
+    where
+        B: DebuggerBackend + 'static,
+    {
+        let d = debugger.clone();
+        engine.register_fn("afore", move || state(d.borrow_mut().afore()));
+
+        let d = debugger.clone();
+        engine.register_fn("cont", move || state(d.borrow_mut().cont()));
+
+        let d = debugger.clone();
+        engine.register_fn("step", move || state(d.borrow_mut().step()));
+
+        let d = debugger.clone();
+        engine.register_fn("turn", move || state(d.borrow_mut().turn()));
+
+        let d = debugger.clone();
+        engine.register_fn("goto", move |id: INT| {
+            state(d.borrow_mut().goto(to_idx(id)?))
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("constraint", move || {
+            constraint(d.borrow_mut().current_constraint())
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("constraint", move |id: INT| {
+            constraint(d.borrow_mut().constraint(to_idx(id)?))
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("witness", move |id: INT| {
+            witness(d.borrow_mut().witness(to_idx(id)?))
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("constraints", move || {
+            let preamble = d.borrow().preamble().map_err(error)?;
+
+            ScriptResult::Ok(preamble.constraints as INT)
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("witnesses", move || {
+            let preamble = d.borrow().preamble().map_err(error)?;
+
+            ScriptResult::Ok(preamble.witnesses as INT)
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("breakpoint", move |source: &str| {
+            let id = d
+                .borrow_mut()
+                .add_breakpoint(source.into(), None)
+                .map_err(error)?;
+
+            ScriptResult::Ok(id as INT)
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("breakpoint", move |source: &str, line: INT| {
+            let id = d
+                .borrow_mut()
+                .add_breakpoint(source.into(), Some(line as u64))
+                .map_err(error)?;
+
+            ScriptResult::Ok(id as INT)
+        });
+
+        let d = debugger.clone();
+        engine.register_fn("delete", move |id: INT| {
+            let id = to_idx(id)?;
+
+            d.borrow_mut().remove_breakpoint(id).map_err(error)
+        });
+    }
+
+    fn error(e: io::Error) -> Box<EvalAltResult> {
+        e.to_string().into()
+    }
+
+    fn to_idx(id: INT) -> ScriptResult<usize> {
+        usize::try_from(id).map_err(|_| format!("invalid index: {}", id).into())
+    }
+
+    fn scalar(scalar: &Scalar) -> Dynamic {
+        let hex = scalar.iter().fold(String::from("0x"), |mut s, b| {
+            s.push_str(&format!("{:02x}", b));
+            s
+        });
+
+        Dynamic::from(hex)
+    }
+
+    fn state(state: io::Result<State>) -> ScriptResult<Map> {
+        let state = state.map_err(error)?;
+        let (name, id) = match &state {
+            State::Beginning => ("beginning", 0),
+            State::Constraint { id, .. } => ("constraint", *id),
+            State::InvalidConstraint { id, .. } => ("invalid", *id),
+            State::Breakpoint { id, .. } => ("breakpoint", *id),
+            State::Corrupt { id, .. } => ("corrupt", *id),
+            State::Witness { id } => ("witness", *id),
+            State::Paused { id } => ("paused", *id),
+            State::Exhausted { id, .. } => ("exhausted", *id),
+            State::End { id, .. } => ("end", *id),
+            // states added by later versions of the backend
+            _ => ("unknown", state.constraint().unwrap_or_default()),
+        };
+
+        let mut map = Map::new();
+
+        map.insert("state".into(), Dynamic::from(name.to_string()));
+        map.insert("id".into(), Dynamic::from(id as INT));
+
+        if let Some(constraint) = state.constraint() {
+            map.insert("constraint".into(), Dynamic::from(constraint as INT));
+        }
+
+        if let Some(location) = state.location() {
+            map.insert("source".into(), Dynamic::from(location.source.clone()));
+            map.insert("line".into(), Dynamic::from(location.line as INT));
+            map.insert("col".into(), Dynamic::from(location.col as INT));
+        }
+
+        Ok(map)
+    }
+
+    fn constraint(
+        constraint: io::Result<EncodableConstraint>,
+    ) -> ScriptResult<Map> {
+        let constraint = constraint.map_err(error)?;
+        let polynomial = constraint.polynomial();
+        let selectors = polynomial.selectors();
+        let witnesses = polynomial.witnesses();
+        let source = constraint.source();
+
+        let mut map = Map::new();
+
+        map.insert("id".into(), Dynamic::from(constraint.id() as INT));
+        map.insert("source".into(), Dynamic::from(source.path().to_string()));
+        map.insert("line".into(), Dynamic::from(source.line() as INT));
+        map.insert("col".into(), Dynamic::from(source.col() as INT));
+        map.insert("evaluation".into(), Dynamic::from(polynomial.is_ok()));
+        map.insert(
+            "gate".into(),
+            Dynamic::from(polynomial.gate_kind().to_string()),
+        );
+
+        map.insert("qm".into(), scalar(&selectors.qm));
+        map.insert("ql".into(), scalar(&selectors.ql));
+        map.insert("qr".into(), scalar(&selectors.qr));
+        map.insert("qd".into(), scalar(&selectors.qd));
+        map.insert("qc".into(), scalar(&selectors.qc));
+        map.insert("qo".into(), scalar(&selectors.qo));
+        map.insert("pi".into(), scalar(&selectors.pi));
+        map.insert("qarith".into(), scalar(&selectors.qarith));
+        map.insert("qlogic".into(), scalar(&selectors.qlogic));
+        map.insert("qrange".into(), scalar(&selectors.qrange));
+        map.insert(
+            "qgroup_variable".into(),
+            scalar(&selectors.qgroup_variable),
+        );
+        map.insert("qfixed_add".into(), scalar(&selectors.qfixed_add));
+
+        map.insert("a".into(), Dynamic::from(witnesses.a as INT));
+        map.insert("b".into(), Dynamic::from(witnesses.b as INT));
+        map.insert("d".into(), Dynamic::from(witnesses.d as INT));
+        map.insert("o".into(), Dynamic::from(witnesses.o as INT));
+
+        Ok(map)
+    }
+
+    fn witness(witness: io::Result<EncodableWitness>) -> ScriptResult<Map> {
+        let witness = witness.map_err(error)?;
+        let source = witness.source();
+
+        let constraint = witness
+            .constraint()
+            .map(|c| Dynamic::from(c as INT))
+            .unwrap_or(Dynamic::UNIT);
+
+        let mut map = Map::new();
+
+        map.insert("id".into(), Dynamic::from(witness.id() as INT));
+        map.insert("value".into(), scalar(witness.value()));
+        map.insert("constraint".into(), constraint);
+        map.insert("source".into(), Dynamic::from(source.path().to_string()));
+        map.insert("line".into(), Dynamic::from(source.line() as INT));
+        map.insert("col".into(), Dynamic::from(source.col() as INT));
+
+        Ok(map)
+    }
+}
+
+#[cfg(feature = "script")]
+#[test]
+fn script_runs_over_cdf() -> io::Result<()> {
+    use std::path::PathBuf;
+
+    use dusk_cdf::ZkDebugger;
+
+    let cdf = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("failed to updir")
+        .join("assets")
+        .join("test.cdf");
+
+    let dir = tempdir::TempDir::new("pdb-script")?;
+    let script = dir.path().join("count.rhai");
+
+    std::fs::write(
+        &script,
+        r#"
+            let invalid = 0;
+            let state = #{ state: "beginning" };
+
+            while state.state != "end" {
+                state = cont();
+
+                if state.state == "invalid" {
+                    invalid += 1;
+                }
+            }
+
+            print(`constraints: ${constraints()}`);
+            print(`invalid: ${invalid}`);
+            print(`witness: ${witness(0).id}`);
+        "#,
+    )?;
+
+    let output = run(ZkDebugger::open(&cdf)?, &script, CancelToken::new())?;
+
+    assert!(output.error.is_empty(), "{:?}", output.error);
+    assert_eq!(output.console.len(), 3);
+    assert_eq!(output.console[2], "witness: 0");
+
+    let output = run(
+        ZkDebugger::open(&cdf)?,
+        dir.path().join("missing.rhai"),
+        CancelToken::new(),
+    )?;
+
+    assert!(!output.error.is_empty());
+
+    let interrupt = CancelToken::new();
+
+    interrupt.cancel();
+
+    let output = run(ZkDebugger::open(&cdf)?, &script, interrupt)?;
+
+    assert_eq!(output.error, vec!["script interrupted at constraint #0"]);
+
+    Ok(())
+}
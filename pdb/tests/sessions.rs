@@ -0,0 +1,159 @@
+//! Replay of the recorded sessions of `tests/sessions`.
+//!
+//! A session is a list of commands, each prefixed by `> `, followed by the
+//! lines expected among its outputs, in order. A line may be a portion of the
+//! output line it matches. The rendered source is written as `@ NAME:LINE`,
+//! and the errors are prefixed by `! `; the lines starting with `#` before
+//! the first command are comments.
+//!
+//! Set `PDB_RECORD=1` to rewrite the sessions with the outputs of their
+//! commands.
+
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use dusk_cdf::ColorChoice;
+use dusk_pdb::prelude::*;
+
+/// Environment variable that switches the replay to a recording
+const PDB_RECORD: &str = "PDB_RECORD";
+
+#[derive(Debug, Default)]
+struct Session {
+    comments: Vec<String>,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug)]
+struct Step {
+    command: String,
+    outputs: Vec<String>,
+}
+
+impl Session {
+    fn parse(contents: &str) -> Self {
+        contents.lines().fold(Self::default(), |mut session, line| {
+            match (line.strip_prefix("> "), session.steps.last_mut()) {
+                (Some(command), _) => session.steps.push(Step {
+                    command: command.into(),
+                    outputs: vec![],
+                }),
+                (None, None) => session.comments.push(line.into()),
+                (None, Some(step)) => step.outputs.push(line.into()),
+            }
+
+            session
+        })
+    }
+
+    fn render(&self) -> String {
+        let mut contents = self.comments.join("\n");
+
+        for Step { command, outputs } in &self.steps {
+            contents.push_str(&format!("\n> {}", command));
+
+            for output in outputs {
+                contents.push_str(&format!("\n{}", output));
+            }
+        }
+
+        contents.push('\n');
+        contents
+    }
+}
+
+/// Lines of an output, as written in the sessions
+fn lines(output: Output) -> Vec<String> {
+    let Output {
+        contents,
+        console,
+        error,
+    } = output;
+
+    contents
+        .map(|Source { name, line, .. }| format!("@ {}:{}", name, line))
+        .into_iter()
+        .chain(error.into_iter().map(|e| format!("! {}", e)))
+        .chain(console)
+        .collect()
+}
+
+/// Check if the expected lines are portions of the output lines, in order
+fn matches(expected: &[String], outputs: &[String]) -> bool {
+    let mut outputs = outputs.iter();
+
+    expected
+        .iter()
+        .all(|e| outputs.any(|o| o.contains(e.as_str())))
+}
+
+async fn replay(name: &str) -> io::Result<()> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let path = root.join("tests").join("sessions").join(name);
+    let path = path.with_extension("session");
+
+    let mut session = Session::parse(&fs::read_to_string(&path)?);
+    let record = env::var_os(PDB_RECORD).is_some();
+
+    let args = ParsedArgs {
+        path: Some(root.join("..").join("assets").join("test.cdf")),
+        attach: None,
+        dap_command: None,
+        color: Some(ColorChoice::Never),
+        compare: None,
+    };
+
+    let commands = session.steps.iter().map(|s| s.command.clone());
+    let mut app = App::replay(args, commands.collect::<Vec<_>>()).await?;
+
+    app.flush_output().await;
+
+    for step in session.steps.iter_mut() {
+        let mut output = app.next_output().await.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("the session ended before `{}`", step.command),
+            )
+        })?;
+
+        // the events of a navigation may be rendered after the response
+        if let Some(late) = app.flush_output().await {
+            output.merge(late);
+        }
+
+        let outputs = lines(output);
+
+        if record {
+            step.outputs = outputs;
+        } else {
+            assert!(
+                matches(&step.outputs, &outputs),
+                "unexpected outputs of `{}`\nexpected: {:#?}\nfound: {:#?}",
+                step.command,
+                step.outputs,
+                outputs
+            );
+        }
+    }
+
+    if record {
+        fs::write(&path, session.render())?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn breakpoints_session() -> io::Result<()> {
+    replay("breakpoints").await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn navigation_session() -> io::Result<()> {
+    replay("navigation").await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn history_session() -> io::Result<()> {
+    replay("history").await
+}